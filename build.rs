@@ -0,0 +1,24 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Exposes the locked `hwpers` version as `HWPERS_VERSION`, so `hwp.server_info`
+/// reports the crate that was actually linked instead of a hand-kept constant.
+fn main() {
+    let lock = Path::new(&env::var("CARGO_MANIFEST_DIR").expect("manifest dir")).join("Cargo.lock");
+    println!("cargo:rerun-if-changed={}", lock.display());
+
+    let version = fs::read_to_string(&lock)
+        .ok()
+        .and_then(|lock| locked_version(&lock, "hwpers"))
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=HWPERS_VERSION={version}");
+}
+
+fn locked_version(lock: &str, package: &str) -> Option<String> {
+    let name = format!("name = \"{package}\"");
+    let mut lines = lock.lines();
+    lines.find(|line| *line == name)?;
+    let version = lines.next()?.strip_prefix("version = \"")?;
+    Some(version.trim_end_matches('"').to_string())
+}
@@ -1,11 +1,16 @@
-use crate::mcp::contracts::MAX_INPUT_BYTES;
+use crate::mcp::contracts::limits;
 use crate::mcp::errors;
 use base64::Engine;
 use base64::engine::general_purpose::STANDARD;
+use hwpers::parser::FileHeader;
+use hwpers::reader::CfbReader;
 use serde_json::Value;
 use std::fmt;
 use std::fs;
+use std::io::{Cursor, Read};
 use std::path::Path;
+use std::sync::OnceLock;
+use std::time::Duration;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InputFormat {
@@ -41,10 +46,122 @@ impl InputFormat {
     }
 }
 
+const CFB_MAGIC: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+const ZIP_MAGIC: [u8; 4] = [0x50, 0x4B, 0x03, 0x04];
+
+/// Detects the container format from content rather than the file name: a CFB compound file is
+/// HWP, and a ZIP archive is HWPX only when it carries a `mimetype` or `version.xml` entry. Anything
+/// else is inconclusive and left to the caller's fallback.
+pub fn sniff_format(bytes: &[u8]) -> Option<InputFormat> {
+    if bytes.starts_with(&CFB_MAGIC) {
+        Some(InputFormat::Hwp)
+    } else if is_hwpx_package(bytes) {
+        Some(InputFormat::Hwpx)
+    } else {
+        None
+    }
+}
+
+/// Reports whether `bytes` is a ZIP archive with the entries an HWPX package starts with.
+fn is_hwpx_package(bytes: &[u8]) -> bool {
+    if !bytes.starts_with(&ZIP_MAGIC) {
+        return false;
+    }
+    let Ok(archive) = zip::ZipArchive::new(Cursor::new(bytes)) else {
+        return false;
+    };
+    archive.index_for_name("mimetype").is_some() || archive.index_for_name("version.xml").is_some()
+}
+
+/// Settles the format `bytes` are read as, with how it was settled: `explicit` when the caller
+/// named it, `magic` when the content names it, and `fallback` (format `Auto`) when the content
+/// is inconclusive and the parser has to try HWP and then HWPX.
+fn detect_format(bytes: &[u8], format: InputFormat) -> (InputFormat, &'static str) {
+    match format {
+        InputFormat::Auto => match sniff_format(bytes) {
+            Some(sniffed) => (sniffed, "magic"),
+            None => (InputFormat::Auto, "fallback"),
+        },
+        explicit => (explicit, "explicit"),
+    }
+}
+
+/// With no explicit format, a ZIP archive that is not an HWPX package (a DOCX renamed to `.hwpx`,
+/// say) is refused as the wrong format instead of being reported as a corrupt document.
+fn check_container(bytes: &[u8], format: InputFormat) -> Result<(), InputError> {
+    if format == InputFormat::Auto && bytes.starts_with(&ZIP_MAGIC) && !is_hwpx_package(bytes) {
+        return Err(InputError::new(
+            errors::UNSUPPORTED_FORMAT,
+            "ZIP archive is not an HWPX package: no mimetype or version.xml entry",
+        ));
+    }
+    Ok(())
+}
+
+/// Reports whether an HWP file has the FileHeader distribution flag set (배포용 문서:
+/// encrypted for viewing only).
+pub fn is_distribution_document(bytes: &[u8]) -> bool {
+    if !bytes.starts_with(&CFB_MAGIC) {
+        return false;
+    }
+    let Ok(mut reader) = CfbReader::new(Cursor::new(bytes)) else {
+        return false;
+    };
+    reader
+        .read_stream("FileHeader")
+        .ok()
+        .and_then(|data| FileHeader::parse(data).ok())
+        .is_some_and(|header| header.is_distribute())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputSource {
+    Path,
+    Base64,
+    Url,
+}
+
+impl InputSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InputSource::Path => "path",
+            InputSource::Base64 => "base64",
+            InputSource::Url => "url",
+        }
+    }
+}
+
+const DEFAULT_ALLOWED_SOURCES: [InputSource; 2] = [InputSource::Path, InputSource::Base64];
+
+static ALLOWED_SOURCES: OnceLock<Vec<InputSource>> = OnceLock::new();
+
+/// Restricts which input sources `load_input` accepts for the rest of the process.
+pub fn set_allowed_sources(sources: Vec<InputSource>) {
+    let _ = ALLOWED_SOURCES.set(sources);
+}
+
+pub fn ensure_source_allowed(source: InputSource) -> Result<(), InputError> {
+    let allowed = ALLOWED_SOURCES
+        .get()
+        .map(|sources| sources.as_slice())
+        .unwrap_or(&DEFAULT_ALLOWED_SOURCES);
+    if allowed.contains(&source) {
+        Ok(())
+    } else {
+        Err(InputError::invalid_input(format!(
+            "input source '{}' is disabled",
+            source.as_str()
+        )))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct InputPayload {
     pub bytes: Vec<u8>,
+    /// The format to read `bytes` as; `Auto` only when the content is inconclusive.
     pub format: InputFormat,
+    /// How `format` was settled: `explicit`, `magic` or `fallback`.
+    pub detection_method: &'static str,
     pub source: String,
 }
 
@@ -90,24 +207,37 @@ pub fn load_input(args: &Value) -> Result<InputPayload, InputError> {
 
     let path_value = obj.get("path");
     let base64_value = obj.get("base64");
+    let url_value = obj.get("url");
 
-    match (path_value, base64_value) {
-        (None, None) => {
+    match (path_value, base64_value, url_value) {
+        (None, None, None) => {
             return Err(InputError::invalid_input(
-                "either path or base64 is required",
+                "one of path, base64, or url is required",
             ));
         }
-        (Some(_), Some(_)) => {
+        (Some(_), None, None) | (None, Some(_), None) | (None, None, Some(_)) => {}
+        _ => {
             return Err(InputError::invalid_input(
-                "path and base64 cannot both be set",
+                "only one of path, base64, or url can be set",
             ));
         }
-        _ => {}
     }
 
     let format = InputFormat::parse(obj.get("format"))?;
+    let password = match obj.get("password") {
+        None | Some(Value::Null) => None,
+        Some(Value::String(password)) => Some(password.clone()),
+        Some(_) => return Err(InputError::invalid_input("password must be a string")),
+    };
 
-    if let Some(value) = path_value {
+    let (bytes, source) = if let Some(value) = url_value {
+        ensure_source_allowed(InputSource::Url)?;
+        let url = value
+            .as_str()
+            .ok_or_else(|| InputError::invalid_input("url must be a string"))?;
+        (fetch_url(url)?, format!("url:{url}"))
+    } else if let Some(value) = path_value {
+        ensure_source_allowed(InputSource::Path)?;
         let path = value
             .as_str()
             .ok_or_else(|| InputError::invalid_input("path must be a string"))?;
@@ -118,40 +248,153 @@ pub fn load_input(args: &Value) -> Result<InputPayload, InputError> {
             return Err(InputError::invalid_input("path must be a file"));
         }
         let len = metadata.len();
-        if len > MAX_INPUT_BYTES {
+        let max_input_bytes = limits().max_input_bytes;
+        if len > max_input_bytes {
             return Err(InputError::too_large(format!(
-                "input exceeds limit: {len} bytes (max {MAX_INPUT_BYTES})"
+                "input exceeds limit: {len} bytes (max {max_input_bytes})"
             )));
         }
         let bytes = fs::read(path_ref)
             .map_err(|_| InputError::invalid_input("failed to read path contents"))?;
-        return Ok(InputPayload {
-            bytes,
-            format,
-            source: format!("path:{path}"),
-        });
-    }
+        (bytes, format!("path:{path}"))
+    } else {
+        let value = base64_value.expect("base64 must be present here");
+        ensure_source_allowed(InputSource::Base64)?;
+        let base64_str = value
+            .as_str()
+            .ok_or_else(|| InputError::invalid_input("base64 must be a string"))?;
+        let bytes = STANDARD.decode(base64_str.as_bytes()).map_err(|err| {
+            InputError::invalid_input(describe_base64_error(&err, base64_str.len()))
+        })?;
+        let max_input_bytes = limits().max_input_bytes;
+        if bytes.len() as u64 > max_input_bytes {
+            return Err(InputError::too_large(format!(
+                "input exceeds limit: {} bytes (max {max_input_bytes})",
+                bytes.len()
+            )));
+        }
+        (bytes, "base64".to_string())
+    };
 
-    let value = base64_value.expect("base64 must be present here");
-    let base64_str = value
-        .as_str()
-        .ok_or_else(|| InputError::invalid_input("base64 must be a string"))?;
-    let bytes = STANDARD
-        .decode(base64_str.as_bytes())
-        .map_err(|_| InputError::invalid_input("base64 must be valid"))?;
-    if bytes.len() as u64 > MAX_INPUT_BYTES {
-        return Err(InputError::too_large(format!(
-            "input exceeds limit: {} bytes (max {MAX_INPUT_BYTES})",
-            bytes.len()
-        )));
-    }
+    check_container(&bytes, format)?;
+    check_password(&bytes, password.as_deref())?;
+    let (format, detection_method) = detect_format(&bytes, format);
     Ok(InputPayload {
         bytes,
         format,
-        source: "base64".to_string(),
+        detection_method,
+        source,
     })
 }
 
+/// Reports whether an HWP file has the FileHeader password-encryption flag set.
+pub fn is_password_encrypted(bytes: &[u8]) -> bool {
+    if !bytes.starts_with(&CFB_MAGIC) {
+        return false;
+    }
+    let Ok(mut reader) = CfbReader::new(Cursor::new(bytes)) else {
+        return false;
+    };
+    reader
+        .read_stream("FileHeader")
+        .ok()
+        .and_then(|data| FileHeader::parse(data).ok())
+        .is_some_and(|header| header.is_encrypted())
+}
+
+/// hwpers 0.5 cannot decrypt password-encrypted documents, so they are refused up front with a
+/// message that says whether a password was missing or supplied; the password itself is never
+/// included.
+fn check_password(bytes: &[u8], password: Option<&str>) -> Result<(), InputError> {
+    if !is_password_encrypted(bytes) {
+        return Ok(());
+    }
+    let message = match password {
+        None | Some("") => {
+            "password required: the document is password-encrypted, and decrypting it is not \
+             supported"
+        }
+        Some(_) => {
+            "password-encrypted document could not be decrypted: decrypting with a password is \
+             not supported, so the supplied password was not checked"
+        }
+    };
+    Err(InputError::new(errors::ENCRYPTED, message))
+}
+
+/// Downloads `url`, streaming at most the input limit and giving up after the parse time
+/// limit.
+fn fetch_url(url: &str) -> Result<Vec<u8>, InputError> {
+    if !(url.starts_with("http://") || url.starts_with("https://")) {
+        return Err(InputError::invalid_input(
+            "url must start with http:// or https://",
+        ));
+    }
+    let limits = limits();
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(Duration::from_millis(limits.max_parse_ms)))
+        .max_redirects(5)
+        .build()
+        .into();
+    let fetch_error = |err: ureq::Error| match err {
+        ureq::Error::Timeout(_) => InputError::new(
+            errors::TIMEOUT,
+            format!("url fetch timed out after {} ms", limits.max_parse_ms),
+        ),
+        err => InputError::invalid_input(format!("failed to fetch url: {err}")),
+    };
+    let response = agent.get(url).call().map_err(fetch_error)?;
+    let too_large = || {
+        InputError::too_large(format!(
+            "input exceeds limit: url content is larger than {} bytes",
+            limits.max_input_bytes
+        ))
+    };
+    if response
+        .body()
+        .content_length()
+        .is_some_and(|length| length > limits.max_input_bytes)
+    {
+        return Err(too_large());
+    }
+
+    // Content-Length may be absent or wrong, so the body itself is capped one byte past the
+    // limit and the download stops there.
+    let mut bytes = Vec::new();
+    response
+        .into_body()
+        .into_reader()
+        .take(limits.max_input_bytes + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|err| match err.downcast::<ureq::Error>() {
+            Ok(err) => fetch_error(err),
+            Err(err) => InputError::invalid_input(format!("failed to fetch url: {err}")),
+        })?;
+    if bytes.len() as u64 > limits.max_input_bytes {
+        return Err(too_large());
+    }
+    Ok(bytes)
+}
+
+fn describe_base64_error(error: &base64::DecodeError, input_len: usize) -> String {
+    let detail = match error {
+        base64::DecodeError::InvalidByte(offset, byte) => {
+            format!("invalid character {:?} at position {offset}", *byte as char)
+        }
+        base64::DecodeError::InvalidLastSymbol(offset, byte) => {
+            format!(
+                "invalid trailing symbol {:?} at position {offset}",
+                *byte as char
+            )
+        }
+        base64::DecodeError::InvalidLength(_) => {
+            "invalid length (input may be truncated)".to_string()
+        }
+        base64::DecodeError::InvalidPadding => "invalid padding".to_string(),
+    };
+    format!("base64 must be valid: {detail} (input length {input_len})")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -166,6 +409,7 @@ mod tests {
         let payload = load_input(&args).expect("payload");
         assert_eq!(payload.bytes, b"hello");
         assert_eq!(payload.format, InputFormat::Auto);
+        assert_eq!(payload.detection_method, "fallback");
         assert_eq!(payload.source, "base64");
     }
 
@@ -176,6 +420,15 @@ mod tests {
         assert_eq!(err.kind, errors::INVALID_INPUT);
     }
 
+    #[test]
+    fn base64_invalid_reports_position() {
+        let args = json!({"base64": "aGVs!G8="});
+        let err = load_input(&args).expect_err("error");
+        assert_eq!(err.kind, errors::INVALID_INPUT);
+        assert!(err.message.contains("position 4"), "{}", err.message);
+        assert!(err.message.contains("input length 8"), "{}", err.message);
+    }
+
     #[test]
     fn missing_input() {
         let args = json!({});
@@ -206,14 +459,124 @@ mod tests {
         assert_eq!(err.kind, errors::INVALID_INPUT);
     }
 
+    #[test]
+    fn password_must_be_a_string() {
+        let encoded = STANDARD.encode(b"hello");
+        let args = json!({"base64": encoded, "password": 1234});
+        let err = load_input(&args).expect_err("error");
+        assert_eq!(err.kind, errors::INVALID_INPUT);
+    }
+
+    fn password_encrypted_hwp() -> Vec<u8> {
+        let mut writer = hwpers::HwpWriter::new();
+        writer.add_paragraph("secret").expect("paragraph");
+        let bytes = writer.to_bytes().expect("bytes");
+        let mut compound = cfb::CompoundFile::open(Cursor::new(bytes)).expect("cfb");
+        let mut header = Vec::new();
+        compound
+            .open_stream("/FileHeader")
+            .expect("header stream")
+            .read_to_end(&mut header)
+            .expect("header");
+        header[36] |= 0x02;
+        std::io::Write::write_all(
+            &mut compound.create_stream("/FileHeader").expect("stream"),
+            &header,
+        )
+        .expect("write header");
+        compound.flush().expect("flush");
+        compound.into_inner().into_inner()
+    }
+
+    #[test]
+    fn password_encrypted_without_password() {
+        let args = json!({"base64": STANDARD.encode(password_encrypted_hwp())});
+        let err = load_input(&args).expect_err("error");
+        assert_eq!(err.kind, errors::ENCRYPTED);
+        assert!(
+            err.message.starts_with("password required"),
+            "{}",
+            err.message
+        );
+    }
+
+    #[test]
+    fn password_encrypted_with_password_hides_it() {
+        let args = json!({
+            "base64": STANDARD.encode(password_encrypted_hwp()),
+            "password": "hunter2"
+        });
+        let err = load_input(&args).expect_err("error");
+        assert_eq!(err.kind, errors::ENCRYPTED);
+        assert!(
+            !err.message.starts_with("password required"),
+            "{}",
+            err.message
+        );
+        assert!(!err.message.contains("hunter2"), "{}", err.message);
+    }
+
     #[test]
     fn too_large() {
         let dir = tempdir().expect("tempdir");
         let file_path = dir.path().join("large.hwp");
         let file = File::create(&file_path).expect("file");
-        file.set_len(MAX_INPUT_BYTES + 1).expect("set_len");
+        file.set_len(limits().max_input_bytes + 1).expect("set_len");
         let args = json!({"path": file_path.to_string_lossy()});
         let err = load_input(&args).expect_err("error");
         assert_eq!(err.kind, errors::TOO_LARGE);
     }
+
+    fn zip_with(entries: &[&str]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for name in entries {
+            writer
+                .start_file(*name, zip::write::SimpleFileOptions::default())
+                .expect("start_file");
+            std::io::Write::write_all(&mut writer, b"x").expect("write");
+        }
+        writer.finish().expect("finish").into_inner()
+    }
+
+    #[test]
+    fn sniff_format_checks_content() {
+        let mut cfb = CFB_MAGIC.to_vec();
+        cfb.extend_from_slice(&[0; 8]);
+        assert_eq!(sniff_format(&cfb), Some(InputFormat::Hwp));
+        assert_eq!(
+            sniff_format(&zip_with(&["mimetype", "Contents/section0.xml"])),
+            Some(InputFormat::Hwpx)
+        );
+        assert_eq!(
+            sniff_format(&zip_with(&["version.xml"])),
+            Some(InputFormat::Hwpx)
+        );
+        assert_eq!(sniff_format(&zip_with(&["word/document.xml"])), None);
+        assert_eq!(sniff_format(b"plain text"), None);
+    }
+
+    #[test]
+    fn non_hwpx_zip_is_unsupported_format() {
+        let encoded = STANDARD.encode(zip_with(&["word/document.xml"]));
+        let err = load_input(&json!({"base64": encoded})).expect_err("error");
+        assert_eq!(err.kind, errors::UNSUPPORTED_FORMAT);
+
+        let payload =
+            load_input(&json!({"base64": encoded, "format": "hwpx"})).expect("explicit format");
+        assert_eq!(payload.format, InputFormat::Hwpx);
+        assert_eq!(payload.detection_method, "explicit");
+    }
+
+    #[test]
+    fn load_input_settles_format_from_content() {
+        let encoded = STANDARD.encode(zip_with(&["mimetype", "Contents/section0.xml"]));
+        let payload = load_input(&json!({"base64": encoded})).expect("payload");
+        assert_eq!(payload.format, InputFormat::Hwpx);
+        assert_eq!(payload.detection_method, "magic");
+
+        let payload =
+            load_input(&json!({"base64": encoded, "format": "hwp"})).expect("explicit format");
+        assert_eq!(payload.format, InputFormat::Hwp);
+        assert_eq!(payload.detection_method, "explicit");
+    }
 }
@@ -1,8 +1,15 @@
 use anyhow::{Context, Result};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
 use clap::{Args, Parser, Subcommand, ValueEnum};
 use serde_json::{Map, Value, json};
-use std::io::{self, BufRead, Write};
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, BufRead, Read, Write};
 use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
 
 mod input;
 mod mcp;
@@ -57,6 +64,23 @@ impl FormatArg {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum InputSourceArg {
+    Path,
+    Base64,
+    Url,
+}
+
+impl InputSourceArg {
+    fn to_source(self) -> input::InputSource {
+        match self {
+            InputSourceArg::Path => input::InputSource::Path,
+            InputSourceArg::Base64 => input::InputSource::Base64,
+            InputSourceArg::Url => input::InputSource::Url,
+        }
+    }
+}
+
 #[derive(Args, Clone)]
 struct ExtractTextArgs {
     #[command(flatten)]
@@ -73,6 +97,9 @@ struct ExtractTextArgs {
     /// Normalize whitespace (true/false)
     #[arg(long)]
     normalize_whitespace: Option<bool>,
+    /// Footnote placement (inline, endnotes, omit)
+    #[arg(long, value_parser = ["inline", "endnotes", "omit"])]
+    footnotes: Option<String>,
 }
 
 #[derive(Args, Clone)]
@@ -100,6 +127,176 @@ struct SummarizeStructureArgs {
     /// Preview character length
     #[arg(long)]
     preview_chars: Option<u64>,
+    /// Show control characters as escape sequences in previews
+    #[arg(long)]
+    show_controls: bool,
+}
+
+#[derive(Args, Clone)]
+struct CountStatsArgs {
+    #[command(flatten)]
+    input: InputArgs,
+    /// Output JSON structuredContent
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args, Clone)]
+struct ValidateArgs {
+    #[command(flatten)]
+    input: InputArgs,
+    /// Output JSON structuredContent
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum RenderOutputArg {
+    Inline,
+    Resource,
+}
+
+impl RenderOutputArg {
+    fn as_str(self) -> &'static str {
+        match self {
+            RenderOutputArg::Inline => "inline",
+            RenderOutputArg::Resource => "resource",
+        }
+    }
+}
+
+#[derive(Args, Clone)]
+struct RenderSvgArgs {
+    #[command(flatten)]
+    input: InputArgs,
+    /// Output JSON structuredContent
+    #[arg(long)]
+    json: bool,
+    /// Page to render (1-based)
+    #[arg(long, conflicts_with_all = ["pages", "all"])]
+    page: Option<u64>,
+    /// Comma-separated pages to render (1-based)
+    #[arg(long, value_delimiter = ',', conflicts_with = "all")]
+    pages: Vec<u64>,
+    /// Render every page
+    #[arg(long)]
+    all: bool,
+    /// Return SVG inline or write each page to a file resource
+    #[arg(long, value_enum)]
+    output: Option<RenderOutputArg>,
+}
+
+#[derive(Args, Clone)]
+struct ConvertArgs {
+    #[command(flatten)]
+    input: InputArgs,
+    /// Output JSON structuredContent
+    #[arg(long)]
+    json: bool,
+    /// Target format
+    #[arg(long, value_parser = ["hwp", "hwpx", "txt", "md"])]
+    to: String,
+    /// File to write the converted document to
+    #[arg(long)]
+    output_path: Option<String>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ImagesArg {
+    None,
+    Metadata,
+    Inline,
+    Resource,
+}
+
+impl ImagesArg {
+    fn as_str(self) -> &'static str {
+        match self {
+            ImagesArg::None => "none",
+            ImagesArg::Metadata => "metadata",
+            ImagesArg::Inline => "inline",
+            ImagesArg::Resource => "resource",
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum TargetArg {
+    Hwp,
+    Hwpx,
+}
+
+impl TargetArg {
+    fn as_str(self) -> &'static str {
+        match self {
+            TargetArg::Hwp => "hwp",
+            TargetArg::Hwpx => "hwpx",
+        }
+    }
+}
+
+#[derive(Args, Clone)]
+struct ExtractRichArgs {
+    #[command(flatten)]
+    input: InputArgs,
+    /// Output JSON structuredContent
+    #[arg(long)]
+    json: bool,
+    /// How images are returned
+    #[arg(long, value_enum)]
+    images: Option<ImagesArg>,
+    /// Skip images larger than this many bytes
+    #[arg(long)]
+    max_image_bytes: Option<u64>,
+    /// Directory for image files when --images resource
+    #[arg(long)]
+    output_path: Option<String>,
+}
+
+#[derive(Args, Clone)]
+struct CreateRichArgs {
+    /// JSON file with the document spec (blocks, styles); reads stdin when omitted or "-"
+    #[arg(long)]
+    spec: Option<String>,
+    /// Output format
+    #[arg(long, value_enum)]
+    to: Option<TargetArg>,
+    /// File to write the document to
+    #[arg(long)]
+    output_path: Option<String>,
+    /// Output JSON structuredContent
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args, Clone)]
+#[command(
+    group(
+        clap::ArgGroup::new("content")
+            .required(true)
+            .multiple(false)
+            .args(["text", "spec_file", "spec_stdin"])
+    )
+)]
+struct CreateArgs {
+    /// Plain text; each line becomes a paragraph
+    #[arg(long)]
+    text: Option<String>,
+    /// JSON file with a rich document spec (blocks, styles)
+    #[arg(long)]
+    spec_file: Option<String>,
+    /// Read the rich document spec JSON from stdin
+    #[arg(long)]
+    spec_stdin: bool,
+    /// Output format for rich documents
+    #[arg(long, value_enum, conflicts_with = "text")]
+    to: Option<TargetArg>,
+    /// File to write the document to
+    #[arg(long)]
+    output_path: Option<String>,
+    /// Output JSON structuredContent
+    #[arg(long)]
+    json: bool,
 }
 
 #[derive(Subcommand)]
@@ -109,6 +306,24 @@ enum Commands {
         /// Serve MCP over stdio (NDJSON)
         #[arg(long)]
         stdio: bool,
+        /// Input sources tools may read from (default: path,base64)
+        #[arg(long, value_enum, value_delimiter = ',')]
+        allow_input: Vec<InputSourceArg>,
+        /// Warm up the parser and renderer before accepting requests
+        #[arg(long)]
+        preload: bool,
+        /// Document to parse and render during --preload instead of the built-in sample
+        #[arg(long, requires = "preload")]
+        preload_sample: Option<String>,
+        /// JSON file mapping font names to replacement fonts for render_svg
+        #[arg(long)]
+        font_map: Option<String>,
+        /// Maximum number of tools/call requests handled at the same time
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..=64))]
+        max_concurrency: u32,
+        /// Delete resource files older than this many minutes, checked on every tools/call
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        resource_ttl_minutes: Option<u64>,
     },
     /// Extract text from HWP inputs
     ExtractText(ExtractTextArgs),
@@ -116,21 +331,63 @@ enum Commands {
     InspectMetadata(InspectMetadataArgs),
     /// Summarize document structure
     SummarizeStructure(SummarizeStructureArgs),
-    /// Render SVG for pages or elements
-    RenderSvg,
-    /// Convert HWP to other formats
-    Convert,
-    /// Create new HWP documents
-    Create,
+    /// Count characters, words, paragraphs, and sections
+    CountStats(CountStatsArgs),
+    /// Check that a document parses and report recoverable problems
+    Validate(ValidateArgs),
+    /// Extract paragraphs, tables, and images as structured blocks
+    ExtractRich(ExtractRichArgs),
+    /// Create an HWP/HWPX document from a JSON document spec
+    CreateRich(CreateRichArgs),
+    /// Render document pages as SVG
+    RenderSvg(RenderSvgArgs),
+    /// Convert HWP/HWPX to another format
+    Convert(ConvertArgs),
+    /// Create an HWP document from text or a JSON document spec
+    Create(CreateArgs),
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    // Resolve the limits up front so invalid MCP_HWP_MAX_* values are reported at startup.
+    mcp::contracts::limits();
 
     match cli.command {
-        Commands::Serve { stdio } => {
+        Commands::Serve {
+            stdio,
+            allow_input,
+            preload,
+            preload_sample,
+            font_map,
+            max_concurrency,
+            resource_ttl_minutes,
+        } => {
+            if let Some(path) = font_map {
+                let text = std::fs::read_to_string(&path)
+                    .with_context(|| format!("failed to read --font-map {path}"))?;
+                let value: serde_json::Value = serde_json::from_str(&text)
+                    .with_context(|| format!("invalid --font-map {path}"))?;
+                let map = tools::render_svg::parse_font_map(&value)
+                    .map_err(|err| anyhow::anyhow!("invalid --font-map {path}: {err}"))?;
+                tools::render_svg::set_font_map(map);
+            }
+            // Preload runs before input gating so the warm-up sample is always readable.
+            if preload {
+                run_preload(preload_sample.as_deref());
+            }
+            if !allow_input.is_empty() {
+                input::set_allowed_sources(
+                    allow_input
+                        .into_iter()
+                        .map(InputSourceArg::to_source)
+                        .collect(),
+                );
+            }
+            if let Some(minutes) = resource_ttl_minutes {
+                mcp::resources::set_ttl(Duration::from_secs(minutes * 60));
+            }
             if stdio {
-                run_stdio_server()
+                run_stdio_server(max_concurrency as usize)
             } else {
                 anyhow::bail!("only --stdio transport is supported")
             }
@@ -138,15 +395,49 @@ fn main() -> Result<()> {
         Commands::ExtractText(args) => run_extract_text(args),
         Commands::InspectMetadata(args) => run_inspect_metadata(args),
         Commands::SummarizeStructure(args) => run_summarize_structure(args),
-        Commands::RenderSvg => stub("render-svg"),
-        Commands::Convert => stub("convert"),
-        Commands::Create => stub("create"),
+        Commands::CountStats(args) => run_count_stats(args),
+        Commands::Validate(args) => run_validate(args),
+        Commands::ExtractRich(args) => run_extract_rich(args),
+        Commands::CreateRich(args) => run_create_rich(args),
+        Commands::RenderSvg(args) => run_render_svg(args),
+        Commands::Convert(args) => run_convert(args),
+        Commands::Create(args) => run_create(args),
     }
 }
 
-fn stub(command: &str) -> Result<()> {
-    println!("{command} stub (not implemented yet)");
-    Ok(())
+fn run_preload(sample: Option<&str>) {
+    let args = match sample {
+        Some(path) => json!({ "path": path }),
+        None => {
+            let mut writer = hwpers::HwpWriter::new();
+            let bytes = writer
+                .add_paragraph("preload")
+                .and_then(|_| writer.to_bytes());
+            match bytes {
+                Ok(bytes) => json!({ "base64": STANDARD.encode(bytes) }),
+                Err(err) => {
+                    eprintln!("preload skipped: failed to build sample document: {err}");
+                    return;
+                }
+            }
+        }
+    };
+
+    for result in [
+        tools::extract_text::call(&args),
+        tools::render_svg::call(&args),
+    ] {
+        if result.get("isError").and_then(|value| value.as_bool()) == Some(true) {
+            let message = result
+                .get("structuredContent")
+                .and_then(|value| value.get("error"))
+                .and_then(|value| value.get("message"))
+                .and_then(|value| value.as_str())
+                .unwrap_or("tool error");
+            eprintln!("preload failed: {message}");
+            return;
+        }
+    }
 }
 
 fn run_extract_text(args: ExtractTextArgs) -> Result<()> {
@@ -163,6 +454,9 @@ fn run_extract_text(args: ExtractTextArgs) -> Result<()> {
             json!(normalize_whitespace),
         );
     }
+    if let Some(footnotes) = args.footnotes {
+        map.insert("footnotes".to_string(), json!(footnotes));
+    }
     let result = tools::extract_text::call(&Value::Object(map));
     print_tool_result(result, args.json)
 }
@@ -187,10 +481,121 @@ fn run_summarize_structure(args: SummarizeStructureArgs) -> Result<()> {
     if let Some(preview_chars) = args.preview_chars {
         map.insert("preview_chars".to_string(), json!(preview_chars));
     }
+    if args.show_controls {
+        map.insert("show_controls".to_string(), json!(true));
+    }
     let result = tools::summarize_structure::call(&Value::Object(map));
     print_tool_result(result, args.json)
 }
 
+fn run_count_stats(args: CountStatsArgs) -> Result<()> {
+    let map = build_input_args(&args.input);
+    let result = tools::count_stats::call(&Value::Object(map));
+    print_tool_result(result, args.json)
+}
+
+fn run_validate(args: ValidateArgs) -> Result<()> {
+    let map = build_input_args(&args.input);
+    let result = tools::validate::call(&Value::Object(map));
+    print_tool_result(result, args.json)
+}
+
+fn run_render_svg(args: RenderSvgArgs) -> Result<()> {
+    let mut map = build_input_args(&args.input);
+    if let Some(page) = args.page {
+        map.insert("page".to_string(), json!(page));
+    }
+    if !args.pages.is_empty() {
+        map.insert("pages".to_string(), json!(args.pages));
+    }
+    if args.all {
+        map.insert("all".to_string(), json!(true));
+    }
+    if let Some(output) = args.output {
+        map.insert("output".to_string(), json!(output.as_str()));
+    }
+    let result = tools::render_svg::call(&Value::Object(map));
+    print_tool_result(result, args.json)
+}
+
+fn run_convert(args: ConvertArgs) -> Result<()> {
+    let mut map = build_input_args(&args.input);
+    map.insert("to".to_string(), json!(args.to));
+    if let Some(output_path) = args.output_path {
+        map.insert("output_path".to_string(), json!(output_path));
+    }
+    let result = tools::convert::call(&Value::Object(map));
+    print_tool_result(result, args.json)
+}
+
+fn run_extract_rich(args: ExtractRichArgs) -> Result<()> {
+    let mut map = build_input_args(&args.input);
+    if let Some(images) = args.images {
+        map.insert("images".to_string(), json!(images.as_str()));
+    }
+    if let Some(max_image_bytes) = args.max_image_bytes {
+        map.insert("max_image_bytes".to_string(), json!(max_image_bytes));
+    }
+    if let Some(output_path) = args.output_path {
+        map.insert("output_path".to_string(), json!(output_path));
+    }
+    let result = tools::extract_rich::call(&Value::Object(map));
+    print_tool_result(result, args.json)
+}
+
+fn run_create_rich(args: CreateRichArgs) -> Result<()> {
+    let spec = args.spec.as_deref().filter(|path| *path != "-");
+    let document = read_document_spec(spec)?;
+    let result = create_rich(document, args.to, args.output_path);
+    print_tool_result(result, args.json)
+}
+
+fn run_create(args: CreateArgs) -> Result<()> {
+    let result = match args.text {
+        Some(text) => {
+            let mut map = Map::new();
+            map.insert("text".to_string(), json!(text));
+            if let Some(output_path) = args.output_path {
+                map.insert("output_path".to_string(), json!(output_path));
+            }
+            tools::create_document::call(&Value::Object(map))
+        }
+        None => {
+            let document = read_document_spec(args.spec_file.as_deref())?;
+            create_rich(document, args.to, args.output_path)
+        }
+    };
+    print_tool_result(result, args.json)
+}
+
+/// Loads a `create_rich_document` spec from `path`, or from stdin when no path is given.
+fn read_document_spec(path: Option<&str>) -> Result<Value> {
+    let text = match path {
+        Some(path) => std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read document spec {path}"))?,
+        None => {
+            let mut text = String::new();
+            io::stdin()
+                .read_to_string(&mut text)
+                .context("failed to read document spec from stdin")?;
+            text
+        }
+    };
+    serde_json::from_str(&text).context("invalid document spec JSON")
+}
+
+fn create_rich(document: Value, to: Option<TargetArg>, output_path: Option<String>) -> Value {
+    let mut map = Map::new();
+    map.insert("document".to_string(), document);
+    if let Some(to) = to {
+        map.insert("to".to_string(), json!(to.as_str()));
+    }
+    if let Some(output_path) = output_path {
+        map.insert("output_path".to_string(), json!(output_path));
+    }
+    tools::create_rich_document::call(&Value::Object(map))
+}
+
 fn build_input_args(input: &InputArgs) -> Map<String, Value> {
     let mut map = Map::new();
     if let Some(path) = &input.path {
@@ -243,13 +648,52 @@ fn print_tool_result(result: Value, json_output: bool) -> Result<()> {
     Ok(())
 }
 
-fn run_stdio_server() -> Result<()> {
-    let stdin = io::stdin();
-    let stdout = io::stdout();
-    let reader = stdin.lock().lines();
-    let mut writer = io::BufWriter::new(stdout.lock());
+/// Reads NDJSON requests from stdin. `tools/call` requests run on a pool of
+/// `max_concurrency` workers and are answered as they finish, so responses may arrive out of
+/// order; clients correlate them by `id`. Other methods are answered inline.
+fn run_stdio_server(max_concurrency: usize) -> Result<()> {
+    let writer = Arc::new(Mutex::new(io::BufWriter::new(io::stdout())));
+    let queue = Arc::new(CallQueue::default());
+    let in_flight = InFlight::default();
+
+    let mut workers = Vec::with_capacity(max_concurrency);
+    for index in 0..max_concurrency {
+        let queue = Arc::clone(&queue);
+        let writer = Arc::clone(&writer);
+        let in_flight = in_flight.clone();
+        let worker = thread::Builder::new()
+            .name(format!("mcp-hwp-worker-{index}"))
+            .spawn(move || {
+                while let Some((id, request, cancelled)) = queue.pop() {
+                    let notify = |notification: &Value| {
+                        let _ = write_response(&writer, notification);
+                    };
+                    let result = (!cancelled.load(Ordering::Relaxed)).then(|| {
+                        tools::with_cancellation(Arc::clone(&cancelled), || {
+                            handle_tool_call(&request, &notify)
+                        })
+                    });
+                    in_flight.finish(&id);
+                    // A cancelled request gets no response at all.
+                    let Some(result) = result.filter(|_| !cancelled.load(Ordering::Relaxed)) else {
+                        continue;
+                    };
+                    let response = json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "result": result
+                    });
+                    if write_response(&writer, &response).is_err() {
+                        break;
+                    }
+                }
+            })
+            .context("failed to spawn worker thread")?;
+        workers.push(worker);
+    }
 
-    for line in reader {
+    let stdin = io::stdin();
+    for line in stdin.lock().lines() {
         let line = line.context("failed to read stdin")?;
         if line.trim().is_empty() {
             continue;
@@ -257,7 +701,20 @@ fn run_stdio_server() -> Result<()> {
 
         let request: serde_json::Value = match serde_json::from_str(&line) {
             Ok(value) => value,
-            Err(_) => continue,
+            Err(_) => {
+                write_response(
+                    &writer,
+                    &json!({
+                        "jsonrpc": "2.0",
+                        "id": null,
+                        "error": {
+                            "code": -32700,
+                            "message": "Parse error"
+                        }
+                    }),
+                )?;
+                continue;
+            }
         };
 
         let method = request.get("method").and_then(|value| value.as_str());
@@ -269,7 +726,8 @@ fn run_stdio_server() -> Result<()> {
                 "result": {
                     "protocolVersion": "2025-11-25",
                     "capabilities": {
-                        "tools": {}
+                        "tools": {},
+                        "resources": {}
                     },
                     "serverInfo": {
                         "name": env!("CARGO_PKG_NAME"),
@@ -277,36 +735,220 @@ fn run_stdio_server() -> Result<()> {
                     }
                 }
             })),
-            (Some("tools/list"), Some(id)) => Some(json!({
+            (Some("tools/list"), Some(id)) => Some(match mcp::list_tools(request.get("params")) {
+                Ok(result) => json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "result": result
+                }),
+                Err(err) => json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": {
+                        "code": err.code,
+                        "message": err.message
+                    }
+                }),
+            }),
+            (Some("resources/list"), Some(id)) => Some(json!({
                 "jsonrpc": "2.0",
                 "id": id,
                 "result": {
-                    "tools": mcp::tool_definitions()
+                    "resources": mcp::resources::list()
                 }
             })),
+            (Some("resources/read"), Some(id)) => {
+                Some(match mcp::resources::read(request.get("params")) {
+                    Ok(result) => json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "result": result
+                    }),
+                    Err(err) => json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": {
+                            "code": err.code,
+                            "message": err.message
+                        }
+                    }),
+                })
+            }
+            (Some("ping"), Some(id)) => Some(json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "result": {}
+            })),
             (Some("tools/call"), Some(id)) => {
-                let result = handle_tool_call(&request);
-                Some(json!({
-                    "jsonrpc": "2.0",
-                    "id": id,
-                    "result": result
-                }))
+                let cancelled = in_flight.start(&id);
+                match queue.push((id.clone(), request, cancelled)) {
+                    Ok(()) => None,
+                    Err(_) => {
+                        in_flight.finish(&id);
+                        Some(json!({
+                            "jsonrpc": "2.0",
+                            "id": id,
+                            "error": {
+                                "code": -32000,
+                                "message": format!(
+                                    "server busy: {} calls are already queued",
+                                    mcp::contracts::MAX_QUEUED_CALLS
+                                )
+                            }
+                        }))
+                    }
+                }
             }
+            (Some(_), Some(id)) => Some(json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": {
+                    "code": -32601,
+                    "message": "Method not found"
+                }
+            })),
+            (Some("notifications/cancelled"), None) => {
+                if let Some(id) = request
+                    .get("params")
+                    .and_then(|params| params.get("requestId"))
+                {
+                    in_flight.cancel(id);
+                    // A call still waiting for a worker leaves the queue at once.
+                    if queue.remove(id) {
+                        in_flight.finish(id);
+                    }
+                }
+                None
+            }
+            // Notifications carry no id and get no response.
             _ => None,
         };
 
         if let Some(response) = response {
-            let serialized =
-                serde_json::to_string(&response).context("failed to serialize response")?;
-            writeln!(writer, "{serialized}").context("failed to write response")?;
-            writer.flush().context("failed to flush response")?;
+            write_response(&writer, &response)?;
         }
     }
 
+    // Let in-flight calls finish and answer before exiting.
+    queue.close();
+    for worker in workers {
+        let _ = worker.join();
+    }
+    // Resource files only live as long as the session that wrote them.
+    mcp::resources::cleanup(None);
+
     Ok(())
 }
 
-fn handle_tool_call(request: &serde_json::Value) -> serde_json::Value {
+/// A queued `tools/call`: its id, the request and its cancellation flag.
+type Call = (Value, Value, Arc<AtomicBool>);
+
+/// `tools/call` requests waiting for a worker. The reader only ever pushes, so it keeps
+/// answering pings and recording cancellations however far behind the workers are; past
+/// [`mcp::contracts::MAX_QUEUED_CALLS`] a push is refused instead of blocking.
+#[derive(Default)]
+struct CallQueue {
+    state: Mutex<CallQueueState>,
+    ready: Condvar,
+}
+
+#[derive(Default)]
+struct CallQueueState {
+    calls: VecDeque<Call>,
+    closed: bool,
+}
+
+impl CallQueue {
+    /// Queues `call`, handing it back when the queue is full.
+    fn push(&self, call: Call) -> Result<(), Call> {
+        let Ok(mut state) = self.state.lock() else {
+            return Err(call);
+        };
+        if state.calls.len() >= mcp::contracts::MAX_QUEUED_CALLS {
+            return Err(call);
+        }
+        state.calls.push_back(call);
+        self.ready.notify_one();
+        Ok(())
+    }
+
+    /// Waits for the next call; `None` once the queue is closed and drained.
+    fn pop(&self) -> Option<Call> {
+        let mut state = self.state.lock().ok()?;
+        loop {
+            if let Some(call) = state.calls.pop_front() {
+                return Some(call);
+            }
+            if state.closed {
+                return None;
+            }
+            state = self.ready.wait(state).ok()?;
+        }
+    }
+
+    /// Drops the queued call with `id`; false when no worker-bound call has it.
+    fn remove(&self, id: &Value) -> bool {
+        let Ok(mut state) = self.state.lock() else {
+            return false;
+        };
+        let before = state.calls.len();
+        state.calls.retain(|(queued, _, _)| queued != id);
+        state.calls.len() != before
+    }
+
+    /// Lets the workers exit once the calls already queued are done.
+    fn close(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.closed = true;
+        }
+        self.ready.notify_all();
+    }
+}
+
+/// Cancellation flags of the `tools/call` requests that are queued or running, keyed by the
+/// JSON text of their id so `1` and `"1"` stay distinct.
+#[derive(Clone, Default)]
+struct InFlight(Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>);
+
+impl InFlight {
+    fn start(&self, id: &Value) -> Arc<AtomicBool> {
+        let flag = Arc::new(AtomicBool::new(false));
+        if let Ok(mut requests) = self.0.lock() {
+            requests.insert(id.to_string(), Arc::clone(&flag));
+        }
+        flag
+    }
+
+    fn finish(&self, id: &Value) {
+        if let Ok(mut requests) = self.0.lock() {
+            requests.remove(&id.to_string());
+        }
+    }
+
+    /// Flags `id` as cancelled; unknown or already answered ids are ignored.
+    fn cancel(&self, id: &Value) {
+        if let Ok(requests) = self.0.lock()
+            && let Some(flag) = requests.get(&id.to_string())
+        {
+            flag.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Writes one response line; the lock keeps concurrent workers from interleaving lines.
+fn write_response(writer: &Mutex<io::BufWriter<io::Stdout>>, response: &Value) -> Result<()> {
+    let serialized = serde_json::to_string(response).context("failed to serialize response")?;
+    let mut writer = writer
+        .lock()
+        .map_err(|_| anyhow::anyhow!("response writer poisoned"))?;
+    writeln!(writer, "{serialized}").context("failed to write response")?;
+    writer.flush().context("failed to flush response")?;
+    Ok(())
+}
+
+/// Runs one `tools/call`. When the caller sets `params._meta.progressToken`, long tools send
+/// `notifications/progress` through `notify` before the result; without it nothing is sent.
+fn handle_tool_call(request: &serde_json::Value, notify: &dyn Fn(&Value)) -> serde_json::Value {
     let params = request.get("params");
     let Some(params) = params.and_then(|value| value.as_object()) else {
         return tools::error_result(mcp::errors::INVALID_INPUT, "params must be an object", None);
@@ -326,15 +968,65 @@ fn handle_tool_call(request: &serde_json::Value) -> serde_json::Value {
         .cloned()
         .unwrap_or_else(|| json!({}));
 
+    mcp::resources::purge_expired();
+
+    let progress_token = params
+        .get("_meta")
+        .and_then(|meta| meta.get("progressToken"))
+        .filter(|token| token.is_string() || token.is_i64() || token.is_u64());
+    match (name, progress_token) {
+        (mcp::contracts::TOOL_RENDER_SVG, Some(token)) => {
+            tools::render_svg::call_with_progress(&args, &mut |progress, total| {
+                notify(&json!({
+                    "jsonrpc": "2.0",
+                    "method": "notifications/progress",
+                    "params": {
+                        "progressToken": token,
+                        "progress": progress,
+                        "total": total
+                    }
+                }));
+            })
+        }
+        _ => dispatch_tool(name, &args),
+    }
+}
+
+fn dispatch_tool(name: &str, args: &serde_json::Value) -> serde_json::Value {
     match name {
-        mcp::contracts::TOOL_EXTRACT_TEXT => tools::extract_text::call(&args),
-        mcp::contracts::TOOL_INSPECT_METADATA => tools::inspect_metadata::call(&args),
-        mcp::contracts::TOOL_SUMMARIZE_STRUCTURE => tools::summarize_structure::call(&args),
-        mcp::contracts::TOOL_RENDER_SVG => tools::render_svg::call(&args),
-        mcp::contracts::TOOL_CONVERT => tools::convert::call(&args),
-        mcp::contracts::TOOL_CREATE_DOCUMENT => tools::create_document::call(&args),
-        mcp::contracts::TOOL_CREATE_RICH_DOCUMENT => tools::create_rich_document::call(&args),
-        mcp::contracts::TOOL_EXTRACT_RICH => tools::extract_rich::call(&args),
+        mcp::contracts::TOOL_EXTRACT_TEXT => tools::extract_text::call(args),
+        mcp::contracts::TOOL_INSPECT_METADATA => tools::inspect_metadata::call(args),
+        mcp::contracts::TOOL_SUMMARIZE_STRUCTURE => tools::summarize_structure::call(args),
+        mcp::contracts::TOOL_RENDER_SVG => tools::render_svg::call(args),
+        mcp::contracts::TOOL_CONVERT => tools::convert::call(args),
+        mcp::contracts::TOOL_CREATE_DOCUMENT => tools::create_document::call(args),
+        mcp::contracts::TOOL_CREATE_RICH_DOCUMENT => tools::create_rich_document::call(args),
+        mcp::contracts::TOOL_EXTRACT_RICH => tools::extract_rich::call(args),
+        mcp::contracts::TOOL_FINGERPRINT => tools::fingerprint::call(args),
+        mcp::contracts::TOOL_SIMILARITY => tools::similarity::call(args),
+        mcp::contracts::TOOL_MERGE => tools::merge::call(args),
+        mcp::contracts::TOOL_EXTRACT_PREVIEW => tools::extract_preview::call(args),
+        mcp::contracts::TOOL_SPLIT => tools::split::call(args),
+        mcp::contracts::TOOL_COUNT_STATS => tools::count_stats::call(args),
+        mcp::contracts::TOOL_EXTRACT_LINKS => tools::extract_links::call(args),
+        mcp::contracts::TOOL_TO_MARKDOWN => tools::to_markdown::call(args),
+        mcp::contracts::TOOL_TO_HTML => tools::to_html::call(args),
+        mcp::contracts::TOOL_EXTRACT_DATES => tools::extract_dates::call(args),
+        mcp::contracts::TOOL_SEARCH_TEXT => tools::search_text::call(args),
+        mcp::contracts::TOOL_REPLACE_TEXT => tools::replace_text::call(args),
+        mcp::contracts::TOOL_EXPLAIN => tools::explain::call(args),
+        mcp::contracts::TOOL_PAGE_COUNT => tools::page_count::call(args),
+        mcp::contracts::TOOL_LIST_STYLES => tools::list_styles::call(args),
+        mcp::contracts::TOOL_BATCH => tools::batch::call(args, &dispatch_tool),
+        mcp::contracts::TOOL_EXTRACT_IMAGES => tools::extract_images::call(args),
+        mcp::contracts::TOOL_EXTRACT_NOTES => tools::extract_notes::call(args),
+        mcp::contracts::TOOL_EXTRACT_TOC => tools::extract_toc::call(args),
+        mcp::contracts::TOOL_DIFF => tools::diff::call(args),
+        mcp::contracts::TOOL_REDACT => tools::redact::call(args),
+        mcp::contracts::TOOL_VALIDATE => tools::validate::call(args),
+        mcp::contracts::TOOL_TO_JSON => tools::to_json::call(args),
+        mcp::contracts::TOOL_SERVER_INFO => tools::server_info::call(args),
+        mcp::contracts::TOOL_CLEANUP => tools::cleanup::call(args),
         _ => tools::error_result(
             mcp::errors::INVALID_INPUT,
             format!("tool not implemented: {name}"),
@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 
 use serde_json::json;
+use std::sync::OnceLock;
 
 pub const TOOL_EXTRACT_TEXT: &str = "hwp.extract_text";
 pub const TOOL_INSPECT_METADATA: &str = "hwp.inspect_metadata";
@@ -10,11 +11,93 @@ pub const TOOL_CONVERT: &str = "hwp.convert";
 pub const TOOL_CREATE_DOCUMENT: &str = "hwp.create_document";
 pub const TOOL_CREATE_RICH_DOCUMENT: &str = "hwp.create_rich_document";
 pub const TOOL_EXTRACT_RICH: &str = "hwp.extract_rich";
+pub const TOOL_FINGERPRINT: &str = "hwp.fingerprint";
+pub const TOOL_SIMILARITY: &str = "hwp.similarity";
+pub const TOOL_MERGE: &str = "hwp.merge";
+pub const TOOL_EXTRACT_PREVIEW: &str = "hwp.extract_preview";
+pub const TOOL_SPLIT: &str = "hwp.split";
+pub const TOOL_COUNT_STATS: &str = "hwp.count_stats";
+pub const TOOL_EXTRACT_LINKS: &str = "hwp.extract_links";
+pub const TOOL_TO_MARKDOWN: &str = "hwp.to_markdown";
+pub const TOOL_TO_HTML: &str = "hwp.to_html";
+pub const TOOL_EXTRACT_DATES: &str = "hwp.extract_dates";
+pub const TOOL_SEARCH_TEXT: &str = "hwp.search_text";
+pub const TOOL_REPLACE_TEXT: &str = "hwp.replace_text";
+pub const TOOL_EXPLAIN: &str = "hwp.explain";
+pub const TOOL_PAGE_COUNT: &str = "hwp.page_count";
+pub const TOOL_LIST_STYLES: &str = "hwp.list_styles";
+pub const TOOL_BATCH: &str = "hwp.batch";
+pub const TOOL_EXTRACT_IMAGES: &str = "hwp.extract_images";
+pub const TOOL_EXTRACT_NOTES: &str = "hwp.extract_notes";
+pub const TOOL_EXTRACT_TOC: &str = "hwp.extract_toc";
+pub const TOOL_DIFF: &str = "hwp.diff";
+pub const TOOL_REDACT: &str = "hwp.redact";
+pub const TOOL_VALIDATE: &str = "hwp.validate";
+pub const TOOL_TO_JSON: &str = "hwp.to_json";
+pub const TOOL_SERVER_INFO: &str = "hwp.server_info";
+pub const TOOL_CLEANUP: &str = "hwp.cleanup";
 
-pub const MAX_INPUT_BYTES: u64 = 50 * 1024 * 1024;
-pub const MAX_OUTPUT_BYTES: u64 = 20 * 1024 * 1024;
-pub const MAX_SVG_OUTPUT_BYTES: u64 = 50 * 1024 * 1024;
-pub const MAX_PARSE_MS: u64 = 10_000;
+/// Version of the linked `hwpers` crate, read from `Cargo.lock` by `build.rs`.
+pub const HWPERS_VERSION: &str = env!("HWPERS_VERSION");
+
+pub const DEFAULT_MAX_INPUT_BYTES: u64 = 50 * 1024 * 1024;
+pub const DEFAULT_MAX_OUTPUT_BYTES: u64 = 20 * 1024 * 1024;
+pub const DEFAULT_MAX_SVG_OUTPUT_BYTES: u64 = 50 * 1024 * 1024;
+pub const DEFAULT_MAX_PARSE_MS: u64 = 10_000;
+pub const DEFAULT_MAX_TABLE_ROWS: u64 = 10_000;
+pub const DEFAULT_MAX_TABLE_COLS: u64 = 256;
+pub const MAX_BATCH_FILES: usize = 1_000;
+/// `tools/call` requests the stdio server holds for its workers; calls past it are refused.
+pub const MAX_QUEUED_CALLS: usize = 1_024;
+
+/// Byte, time and table-size limits in effect for this process, resolved once from the environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    pub max_input_bytes: u64,
+    pub max_output_bytes: u64,
+    pub max_svg_output_bytes: u64,
+    pub max_parse_ms: u64,
+    pub max_table_rows: u64,
+    pub max_table_cols: u64,
+}
+
+static LIMITS: OnceLock<Limits> = OnceLock::new();
+
+/// Returns the effective limits, reading `MCP_HWP_MAX_*` on first use.
+pub fn limits() -> &'static Limits {
+    LIMITS.get_or_init(|| Limits::from_lookup(|name| std::env::var(name).ok()))
+}
+
+impl Limits {
+    /// Each variable must be a positive integer; anything else is reported on stderr and the
+    /// default is used instead.
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let read = |name: &str, default: u64| match lookup(name) {
+            None => default,
+            Some(value) => match value.trim().parse::<u64>() {
+                Ok(parsed) if parsed > 0 => parsed,
+                _ => {
+                    eprintln!(
+                        "warning: ignoring {name}={value:?}: expected a positive integer; \
+                         using the default {default}"
+                    );
+                    default
+                }
+            },
+        };
+        Limits {
+            max_input_bytes: read("MCP_HWP_MAX_INPUT_BYTES", DEFAULT_MAX_INPUT_BYTES),
+            max_output_bytes: read("MCP_HWP_MAX_OUTPUT_BYTES", DEFAULT_MAX_OUTPUT_BYTES),
+            max_svg_output_bytes: read(
+                "MCP_HWP_MAX_SVG_OUTPUT_BYTES",
+                DEFAULT_MAX_SVG_OUTPUT_BYTES,
+            ),
+            max_parse_ms: read("MCP_HWP_MAX_PARSE_MS", DEFAULT_MAX_PARSE_MS),
+            max_table_rows: read("MCP_HWP_MAX_TABLE_ROWS", DEFAULT_MAX_TABLE_ROWS),
+            max_table_cols: read("MCP_HWP_MAX_TABLE_COLS", DEFAULT_MAX_TABLE_COLS),
+        }
+    }
+}
 
 pub fn extract_text_schema() -> serde_json::Value {
     json!({
@@ -22,14 +105,34 @@ pub fn extract_text_schema() -> serde_json::Value {
         "properties": {
             "path": { "type": "string" },
             "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
             "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] },
             "max_chars": { "type": "integer", "minimum": 0 },
             "include_newlines": { "type": "boolean" },
-            "normalize_whitespace": { "type": "boolean" }
+            "by_section": { "type": "boolean" },
+            "granularity": { "type": "string", "enum": ["document", "section", "paragraph"] },
+            "normalize_whitespace": { "type": "boolean" },
+            "footnotes": { "type": "string", "enum": ["inline", "endnotes", "omit"] },
+            "table_format": {
+                "type": "string",
+                "enum": ["ignore", "tsv", "markdown"],
+                "default": "ignore"
+            },
+            "representations": {
+                "type": "array",
+                "items": { "type": "string", "enum": ["text", "markdown"] },
+                "minItems": 1
+            },
+            "ocr": { "type": "boolean" },
+            "timeout_ms": { "type": "integer", "minimum": 0 },
+            "partial_on_timeout": { "type": "boolean", "default": false },
+            "output_path": { "type": "string" }
         },
         "oneOf": [
             { "required": ["path"] },
-            { "required": ["base64"] }
+            { "required": ["base64"] },
+            { "required": ["url"] }
         ],
         "additionalProperties": false
     })
@@ -41,11 +144,15 @@ pub fn inspect_metadata_schema() -> serde_json::Value {
         "properties": {
             "path": { "type": "string" },
             "base64": { "type": "string" },
-            "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] }
+            "url": { "type": "string" },
+            "password": { "type": "string" },
+            "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] },
+            "counts": { "type": "boolean", "default": true }
         },
         "oneOf": [
             { "required": ["path"] },
-            { "required": ["base64"] }
+            { "required": ["base64"] },
+            { "required": ["url"] }
         ],
         "additionalProperties": false
     })
@@ -57,14 +164,18 @@ pub fn summarize_structure_schema() -> serde_json::Value {
         "properties": {
             "path": { "type": "string" },
             "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
             "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] },
             "max_sections": { "type": "integer", "minimum": 0 },
             "max_paragraphs_per_section": { "type": "integer", "minimum": 0 },
-            "preview_chars": { "type": "integer", "minimum": 0 }
+            "preview_chars": { "type": "integer", "minimum": 0 },
+            "show_controls": { "type": "boolean" }
         },
         "oneOf": [
             { "required": ["path"] },
-            { "required": ["base64"] }
+            { "required": ["base64"] },
+            { "required": ["url"] }
         ],
         "additionalProperties": false
     })
@@ -76,17 +187,33 @@ pub fn render_svg_schema() -> serde_json::Value {
         "properties": {
             "path": { "type": "string" },
             "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
             "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] },
             "page": { "type": "integer", "minimum": 1 },
             "pages": {
                 "type": "array",
                 "items": { "type": "integer", "minimum": 1 }
             },
-            "output": { "type": "string", "enum": ["inline", "resource"] }
+            "range": { "type": "string", "pattern": "^\\s*[0-9]+(\\s*-\\s*[0-9]+)?(\\s*,\\s*[0-9]+(\\s*-\\s*[0-9]+)?)*\\s*$" },
+            "all": { "type": "boolean", "default": false },
+            "output": { "type": "string", "enum": ["inline", "resource"] },
+            "output_dir": { "type": "string" },
+            "as": { "type": "string", "enum": ["svg", "png"], "default": "svg" },
+            "scale": { "type": "number", "exclusiveMinimum": 0, "maximum": 8 },
+            "dpi": { "type": "number", "exclusiveMinimum": 0, "maximum": 768 },
+            "line_numbers": { "type": "boolean", "default": false },
+            "line_number_start": { "type": "integer", "minimum": 0, "default": 1 },
+            "line_number_interval": { "type": "integer", "minimum": 1, "default": 1 },
+            "font_map": {
+                "type": "object",
+                "additionalProperties": { "type": "string" }
+            }
         },
         "oneOf": [
             { "required": ["path"] },
-            { "required": ["base64"] }
+            { "required": ["base64"] },
+            { "required": ["url"] }
         ],
         "additionalProperties": false
     })
@@ -98,14 +225,22 @@ pub fn convert_schema() -> serde_json::Value {
         "properties": {
             "path": { "type": "string" },
             "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
             "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] },
-            "to": { "type": "string", "enum": ["hwp", "hwpx"] },
-            "output_path": { "type": "string" }
+            "to": { "type": "string", "enum": ["hwp", "hwpx", "txt", "md"] },
+            "output_path": { "type": "string" },
+            "include_base64": { "type": "boolean", "default": false },
+            "compress": { "type": "string", "enum": ["none", "gzip"], "default": "none" },
+            "accept_revisions": { "type": "boolean" },
+            "reject_revisions": { "type": "boolean" },
+            "metadata": { "type": "string", "enum": ["preserve", "strip"] }
         },
         "required": ["to"],
         "oneOf": [
             { "required": ["path"] },
-            { "required": ["base64"] }
+            { "required": ["base64"] },
+            { "required": ["url"] }
         ],
         "additionalProperties": false
     })
@@ -116,7 +251,8 @@ pub fn create_document_schema() -> serde_json::Value {
         "type": "object",
         "properties": {
             "text": { "type": "string" },
-            "output_path": { "type": "string" }
+            "output_path": { "type": "string" },
+            "include_base64": { "type": "boolean", "default": false }
         },
         "required": ["text"],
         "additionalProperties": false
@@ -124,11 +260,146 @@ pub fn create_document_schema() -> serde_json::Value {
 }
 
 pub fn create_rich_document_schema() -> serde_json::Value {
-    json!({
+    let line_spacing = json!({
+        "oneOf": [
+            { "type": "integer", "minimum": 1 },
+            { "type": "string", "pattern": "^\\s*[0-9.]+\\s*(%|pt|mm)$" }
+        ],
+        "description": "percent (160 or \"160%\") or fixed line height (\"12pt\", \"5mm\")"
+    });
+    let text_style = json!({
+        "type": "object",
+        "properties": {
+            "font_name": { "type": "string" },
+            "font_size": { "type": "integer", "minimum": 1 },
+            "bold": { "type": "boolean" },
+            "italic": { "type": "boolean" },
+            "underline": { "type": "boolean" },
+            "color": { "type": "string", "description": "0xRRGGBB (hex), e.g. 0xFF0000" },
+            "line_spacing": line_spacing,
+            "space_before": { "type": "number", "minimum": 0, "description": "points" },
+            "space_after": { "type": "number", "minimum": 0, "description": "points" }
+        },
+        "additionalProperties": false
+    });
+    let default_paragraph_style = json!({
+        "type": "object",
+        "properties": {
+            "line_spacing": line_spacing,
+            "space_before": { "type": "number", "minimum": 0, "description": "points" },
+            "space_after": { "type": "number", "minimum": 0, "description": "points" }
+        },
+        "additionalProperties": false
+    });
+    let paragraph_block = json!({
+        "type": "object",
+        "properties": {
+            "type": { "const": "paragraph" },
+            "text": { "type": "string" },
+            "runs": {
+                "type": "array",
+                "minItems": 1,
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "text": { "type": "string" },
+                        "style_ref": { "type": "string" },
+                        "style": text_style.clone()
+                    },
+                    "required": ["text"],
+                    "additionalProperties": false
+                },
+                "description": "styled pieces of the paragraph, in order; use instead of text"
+            },
+            "style_ref": { "type": "string" },
+            "style": text_style.clone()
+        },
+        "required": ["type"],
+        "oneOf": [
+            { "required": ["text"] },
+            { "required": ["runs"] }
+        ],
+        "additionalProperties": false
+    });
+    let table_block = json!({
+        "type": "object",
+        "properties": {
+            "type": { "const": "table" },
+            "rows": {
+                "type": "array",
+                "items": {
+                    "type": "array",
+                    "items": { "type": "string" }
+                }
+            },
+            "header_row": { "type": "boolean" },
+            "column_widths": {
+                "type": "array",
+                "items": { "type": "number", "exclusiveMinimum": 0 },
+                "description": "millimetres, one per column"
+            },
+            "layout": { "type": "string", "enum": ["auto", "fixed"] },
+            "fit_to_page": { "type": "boolean" },
+            "cell_margin_mm": {
+                "type": "object",
+                "properties": {
+                    "top": { "type": "number", "minimum": 0 },
+                    "right": { "type": "number", "minimum": 0 },
+                    "bottom": { "type": "number", "minimum": 0 },
+                    "left": { "type": "number", "minimum": 0 }
+                },
+                "additionalProperties": false,
+                "description": "millimetres; written to HWP output only, HWPX ignores it with a warning"
+            }
+        },
+        "required": ["type", "rows"],
+        "additionalProperties": false
+    });
+    let list_block = json!({
+        "type": "object",
+        "properties": {
+            "type": { "const": "list" },
+            "items": {
+                "type": "array",
+                "items": {
+                    "oneOf": [
+                        { "type": "string" },
+                        {
+                            "type": "object",
+                            "properties": {
+                                "text": { "type": "string" },
+                                "level": { "type": "integer", "minimum": 0, "maximum": 9, "default": 0 }
+                            },
+                            "required": ["text"],
+                            "additionalProperties": false
+                        }
+                    ]
+                }
+            },
+            "list_type": { "type": "string", "enum": ["bullet", "numbered", "alphabetic", "roman", "korean"] },
+            "ordered": { "type": "boolean" }
+        },
+        "required": ["type", "items"],
+        "additionalProperties": false
+    });
+    let quote_block = json!({
+        "type": "object",
+        "properties": {
+            "type": { "const": "quote" },
+            "text": { "type": "string" },
+            "italic": { "type": "boolean", "default": true },
+            "style_ref": { "type": "string" },
+            "style": text_style.clone()
+        },
+        "required": ["type", "text"],
+        "additionalProperties": false
+    });
+    let mut schema = json!({
         "type": "object",
         "properties": {
             "to": { "type": "string", "enum": ["hwp", "hwpx"], "default": "hwp" },
             "output_path": { "type": "string" },
+            "include_base64": { "type": "boolean", "default": false },
             "document": {
                 "type": "object",
                 "properties": {
@@ -137,60 +408,65 @@ pub fn create_rich_document_schema() -> serde_json::Value {
                     "page": {
                         "type": "object",
                         "properties": {
-                            "size": { "type": "string", "enum": ["a4"] },
+                            "size": { "type": "string", "enum": ["a4", "letter"] },
                             "orientation": { "type": "string", "enum": ["portrait", "landscape"] }
                         },
                         "additionalProperties": false
                     },
                     "header": { "type": "string" },
                     "footer": { "type": "string" },
+                    "styles": {
+                        "type": "object",
+                        "additionalProperties": text_style
+                    },
                     "blocks": {
                         "type": "array",
                         "items": {
                             "oneOf": [
+                                paragraph_block,
                                 {
                                     "type": "object",
                                     "properties": {
-                                        "type": { "const": "paragraph" },
+                                        "type": { "const": "heading" },
+                                        "level": { "type": "integer", "minimum": 1, "maximum": 6 },
                                         "text": { "type": "string" },
-                                        "style": {
-                                            "type": "object",
-                                            "properties": {
-                                                "font_name": { "type": "string" },
-                                                "font_size": { "type": "integer", "minimum": 1 },
-                                                "bold": { "type": "boolean" },
-                                                "italic": { "type": "boolean" },
-                                                "underline": { "type": "boolean" },
-                                                "color": { "type": "string", "description": "0xRRGGBB (hex), e.g. 0xFF0000" }
-                                            },
-                                            "additionalProperties": false
-                                        }
+                                        "style_ref": { "type": "string" },
+                                        "style": text_style
                                     },
-                                    "required": ["type", "text"],
+                                    "required": ["type", "level", "text"],
                                     "additionalProperties": false
                                 },
+                                table_block,
                                 {
                                     "type": "object",
                                     "properties": {
-                                        "type": { "const": "heading" },
-                                        "level": { "type": "integer", "minimum": 1, "maximum": 6 },
-                                        "text": { "type": "string" }
+                                        "type": { "const": "image" },
+                                        "data_base64": { "type": "string" },
+                                        "mimeType": { "type": "string", "enum": ["image/png", "image/jpeg", "image/gif", "image/bmp"] },
+                                        "width_mm": { "type": "integer", "minimum": 1 },
+                                        "height_mm": { "type": "integer", "minimum": 1 },
+                                        "caption": { "type": "string" }
                                     },
-                                    "required": ["type", "level", "text"],
+                                    "required": ["type", "data_base64", "mimeType"],
                                     "additionalProperties": false
                                 },
                                 {
                                     "type": "object",
                                     "properties": {
-                                        "type": { "const": "table" },
+                                        "type": { "const": "chart" },
+                                        "chart_type": { "type": "string", "enum": ["bar", "line", "pie"], "default": "bar" },
                                         "rows": {
                                             "type": "array",
                                             "items": {
                                                 "type": "array",
-                                                "items": { "type": "string" }
+                                                "items": { "type": ["string", "number"] },
+                                                "minItems": 2
                                             }
                                         },
-                                        "header_row": { "type": "boolean" }
+                                        "header_row": { "type": "boolean" },
+                                        "width_mm": { "type": "integer", "minimum": 1 },
+                                        "height_mm": { "type": "integer", "minimum": 1 },
+                                        "caption": { "type": "string" }
                                     },
                                     "required": ["type", "rows"],
                                     "additionalProperties": false
@@ -198,14 +474,20 @@ pub fn create_rich_document_schema() -> serde_json::Value {
                                 {
                                     "type": "object",
                                     "properties": {
-                                        "type": { "const": "image" },
-                                        "data_base64": { "type": "string" },
-                                        "mimeType": { "type": "string", "enum": ["image/png", "image/jpeg", "image/gif", "image/bmp"] },
-                                        "width_mm": { "type": "integer", "minimum": 1 },
-                                        "height_mm": { "type": "integer", "minimum": 1 },
-                                        "caption": { "type": "string" }
+                                        "type": { "const": "equation" },
+                                        "latex": { "type": "string" }
                                     },
-                                    "required": ["type", "data_base64", "mimeType"],
+                                    "required": ["type", "latex"],
+                                    "additionalProperties": false
+                                },
+                                {
+                                    "type": "object",
+                                    "properties": {
+                                        "type": { "const": "link" },
+                                        "text": { "type": "string" },
+                                        "href": { "type": "string", "minLength": 1 }
+                                    },
+                                    "required": ["type", "href"],
                                     "additionalProperties": false
                                 }
                             ]
@@ -218,7 +500,17 @@ pub fn create_rich_document_schema() -> serde_json::Value {
         },
         "required": ["document"],
         "additionalProperties": false
-    })
+    });
+    // Added separately: nesting these in the literal exceeds `json!`'s macro recursion limit.
+    schema["properties"]["document"]["properties"]["default_paragraph_style"] =
+        default_paragraph_style;
+    if let Some(blocks) =
+        schema["properties"]["document"]["properties"]["blocks"]["items"]["oneOf"].as_array_mut()
+    {
+        blocks.push(list_block);
+        blocks.push(quote_block);
+    }
+    schema
 }
 
 pub fn extract_rich_schema() -> serde_json::Value {
@@ -227,14 +519,1244 @@ pub fn extract_rich_schema() -> serde_json::Value {
         "properties": {
             "path": { "type": "string" },
             "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
+            "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] },
+            "images": { "type": "string", "enum": ["none", "metadata", "inline", "resource"], "default": "metadata" },
+            "max_image_bytes": { "type": "integer", "minimum": 0 },
+            "timeout_ms": { "type": "integer", "minimum": 0 },
+            "partial_on_timeout": { "type": "boolean", "default": false },
+            "summary": { "type": "string", "enum": ["brief", "counts"], "default": "counts" },
+            "as_html": { "type": "boolean", "default": false },
+            "compress": { "type": "string", "enum": ["none", "gzip"], "default": "none" }
+        },
+        "oneOf": [
+            { "required": ["path"] },
+            { "required": ["base64"] },
+            { "required": ["url"] }
+        ],
+        "additionalProperties": false
+    })
+}
+
+pub fn fingerprint_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "path": { "type": "string" },
+            "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
+            "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] },
+            "shingle_size": { "type": "integer", "minimum": 1 }
+        },
+        "oneOf": [
+            { "required": ["path"] },
+            { "required": ["base64"] },
+            { "required": ["url"] }
+        ],
+        "additionalProperties": false
+    })
+}
+
+pub fn similarity_schema() -> serde_json::Value {
+    let input = json!({
+        "type": "object",
+        "properties": {
+            "path": { "type": "string" },
+            "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
+            "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] }
+        },
+        "oneOf": [
+            { "required": ["path"] },
+            { "required": ["base64"] },
+            { "required": ["url"] }
+        ],
+        "additionalProperties": false
+    });
+    json!({
+        "type": "object",
+        "properties": {
+            "left": input.clone(),
+            "right": input,
+            "shingle_size": { "type": "integer", "minimum": 1 }
+        },
+        "required": ["left", "right"],
+        "additionalProperties": false
+    })
+}
+
+pub fn merge_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "inputs": {
+                "type": "array",
+                "minItems": 2,
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "path": { "type": "string" },
+                        "base64": { "type": "string" },
+                        "url": { "type": "string" },
+                        "password": { "type": "string" },
+                        "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] }
+                    },
+                    "oneOf": [
+                        { "required": ["path"] },
+                        { "required": ["base64"] },
+                        { "required": ["url"] }
+                    ],
+                    "additionalProperties": false
+                }
+            },
+            "to": { "type": "string", "enum": ["hwp", "hwpx"] },
+            "separator": {
+                "type": "string",
+                "enum": ["none", "page_break", "section_break"]
+            },
+            "output_path": { "type": "string" }
+        },
+        "required": ["inputs", "to"],
+        "additionalProperties": false
+    })
+}
+
+pub fn extract_preview_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "path": { "type": "string" },
+            "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
+            "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] },
+            "output": { "type": "string", "enum": ["inline", "resource"] },
+            "fallback_render": { "type": "boolean" }
+        },
+        "oneOf": [
+            { "required": ["path"] },
+            { "required": ["base64"] },
+            { "required": ["url"] }
+        ],
+        "additionalProperties": false
+    })
+}
+
+pub fn split_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "path": { "type": "string" },
+            "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
+            "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] },
+            "by": {
+                "type": "string",
+                "enum": ["heading", "page_break", "section"],
+                "default": "heading"
+            },
+            "level": { "type": "integer", "minimum": 1, "maximum": 6, "default": 1 },
+            "every": { "type": "integer", "minimum": 1, "default": 1 },
+            "to": { "type": "string", "enum": ["hwp", "hwpx"] },
+            "output_dir": { "type": "string" }
+        },
+        "oneOf": [
+            { "required": ["path"] },
+            { "required": ["base64"] },
+            { "required": ["url"] }
+        ],
+        "additionalProperties": false
+    })
+}
+
+pub fn count_stats_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "path": { "type": "string" },
+            "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
+            "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] }
+        },
+        "oneOf": [
+            { "required": ["path"] },
+            { "required": ["base64"] },
+            { "required": ["url"] }
+        ],
+        "additionalProperties": false
+    })
+}
+
+pub fn extract_links_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "path": { "type": "string" },
+            "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
+            "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] }
+        },
+        "oneOf": [
+            { "required": ["path"] },
+            { "required": ["base64"] },
+            { "required": ["url"] }
+        ],
+        "additionalProperties": false
+    })
+}
+
+pub fn to_markdown_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "path": { "type": "string" },
+            "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
+            "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] },
+            "images": { "type": "string", "enum": ["none", "metadata", "inline", "resource"], "default": "metadata" },
+            "max_image_bytes": { "type": "integer", "minimum": 0 },
+            "output_path": { "type": "string" },
+            "timeout_ms": { "type": "integer", "minimum": 0 },
+            "partial_on_timeout": { "type": "boolean", "default": false }
+        },
+        "oneOf": [
+            { "required": ["path"] },
+            { "required": ["base64"] },
+            { "required": ["url"] }
+        ],
+        "additionalProperties": false
+    })
+}
+
+pub fn to_html_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "path": { "type": "string" },
+            "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
             "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] },
             "images": { "type": "string", "enum": ["none", "metadata", "inline", "resource"], "default": "metadata" },
-            "max_image_bytes": { "type": "integer", "minimum": 0 }
+            "max_image_bytes": { "type": "integer", "minimum": 0 },
+            "output_path": { "type": "string" },
+            "timeout_ms": { "type": "integer", "minimum": 0 },
+            "partial_on_timeout": { "type": "boolean", "default": false }
+        },
+        "oneOf": [
+            { "required": ["path"] },
+            { "required": ["base64"] },
+            { "required": ["url"] }
+        ],
+        "additionalProperties": false
+    })
+}
+
+pub fn extract_dates_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "path": { "type": "string" },
+            "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
+            "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] },
+            "date_order": { "type": "string", "enum": ["mdy", "dmy"], "default": "mdy" }
         },
         "oneOf": [
             { "required": ["path"] },
-            { "required": ["base64"] }
+            { "required": ["base64"] },
+            { "required": ["url"] }
         ],
         "additionalProperties": false
     })
 }
+
+pub fn search_text_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "path": { "type": "string" },
+            "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
+            "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] },
+            "query": { "type": "string", "minLength": 1 },
+            "case_sensitive": { "type": "boolean", "default": false },
+            "regex": { "type": "boolean", "default": false }
+        },
+        "required": ["query"],
+        "oneOf": [
+            { "required": ["path"] },
+            { "required": ["base64"] },
+            { "required": ["url"] }
+        ],
+        "additionalProperties": false
+    })
+}
+
+pub fn replace_text_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "path": { "type": "string" },
+            "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
+            "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] },
+            "find": { "type": "string", "minLength": 1 },
+            "replace": { "type": "string" },
+            "max_replacements": { "type": "integer", "minimum": 0 },
+            "to": { "type": "string", "enum": ["hwp", "hwpx"] },
+            "output_path": { "type": "string" }
+        },
+        "required": ["find", "replace"],
+        "oneOf": [
+            { "required": ["path"] },
+            { "required": ["base64"] },
+            { "required": ["url"] }
+        ],
+        "additionalProperties": false
+    })
+}
+
+pub fn explain_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "path": { "type": "string" },
+            "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
+            "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] },
+            "to": { "type": "string", "enum": ["hwp", "hwpx"] }
+        },
+        "oneOf": [
+            { "required": ["path"] },
+            { "required": ["base64"] },
+            { "required": ["url"] }
+        ],
+        "additionalProperties": false
+    })
+}
+
+pub fn page_count_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "path": { "type": "string" },
+            "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
+            "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] }
+        },
+        "oneOf": [
+            { "required": ["path"] },
+            { "required": ["base64"] },
+            { "required": ["url"] }
+        ],
+        "additionalProperties": false
+    })
+}
+
+pub fn list_styles_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "path": { "type": "string" },
+            "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
+            "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] }
+        },
+        "oneOf": [
+            { "required": ["path"] },
+            { "required": ["base64"] },
+            { "required": ["url"] }
+        ],
+        "additionalProperties": false
+    })
+}
+
+pub fn batch_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "dir": { "type": "string" },
+            "paths": {
+                "type": "array",
+                "items": { "type": "string" },
+                "minItems": 1
+            },
+            "tool": { "type": "string" },
+            "arguments": { "type": "object" },
+            "max_files": {
+                "type": "integer",
+                "minimum": 1,
+                "maximum": MAX_BATCH_FILES,
+                "default": 100
+            }
+        },
+        "required": ["tool"],
+        "oneOf": [
+            { "required": ["dir"] },
+            { "required": ["paths"] }
+        ],
+        "additionalProperties": false
+    })
+}
+
+pub fn extract_images_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "path": { "type": "string" },
+            "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
+            "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] },
+            "output": { "type": "string", "enum": ["inline", "resource"] },
+            "output_dir": { "type": "string" },
+            "min_bytes": { "type": "integer", "minimum": 0, "default": 0 },
+            "compress": { "type": "string", "enum": ["none", "gzip"], "default": "none" }
+        },
+        "oneOf": [
+            { "required": ["path"] },
+            { "required": ["base64"] },
+            { "required": ["url"] }
+        ],
+        "additionalProperties": false
+    })
+}
+
+pub fn extract_notes_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "path": { "type": "string" },
+            "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
+            "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] }
+        },
+        "oneOf": [
+            { "required": ["path"] },
+            { "required": ["base64"] },
+            { "required": ["url"] }
+        ],
+        "additionalProperties": false
+    })
+}
+
+pub fn extract_toc_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "path": { "type": "string" },
+            "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
+            "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] },
+            "max_level": { "type": "integer", "minimum": 1, "maximum": 10 }
+        },
+        "oneOf": [
+            { "required": ["path"] },
+            { "required": ["base64"] },
+            { "required": ["url"] }
+        ],
+        "additionalProperties": false
+    })
+}
+
+pub fn diff_schema() -> serde_json::Value {
+    let input = json!({
+        "type": "object",
+        "properties": {
+            "path": { "type": "string" },
+            "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
+            "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] }
+        },
+        "oneOf": [
+            { "required": ["path"] },
+            { "required": ["base64"] },
+            { "required": ["url"] }
+        ],
+        "additionalProperties": false
+    });
+    json!({
+        "type": "object",
+        "properties": {
+            "left": input.clone(),
+            "right": input
+        },
+        "required": ["left", "right"],
+        "additionalProperties": false
+    })
+}
+
+pub fn redact_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "path": { "type": "string" },
+            "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
+            "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] },
+            "patterns": {
+                "type": "array",
+                "minItems": 1,
+                "items": {
+                    "oneOf": [
+                        { "type": "string", "minLength": 1 },
+                        {
+                            "type": "object",
+                            "properties": {
+                                "literal": { "type": "string", "minLength": 1 }
+                            },
+                            "required": ["literal"],
+                            "additionalProperties": false
+                        },
+                        {
+                            "type": "object",
+                            "properties": {
+                                "regex": { "type": "string", "minLength": 1 }
+                            },
+                            "required": ["regex"],
+                            "additionalProperties": false
+                        }
+                    ]
+                }
+            },
+            "case_sensitive": { "type": "boolean" },
+            "mask": { "type": "string", "minLength": 1, "maxLength": 1 },
+            "to": { "type": "string", "enum": ["hwp", "hwpx"] },
+            "output_path": { "type": "string" }
+        },
+        "required": ["patterns"],
+        "oneOf": [
+            { "required": ["path"] },
+            { "required": ["base64"] },
+            { "required": ["url"] }
+        ],
+        "additionalProperties": false
+    })
+}
+
+pub fn validate_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "path": { "type": "string" },
+            "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
+            "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] }
+        },
+        "oneOf": [
+            { "required": ["path"] },
+            { "required": ["base64"] },
+            { "required": ["url"] }
+        ],
+        "additionalProperties": false
+    })
+}
+
+pub fn to_json_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "path": { "type": "string" },
+            "base64": { "type": "string" },
+            "url": { "type": "string" },
+            "password": { "type": "string" },
+            "format": { "type": "string", "enum": ["auto", "hwp", "hwpx"] },
+            "output_path": { "type": "string" }
+        },
+        "oneOf": [
+            { "required": ["path"] },
+            { "required": ["base64"] },
+            { "required": ["url"] }
+        ],
+        "additionalProperties": false
+    })
+}
+
+/// Wraps the `structuredContent` properties of a successful call into an output schema. Every
+/// tool reports failures as `{ error: { kind, code, message, source? } }` in the same field, so
+/// `error` is declared for all of them and no property is required.
+fn output_schema(mut properties: serde_json::Value) -> serde_json::Value {
+    properties["error"] = json!({
+        "type": "object",
+        "properties": {
+            "kind": { "type": "string" },
+            "code": { "type": "integer", "minimum": 1 },
+            "message": { "type": "string" },
+            "source": { "type": "string" }
+        },
+        "required": ["kind", "code", "message"]
+    });
+    json!({
+        "type": "object",
+        "properties": properties
+    })
+}
+
+/// `format`, `detected_format`, `detection_method` and `warnings`, shared by the tools that
+/// parse one input document, merged with the tool's own properties.
+fn parsed_output_schema(properties: serde_json::Value) -> serde_json::Value {
+    let mut merged = json!({
+        "format": { "type": "string", "enum": ["hwp", "hwpx"] },
+        "detected_format": { "type": "string", "enum": ["hwp", "hwpx"] },
+        "detection_method": { "type": "string", "enum": ["explicit", "magic", "fallback"] },
+        "warnings": { "type": "array", "items": { "type": "string" } }
+    });
+    if let (Some(merged), Some(properties)) = (merged.as_object_mut(), properties.as_object()) {
+        for (key, value) in properties {
+            merged.insert(key.clone(), value.clone());
+        }
+    }
+    output_schema(merged)
+}
+
+/// `base64` for inline output, or `path`/`uri` when the result was written to `output_path`.
+fn written_output_properties() -> serde_json::Value {
+    json!({
+        "base64": { "type": "string" },
+        "path": { "type": "string" },
+        "uri": { "type": "string" },
+        "bytes_len": { "type": "integer", "minimum": 0 }
+    })
+}
+
+fn merge_properties(mut base: serde_json::Value, extra: serde_json::Value) -> serde_json::Value {
+    if let (Some(base), Some(extra)) = (base.as_object_mut(), extra.as_object()) {
+        for (key, value) in extra {
+            base.insert(key.clone(), value.clone());
+        }
+    }
+    base
+}
+
+fn paragraph_location(mut properties: serde_json::Value) -> serde_json::Value {
+    properties["section_index"] = json!({ "type": "integer", "minimum": 0 });
+    properties["paragraph_index"] = json!({ "type": "integer", "minimum": 0 });
+    json!({ "type": "object", "properties": properties })
+}
+
+pub fn extract_text_output_schema() -> serde_json::Value {
+    parsed_output_schema(json!({
+        "text": { "type": "string" },
+        "markdown": { "type": "string" },
+        "sections": {
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "section_index": { "type": "integer", "minimum": 0 },
+                    "text": { "type": "string" }
+                }
+            }
+        },
+        "paragraphs": {
+            "type": "array",
+            "items": paragraph_location(json!({
+                "char_start": { "type": "integer", "minimum": 0 },
+                "char_end": { "type": "integer", "minimum": 0 },
+                "text": { "type": "string" }
+            }))
+        },
+        "path": { "type": "string" },
+        "uri": { "type": "string" },
+        "bytes_len": { "type": "integer", "minimum": 0 },
+        "source": { "type": "string", "enum": ["document", "ocr"] },
+        "ocr": { "type": "object" },
+        "likely_scanned": { "type": "boolean" },
+        "timed_out": { "type": "boolean" }
+    }))
+}
+
+pub fn inspect_metadata_output_schema() -> serde_json::Value {
+    let nullable_string = json!({ "type": ["string", "null"] });
+    parsed_output_schema(json!({
+        "sections": { "type": "integer", "minimum": 0 },
+        "paragraphs": { "type": "integer", "minimum": 0 },
+        "encrypted": { "type": "boolean" },
+        "distribution": { "type": "boolean" },
+        "compressed": { "type": "boolean" },
+        "version": { "type": "string" },
+        "summary": {
+            "type": "object",
+            "properties": {
+                "title": nullable_string,
+                "subject": nullable_string,
+                "author": nullable_string,
+                "keywords": nullable_string,
+                "comments": nullable_string,
+                "last_saved_by": nullable_string,
+                "company": nullable_string,
+                "created": nullable_string,
+                "modified": nullable_string
+            }
+        },
+        "fonts": {
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string" },
+                    "substitute": nullable_string,
+                    "substitute_type": { "type": ["string", "null"], "enum": ["ttf", "hft", "unknown", null] }
+                }
+            }
+        }
+    }))
+}
+
+pub fn summarize_structure_output_schema() -> serde_json::Value {
+    parsed_output_schema(json!({
+        "sections": {
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "index": { "type": "integer", "minimum": 0 },
+                    "paragraphs": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "index": { "type": "integer", "minimum": 0 },
+                                "char_count": { "type": "integer", "minimum": 0 },
+                                "preview": { "type": "string" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }))
+}
+
+pub fn render_svg_output_schema() -> serde_json::Value {
+    parsed_output_schema(json!({
+        "pages": {
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "page": { "type": "integer", "minimum": 1 },
+                    "svg": { "type": "string" },
+                    "png": { "type": "string" },
+                    "path": { "type": "string" },
+                    "uri": { "type": "string" }
+                }
+            }
+        },
+        "rasterizer": { "type": "string" }
+    }))
+}
+
+pub fn convert_output_schema() -> serde_json::Value {
+    parsed_output_schema(merge_properties(
+        written_output_properties(),
+        json!({
+            "to": { "type": "string", "enum": ["hwp", "hwpx", "txt", "md"] },
+            "text": { "type": "string" },
+            "markdown": { "type": "string" },
+            "stripped": { "type": "array", "items": { "type": "string" } },
+            "encoding": { "type": "string", "enum": ["gzip"] }
+        }),
+    ))
+}
+
+pub fn create_document_output_schema() -> serde_json::Value {
+    output_schema(written_output_properties())
+}
+
+pub fn create_rich_document_output_schema() -> serde_json::Value {
+    output_schema(merge_properties(
+        written_output_properties(),
+        json!({
+            "to": { "type": "string", "enum": ["hwp", "hwpx"] },
+            "warnings": { "type": "array", "items": { "type": "string" } }
+        }),
+    ))
+}
+
+pub fn extract_rich_output_schema() -> serde_json::Value {
+    parsed_output_schema(json!({
+        "blocks": {
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "type": { "type": "string", "enum": ["paragraph", "table", "image"] },
+                    "section_index": { "type": "integer", "minimum": 0 },
+                    "paragraph_index": { "type": "integer", "minimum": 0 }
+                },
+                "required": ["type"]
+            }
+        },
+        "html": { "type": "string" },
+        "timed_out": { "type": "boolean" },
+        "encoding": { "type": "string", "enum": ["gzip"] }
+    }))
+}
+
+pub fn fingerprint_output_schema() -> serde_json::Value {
+    parsed_output_schema(json!({
+        "algorithm": { "type": "string", "const": "minhash" },
+        "shingle_size": { "type": "integer", "minimum": 1 },
+        "token_count": { "type": "integer", "minimum": 0 },
+        "shingle_count": { "type": "integer", "minimum": 0 },
+        "signature": { "type": "array", "items": { "type": "string" } }
+    }))
+}
+
+pub fn similarity_output_schema() -> serde_json::Value {
+    let side = json!({
+        "type": "object",
+        "properties": {
+            "format": { "type": "string", "enum": ["hwp", "hwpx"] },
+            "detected_format": { "type": "string", "enum": ["hwp", "hwpx"] },
+            "detection_method": { "type": "string" },
+            "token_count": { "type": "integer", "minimum": 0 },
+            "shingle_count": { "type": "integer", "minimum": 0 }
+        }
+    });
+    output_schema(json!({
+        "similarity": { "type": "number", "minimum": 0, "maximum": 1 },
+        "algorithm": { "type": "string", "const": "minhash" },
+        "shingle_size": { "type": "integer", "minimum": 1 },
+        "left": side,
+        "right": side,
+        "warnings": { "type": "array", "items": { "type": "string" } }
+    }))
+}
+
+pub fn merge_output_schema() -> serde_json::Value {
+    output_schema(merge_properties(
+        written_output_properties(),
+        json!({
+            "to": { "type": "string", "enum": ["hwp", "hwpx"] },
+            "separator": { "type": "string", "enum": ["none", "page_break", "section_break"] },
+            "documents": { "type": "integer", "minimum": 0 },
+            "sections": { "type": "integer", "minimum": 0 },
+            "inputs": { "type": "array", "items": { "type": "object" } },
+            "warnings": { "type": "array", "items": { "type": "string" } }
+        }),
+    ))
+}
+
+pub fn extract_preview_output_schema() -> serde_json::Value {
+    parsed_output_schema(merge_properties(
+        written_output_properties(),
+        json!({
+            "found": { "type": "boolean" },
+            "source": { "type": "string", "enum": ["embedded", "rendered"] },
+            "mimeType": { "type": "string" }
+        }),
+    ))
+}
+
+pub fn split_output_schema() -> serde_json::Value {
+    parsed_output_schema(json!({
+        "to": { "type": "string", "enum": ["hwp", "hwpx"] },
+        "by": { "type": "string", "enum": ["heading", "page_break", "section"] },
+        "level": { "type": "integer", "minimum": 1, "maximum": 6 },
+        "every": { "type": "integer", "minimum": 1 },
+        "parts": {
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": merge_properties(
+                    written_output_properties(),
+                    json!({
+                        "index": { "type": "integer", "minimum": 0 },
+                        "heading": { "type": ["string", "null"] },
+                        "paragraphs": { "type": "integer", "minimum": 0 }
+                    }),
+                )
+            }
+        }
+    }))
+}
+
+pub fn count_stats_output_schema() -> serde_json::Value {
+    let count = json!({ "type": "integer", "minimum": 0 });
+    parsed_output_schema(json!({
+        "char_count": count,
+        "char_count_no_spaces": count,
+        "cjk_char_count": count,
+        "word_count": count,
+        "paragraph_count": count,
+        "section_count": count
+    }))
+}
+
+pub fn extract_links_output_schema() -> serde_json::Value {
+    parsed_output_schema(json!({
+        "links": {
+            "type": "array",
+            "items": paragraph_location(json!({
+                "text": { "type": "string" },
+                "target": { "type": "string" },
+                "kind": { "type": "string", "enum": ["url", "email", "bookmark", "file"] }
+            }))
+        }
+    }))
+}
+
+pub fn to_markdown_output_schema() -> serde_json::Value {
+    parsed_output_schema(json!({
+        "markdown": { "type": "string" },
+        "timed_out": { "type": "boolean" }
+    }))
+}
+
+pub fn to_html_output_schema() -> serde_json::Value {
+    parsed_output_schema(json!({
+        "html": { "type": "string" },
+        "timed_out": { "type": "boolean" }
+    }))
+}
+
+pub fn extract_dates_output_schema() -> serde_json::Value {
+    parsed_output_schema(json!({
+        "date_order": { "type": "string", "enum": ["mdy", "dmy"] },
+        "dates": {
+            "type": "array",
+            "items": paragraph_location(json!({
+                "raw": { "type": "string" },
+                "iso": { "type": "string" }
+            }))
+        }
+    }))
+}
+
+pub fn search_text_output_schema() -> serde_json::Value {
+    parsed_output_schema(json!({
+        "total_matches": { "type": "integer", "minimum": 0 },
+        "matches": {
+            "type": "array",
+            "items": paragraph_location(json!({
+                "char_offset": { "type": "integer", "minimum": 0 },
+                "text": { "type": "string" },
+                "context": { "type": "string" }
+            }))
+        }
+    }))
+}
+
+pub fn replace_text_output_schema() -> serde_json::Value {
+    parsed_output_schema(merge_properties(
+        written_output_properties(),
+        json!({
+            "to": { "type": "string", "enum": ["hwp", "hwpx"] },
+            "replacements": { "type": "integer", "minimum": 0 }
+        }),
+    ))
+}
+
+pub fn explain_output_schema() -> serde_json::Value {
+    let nullable_string = json!({ "type": ["string", "null"] });
+    output_schema(json!({
+        "ok": { "type": "boolean" },
+        "detected_format": nullable_string,
+        "to": nullable_string,
+        "stages_passed": { "type": "array", "items": { "type": "string" } },
+        "stage": nullable_string,
+        "kind": nullable_string,
+        "error_variant": nullable_string,
+        "backend_error": nullable_string,
+        "remedy": nullable_string
+    }))
+}
+
+pub fn page_count_output_schema() -> serde_json::Value {
+    parsed_output_schema(json!({
+        "page_count": { "type": "integer", "minimum": 0 }
+    }))
+}
+
+pub fn list_styles_output_schema() -> serde_json::Value {
+    parsed_output_schema(json!({
+        "para_styles": {
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "id": { "type": "integer", "minimum": 0 },
+                    "name": { "type": "string" },
+                    "english_name": { "type": "string" },
+                    "kind": { "type": "string", "enum": ["paragraph", "character"] },
+                    "para_shape_id": { "type": "integer", "minimum": 0 },
+                    "char_shape_id": { "type": "integer", "minimum": 0 },
+                    "next_style_id": { "type": "integer", "minimum": 0 },
+                    "alignment": { "type": ["string", "null"] },
+                    "line_spacing": { "type": ["object", "null"] }
+                }
+            }
+        },
+        "char_shapes": {
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "id": { "type": "integer", "minimum": 0 },
+                    "font_id": { "type": "integer", "minimum": 0 },
+                    "font_name": { "type": ["string", "null"] },
+                    "size_pt": { "type": "number" },
+                    "bold": { "type": "boolean" },
+                    "italic": { "type": "boolean" },
+                    "underline": { "type": "boolean" },
+                    "color": { "type": "string" }
+                }
+            }
+        }
+    }))
+}
+
+pub fn batch_output_schema() -> serde_json::Value {
+    output_schema(json!({
+        "tool": { "type": "string" },
+        "files": { "type": "integer", "minimum": 0 },
+        "succeeded": { "type": "integer", "minimum": 0 },
+        "failed": { "type": "integer", "minimum": 0 },
+        "results": {
+            "type": "object",
+            "additionalProperties": {
+                "type": "object",
+                "properties": {
+                    "isError": { "type": "boolean" },
+                    "kind": { "type": ["string", "null"] },
+                    "structuredContent": { "type": "object" }
+                }
+            }
+        },
+        "warnings": { "type": "array", "items": { "type": "string" } }
+    }))
+}
+
+pub fn extract_images_output_schema() -> serde_json::Value {
+    parsed_output_schema(json!({
+        "output": { "type": "string", "enum": ["inline", "resource"] },
+        "count": { "type": "integer", "minimum": 0 },
+        "skipped": { "type": "integer", "minimum": 0 },
+        "encoding": { "type": "string", "enum": ["gzip"] },
+        "images": {
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "bin_id": { "type": "integer", "minimum": 0 },
+                    "extension": { "type": "string" },
+                    "mimeType": { "type": "string" },
+                    "bytes_len": { "type": "integer", "minimum": 0 }
+                }
+            }
+        }
+    }))
+}
+
+pub fn extract_notes_output_schema() -> serde_json::Value {
+    let notes = json!({
+        "type": "array",
+        "items": {
+            "type": "object",
+            "properties": {
+                "number": { "type": "integer", "minimum": 1 },
+                "anchor": { "type": ["object", "null"] },
+                "text": { "type": "string" }
+            }
+        }
+    });
+    parsed_output_schema(json!({
+        "footnotes": notes,
+        "endnotes": notes
+    }))
+}
+
+pub fn extract_toc_output_schema() -> serde_json::Value {
+    parsed_output_schema(json!({
+        "max_level": { "type": ["integer", "null"] },
+        "toc": {
+            "type": "array",
+            "items": paragraph_location(json!({
+                "level": { "type": "integer", "minimum": 1 },
+                "text": { "type": "string" },
+                "source": { "type": "string" }
+            }))
+        }
+    }))
+}
+
+pub fn diff_output_schema() -> serde_json::Value {
+    let side = json!({
+        "type": "object",
+        "properties": {
+            "format": { "type": "string", "enum": ["hwp", "hwpx"] },
+            "detected_format": { "type": "string", "enum": ["hwp", "hwpx"] },
+            "detection_method": { "type": "string" },
+            "paragraph_count": { "type": "integer", "minimum": 0 }
+        }
+    });
+    output_schema(json!({
+        "diff": {
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "op": { "type": "string", "enum": ["added", "removed", "unchanged"] },
+                    "left_index": { "type": ["integer", "null"] },
+                    "right_index": { "type": ["integer", "null"] },
+                    "text": { "type": "string" }
+                }
+            }
+        },
+        "counts": {
+            "type": "object",
+            "properties": {
+                "added": { "type": "integer", "minimum": 0 },
+                "removed": { "type": "integer", "minimum": 0 },
+                "unchanged": { "type": "integer", "minimum": 0 }
+            }
+        },
+        "left": side,
+        "right": side,
+        "warnings": { "type": "array", "items": { "type": "string" } }
+    }))
+}
+
+pub fn redact_output_schema() -> serde_json::Value {
+    parsed_output_schema(merge_properties(
+        written_output_properties(),
+        json!({
+            "to": { "type": "string", "enum": ["hwp", "hwpx"] },
+            "redactions": { "type": "integer", "minimum": 0 },
+            "matched_patterns": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "pattern": { "type": "string" },
+                        "regex": { "type": "boolean" },
+                        "count": { "type": "integer", "minimum": 1 }
+                    }
+                }
+            }
+        }),
+    ))
+}
+
+pub fn validate_output_schema() -> serde_json::Value {
+    parsed_output_schema(json!({
+        "parseable": { "type": "boolean" },
+        "encrypted": { "type": "boolean" },
+        "section_count": { "type": "integer", "minimum": 0 },
+        "problems": {
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "kind": { "type": "string", "enum": ["no_sections", "missing_page_def", "empty_content"] },
+                    "message": { "type": "string" },
+                    "section_index": { "type": "integer", "minimum": 0 }
+                },
+                "required": ["kind", "message"]
+            }
+        }
+    }))
+}
+
+pub fn to_json_output_schema() -> serde_json::Value {
+    parsed_output_schema(json!({
+        "model_version": { "type": "integer", "minimum": 1 },
+        "document": {
+            "type": "object",
+            "properties": {
+                "sections": { "type": "array", "items": { "type": "object" } }
+            }
+        },
+        "path": { "type": "string" },
+        "uri": { "type": "string" },
+        "bytes_len": { "type": "integer", "minimum": 0 }
+    }))
+}
+
+pub fn server_info_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {},
+        "additionalProperties": false
+    })
+}
+
+pub fn server_info_output_schema() -> serde_json::Value {
+    output_schema(json!({
+        "server": {
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "version": { "type": "string" },
+                "hwpers_version": { "type": "string" },
+                "limits": {
+                    "type": "object",
+                    "properties": {
+                        "max_input_bytes": { "type": "integer", "minimum": 0 },
+                        "max_output_bytes": { "type": "integer", "minimum": 0 },
+                        "max_svg_output_bytes": { "type": "integer", "minimum": 0 },
+                        "max_parse_ms": { "type": "integer", "minimum": 0 },
+                        "max_table_rows": { "type": "integer", "minimum": 0 },
+                        "max_table_cols": { "type": "integer", "minimum": 0 }
+                    }
+                },
+                "formats": {
+                    "type": "object",
+                    "properties": {
+                        "input": { "type": "array", "items": { "type": "string" } },
+                        "output": { "type": "array", "items": { "type": "string" } }
+                    }
+                }
+            }
+        }
+    }))
+}
+
+pub fn cleanup_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "older_than_minutes": { "type": "integer", "minimum": 0 }
+        },
+        "additionalProperties": false
+    })
+}
+
+pub fn cleanup_output_schema() -> serde_json::Value {
+    output_schema(json!({
+        "removed": { "type": "array", "items": { "type": "string" } },
+        "failed": {
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string" },
+                    "message": { "type": "string" }
+                },
+                "required": ["path", "message"]
+            }
+        },
+        "remaining": { "type": "integer", "minimum": 0 }
+    }))
+}
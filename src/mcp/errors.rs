@@ -5,4 +5,43 @@ pub const TOO_LARGE: &str = "too_large";
 pub const UNSUPPORTED_FORMAT: &str = "unsupported_format";
 pub const ENCRYPTED: &str = "encrypted";
 pub const PARSE_FAILED: &str = "parse_failed";
+pub const TIMEOUT: &str = "timeout";
 pub const INTERNAL_ERROR: &str = "internal_error";
+/// The caller cancelled the request; not a server fault.
+pub const CANCELLED: &str = "cancelled";
+
+/// Stable numeric code for an error kind, reported as `error.code` next to `error.kind`.
+/// Codes are never reused or renumbered; new kinds get the next free number.
+pub fn code(kind: &str) -> i64 {
+    match kind {
+        INVALID_INPUT => 1,
+        TOO_LARGE => 2,
+        UNSUPPORTED_FORMAT => 3,
+        ENCRYPTED => 4,
+        PARSE_FAILED => 5,
+        TIMEOUT => 6,
+        CANCELLED => 8,
+        _ => 7,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_stable_and_distinct() {
+        let kinds = [
+            INVALID_INPUT,
+            TOO_LARGE,
+            UNSUPPORTED_FORMAT,
+            ENCRYPTED,
+            PARSE_FAILED,
+            TIMEOUT,
+            INTERNAL_ERROR,
+            CANCELLED,
+        ];
+        let codes: Vec<i64> = kinds.iter().map(|kind| code(kind)).collect();
+        assert_eq!(codes, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+}
@@ -1,49 +1,294 @@
-use serde_json::json;
+use serde_json::{Value, json};
 
 pub mod contracts;
 pub mod errors;
+pub mod resources;
+
+/// Tools returned per `tools/list` page. Larger than the catalog today, so clients that never
+/// send a cursor still get every tool in one response.
+pub const TOOLS_PAGE_SIZE: usize = 50;
+
+pub struct ListError {
+    pub code: i64,
+    pub message: String,
+}
+
+/// Builds the `tools/list` result for the optional `params.cursor`, adding `nextCursor` when
+/// more tools remain.
+pub fn list_tools(params: Option<&Value>) -> Result<Value, ListError> {
+    paginate(
+        tool_definitions(),
+        params.and_then(|params| params.get("cursor")),
+        TOOLS_PAGE_SIZE,
+    )
+}
+
+/// Cursors are the decimal offset of the next item; clients must treat them as opaque.
+fn paginate(
+    items: Vec<Value>,
+    cursor: Option<&Value>,
+    page_size: usize,
+) -> Result<Value, ListError> {
+    let invalid = || ListError {
+        code: resources::INVALID_PARAMS,
+        message: "Invalid cursor".to_string(),
+    };
+    let start = match cursor {
+        None | Some(Value::Null) => 0,
+        Some(Value::String(cursor)) => cursor.parse::<usize>().map_err(|_| invalid())?,
+        Some(_) => return Err(invalid()),
+    };
+    if start > items.len() {
+        return Err(invalid());
+    }
+    let end = start.saturating_add(page_size).min(items.len());
+    let page: Vec<Value> = items[start..end].to_vec();
+    let mut result = json!({ "tools": page });
+    if end < items.len() {
+        result["nextCursor"] = json!(end.to_string());
+    }
+    Ok(result)
+}
 
 pub fn tool_definitions() -> Vec<serde_json::Value> {
     vec![
         json!({
             "name": contracts::TOOL_EXTRACT_TEXT,
             "description": "Extract plain text from HWP documents.",
-            "inputSchema": contracts::extract_text_schema()
+            "inputSchema": contracts::extract_text_schema(),
+            "outputSchema": contracts::extract_text_output_schema()
         }),
         json!({
             "name": contracts::TOOL_INSPECT_METADATA,
             "description": "Inspect metadata from HWP documents.",
-            "inputSchema": contracts::inspect_metadata_schema()
+            "inputSchema": contracts::inspect_metadata_schema(),
+            "outputSchema": contracts::inspect_metadata_output_schema()
         }),
         json!({
             "name": contracts::TOOL_SUMMARIZE_STRUCTURE,
             "description": "Summarize document structure for HWP documents.",
-            "inputSchema": contracts::summarize_structure_schema()
+            "inputSchema": contracts::summarize_structure_schema(),
+            "outputSchema": contracts::summarize_structure_output_schema()
         }),
         json!({
             "name": contracts::TOOL_RENDER_SVG,
             "description": "Render HWP pages or elements into SVG.",
-            "inputSchema": contracts::render_svg_schema()
+            "inputSchema": contracts::render_svg_schema(),
+            "outputSchema": contracts::render_svg_output_schema()
         }),
         json!({
             "name": contracts::TOOL_CONVERT,
             "description": "Convert HWP documents between formats.",
-            "inputSchema": contracts::convert_schema()
+            "inputSchema": contracts::convert_schema(),
+            "outputSchema": contracts::convert_output_schema()
         }),
         json!({
             "name": contracts::TOOL_CREATE_DOCUMENT,
             "description": "Create new HWP documents from text.",
-            "inputSchema": contracts::create_document_schema()
+            "inputSchema": contracts::create_document_schema(),
+            "outputSchema": contracts::create_document_output_schema()
         }),
         json!({
             "name": contracts::TOOL_CREATE_RICH_DOCUMENT,
             "description": "Create a rich HWP/HWPX document from a block-based JSON spec (paragraphs/headings/tables/images).",
-            "inputSchema": contracts::create_rich_document_schema()
+            "inputSchema": contracts::create_rich_document_schema(),
+            "outputSchema": contracts::create_rich_document_output_schema()
         }),
         json!({
             "name": contracts::TOOL_EXTRACT_RICH,
             "description": "Extract a rich block structure (paragraphs/tables/images) from HWP/HWPX documents.",
-            "inputSchema": contracts::extract_rich_schema()
+            "inputSchema": contracts::extract_rich_schema(),
+            "outputSchema": contracts::extract_rich_output_schema()
+        }),
+        json!({
+            "name": contracts::TOOL_FINGERPRINT,
+            "description": "Compute a MinHash fingerprint over shingled document text for near-duplicate detection.",
+            "inputSchema": contracts::fingerprint_schema(),
+            "outputSchema": contracts::fingerprint_output_schema()
+        }),
+        json!({
+            "name": contracts::TOOL_SIMILARITY,
+            "description": "Estimate the 0-1 text similarity of two HWP/HWPX documents.",
+            "inputSchema": contracts::similarity_schema(),
+            "outputSchema": contracts::similarity_output_schema()
+        }),
+        json!({
+            "name": contracts::TOOL_MERGE,
+            "description": "Merge multiple HWP/HWPX documents into one, separated by section or page breaks.",
+            "inputSchema": contracts::merge_schema(),
+            "outputSchema": contracts::merge_output_schema()
+        }),
+        json!({
+            "name": contracts::TOOL_EXTRACT_PREVIEW,
+            "description": "Extract the embedded preview thumbnail (PrvImage) from HWP documents.",
+            "inputSchema": contracts::extract_preview_schema(),
+            "outputSchema": contracts::extract_preview_output_schema()
+        }),
+        json!({
+            "name": contracts::TOOL_SPLIT,
+            "description": "Split an HWP/HWPX document into parts at heading boundaries.",
+            "inputSchema": contracts::split_schema(),
+            "outputSchema": contracts::split_output_schema()
+        }),
+        json!({
+            "name": contracts::TOOL_COUNT_STATS,
+            "description": "Count characters, words, paragraphs, and sections in an HWP/HWPX document.",
+            "inputSchema": contracts::count_stats_schema(),
+            "outputSchema": contracts::count_stats_output_schema()
+        }),
+        json!({
+            "name": contracts::TOOL_EXTRACT_LINKS,
+            "description": "List hyperlinks (anchor text and target URL/bookmark) in an HWP/HWPX document.",
+            "inputSchema": contracts::extract_links_schema(),
+            "outputSchema": contracts::extract_links_output_schema()
+        }),
+        json!({
+            "name": contracts::TOOL_TO_MARKDOWN,
+            "description": "Convert an HWP/HWPX document to GitHub-flavored Markdown (headings, pipe tables, images).",
+            "inputSchema": contracts::to_markdown_schema(),
+            "outputSchema": contracts::to_markdown_output_schema()
+        }),
+        json!({
+            "name": contracts::TOOL_TO_HTML,
+            "description": "Convert an HWP/HWPX document to a self-contained HTML page (paragraphs, tables, images).",
+            "inputSchema": contracts::to_html_schema(),
+            "outputSchema": contracts::to_html_output_schema()
+        }),
+        json!({
+            "name": contracts::TOOL_EXTRACT_DATES,
+            "description": "Find Korean and Western date expressions in an HWP/HWPX document and normalize them to ISO 8601.",
+            "inputSchema": contracts::extract_dates_schema(),
+            "outputSchema": contracts::extract_dates_output_schema()
+        }),
+        json!({
+            "name": contracts::TOOL_SEARCH_TEXT,
+            "description": "Search an HWP/HWPX document for text or a regular expression and return match locations with context.",
+            "inputSchema": contracts::search_text_schema(),
+            "outputSchema": contracts::search_text_output_schema()
+        }),
+        json!({
+            "name": contracts::TOOL_REPLACE_TEXT,
+            "description": "Replace text across paragraphs of an HWP/HWPX document without disturbing character formatting.",
+            "inputSchema": contracts::replace_text_schema(),
+            "outputSchema": contracts::replace_text_output_schema()
+        }),
+        json!({
+            "name": contracts::TOOL_EXPLAIN,
+            "description": "Diagnose why an HWP/HWPX document fails to parse or convert: the failing stage, the raw backend error, and a suggested remedy.",
+            "inputSchema": contracts::explain_schema(),
+            "outputSchema": contracts::explain_output_schema()
+        }),
+        json!({
+            "name": contracts::TOOL_PAGE_COUNT,
+            "description": "Count the laid-out pages of an HWP/HWPX document without producing SVG.",
+            "inputSchema": contracts::page_count_schema(),
+            "outputSchema": contracts::page_count_output_schema()
+        }),
+        json!({
+            "name": contracts::TOOL_LIST_STYLES,
+            "description": "List paragraph styles and character shapes from the DocInfo tables of an HWP/HWPX document.",
+            "inputSchema": contracts::list_styles_schema(),
+            "outputSchema": contracts::list_styles_output_schema()
+        }),
+        json!({
+            "name": contracts::TOOL_BATCH,
+            "description": "Run one path-based tool over a directory or list of HWP/HWPX files and collect the results per file.",
+            "inputSchema": contracts::batch_schema(),
+            "outputSchema": contracts::batch_output_schema()
+        }),
+        json!({
+            "name": contracts::TOOL_EXTRACT_IMAGES,
+            "description": "Extract the embedded images of an HWP/HWPX document as base64 or files.",
+            "inputSchema": contracts::extract_images_schema(),
+            "outputSchema": contracts::extract_images_output_schema()
+        }),
+        json!({
+            "name": contracts::TOOL_EXTRACT_NOTES,
+            "description": "Extract footnotes and endnotes with the paragraph they are anchored in.",
+            "inputSchema": contracts::extract_notes_schema(),
+            "outputSchema": contracts::extract_notes_output_schema()
+        }),
+        json!({
+            "name": contracts::TOOL_EXTRACT_TOC,
+            "description": "Infer a table of contents from heading paragraphs as a flat list of levelled entries.",
+            "inputSchema": contracts::extract_toc_schema(),
+            "outputSchema": contracts::extract_toc_output_schema()
+        }),
+        json!({
+            "name": contracts::TOOL_DIFF,
+            "description": "Diff the paragraph text of two documents (added/removed/unchanged, with char-level changes for replaced paragraphs).",
+            "inputSchema": contracts::diff_schema(),
+            "outputSchema": contracts::diff_output_schema()
+        }),
+        json!({
+            "name": contracts::TOOL_REDACT,
+            "description": "Mask every match of literal or regex patterns in paragraph, table cell, and text box text, and return the redacted document.",
+            "inputSchema": contracts::redact_schema(),
+            "outputSchema": contracts::redact_output_schema()
+        }),
+        json!({
+            "name": contracts::TOOL_VALIDATE,
+            "description": "Check that an HWP/HWPX document parses and report recoverable problems (missing page definitions, no sections, empty content) without producing output.",
+            "inputSchema": contracts::validate_schema(),
+            "outputSchema": contracts::validate_output_schema()
+        }),
+        json!({
+            "name": contracts::TOOL_TO_JSON,
+            "description": "Return the parsed document as a versioned JSON tree: sections, paragraphs, character-shape runs, and table/picture/text box controls.",
+            "inputSchema": contracts::to_json_schema(),
+            "outputSchema": contracts::to_json_output_schema()
+        }),
+        json!({
+            "name": contracts::TOOL_SERVER_INFO,
+            "description": "Report the server version, linked hwpers version, size and time limits, and supported input/output formats.",
+            "inputSchema": contracts::server_info_schema(),
+            "outputSchema": contracts::server_info_output_schema()
+        }),
+        json!({
+            "name": contracts::TOOL_CLEANUP,
+            "description": "Delete resource files this server wrote to its output directory during the session, optionally only those older than N minutes.",
+            "inputSchema": contracts::cleanup_schema(),
+            "outputSchema": contracts::cleanup_output_schema()
         }),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items(count: usize) -> Vec<Value> {
+        (0..count).map(|index| json!({ "name": index })).collect()
+    }
+
+    #[test]
+    fn paginate_without_cursor_returns_first_page() {
+        let result = paginate(items(3), None, 50).ok().expect("page");
+        assert_eq!(result["tools"].as_array().map(Vec::len), Some(3));
+        assert!(result.get("nextCursor").is_none());
+    }
+
+    #[test]
+    fn paginate_follows_next_cursor_to_the_end() {
+        let first = paginate(items(5), None, 2).ok().expect("first page");
+        assert_eq!(first["nextCursor"], "2");
+        let second = paginate(items(5), Some(&first["nextCursor"]), 2)
+            .ok()
+            .expect("second page");
+        assert_eq!(second["tools"][0]["name"], 2);
+        assert_eq!(second["nextCursor"], "4");
+        let last = paginate(items(5), Some(&second["nextCursor"]), 2)
+            .ok()
+            .expect("last page");
+        assert_eq!(last["tools"].as_array().map(Vec::len), Some(1));
+        assert!(last.get("nextCursor").is_none());
+    }
+
+    #[test]
+    fn paginate_rejects_bad_cursors() {
+        for cursor in [json!("abc"), json!(3), json!("9")] {
+            let err = paginate(items(5), Some(&cursor), 2).expect_err("error");
+            assert_eq!(err.code, resources::INVALID_PARAMS);
+        }
+    }
+}
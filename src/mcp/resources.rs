@@ -0,0 +1,239 @@
+use crate::mcp::contracts::limits;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+pub const HWP_MIME_TYPE: &str = "application/x-hwp";
+pub const HWPX_MIME_TYPE: &str = "application/hwp+zip";
+
+/// JSON-RPC error codes used by `resources/read`.
+pub const RESOURCE_NOT_FOUND: i64 = -32002;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const INTERNAL_ERROR: i64 = -32603;
+
+struct Resource {
+    path: String,
+    name: String,
+    mime_type: &'static str,
+}
+
+/// Files written by tools during this process, keyed by URI. Only these can be read back
+/// through `resources/read`, so the protocol never exposes arbitrary local files.
+static REGISTRY: OnceLock<Mutex<BTreeMap<String, Resource>>> = OnceLock::new();
+
+fn registry() -> &'static Mutex<BTreeMap<String, Resource>> {
+    REGISTRY.get_or_init(|| Mutex::new(BTreeMap::new()))
+}
+
+/// Environment variable that moves resource files out of the system temp directory.
+pub const OUTPUT_DIR_ENV: &str = "MCP_HWP_OUTPUT_DIR";
+
+/// Resolves where a tool writes resource files: `requested` (the tool's `output_dir` argument)
+/// wins, then `MCP_HWP_OUTPUT_DIR`, then the system temp directory. The directory is created if
+/// it does not exist yet.
+pub fn output_dir(requested: Option<&str>) -> std::io::Result<PathBuf> {
+    let dir = match requested {
+        Some(dir) => PathBuf::from(dir),
+        None => match std::env::var(OUTPUT_DIR_ENV) {
+            Ok(dir) if !dir.trim().is_empty() => PathBuf::from(dir),
+            _ => std::env::temp_dir(),
+        },
+    };
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// `{pid}-{millis}-{seq}` for resource file names, so concurrent calls and server processes
+/// sharing one output directory never overwrite each other's files.
+pub fn unique_suffix() -> String {
+    static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis())
+        .unwrap_or(0);
+    let sequence = SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    format!("{}-{millis}-{sequence}", std::process::id())
+}
+
+/// A file the server named and placed itself, as opposed to one written to a caller-chosen
+/// `output_path`/`output_dir`. Only these are ever deleted by [`cleanup`].
+struct Temporary {
+    path: PathBuf,
+    written: Instant,
+}
+
+static TEMPORARY: OnceLock<Mutex<Vec<Temporary>>> = OnceLock::new();
+
+static TTL: OnceLock<Duration> = OnceLock::new();
+
+fn temporary() -> &'static Mutex<Vec<Temporary>> {
+    TEMPORARY.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+/// Records a server-placed file so [`cleanup`] can delete it later in this session.
+pub fn track(path: &Path) {
+    if let Ok(mut temporary) = temporary().lock() {
+        temporary.push(Temporary {
+            path: path.to_path_buf(),
+            written: Instant::now(),
+        });
+    }
+}
+
+/// Purges tracked files older than `ttl` lazily, on every tool call, for the rest of the process.
+pub fn set_ttl(ttl: Duration) {
+    let _ = TTL.set(ttl);
+}
+
+/// Outcome of [`cleanup`]: deleted paths, paths that could not be deleted (still tracked), and
+/// how many tracked files are left.
+pub struct Cleanup {
+    pub removed: Vec<String>,
+    pub failed: Vec<(String, String)>,
+    pub remaining: usize,
+}
+
+/// Deletes tracked files written at least `older_than` ago, or all of them when `None`, and
+/// drops them from the resource registry. Files already gone count as removed.
+pub fn cleanup(older_than: Option<Duration>) -> Cleanup {
+    let mut report = Cleanup {
+        removed: Vec::new(),
+        failed: Vec::new(),
+        remaining: 0,
+    };
+    let Ok(mut temporary) = temporary().lock() else {
+        return report;
+    };
+    temporary.retain(|file| {
+        if older_than.is_some_and(|age| file.written.elapsed() < age) {
+            return true;
+        }
+        let path = file.path.to_string_lossy().to_string();
+        match fs::remove_file(&file.path) {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => {
+                report.failed.push((path, err.to_string()));
+                return true;
+            }
+        }
+        report.removed.push(path);
+        false
+    });
+    report.remaining = temporary.len();
+    drop(temporary);
+
+    if !report.removed.is_empty()
+        && let Ok(mut registry) = registry().lock()
+    {
+        registry.retain(|_, resource| !report.removed.contains(&resource.path));
+    }
+    report
+}
+
+/// Applies the TTL set with [`set_ttl`], if any.
+pub fn purge_expired() {
+    if let Some(ttl) = TTL.get() {
+        cleanup(Some(*ttl));
+    }
+}
+
+pub struct ResourceError {
+    pub code: i64,
+    pub message: String,
+}
+
+/// Records a file a tool has written and returns its `file://` URI.
+pub fn register(path: &str, mime_type: &'static str) -> String {
+    let uri = format!("file://{path}");
+    let name = Path::new(path)
+        .file_name()
+        .and_then(|value| value.to_str())
+        .unwrap_or(path)
+        .to_string();
+    if let Ok(mut registry) = registry().lock() {
+        registry.insert(
+            uri.clone(),
+            Resource {
+                path: path.to_string(),
+                name,
+                mime_type,
+            },
+        );
+    }
+    uri
+}
+
+/// Lists registered resources whose files still exist.
+pub fn list() -> Vec<Value> {
+    let Ok(registry) = registry().lock() else {
+        return Vec::new();
+    };
+    registry
+        .iter()
+        .filter_map(|(uri, resource)| {
+            let size = fs::metadata(&resource.path).ok()?.len();
+            Some(json!({
+                "uri": uri,
+                "name": resource.name,
+                "mimeType": resource.mime_type,
+                "size": size
+            }))
+        })
+        .collect()
+}
+
+/// Reads a registered resource as a base64 `blob`, refusing files above the output limit.
+pub fn read(params: Option<&Value>) -> Result<Value, ResourceError> {
+    let Some(uri) = params
+        .and_then(|params| params.get("uri"))
+        .and_then(|uri| uri.as_str())
+    else {
+        return Err(ResourceError {
+            code: INVALID_PARAMS,
+            message: "params.uri must be a string".to_string(),
+        });
+    };
+    let (path, mime_type) = {
+        let registry = registry().lock().map_err(|_| ResourceError {
+            code: INTERNAL_ERROR,
+            message: "resource registry poisoned".to_string(),
+        })?;
+        match registry.get(uri) {
+            Some(resource) => (resource.path.clone(), resource.mime_type),
+            None => {
+                return Err(ResourceError {
+                    code: RESOURCE_NOT_FOUND,
+                    message: format!("Resource not found: {uri}"),
+                });
+            }
+        }
+    };
+
+    let not_found = |_| ResourceError {
+        code: RESOURCE_NOT_FOUND,
+        message: format!("Resource not found: {uri}"),
+    };
+    let len = fs::metadata(&path).map_err(not_found)?.len();
+    let max_output_bytes = limits().max_output_bytes;
+    if len > max_output_bytes {
+        return Err(ResourceError {
+            code: INTERNAL_ERROR,
+            message: format!("resource exceeds limit: {len} bytes (max {max_output_bytes})"),
+        });
+    }
+    let bytes = fs::read(&path).map_err(not_found)?;
+    Ok(json!({
+        "contents": [{
+            "uri": uri,
+            "mimeType": mime_type,
+            "blob": STANDARD.encode(bytes)
+        }]
+    }))
+}
@@ -0,0 +1,224 @@
+use crate::input::{InputSource, ensure_source_allowed};
+use crate::mcp::contracts::{MAX_BATCH_FILES, TOOL_BATCH};
+use crate::mcp::errors;
+use crate::tools::{ToolError, cancelled_result, check_arguments, error_result, is_cancelled};
+use serde_json::{Map, Value, json};
+use std::fs;
+use std::path::Path;
+
+const DEFAULT_MAX_FILES: usize = 100;
+
+/// Runs one tool over many files. `dispatch` is the server's tool router, so every tool
+/// that takes a `path` works here unchanged. A cancelled request stops between files.
+pub fn call(args: &Value, dispatch: &dyn Fn(&str, &Value) -> Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let request = match parse_request(args) {
+        Ok(request) => request,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let mut warnings = Vec::new();
+    let mut paths = match &request.files {
+        FileSource::Dir(dir) => match list_dir(dir) {
+            Ok(paths) => paths,
+            Err(err) => return error_result(err.kind, err.message, Some(dir)),
+        },
+        FileSource::Paths(paths) => paths.clone(),
+    };
+    let found = paths.len();
+    if found > request.max_files {
+        paths.truncate(request.max_files);
+        warnings.push(format!(
+            "max_files reached: processed {} of {found} files",
+            request.max_files
+        ));
+    }
+
+    let mut results = Map::new();
+    let mut succeeded = 0u64;
+    let mut failed = 0u64;
+    for path in &paths {
+        if is_cancelled() {
+            return cancelled_result();
+        }
+        let mut arguments = request.arguments.clone();
+        arguments.insert("path".to_string(), json!(path));
+        let result = dispatch(&request.tool, &Value::Object(arguments));
+
+        let is_error = result
+            .get("isError")
+            .and_then(|value| value.as_bool())
+            .unwrap_or(true);
+        let structured = result
+            .get("structuredContent")
+            .cloned()
+            .unwrap_or(Value::Null);
+        let kind = is_error.then(|| {
+            structured
+                .get("error")
+                .and_then(|error| error.get("kind"))
+                .cloned()
+                .unwrap_or(Value::Null)
+        });
+        if is_error {
+            failed += 1;
+        } else {
+            succeeded += 1;
+        }
+        results.insert(
+            path.clone(),
+            json!({
+                "isError": is_error,
+                "kind": kind,
+                "structuredContent": structured
+            }),
+        );
+    }
+
+    json!({
+        "content": [{
+            "type": "text",
+            "text": format!(
+                "{} on {} file(s): {succeeded} succeeded, {failed} failed",
+                request.tool,
+                paths.len()
+            )
+        }],
+        "structuredContent": {
+            "tool": request.tool,
+            "files": paths.len(),
+            "succeeded": succeeded,
+            "failed": failed,
+            "results": results,
+            "warnings": warnings
+        },
+        "isError": false
+    })
+}
+
+enum FileSource {
+    Dir(String),
+    Paths(Vec<String>),
+}
+
+struct BatchRequest {
+    files: FileSource,
+    tool: String,
+    arguments: Map<String, Value>,
+    max_files: usize,
+}
+
+fn invalid(message: impl Into<String>) -> ToolError {
+    ToolError {
+        kind: errors::INVALID_INPUT,
+        message: message.into(),
+    }
+}
+
+fn parse_request(args: &Value) -> Result<BatchRequest, ToolError> {
+    let files = match (args.get("dir"), args.get("paths")) {
+        (Some(_), Some(_)) => return Err(invalid("only one of dir or paths can be set")),
+        (None, None) => return Err(invalid("one of dir or paths is required")),
+        (Some(dir), None) => {
+            let dir = dir
+                .as_str()
+                .filter(|dir| !dir.trim().is_empty())
+                .ok_or_else(|| invalid("dir must be a non-empty string"))?;
+            FileSource::Dir(dir.to_string())
+        }
+        (None, Some(paths)) => {
+            let paths = paths
+                .as_array()
+                .filter(|paths| !paths.is_empty())
+                .ok_or_else(|| invalid("paths must be a non-empty array of strings"))?;
+            let mut list = Vec::new();
+            for path in paths {
+                let path = path
+                    .as_str()
+                    .ok_or_else(|| invalid("paths must be a non-empty array of strings"))?;
+                if !list.iter().any(|seen| seen == path) {
+                    list.push(path.to_string());
+                }
+            }
+            FileSource::Paths(list)
+        }
+    };
+    ensure_source_allowed(InputSource::Path).map_err(|err| ToolError {
+        kind: err.kind,
+        message: err.message,
+    })?;
+
+    let tool = args
+        .get("tool")
+        .and_then(|value| value.as_str())
+        .ok_or_else(|| invalid("tool is required"))?;
+    if tool == TOOL_BATCH {
+        return Err(invalid("tool cannot be hwp.batch"));
+    }
+    let takes_path = crate::mcp::tool_definitions().iter().any(|definition| {
+        definition.get("name").and_then(|name| name.as_str()) == Some(tool)
+            && definition.pointer("/inputSchema/properties/path").is_some()
+    });
+    if !takes_path {
+        return Err(invalid(format!(
+            "tool {tool} does not exist or does not take a path input"
+        )));
+    }
+
+    let arguments = match args.get("arguments") {
+        None => Map::new(),
+        Some(Value::Object(arguments)) => arguments.clone(),
+        Some(_) => return Err(invalid("arguments must be an object")),
+    };
+    for key in ["path", "base64", "url"] {
+        if arguments.contains_key(key) {
+            return Err(invalid(format!(
+                "arguments.{key} cannot be set; the input comes from dir/paths"
+            )));
+        }
+    }
+
+    let max_files = match args.get("max_files") {
+        None => DEFAULT_MAX_FILES,
+        Some(value) => match value.as_u64() {
+            Some(max) if (1..=MAX_BATCH_FILES as u64).contains(&max) => max as usize,
+            _ => {
+                return Err(invalid(format!(
+                    "max_files must be an integer between 1 and {MAX_BATCH_FILES}"
+                )));
+            }
+        },
+    };
+
+    Ok(BatchRequest {
+        files,
+        tool: tool.to_string(),
+        arguments,
+        max_files,
+    })
+}
+
+/// Lists `.hwp`/`.hwpx` files directly inside `dir`, sorted by name.
+fn list_dir(dir: &str) -> Result<Vec<String>, ToolError> {
+    let entries = fs::read_dir(Path::new(dir))
+        .map_err(|err| invalid(format!("dir cannot be read: {err}")))?;
+    let mut paths = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let is_hwp = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("hwp") || ext.eq_ignore_ascii_case("hwpx"));
+        if is_hwp {
+            paths.push(path.to_string_lossy().to_string());
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
@@ -0,0 +1,269 @@
+use flate2::Compression;
+use flate2::write::ZlibEncoder;
+use std::f64::consts::PI;
+use std::io::Write;
+
+/// Canvas size of rendered charts, in pixels.
+pub(crate) const CHART_WIDTH: u32 = 640;
+pub(crate) const CHART_HEIGHT: u32 = 400;
+
+const MARGIN: i64 = 32;
+const BACKGROUND: [u8; 3] = [0xFF, 0xFF, 0xFF];
+const AXIS: [u8; 3] = [0x60, 0x60, 0x60];
+const PALETTE: [[u8; 3]; 6] = [
+    [0x4E, 0x79, 0xA7],
+    [0xF2, 0x8E, 0x2B],
+    [0xE1, 0x57, 0x59],
+    [0x76, 0xB7, 0xB2],
+    [0x59, 0xA1, 0x4F],
+    [0xED, 0xC9, 0x48],
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ChartType {
+    Bar,
+    Line,
+    Pie,
+}
+
+impl ChartType {
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        match value {
+            "bar" => Some(ChartType::Bar),
+            "line" => Some(ChartType::Line),
+            "pie" => Some(ChartType::Pie),
+            _ => None,
+        }
+    }
+}
+
+/// `series[s][i]` is the value of series `s` for the `i`-th category.
+pub(crate) struct ChartData {
+    pub series: Vec<Vec<f64>>,
+}
+
+/// Rasterizes the chart and encodes it as PNG. Labels are not drawn (there is no font rasterizer).
+pub(crate) fn render_png(chart_type: ChartType, data: &ChartData) -> Vec<u8> {
+    let mut canvas = Canvas::new(CHART_WIDTH, CHART_HEIGHT);
+    match chart_type {
+        ChartType::Bar => draw_bars(&mut canvas, data),
+        ChartType::Line => draw_lines(&mut canvas, data),
+        ChartType::Pie => draw_pie(&mut canvas, data),
+    }
+    canvas.to_png()
+}
+
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32) -> Self {
+        let pixels = BACKGROUND.repeat((width * height) as usize);
+        Canvas {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    fn set(&mut self, x: i64, y: i64, color: [u8; 3]) {
+        if x < 0 || y < 0 || x >= i64::from(self.width) || y >= i64::from(self.height) {
+            return;
+        }
+        let offset = ((y as usize) * self.width as usize + x as usize) * 3;
+        self.pixels[offset..offset + 3].copy_from_slice(&color);
+    }
+
+    fn fill_rect(&mut self, x0: i64, y0: i64, x1: i64, y1: i64, color: [u8; 3]) {
+        for y in y0.min(y1)..y0.max(y1) {
+            for x in x0.min(x1)..x0.max(x1) {
+                self.set(x, y, color);
+            }
+        }
+    }
+
+    fn line(&mut self, (x0, y0): (i64, i64), (x1, y1): (i64, i64), thickness: i64, color: [u8; 3]) {
+        let steps = (x1 - x0).abs().max((y1 - y0).abs()).max(1);
+        let half = thickness / 2;
+        for step in 0..=steps {
+            let x = x0 + (x1 - x0) * step / steps;
+            let y = y0 + (y1 - y0) * step / steps;
+            self.fill_rect(
+                x - half,
+                y - half,
+                x - half + thickness,
+                y - half + thickness,
+                color,
+            );
+        }
+    }
+
+    fn disc(&mut self, cx: i64, cy: i64, radius: i64, color: [u8; 3]) {
+        for y in -radius..=radius {
+            for x in -radius..=radius {
+                if x * x + y * y <= radius * radius {
+                    self.set(cx + x, cy + y, color);
+                }
+            }
+        }
+    }
+
+    fn to_png(&self) -> Vec<u8> {
+        let row_len = self.width as usize * 3;
+        let mut raw = Vec::with_capacity((row_len + 1) * self.height as usize);
+        for row in self.pixels.chunks(row_len) {
+            raw.push(0); // filter: none
+            raw.extend_from_slice(row);
+        }
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        // Writing to a Vec cannot fail.
+        let _ = encoder.write_all(&raw);
+        let compressed = encoder.finish().unwrap_or_default();
+
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&self.width.to_be_bytes());
+        header.extend_from_slice(&self.height.to_be_bytes());
+        header.extend_from_slice(&[8, 2, 0, 0, 0]); // 8-bit RGB, no interlace
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        write_chunk(&mut png, b"IHDR", &header);
+        write_chunk(&mut png, b"IDAT", &compressed);
+        write_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(kind);
+    hasher.update(data);
+    png.extend_from_slice(&hasher.finalize().to_be_bytes());
+}
+
+/// Plot area as (left, top, right, bottom) plus a value-to-y mapping that always includes zero.
+struct Plot {
+    left: i64,
+    top: i64,
+    right: i64,
+    bottom: i64,
+    min: f64,
+    max: f64,
+}
+
+impl Plot {
+    fn new(canvas: &Canvas, data: &ChartData) -> Self {
+        let values = data.series.iter().flatten().copied();
+        let (min, max) = values.fold((0.0f64, 0.0f64), |(lo, hi), v| (lo.min(v), hi.max(v)));
+        let max = if max - min <= f64::EPSILON {
+            min + 1.0
+        } else {
+            max
+        };
+        Plot {
+            left: MARGIN,
+            top: MARGIN,
+            right: i64::from(canvas.width) - MARGIN,
+            bottom: i64::from(canvas.height) - MARGIN,
+            min,
+            max,
+        }
+    }
+
+    fn y(&self, value: f64) -> i64 {
+        let ratio = (value - self.min) / (self.max - self.min);
+        self.bottom - (ratio * (self.bottom - self.top) as f64).round() as i64
+    }
+
+    fn draw_axes(&self, canvas: &mut Canvas) {
+        canvas.line((self.left, self.top), (self.left, self.bottom), 2, AXIS);
+        let zero = self.y(0.0);
+        canvas.line((self.left, zero), (self.right, zero), 2, AXIS);
+    }
+}
+
+fn draw_bars(canvas: &mut Canvas, data: &ChartData) {
+    let plot = Plot::new(canvas, data);
+    let categories = data.series.first().map(Vec::len).unwrap_or(0) as i64;
+    let series = data.series.len() as i64;
+    if categories > 0 && series > 0 {
+        let group = (plot.right - plot.left) / categories;
+        let bar = (group * 4 / 5 / series).max(1);
+        let zero = plot.y(0.0);
+        for (s, values) in data.series.iter().enumerate() {
+            let color = PALETTE[s % PALETTE.len()];
+            for (i, value) in values.iter().enumerate() {
+                let x = plot.left + group * i as i64 + group / 10 + bar * s as i64;
+                canvas.fill_rect(x, plot.y(*value), x + bar, zero, color);
+            }
+        }
+    }
+    plot.draw_axes(canvas);
+}
+
+fn draw_lines(canvas: &mut Canvas, data: &ChartData) {
+    let plot = Plot::new(canvas, data);
+    plot.draw_axes(canvas);
+    for (s, values) in data.series.iter().enumerate() {
+        let color = PALETTE[s % PALETTE.len()];
+        let step = if values.len() > 1 {
+            (plot.right - plot.left) / (values.len() as i64 - 1)
+        } else {
+            0
+        };
+        let points: Vec<(i64, i64)> = values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (plot.left + step * i as i64, plot.y(*value)))
+            .collect();
+        for pair in points.windows(2) {
+            canvas.line(pair[0], pair[1], 3, color);
+        }
+        for (x, y) in points {
+            canvas.disc(x, y, 4, color);
+        }
+    }
+}
+
+/// Draws the first series as a pie, clockwise from 12 o'clock. Values must be non-negative.
+fn draw_pie(canvas: &mut Canvas, data: &ChartData) {
+    let Some(values) = data.series.first() else {
+        return;
+    };
+    let total: f64 = values.iter().sum();
+    if total <= 0.0 {
+        return;
+    }
+    let mut bounds = Vec::with_capacity(values.len());
+    let mut acc = 0.0;
+    for value in values {
+        acc += value / total;
+        bounds.push(acc * 2.0 * PI);
+    }
+
+    let cx = i64::from(canvas.width) / 2;
+    let cy = i64::from(canvas.height) / 2;
+    let radius = (i64::from(canvas.width.min(canvas.height)) / 2 - MARGIN).max(1);
+    for y in -radius..=radius {
+        for x in -radius..=radius {
+            if x * x + y * y > radius * radius {
+                continue;
+            }
+            // Angle measured clockwise from straight up.
+            let mut angle = (x as f64).atan2(-(y as f64));
+            if angle < 0.0 {
+                angle += 2.0 * PI;
+            }
+            let slice = bounds
+                .iter()
+                .position(|bound| angle < *bound)
+                .unwrap_or(values.len() - 1);
+            canvas.set(cx + x, cy + y, PALETTE[slice % PALETTE.len()]);
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use crate::mcp::errors;
+use crate::mcp::resources;
+use crate::tools::{check_arguments, error_result};
+use serde_json::{Value, json};
+use std::time::Duration;
+
+pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let older_than = match args.get("older_than_minutes") {
+        None | Some(Value::Null) => None,
+        Some(value) => match value.as_u64() {
+            Some(minutes) => Some(Duration::from_secs(minutes.saturating_mul(60))),
+            None => {
+                return error_result(
+                    errors::INVALID_INPUT,
+                    "older_than_minutes must be a non-negative integer",
+                    None,
+                );
+            }
+        },
+    };
+
+    let report = resources::cleanup(older_than);
+    let failed: Vec<Value> = report
+        .failed
+        .iter()
+        .map(|(path, message)| json!({ "path": path, "message": message }))
+        .collect();
+
+    let mut text = format!(
+        "removed {} file(s), {} remaining",
+        report.removed.len(),
+        report.remaining
+    );
+    for (path, message) in &report.failed {
+        text.push_str(&format!("\nfailed to remove {path}: {message}"));
+    }
+
+    json!({
+        "content": [{"type": "text", "text": text}],
+        "structuredContent": {
+            "removed": report.removed,
+            "failed": failed,
+            "remaining": report.remaining
+        },
+        "isError": false
+    })
+}
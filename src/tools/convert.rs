@@ -1,19 +1,24 @@
-use crate::input::{InputFormat, load_input};
-use crate::mcp::contracts::MAX_OUTPUT_BYTES;
+use crate::input::{InputFormat, is_distribution_document, load_input};
+use crate::mcp::contracts::limits;
 use crate::mcp::errors;
-use crate::tools::error_result;
+use crate::tools::extract_rich::extract_blocks;
+use crate::tools::to_markdown::render_markdown;
+use crate::tools::{
+    BuiltDocument, Compress, ParsedDocument, ToolError, check_arguments, error_result,
+    map_hwp_error_with_stage, parse_document, parse_output_path, write_output,
+};
 use base64::Engine;
 use base64::engine::general_purpose::STANDARD;
-use hwpers::{HwpError, HwpReader, HwpWriter, HwpxReader, HwpxWriter};
+use hwpers::reader::CfbReader;
+use hwpers::{HwpWriter, HwpxWriter};
 use serde_json::{Value, json};
 use std::fs;
-use std::path::Path;
+use std::io::Cursor;
 
 pub fn call(args: &Value) -> Value {
-    let payload = match load_input(args) {
-        Ok(payload) => payload,
-        Err(err) => return error_result(err.kind, err.message, None),
-    };
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
 
     let to_format = match OutputFormat::parse(args.get("to")) {
         Ok(to_format) => to_format,
@@ -25,88 +30,418 @@ pub fn call(args: &Value) -> Value {
         Err(err) => return error_result(err.kind, err.message, None),
     };
 
-    let parsed = match parse_document(&payload.bytes, payload.format) {
-        Ok(parsed) => parsed,
-        Err(err) => return error_result(err.kind, err.message, Some(payload.source.as_str())),
+    let include_base64 = match parse_include_base64(args.get("include_base64")) {
+        Ok(include_base64) => include_base64,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let compress = match Compress::parse(args.get("compress")) {
+        Ok(compress) => compress,
+        Err(message) => return error_result(errors::INVALID_INPUT, message, None),
     };
 
-    let output_bytes = match to_format {
-        OutputFormat::Hwp => HwpWriter::from_document(parsed.document)
-            .to_bytes()
-            .map_err(|error| map_hwp_error_with_stage(error, "convert to hwp")),
-        OutputFormat::Hwpx => HwpxWriter::from_document(parsed.document)
-            .to_bytes()
-            .map_err(|error| map_hwp_error_with_stage(error, "convert to hwpx")),
+    let revisions = match RevisionMode::parse(args) {
+        Ok(revisions) => revisions,
+        Err(err) => return error_result(err.kind, err.message, None),
     };
 
-    let output_bytes = match output_bytes {
-        Ok(bytes) => bytes,
+    let metadata = match MetadataMode::parse(args.get("metadata")) {
+        Ok(metadata) => metadata,
         Err(err) => return error_result(err.kind, err.message, None),
     };
 
-    let bytes_len = output_bytes.len() as u64;
-    let warnings = parsed.warnings;
+    let (mut parsed, blocks, source_counts) = if to_format.is_text() {
+        // Text targets only read the document, so they go through the extraction
+        // pipeline (which also opens distribution documents).
+        let mut input_args = args.clone();
+        if let Some(object) = input_args.as_object_mut() {
+            object.remove("output_path");
+        }
+        match extract_blocks(&input_args, Compress::None) {
+            Ok(extracted) => (
+                ParsedDocument {
+                    document: extracted.document,
+                    format: extracted.format,
+                    detection_method: extracted.detection_method,
+                    warnings: extracted.warnings,
+                },
+                extracted.blocks,
+                // Text targets are renderings, not documents, so there is nothing to re-parse.
+                None,
+            ),
+            Err(tool_result) => return tool_result,
+        }
+    } else {
+        let payload = match load_input(args) {
+            Ok(payload) => payload,
+            Err(err) => return error_result(err.kind, err.message, None),
+        };
+
+        if is_distribution_document(&payload.bytes) {
+            return error_result(
+                errors::ENCRYPTED,
+                "distribution document (배포용 문서) is read-only and cannot be converted",
+                Some(payload.source.as_str()),
+            );
+        }
+
+        match parse_document(&payload.bytes, payload.format) {
+            Ok(parsed) => {
+                let counts = ContentCounts::of(&parsed.document, &payload.bytes, parsed.format);
+                (parsed, Vec::new(), Some(counts))
+            }
+            Err(err) => {
+                return error_result(err.kind, err.message, Some(payload.source.as_str()));
+            }
+        }
+    };
+
+    let detected_format = parsed.format.as_str();
+    let detection_method = payload.detection_method;
+    let mut warnings = parsed.warnings;
+    if let Some(option) = revisions.option_name() {
+        // hwpers 0.5.0 does not model tracked insertions/deletions, so there is
+        // nothing to materialize or discard before re-serialization.
+        warnings.push(format!(
+            "{option}: applied 0 tracked changes (revision data is not exposed by hwpers 0.5.0)"
+        ));
+    }
+
+    let stripped = match metadata {
+        MetadataMode::Preserve => None,
+        MetadataMode::Strip => Some(strip_metadata(&mut parsed.document)),
+    };
+
+    let output = match to_format {
+        OutputFormat::Hwp => match HwpWriter::from_document(parsed.document).to_bytes() {
+            Ok(bytes) => BuiltDocument::Bytes(bytes),
+            Err(error) => {
+                let err = map_hwp_error_with_stage(error, "convert to hwp");
+                return error_result(err.kind, err.message, None);
+            }
+        },
+        OutputFormat::Hwpx => {
+            BuiltDocument::Hwpx(Box::new(HwpxWriter::from_document(parsed.document)))
+        }
+        OutputFormat::Txt => BuiltDocument::Text(parsed.document.extract_text(), "text/plain"),
+        OutputFormat::Md => {
+            BuiltDocument::Text(render_markdown(&parsed.document, &blocks), "text/markdown")
+        }
+    };
 
-    match output_path {
-        Some(path) => match write_output(&path, &output_bytes) {
-            Ok(output) => json!({
-                "content": output.content,
+    let mut result = match output_path {
+        Some(path) => match write_output(
+            &path,
+            output,
+            &format!("converted to {}", to_format.as_str()),
+        ) {
+            Ok(output) => {
+                if let Some(source_counts) = &source_counts {
+                    match fs::read(&output.path) {
+                        Ok(bytes) => {
+                            warnings.extend(fidelity_warnings(source_counts, &bytes, &to_format))
+                        }
+                        Err(err) => warnings.push(format!(
+                            "fidelity: could not re-read output for comparison: {err}"
+                        )),
+                    }
+                }
+                let mut result = json!({
+                    "content": output.content,
+                    "structuredContent": {
+                        "to": to_format.as_str(),
+                        "detected_format": detected_format,
+                        "detection_method": detection_method,
+                        "path": output.path,
+                        "uri": output.uri,
+                        "bytes_len": output.bytes_len,
+                        "warnings": warnings
+                    },
+                    "isError": false
+                });
+                if include_base64 {
+                    match inline_copy(&output.path, compress) {
+                        Ok(base64) => result["structuredContent"]["base64"] = json!(base64),
+                        Err(err) => return error_result(err.kind, err.message, None),
+                    }
+                    if let Some(encoding) = compress.encoding() {
+                        result["structuredContent"]["encoding"] = json!(encoding);
+                    }
+                }
+                result
+            }
+            Err(err) => error_result(err.kind, err.message, None),
+        },
+        None if to_format.is_text() => {
+            let text = match output.into_bytes() {
+                Ok(bytes) => String::from_utf8(bytes).unwrap_or_default(),
+                Err(err) => return error_result(err.kind, err.message, None),
+            };
+            let bytes_len = text.len() as u64;
+            let max_output_bytes = limits().max_output_bytes;
+            if bytes_len > max_output_bytes {
+                return error_result(
+                    errors::TOO_LARGE,
+                    format!("output exceeds limit: {bytes_len} bytes (max {max_output_bytes})"),
+                    None,
+                );
+            }
+            let key = match to_format {
+                OutputFormat::Md => "markdown",
+                _ => "text",
+            };
+            json!({
+                "content": [{
+                    "type": "text",
+                    "text": text
+                }],
                 "structuredContent": {
                     "to": to_format.as_str(),
-                    "path": output.path,
-                    "uri": output.uri,
+                    "detected_format": detected_format,
+                    "detection_method": detection_method,
+                    key: text,
                     "bytes_len": bytes_len,
                     "warnings": warnings
                 },
                 "isError": false
-            }),
-            Err(err) => error_result(err.kind, err.message, None),
-        },
+            })
+        }
         None => {
-            if bytes_len > MAX_OUTPUT_BYTES {
+            let output_bytes = match output.into_bytes() {
+                Ok(bytes) => bytes,
+                Err(err) => return error_result(err.kind, err.message, None),
+            };
+            let bytes_len = output_bytes.len() as u64;
+            let payload = compress.apply(&output_bytes);
+            let payload_len = payload.len() as u64;
+            let max_output_bytes = limits().max_output_bytes;
+            if payload_len > max_output_bytes {
                 return error_result(
                     errors::TOO_LARGE,
-                    format!("output exceeds limit: {bytes_len} bytes (max {MAX_OUTPUT_BYTES})"),
+                    format!("output exceeds limit: {payload_len} bytes (max {max_output_bytes})"),
                     None,
                 );
             }
-            let base64 = STANDARD.encode(&output_bytes);
-            json!({
+            if let Some(source_counts) = &source_counts {
+                warnings.extend(fidelity_warnings(source_counts, &output_bytes, &to_format));
+            }
+            let base64 = STANDARD.encode(&payload);
+            let mut result = json!({
                 "content": [{
                     "type": "text",
                     "text": format!("converted to {} ({bytes_len} bytes)", to_format.as_str())
                 }],
                 "structuredContent": {
                     "to": to_format.as_str(),
+                    "detected_format": detected_format,
+                    "detection_method": detection_method,
                     "base64": base64,
                     "bytes_len": bytes_len,
                     "warnings": warnings
                 },
                 "isError": false
-            })
+            });
+            if let Some(encoding) = compress.encoding() {
+                result["structuredContent"]["encoding"] = json!(encoding);
+            }
+            result
         }
+    };
+    if let Some(stripped) = stripped {
+        result["structuredContent"]["stripped"] = json!(stripped);
     }
+    result
 }
 
-struct ToolError {
-    kind: &'static str,
-    message: String,
+enum OutputFormat {
+    Hwp,
+    Hwpx,
+    Txt,
+    Md,
 }
 
-struct ParsedDocument {
-    document: hwpers::HwpDocument,
-    warnings: Vec<String>,
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum RevisionMode {
+    Keep,
+    Accept,
+    Reject,
 }
 
-struct OutputResource {
-    path: String,
-    uri: String,
-    content: Vec<Value>,
+impl RevisionMode {
+    fn parse(args: &Value) -> Result<Self, ToolError> {
+        let accept = parse_bool_flag(args.get("accept_revisions"), "accept_revisions")?;
+        let reject = parse_bool_flag(args.get("reject_revisions"), "reject_revisions")?;
+        match (accept, reject) {
+            (true, true) => Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "accept_revisions and reject_revisions are mutually exclusive".to_string(),
+            }),
+            (true, false) => Ok(RevisionMode::Accept),
+            (false, true) => Ok(RevisionMode::Reject),
+            (false, false) => Ok(RevisionMode::Keep),
+        }
+    }
+
+    fn option_name(self) -> Option<&'static str> {
+        match self {
+            RevisionMode::Keep => None,
+            RevisionMode::Accept => Some("accept_revisions"),
+            RevisionMode::Reject => Some("reject_revisions"),
+        }
+    }
 }
 
-enum OutputFormat {
-    Hwp,
-    Hwpx,
+/// Structure counted on both sides of a conversion to spot content the writers dropped.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct ContentCounts {
+    paragraphs: usize,
+    tables: usize,
+    images: usize,
+}
+
+impl ContentCounts {
+    fn of(document: &hwpers::HwpDocument, bytes: &[u8], format: InputFormat) -> Self {
+        let mut counts = ContentCounts {
+            paragraphs: 0,
+            tables: 0,
+            images: container_image_count(bytes, format),
+        };
+        for section in document.sections() {
+            counts.paragraphs += section.paragraphs.len();
+            counts.tables += section
+                .paragraphs
+                .iter()
+                .filter(|paragraph| paragraph.table_data.is_some())
+                .count();
+        }
+        counts
+    }
+}
+
+/// Re-parses the produced document and reports every count that changed, e.g.
+/// "fidelity: 3 tables in source, 2 in output".
+fn fidelity_warnings(source: &ContentCounts, output: &[u8], to: &OutputFormat) -> Vec<String> {
+    let format = match to {
+        OutputFormat::Hwpx => InputFormat::Hwpx,
+        _ => InputFormat::Hwp,
+    };
+    let reparsed = match parse_document(output, format) {
+        Ok(reparsed) => reparsed,
+        Err(err) => {
+            return vec![format!(
+                "fidelity: output could not be re-parsed for comparison ({})",
+                err.message
+            )];
+        }
+    };
+    let output = ContentCounts::of(&reparsed.document, output, format);
+    [
+        ("paragraphs", source.paragraphs, output.paragraphs),
+        ("tables", source.tables, output.tables),
+        ("images", source.images, output.images),
+    ]
+    .into_iter()
+    .filter(|(_, source, output)| source != output)
+    .map(|(name, source, output)| {
+        format!("fidelity: {source} {name} in source, {output} in output")
+    })
+    .collect()
+}
+
+/// Counts image entries in the container: `BinData/BIN{id}.{ext}` streams in HWP, `BinData/`
+/// entries in HWPX. The hwpers readers do not load image payloads, so the document model
+/// cannot be used for this.
+fn container_image_count(bytes: &[u8], format: InputFormat) -> usize {
+    let is_image = |name: &str| {
+        name.rsplit_once('.').is_some_and(|(_, extension)| {
+            matches!(
+                extension.to_ascii_lowercase().as_str(),
+                "bmp" | "gif" | "jpg" | "jpeg" | "png" | "tif" | "tiff" | "wmf" | "emf"
+            )
+        })
+    };
+    match format {
+        InputFormat::Hwpx => zip::ZipArchive::new(Cursor::new(bytes))
+            .map(|archive| {
+                archive
+                    .file_names()
+                    .filter(|name| name.starts_with("BinData/") && is_image(name))
+                    .count()
+            })
+            .unwrap_or(0),
+        _ => CfbReader::new(Cursor::new(bytes))
+            .map(|reader| {
+                reader
+                    .list_streams()
+                    .iter()
+                    .filter_map(|stream| stream.trim_start_matches('/').strip_prefix("BinData/"))
+                    .filter(|name| is_image(name))
+                    .count()
+            })
+            .unwrap_or(0),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum MetadataMode {
+    Preserve,
+    Strip,
+}
+
+impl MetadataMode {
+    fn parse(value: Option<&Value>) -> Result<Self, ToolError> {
+        let Some(value) = value else {
+            return Ok(MetadataMode::Preserve);
+        };
+        match value.as_str() {
+            Some("preserve") => Ok(MetadataMode::Preserve),
+            Some("strip") => Ok(MetadataMode::Strip),
+            _ => Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "metadata must be preserve or strip".to_string(),
+            }),
+        }
+    }
+}
+
+/// Drops the document summary (title, author, ...) and returns the names of the
+/// fields that carried a value. Only fields `hwpers` decoded are reported; it skips
+/// UTF-16 (`VT_LPWSTR`) strings, but the whole summary is dropped either way.
+fn strip_metadata(document: &mut hwpers::HwpDocument) -> Vec<&'static str> {
+    let Some(info) = document.summary_info.take() else {
+        return Vec::new();
+    };
+    let text_fields = [
+        ("title", &info.title),
+        ("subject", &info.subject),
+        ("author", &info.author),
+        ("keywords", &info.keywords),
+        ("comments", &info.comments),
+        ("last_saved_by", &info.last_saved_by),
+        ("revision_number", &info.revision_number),
+    ];
+    let mut stripped: Vec<&'static str> = text_fields
+        .into_iter()
+        .filter(|(_, value)| value.as_deref().is_some_and(|value| !value.is_empty()))
+        .map(|(name, _)| name)
+        .collect();
+    if info.creation_date.is_some() {
+        stripped.push("created");
+    }
+    if info.last_saved_date.is_some() {
+        stripped.push("modified");
+    }
+    stripped
+}
+
+fn parse_bool_flag(value: Option<&Value>, name: &str) -> Result<bool, ToolError> {
+    match value {
+        None => Ok(false),
+        Some(value) => value.as_bool().ok_or_else(|| ToolError {
+            kind: errors::INVALID_INPUT,
+            message: format!("{name} must be a boolean"),
+        }),
+    }
 }
 
 impl OutputFormat {
@@ -126,9 +461,11 @@ impl OutputFormat {
         match value {
             "hwp" => Ok(OutputFormat::Hwp),
             "hwpx" => Ok(OutputFormat::Hwpx),
+            "txt" => Ok(OutputFormat::Txt),
+            "md" => Ok(OutputFormat::Md),
             _ => Err(ToolError {
                 kind: errors::INVALID_INPUT,
-                message: "to must be hwp or hwpx".to_string(),
+                message: "to must be hwp, hwpx, txt, or md".to_string(),
             }),
         }
     }
@@ -137,143 +474,45 @@ impl OutputFormat {
         match self {
             OutputFormat::Hwp => "hwp",
             OutputFormat::Hwpx => "hwpx",
+            OutputFormat::Txt => "txt",
+            OutputFormat::Md => "md",
         }
     }
-}
 
-fn parse_output_path(value: Option<&Value>) -> Result<Option<String>, ToolError> {
-    let Some(value) = value else {
-        return Ok(None);
-    };
-    let Some(path) = value.as_str() else {
-        return Err(ToolError {
-            kind: errors::INVALID_INPUT,
-            message: "output_path must be a string".to_string(),
-        });
-    };
-    if path.trim().is_empty() {
-        return Err(ToolError {
-            kind: errors::INVALID_INPUT,
-            message: "output_path must not be empty".to_string(),
-        });
+    fn is_text(&self) -> bool {
+        matches!(self, OutputFormat::Txt | OutputFormat::Md)
     }
-    Ok(Some(path.to_string()))
 }
 
-fn parse_document(bytes: &[u8], format: InputFormat) -> Result<ParsedDocument, ToolError> {
-    match format {
-        InputFormat::Hwp => HwpReader::from_bytes(bytes)
-            .map(|document| ParsedDocument {
-                document,
-                warnings: Vec::new(),
-            })
-            .map_err(|error| map_hwp_error_with_format(error, format.as_str())),
-        InputFormat::Hwpx => HwpxReader::from_bytes(bytes)
-            .map(|document| ParsedDocument {
-                document,
-                warnings: Vec::new(),
-            })
-            .map_err(|error| map_hwp_error_with_format(error, format.as_str())),
-        InputFormat::Auto => {
-            let hwp_result = HwpReader::from_bytes(bytes);
-            match hwp_result {
-                Ok(document) => Ok(ParsedDocument {
-                    document,
-                    warnings: Vec::new(),
-                }),
-                Err(hwp_err) => match HwpxReader::from_bytes(bytes) {
-                    Ok(document) => Ok(ParsedDocument {
-                        document,
-                        warnings: vec!["auto format: hwp parse failed; hwpx succeeded".to_string()],
-                    }),
-                    Err(hwpx_err) => Err(ToolError {
-                        kind: errors::PARSE_FAILED,
-                        message: format!(
-                            "auto format parse failed (hwp: {}; hwpx: {})",
-                            hwp_err, hwpx_err
-                        ),
-                    }),
-                },
-            }
-        }
+fn parse_include_base64(value: Option<&Value>) -> Result<bool, ToolError> {
+    match value {
+        None | Some(Value::Null) => Ok(false),
+        Some(Value::Bool(flag)) => Ok(*flag),
+        Some(_) => Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "include_base64 must be a boolean".to_string(),
+        }),
     }
 }
 
-fn write_output(path: &str, bytes: &[u8]) -> Result<OutputResource, ToolError> {
-    fs::write(path, bytes).map_err(|err| ToolError {
+/// The copy `include_base64` returns next to a written `output_path`, read back from the file so
+/// it matches what was written, and held to the same limit as inline output (after `compress`).
+fn inline_copy(path: &str, compress: Compress) -> Result<String, ToolError> {
+    let bytes = fs::read(path).map_err(|err| ToolError {
         kind: errors::INTERNAL_ERROR,
-        message: format!("failed to write output: {err}"),
+        message: format!("failed to re-read output: {err}"),
     })?;
-
-    let uri = format!("file://{path}");
-    let name = Path::new(path)
-        .file_name()
-        .and_then(|value| value.to_str())
-        .unwrap_or("converted");
-
-    let content = vec![
-        json!({
-            "type": "text",
-            "text": format!("converted output written to {path}")
-        }),
-        json!({
-            "type": "resource_link",
-            "uri": uri,
-            "name": name,
-            "mimeType": "application/octet-stream"
-        }),
-    ];
-
-    Ok(OutputResource {
-        path: path.to_string(),
-        uri: format!("file://{path}"),
-        content,
-    })
-}
-
-fn map_hwp_error(error: HwpError) -> ToolError {
-    match error {
-        HwpError::UnsupportedVersion(message) => {
-            if message.contains("Password-encrypted") {
-                ToolError {
-                    kind: errors::ENCRYPTED,
-                    message,
-                }
-            } else {
-                ToolError {
-                    kind: errors::PARSE_FAILED,
-                    message,
-                }
-            }
-        }
-        HwpError::InvalidInput(message) => ToolError {
-            kind: errors::INVALID_INPUT,
-            message,
-        },
-        HwpError::Io(err) => ToolError {
-            kind: errors::INVALID_INPUT,
-            message: err.to_string(),
-        },
-        HwpError::InvalidFormat(message)
-        | HwpError::Cfb(message)
-        | HwpError::CompressionError(message)
-        | HwpError::ParseError(message)
-        | HwpError::EncodingError(message)
-        | HwpError::NotFound(message) => ToolError {
-            kind: errors::PARSE_FAILED,
-            message,
-        },
+    let bytes = compress.apply(&bytes);
+    let bytes_len = bytes.len() as u64;
+    let max_output_bytes = limits().max_output_bytes;
+    if bytes_len > max_output_bytes {
+        return Err(ToolError {
+            kind: errors::TOO_LARGE,
+            message: format!(
+                "output exceeds limit for include_base64: {bytes_len} bytes (max \
+                 {max_output_bytes}); {path} was still written"
+            ),
+        });
     }
-}
-
-fn map_hwp_error_with_format(error: HwpError, format: &str) -> ToolError {
-    let mut mapped = map_hwp_error(error);
-    mapped.message = format!("{format} parse failed: {}", mapped.message);
-    mapped
-}
-
-fn map_hwp_error_with_stage(error: HwpError, stage: &str) -> ToolError {
-    let mut mapped = map_hwp_error(error);
-    mapped.message = format!("{stage} failed: {}", mapped.message);
-    mapped
+    Ok(STANDARD.encode(bytes))
 }
@@ -0,0 +1,100 @@
+use crate::input::load_input;
+use crate::tools::{check_arguments, error_result, parse_document};
+use serde_json::{Value, json};
+
+pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let payload = match load_input(args) {
+        Ok(payload) => payload,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let parsed = match parse_document(&payload.bytes, payload.format) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return error_result(err.kind, err.message, Some(payload.source.as_str()));
+        }
+    };
+
+    let mut stats = Stats::default();
+    for section in parsed.document.sections() {
+        stats.section_count += 1;
+        for paragraph in &section.paragraphs {
+            stats.paragraph_count += 1;
+            if let Some(text) = &paragraph.text {
+                stats.add_text(&text.content);
+            }
+        }
+    }
+
+    let summary = format!(
+        "{} words, {} characters ({} without spaces)",
+        stats.word_count, stats.char_count, stats.char_count_no_spaces
+    );
+
+    json!({
+        "content": [{"type": "text", "text": summary}],
+        "structuredContent": {
+            "format": parsed.format.as_str(),
+            "detected_format": parsed.format.as_str(),
+            "detection_method": payload.detection_method,
+            "char_count": stats.char_count,
+            "char_count_no_spaces": stats.char_count_no_spaces,
+            "cjk_char_count": stats.cjk_char_count,
+            "word_count": stats.word_count,
+            "paragraph_count": stats.paragraph_count,
+            "section_count": stats.section_count,
+            "warnings": parsed.warnings
+        },
+        "isError": false
+    })
+}
+
+#[derive(Default)]
+struct Stats {
+    char_count: u64,
+    char_count_no_spaces: u64,
+    cjk_char_count: u64,
+    word_count: u64,
+    paragraph_count: u64,
+    section_count: u64,
+}
+
+impl Stats {
+    /// Control characters (paragraph terminators, inline control markers) are not counted.
+    /// Words are whitespace-separated, which matches eojeol for Korean text.
+    fn add_text(&mut self, text: &str) {
+        for ch in text.chars().filter(|ch| !ch.is_control()) {
+            self.char_count += 1;
+            if !ch.is_whitespace() {
+                self.char_count_no_spaces += 1;
+            }
+            if is_cjk(ch) {
+                self.cjk_char_count += 1;
+            }
+        }
+        self.word_count += text
+            .split(|ch: char| ch.is_whitespace() || ch.is_control())
+            .filter(|word| !word.is_empty())
+            .count() as u64;
+    }
+}
+
+/// Hangul (syllables and jamo), CJK ideographs, and Japanese kana.
+fn is_cjk(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{1100}'..='\u{11FF}'
+            | '\u{3040}'..='\u{30FF}'
+            | '\u{3130}'..='\u{318F}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{A960}'..='\u{A97F}'
+            | '\u{AC00}'..='\u{D7AF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{20000}'..='\u{2FA1F}'
+    )
+}
@@ -1,14 +1,20 @@
-use crate::mcp::contracts::MAX_OUTPUT_BYTES;
+use crate::mcp::contracts::limits;
 use crate::mcp::errors;
-use crate::tools::error_result;
+use crate::mcp::resources;
+use crate::tools::{
+    ToolError, check_arguments, error_result, map_hwp_error_with_stage, write_file_atomically,
+};
 use base64::Engine;
 use base64::engine::general_purpose::STANDARD;
-use hwpers::{HwpError, HwpWriter};
+use hwpers::HwpWriter;
 use serde_json::{Value, json};
-use std::fs;
 use std::path::Path;
 
 pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
     let text = match parse_text(args.get("text")) {
         Ok(text) => text,
         Err(err) => return error_result(err.kind, err.message, None),
@@ -19,6 +25,11 @@ pub fn call(args: &Value) -> Value {
         Err(err) => return error_result(err.kind, err.message, None),
     };
 
+    let include_base64 = match parse_include_base64(args.get("include_base64")) {
+        Ok(include_base64) => include_base64,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
     let mut writer = HwpWriter::new();
     let normalized = text.replace("\r\n", "\n").replace('\r', "\n");
     for paragraph in normalized.split('\n') {
@@ -37,25 +48,47 @@ pub fn call(args: &Value) -> Value {
     };
 
     let bytes_len = output_bytes.len() as u64;
+    let max_output_bytes = limits().max_output_bytes;
 
     match output_path {
-        Some(path) => match write_output(&path, &output_bytes) {
-            Ok(output) => json!({
-                "content": output.content,
-                "structuredContent": {
-                    "path": output.path,
-                    "uri": output.uri,
-                    "bytes_len": bytes_len
-                },
-                "isError": false
-            }),
-            Err(err) => error_result(err.kind, err.message, None),
-        },
+        Some(path) => {
+            // The document is already in memory, so an oversized inline copy is refused before
+            // anything is written.
+            if include_base64 && bytes_len > max_output_bytes {
+                return error_result(
+                    errors::TOO_LARGE,
+                    format!(
+                        "output exceeds limit for include_base64: {bytes_len} bytes (max \
+                         {max_output_bytes})"
+                    ),
+                    None,
+                );
+            }
+            match write_output(&path, &output_bytes) {
+                Ok(output) => {
+                    let mut result = json!({
+                        "content": output.content,
+                        "structuredContent": {
+                            "path": output.path,
+                            "uri": output.uri,
+                            "bytes_len": bytes_len
+                        },
+                        "isError": false
+                    });
+                    if include_base64 {
+                        result["structuredContent"]["base64"] =
+                            json!(STANDARD.encode(&output_bytes));
+                    }
+                    result
+                }
+                Err(err) => error_result(err.kind, err.message, None),
+            }
+        }
         None => {
-            if bytes_len > MAX_OUTPUT_BYTES {
+            if bytes_len > max_output_bytes {
                 return error_result(
                     errors::TOO_LARGE,
-                    format!("output exceeds limit: {bytes_len} bytes (max {MAX_OUTPUT_BYTES})"),
+                    format!("output exceeds limit: {bytes_len} bytes (max {max_output_bytes})"),
                     None,
                 );
             }
@@ -75,11 +108,6 @@ pub fn call(args: &Value) -> Value {
     }
 }
 
-struct ToolError {
-    kind: &'static str,
-    message: String,
-}
-
 struct OutputResource {
     path: String,
     uri: String,
@@ -108,6 +136,17 @@ fn parse_text(value: Option<&Value>) -> Result<String, ToolError> {
     Ok(text.to_string())
 }
 
+fn parse_include_base64(value: Option<&Value>) -> Result<bool, ToolError> {
+    match value {
+        None | Some(Value::Null) => Ok(false),
+        Some(Value::Bool(flag)) => Ok(*flag),
+        Some(_) => Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "include_base64 must be a boolean".to_string(),
+        }),
+    }
+}
+
 fn parse_output_path(value: Option<&Value>) -> Result<Option<String>, ToolError> {
     let Some(value) = value else {
         return Ok(None);
@@ -128,12 +167,12 @@ fn parse_output_path(value: Option<&Value>) -> Result<Option<String>, ToolError>
 }
 
 fn write_output(path: &str, bytes: &[u8]) -> Result<OutputResource, ToolError> {
-    fs::write(path, bytes).map_err(|err| ToolError {
+    write_file_atomically(Path::new(path), bytes).map_err(|err| ToolError {
         kind: errors::INTERNAL_ERROR,
         message: format!("failed to write output: {err}"),
     })?;
 
-    let uri = format!("file://{path}");
+    let uri = resources::register(path, resources::HWP_MIME_TYPE);
     let name = Path::new(path)
         .file_name()
         .and_then(|value| value.to_str())
@@ -148,54 +187,13 @@ fn write_output(path: &str, bytes: &[u8]) -> Result<OutputResource, ToolError> {
             "type": "resource_link",
             "uri": uri,
             "name": name,
-            "mimeType": "application/octet-stream"
+            "mimeType": resources::HWP_MIME_TYPE
         }),
     ];
 
     Ok(OutputResource {
         path: path.to_string(),
-        uri: format!("file://{path}"),
+        uri: uri.clone(),
         content,
     })
 }
-
-fn map_hwp_error(error: HwpError) -> ToolError {
-    match error {
-        HwpError::UnsupportedVersion(message) => {
-            if message.contains("Password-encrypted") {
-                ToolError {
-                    kind: errors::ENCRYPTED,
-                    message,
-                }
-            } else {
-                ToolError {
-                    kind: errors::PARSE_FAILED,
-                    message,
-                }
-            }
-        }
-        HwpError::InvalidInput(message) => ToolError {
-            kind: errors::INVALID_INPUT,
-            message,
-        },
-        HwpError::Io(err) => ToolError {
-            kind: errors::INVALID_INPUT,
-            message: err.to_string(),
-        },
-        HwpError::InvalidFormat(message)
-        | HwpError::Cfb(message)
-        | HwpError::CompressionError(message)
-        | HwpError::ParseError(message)
-        | HwpError::EncodingError(message)
-        | HwpError::NotFound(message) => ToolError {
-            kind: errors::PARSE_FAILED,
-            message,
-        },
-    }
-}
-
-fn map_hwp_error_with_stage(error: HwpError, stage: &str) -> ToolError {
-    let mut mapped = map_hwp_error(error);
-    mapped.message = format!("{stage} failed: {}", mapped.message);
-    mapped
-}
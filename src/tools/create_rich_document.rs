@@ -1,14 +1,57 @@
-use crate::mcp::contracts::MAX_OUTPUT_BYTES;
+use crate::mcp::contracts::limits;
 use crate::mcp::errors;
-use crate::tools::error_result;
+use crate::tools::records::{
+    HWPTAG_CTRL_HEADER, HWPTAG_LIST_HEADER, HWPTAG_PAGE_DEF, HWPTAG_PARA_HEADER, HWPTAG_PARA_TEXT,
+    HWPTAG_TABLE, parse_records, write_records,
+};
+use crate::tools::{
+    BuiltDocument, ToolError, check_arguments, error_result, map_hwp_error_with_stage,
+    parse_output_path, write_output,
+};
 use base64::Engine;
 use base64::engine::general_purpose::STANDARD;
-use hwpers::{HwpError, HwpWriter, HwpxWriter};
-use serde_json::{Value, json};
+use flate2::Compression;
+use flate2::write::DeflateEncoder;
+use hwpers::model::control::{Table, TableCell};
+use hwpers::model::page_layout::PageLayout;
+use hwpers::parser::FileHeader;
+use hwpers::parser::record::{Record, RecordHeader};
+use hwpers::utils::compression::decompress_stream;
+use hwpers::{HwpReader, HwpWriter, HwpxWriter};
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::io::{Cursor, Read, Write};
+
+/// Printable width of the default HWPX page (A4 minus 30mm side margins), in HWPUNIT.
+const HWPX_PRINTABLE_WIDTH: u32 = 42_520;
+
+/// Default printed width of `chart` blocks; the height follows the canvas aspect ratio.
+const DEFAULT_CHART_WIDTH_MM: u32 = 120;
+
+/// Monospace font used for equation markup, since `hwpers` cannot write equation objects.
+const EQUATION_FONT: &str = "Courier New";
+
+/// Text color of `link` blocks, matching the one `hwpers` gives its own hyperlinks.
+const LINK_COLOR: u32 = 0x0000FF;
+
+/// Deepest `level` a list item may have.
+const MAX_LIST_LEVEL: u64 = 9;
+
+/// One level of indentation written as spaces, for writers without paragraph indents.
+const SPACE_INDENT: &str = "    ";
+
+/// HWPUNIT per typographic point.
+const HWPUNIT_PER_PT: f64 = 100.0;
+
+/// HWPUNIT per millimetre.
+const HWPUNIT_PER_MM: f64 = 283.465;
 
 pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
     let to_format = match OutputFormat::parse(args.get("to")) {
         Ok(value) => value,
         Err(err) => return error_result(err.kind, err.message, None),
@@ -19,6 +62,11 @@ pub fn call(args: &Value) -> Value {
         Err(err) => return error_result(err.kind, err.message, None),
     };
 
+    let include_base64 = match parse_include_base64(args.get("include_base64")) {
+        Ok(include_base64) => include_base64,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
     let document = match parse_document_spec(args.get("document")) {
         Ok(doc) => doc,
         Err(err) => return error_result(err.kind, err.message, None),
@@ -26,39 +74,56 @@ pub fn call(args: &Value) -> Value {
 
     let mut warnings: Vec<String> = Vec::new();
 
-    let output_bytes = match to_format {
-        OutputFormat::Hwp => match build_hwp(&document, &mut warnings) {
-            Ok(bytes) => bytes,
-            Err(err) => return error_result(err.kind, err.message, None),
-        },
-        OutputFormat::Hwpx => match build_hwpx(&document, &mut warnings) {
-            Ok(bytes) => bytes,
-            Err(err) => return error_result(err.kind, err.message, None),
-        },
+    let output = match to_format {
+        OutputFormat::Hwp => build_hwp(&document, &mut warnings).map(BuiltDocument::Bytes),
+        OutputFormat::Hwpx => {
+            build_hwpx(&document, &mut warnings).map(|writer| BuiltDocument::Hwpx(Box::new(writer)))
+        }
+    };
+    let output = match output {
+        Ok(output) => output,
+        Err(err) => return error_result(err.kind, err.message, None),
     };
-
-    let bytes_len = output_bytes.len() as u64;
 
     match output_path {
-        Some(path) => match write_output(&path, &output_bytes) {
-            Ok(output) => json!({
-                "content": output.content,
-                "structuredContent": {
-                    "to": to_format.as_str(),
-                    "path": output.path,
-                    "uri": output.uri,
-                    "bytes_len": bytes_len,
-                    "warnings": warnings
-                },
-                "isError": false
-            }),
+        Some(path) => match write_output(
+            &path,
+            output,
+            &format!("created rich document ({})", to_format.as_str()),
+        ) {
+            Ok(output) => {
+                let mut result = json!({
+                    "content": output.content,
+                    "structuredContent": {
+                        "to": to_format.as_str(),
+                        "path": output.path,
+                        "uri": output.uri,
+                        "bytes_len": output.bytes_len,
+                        "warnings": warnings
+                    },
+                    "isError": false
+                });
+                if include_base64 {
+                    match inline_copy(&output.path) {
+                        Ok(base64) => result["structuredContent"]["base64"] = json!(base64),
+                        Err(err) => return error_result(err.kind, err.message, None),
+                    }
+                }
+                result
+            }
             Err(err) => error_result(err.kind, err.message, None),
         },
         None => {
-            if bytes_len > MAX_OUTPUT_BYTES {
+            let output_bytes = match output.into_bytes() {
+                Ok(bytes) => bytes,
+                Err(err) => return error_result(err.kind, err.message, None),
+            };
+            let bytes_len = output_bytes.len() as u64;
+            let max_output_bytes = limits().max_output_bytes;
+            if bytes_len > max_output_bytes {
                 return error_result(
                     errors::TOO_LARGE,
-                    format!("output exceeds limit: {bytes_len} bytes (max {MAX_OUTPUT_BYTES})"),
+                    format!("output exceeds limit: {bytes_len} bytes (max {max_output_bytes})"),
                     None,
                 );
             }
@@ -80,17 +145,6 @@ pub fn call(args: &Value) -> Value {
     }
 }
 
-struct ToolError {
-    kind: &'static str,
-    message: String,
-}
-
-struct OutputResource {
-    path: String,
-    uri: String,
-    content: Vec<Value>,
-}
-
 enum OutputFormat {
     Hwp,
     Hwpx,
@@ -131,24 +185,56 @@ struct DocumentSpec {
     author: Option<String>,
     header: Option<String>,
     footer: Option<String>,
+    page: PageSpec,
+    default_spacing: ParagraphSpacing,
     blocks: Vec<BlockSpec>,
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct PageSpec {
+    size: PageSize,
+    landscape: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum PageSize {
+    #[default]
+    A4,
+    Letter,
+}
+
+impl PageSpec {
+    fn layout(&self) -> PageLayout {
+        match (self.size, self.landscape) {
+            (PageSize::A4, false) => PageLayout::a4_portrait(),
+            (PageSize::A4, true) => PageLayout::a4_landscape(),
+            (PageSize::Letter, false) => PageLayout::letter_portrait(),
+            (PageSize::Letter, true) => PageLayout::letter_landscape(),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 enum BlockSpec {
     Paragraph {
         text: String,
         style: Option<TextStyleSpec>,
+        /// Styled pieces of `text`, in order; empty when one style covers it all.
+        runs: Vec<RunSpec>,
     },
     Heading {
         level: u8,
         text: String,
+        style: Option<TextStyleSpec>,
     },
     Table {
         rows: Vec<Vec<TableCellSpec>>,
         header_row: bool,
         column_widths: Option<Vec<u32>>,
         border_style: Option<TableBorderStyle>,
+        layout: TableLayout,
+        fit_to_page: bool,
+        cell_margin: Option<CellMargin>,
     },
     Image {
         source: ImageSource,
@@ -160,9 +246,44 @@ enum BlockSpec {
     },
     PageBreak,
     List {
-        items: Vec<String>,
+        items: Vec<ListItemSpec>,
         list_type: ListTypeSpec,
     },
+    Equation {
+        latex: String,
+    },
+    Link {
+        text: String,
+        href: String,
+    },
+    Quote {
+        text: String,
+        style: TextStyleSpec,
+    },
+}
+
+impl BlockSpec {
+    /// The character/paragraph style of blocks that carry one.
+    fn text_style(&self) -> Option<&TextStyleSpec> {
+        match self {
+            BlockSpec::Paragraph { style, .. } | BlockSpec::Heading { style, .. } => style.as_ref(),
+            BlockSpec::Quote { style, .. } => Some(style),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct RunSpec {
+    text: String,
+    style: TextStyleSpec,
+}
+
+#[derive(Clone, Debug)]
+struct ListItemSpec {
+    text: String,
+    /// 0-based nesting depth.
+    level: u32,
 }
 
 #[derive(Clone, Debug)]
@@ -196,6 +317,36 @@ struct TableCellSpec {
     background_color: Option<u32>,
     text_align: Option<TextAlign>,
     style: Option<TextStyleSpec>,
+    cell_margin: Option<CellMargin>,
+}
+
+/// Inner cell padding in millimetres.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct CellMargin {
+    top: f64,
+    right: f64,
+    bottom: f64,
+    left: f64,
+}
+
+impl CellMargin {
+    /// Left, right, top and bottom in HWPUNIT, the order the HWP table records store them.
+    fn hwp_units(&self) -> [u16; 4] {
+        [self.left, self.right, self.top, self.bottom]
+            .map(|mm| (mm * HWPUNIT_PER_MM).round().min(f64::from(u16::MAX)) as u16)
+    }
+}
+
+impl Default for CellMargin {
+    /// The padding both writers emit: 510/142 HWPUNIT (about 1.8 mm / 0.5 mm).
+    fn default() -> Self {
+        CellMargin {
+            top: 0.5,
+            right: 1.8,
+            bottom: 0.5,
+            left: 1.8,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -205,6 +356,12 @@ enum TextAlign {
     Right,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TableLayout {
+    Auto,
+    Fixed,
+}
+
 #[derive(Clone, Debug)]
 enum TableBorderStyle {
     None,
@@ -220,25 +377,60 @@ struct TextStyleSpec {
     italic: bool,
     underline: bool,
     color: Option<u32>,
+    spacing: ParagraphSpacing,
 }
 
-fn parse_output_path(value: Option<&Value>) -> Result<Option<String>, ToolError> {
-    let Some(value) = value else {
-        return Ok(None);
-    };
-    let Some(path) = value.as_str() else {
-        return Err(ToolError {
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum LineSpacing {
+    Percent(u32),
+    /// Fixed line height in HWPUNIT.
+    Fixed(u32),
+}
+
+/// Paragraph-shape spacing; lengths are in HWPUNIT.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct ParagraphSpacing {
+    line_spacing: Option<LineSpacing>,
+    space_before: Option<u32>,
+    space_after: Option<u32>,
+}
+
+impl ParagraphSpacing {
+    fn is_empty(&self) -> bool {
+        *self == ParagraphSpacing::default()
+    }
+}
+
+fn parse_include_base64(value: Option<&Value>) -> Result<bool, ToolError> {
+    match value {
+        None | Some(Value::Null) => Ok(false),
+        Some(Value::Bool(flag)) => Ok(*flag),
+        Some(_) => Err(ToolError {
             kind: errors::INVALID_INPUT,
-            message: "output_path must be a string".to_string(),
-        });
-    };
-    if path.trim().is_empty() {
+            message: "include_base64 must be a boolean".to_string(),
+        }),
+    }
+}
+
+/// The copy `include_base64` returns next to a written `output_path`, read back from the file so
+/// it matches what was written, and held to the same limit as inline output.
+fn inline_copy(path: &str) -> Result<String, ToolError> {
+    let bytes = fs::read(path).map_err(|err| ToolError {
+        kind: errors::INTERNAL_ERROR,
+        message: format!("failed to re-read output: {err}"),
+    })?;
+    let bytes_len = bytes.len() as u64;
+    let max_output_bytes = limits().max_output_bytes;
+    if bytes_len > max_output_bytes {
         return Err(ToolError {
-            kind: errors::INVALID_INPUT,
-            message: "output_path must not be empty".to_string(),
+            kind: errors::TOO_LARGE,
+            message: format!(
+                "output exceeds limit for include_base64: {bytes_len} bytes (max \
+                 {max_output_bytes}); {path} was still written"
+            ),
         });
     }
-    Ok(Some(path.to_string()))
+    Ok(STANDARD.encode(bytes))
 }
 
 fn parse_document_spec(value: Option<&Value>) -> Result<DocumentSpec, ToolError> {
@@ -272,6 +464,26 @@ fn parse_document_spec(value: Option<&Value>) -> Result<DocumentSpec, ToolError>
         .and_then(|v| v.as_str())
         .map(|s| s.to_string());
 
+    let page = parse_page(obj.get("page"))?;
+
+    let styles = parse_named_styles(obj.get("styles"))?;
+
+    let default_spacing = match obj.get("default_paragraph_style") {
+        None => ParagraphSpacing::default(),
+        Some(value) => {
+            let Some(style) = value.as_object() else {
+                return Err(ToolError {
+                    kind: errors::INVALID_INPUT,
+                    message: "document.default_paragraph_style must be an object".to_string(),
+                });
+            };
+            parse_paragraph_spacing(style).map_err(|mut err| {
+                err.message = format!("document.default_paragraph_style: {}", err.message);
+                err
+            })?
+        }
+    };
+
     let blocks_value = obj.get("blocks").ok_or_else(|| ToolError {
         kind: errors::INVALID_INPUT,
         message: "document.blocks is required".to_string(),
@@ -285,7 +497,7 @@ fn parse_document_spec(value: Option<&Value>) -> Result<DocumentSpec, ToolError>
 
     let mut blocks = Vec::with_capacity(blocks_array.len());
     for (idx, item) in blocks_array.iter().enumerate() {
-        let block = parse_block(item).map_err(|mut err| {
+        let block = parse_block(item, &styles).map_err(|mut err| {
             err.message = format!("document.blocks[{idx}]: {}", err.message);
             err
         })?;
@@ -297,11 +509,185 @@ fn parse_document_spec(value: Option<&Value>) -> Result<DocumentSpec, ToolError>
         author,
         header,
         footer,
+        page,
+        default_spacing,
         blocks,
     })
 }
 
-fn parse_block(value: &Value) -> Result<BlockSpec, ToolError> {
+/// `document.page`; A4 portrait when omitted.
+fn parse_page(value: Option<&Value>) -> Result<PageSpec, ToolError> {
+    let Some(value) = value else {
+        return Ok(PageSpec::default());
+    };
+    let Some(obj) = value.as_object() else {
+        return Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "document.page must be an object".to_string(),
+        });
+    };
+    let size = match obj.get("size") {
+        None => PageSize::A4,
+        Some(value) => match value.as_str() {
+            Some("a4") => PageSize::A4,
+            Some("letter") => PageSize::Letter,
+            _ => {
+                return Err(ToolError {
+                    kind: errors::INVALID_INPUT,
+                    message: format!("document.page.size must be a4 or letter, got {value}"),
+                });
+            }
+        },
+    };
+    let landscape = match obj.get("orientation") {
+        None => false,
+        Some(value) => match value.as_str() {
+            Some("portrait") => false,
+            Some("landscape") => true,
+            _ => {
+                return Err(ToolError {
+                    kind: errors::INVALID_INPUT,
+                    message: format!(
+                        "document.page.orientation must be portrait or landscape, got {value}"
+                    ),
+                });
+            }
+        },
+    };
+    Ok(PageSpec { size, landscape })
+}
+
+fn parse_named_styles(
+    value: Option<&Value>,
+) -> Result<HashMap<String, Map<String, Value>>, ToolError> {
+    let Some(value) = value else {
+        return Ok(HashMap::new());
+    };
+    let Some(obj) = value.as_object() else {
+        return Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "document.styles must be an object".to_string(),
+        });
+    };
+
+    let mut styles = HashMap::with_capacity(obj.len());
+    for (name, style) in obj {
+        parse_text_style(style).map_err(|mut err| {
+            err.message = format!("document.styles.{name}: {}", err.message);
+            err
+        })?;
+        if let Some(style) = style.as_object() {
+            styles.insert(name.clone(), style.clone());
+        }
+    }
+    Ok(styles)
+}
+
+/// Parses `paragraph.runs`. Each run's `style_ref`/`style` is layered over the
+/// paragraph's own, so a run only names what differs from its paragraph.
+fn parse_runs(
+    value: &Value,
+    paragraph: &Map<String, Value>,
+    styles: &HashMap<String, Map<String, Value>>,
+) -> Result<Vec<RunSpec>, ToolError> {
+    let Some(items) = value.as_array().filter(|items| !items.is_empty()) else {
+        return Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "paragraph.runs must be a non-empty array".to_string(),
+        });
+    };
+    let base = resolve_style_map(paragraph, styles)?.unwrap_or_default();
+    items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let Some(run) = item.as_object() else {
+                return Err(ToolError {
+                    kind: errors::INVALID_INPUT,
+                    message: format!("paragraph.runs[{index}] must be an object"),
+                });
+            };
+            let text = run
+                .get("text")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| ToolError {
+                    kind: errors::INVALID_INPUT,
+                    message: format!("paragraph.runs[{index}].text is required"),
+                })?
+                .to_string();
+            let mut merged = base.clone();
+            if let Some(own) = resolve_style_map(run, styles)? {
+                merged.extend(own);
+            }
+            let style = parse_text_style(&Value::Object(merged))?;
+            Ok(RunSpec { text, style })
+        })
+        .collect()
+}
+
+/// Resolves `style_ref` against the named styles, letting inline `style` fields override it.
+fn resolve_block_style(
+    obj: &Map<String, Value>,
+    styles: &HashMap<String, Map<String, Value>>,
+) -> Result<Option<TextStyleSpec>, ToolError> {
+    match resolve_style_map(obj, styles)? {
+        None => Ok(None),
+        Some(merged) => parse_text_style(&Value::Object(merged)).map(Some),
+    }
+}
+
+/// The raw style fields of `style_ref` overlaid with inline `style`, before parsing.
+fn resolve_style_map(
+    obj: &Map<String, Value>,
+    styles: &HashMap<String, Map<String, Value>>,
+) -> Result<Option<Map<String, Value>>, ToolError> {
+    let mut merged = match obj.get("style_ref") {
+        None => None,
+        Some(value) => {
+            let Some(name) = value.as_str() else {
+                return Err(ToolError {
+                    kind: errors::INVALID_INPUT,
+                    message: "style_ref must be a string".to_string(),
+                });
+            };
+            let Some(named) = styles.get(name) else {
+                return Err(ToolError {
+                    kind: errors::INVALID_INPUT,
+                    message: format!("style_ref '{name}' is not defined in document.styles"),
+                });
+            };
+            Some(named.clone())
+        }
+    };
+
+    if let Some(inline) = obj.get("style") {
+        let Some(inline) = inline.as_object() else {
+            return Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "style must be an object".to_string(),
+            });
+        };
+        merged.get_or_insert_with(Map::new).extend(
+            inline
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
+    }
+
+    Ok(merged)
+}
+
+/// `MAX_TABLE_ROWS` / `MAX_TABLE_COLS` as configured, held to the 16-bit row and column
+/// counts an HWP table record can store.
+fn table_limits() -> (usize, usize) {
+    let cap = |limit: u64| limit.min(u64::from(u16::MAX)) as usize;
+    (cap(limits().max_table_rows), cap(limits().max_table_cols))
+}
+
+fn parse_block(
+    value: &Value,
+    styles: &HashMap<String, Map<String, Value>>,
+) -> Result<BlockSpec, ToolError> {
     let Some(obj) = value.as_object() else {
         return Err(ToolError {
             kind: errors::INVALID_INPUT,
@@ -319,19 +705,31 @@ fn parse_block(value: &Value) -> Result<BlockSpec, ToolError> {
 
     match block_type {
         "paragraph" => {
-            let text = obj
-                .get("text")
-                .and_then(|v| v.as_str())
-                .ok_or_else(|| ToolError {
-                    kind: errors::INVALID_INPUT,
-                    message: "paragraph.text is required".to_string(),
-                })?
-                .to_string();
-            let style = match obj.get("style") {
-                None => None,
-                Some(v) => Some(parse_text_style(v)?),
+            let style = resolve_block_style(obj, styles)?;
+            let Some(runs) = obj.get("runs") else {
+                let text = obj
+                    .get("text")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| ToolError {
+                        kind: errors::INVALID_INPUT,
+                        message: "paragraph.text is required".to_string(),
+                    })?
+                    .to_string();
+                return Ok(BlockSpec::Paragraph {
+                    text,
+                    style,
+                    runs: Vec::new(),
+                });
             };
-            Ok(BlockSpec::Paragraph { text, style })
+            if obj.contains_key("text") {
+                return Err(ToolError {
+                    kind: errors::INVALID_INPUT,
+                    message: "paragraph takes either text or runs, not both".to_string(),
+                });
+            }
+            let runs = parse_runs(runs, obj, styles)?;
+            let text = runs.iter().map(|run| run.text.as_str()).collect();
+            Ok(BlockSpec::Paragraph { text, style, runs })
         }
         "heading" => {
             let level = obj
@@ -350,9 +748,11 @@ fn parse_block(value: &Value) -> Result<BlockSpec, ToolError> {
                     message: "heading.text is required".to_string(),
                 })?
                 .to_string();
+            let style = resolve_block_style(obj, styles)?;
             Ok(BlockSpec::Heading {
                 level: level_u8,
                 text,
+                style,
             })
         }
         "table" => {
@@ -366,6 +766,16 @@ fn parse_block(value: &Value) -> Result<BlockSpec, ToolError> {
                     message: "table.rows must be an array".to_string(),
                 });
             };
+            let (max_rows, max_cols) = table_limits();
+            if rows_array.len() > max_rows {
+                return Err(ToolError {
+                    kind: errors::INVALID_INPUT,
+                    message: format!(
+                        "table has {} rows, exceeding the limit of {max_rows}",
+                        rows_array.len()
+                    ),
+                });
+            }
 
             let mut rows: Vec<Vec<TableCellSpec>> = Vec::with_capacity(rows_array.len());
             for row_value in rows_array {
@@ -375,6 +785,15 @@ fn parse_block(value: &Value) -> Result<BlockSpec, ToolError> {
                         message: "table.rows items must be arrays".to_string(),
                     });
                 };
+                if cols_array.len() > max_cols {
+                    return Err(ToolError {
+                        kind: errors::INVALID_INPUT,
+                        message: format!(
+                            "table has {} columns, exceeding the limit of {max_cols}",
+                            cols_array.len()
+                        ),
+                    });
+                }
                 let mut row: Vec<TableCellSpec> = Vec::with_capacity(cols_array.len());
                 for cell in cols_array {
                     let cell_spec = parse_table_cell(cell)?;
@@ -408,14 +827,10 @@ fn parse_block(value: &Value) -> Result<BlockSpec, ToolError> {
                 .and_then(|v| v.as_bool())
                 .unwrap_or(false);
 
-            let column_widths = obj
-                .get("column_widths")
-                .and_then(|v| v.as_array())
-                .map(|arr| {
-                    arr.iter()
-                        .filter_map(|v| v.as_u64().and_then(|n| u32::try_from(n).ok()))
-                        .collect()
-                });
+            let column_widths = match obj.get("column_widths") {
+                None => None,
+                Some(v) => Some(parse_column_widths(v, cols)?),
+            };
 
             let border_style = obj
                 .get("border_style")
@@ -427,11 +842,35 @@ fn parse_block(value: &Value) -> Result<BlockSpec, ToolError> {
                     _ => TableBorderStyle::Basic,
                 });
 
+            let layout = match obj.get("layout").map(|v| v.as_str()) {
+                None | Some(Some("auto")) => TableLayout::Auto,
+                Some(Some("fixed")) => TableLayout::Fixed,
+                Some(_) => {
+                    return Err(ToolError {
+                        kind: errors::INVALID_INPUT,
+                        message: "table.layout must be auto or fixed".to_string(),
+                    });
+                }
+            };
+
+            let fit_to_page = obj
+                .get("fit_to_page")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+
+            let cell_margin = match obj.get("cell_margin_mm") {
+                None => None,
+                Some(v) => Some(parse_cell_margin(v, "table.cell_margin_mm")?),
+            };
+
             Ok(BlockSpec::Table {
                 rows,
                 header_row,
                 column_widths,
                 border_style,
+                layout,
+                fit_to_page,
+                cell_margin,
             })
         }
         "image" => {
@@ -499,6 +938,7 @@ fn parse_block(value: &Value) -> Result<BlockSpec, ToolError> {
                 })
             }
         }
+        "chart" => parse_chart_block(obj),
         "page_break" => Ok(BlockSpec::PageBreak),
         "list" => {
             let items_value = obj.get("items").ok_or_else(|| ToolError {
@@ -511,10 +951,11 @@ fn parse_block(value: &Value) -> Result<BlockSpec, ToolError> {
                     message: "list.items must be an array".to_string(),
                 });
             };
-            let items: Vec<String> = items_array
+            let items = items_array
                 .iter()
-                .map(|v| v.as_str().unwrap_or("").to_string())
-                .collect();
+                .enumerate()
+                .map(|(index, item)| parse_list_item(index, item))
+                .collect::<Result<Vec<_>, _>>()?;
 
             let list_type = if let Some(type_str) = obj.get("list_type").and_then(|v| v.as_str()) {
                 match type_str {
@@ -538,6 +979,64 @@ fn parse_block(value: &Value) -> Result<BlockSpec, ToolError> {
             };
             Ok(BlockSpec::List { items, list_type })
         }
+        "equation" => {
+            let latex = obj
+                .get("latex")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| ToolError {
+                    kind: errors::INVALID_INPUT,
+                    message: "equation.latex is required".to_string(),
+                })?
+                .to_string();
+            Ok(BlockSpec::Equation { latex })
+        }
+        "link" => {
+            let href = obj
+                .get("href")
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|href| !href.is_empty())
+                .ok_or_else(|| ToolError {
+                    kind: errors::INVALID_INPUT,
+                    message: "link.href is required".to_string(),
+                })?
+                .to_string();
+            let text = match obj.get("text") {
+                None => href.clone(),
+                Some(Value::String(text)) if text.is_empty() => href.clone(),
+                Some(Value::String(text)) => text.clone(),
+                Some(_) => {
+                    return Err(ToolError {
+                        kind: errors::INVALID_INPUT,
+                        message: "link.text must be a string".to_string(),
+                    });
+                }
+            };
+            Ok(BlockSpec::Link { text, href })
+        }
+        "quote" => {
+            let text = obj
+                .get("text")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| ToolError {
+                    kind: errors::INVALID_INPUT,
+                    message: "quote.text is required".to_string(),
+                })?
+                .to_string();
+            let italic = match obj.get("italic") {
+                None => true,
+                Some(Value::Bool(italic)) => *italic,
+                Some(_) => {
+                    return Err(ToolError {
+                        kind: errors::INVALID_INPUT,
+                        message: "quote.italic must be a boolean".to_string(),
+                    });
+                }
+            };
+            let mut style = resolve_block_style(obj, styles)?.unwrap_or_default();
+            style.italic |= italic;
+            Ok(BlockSpec::Quote { text, style })
+        }
         _ => Err(ToolError {
             kind: errors::INVALID_INPUT,
             message: format!("unsupported block.type: {block_type}"),
@@ -545,15 +1044,173 @@ fn parse_block(value: &Value) -> Result<BlockSpec, ToolError> {
     }
 }
 
-fn parse_text_style(value: &Value) -> Result<TextStyleSpec, ToolError> {
+/// Parses a list item: a plain string at level 0, or `{ text, level? }`.
+fn parse_list_item(index: usize, value: &Value) -> Result<ListItemSpec, ToolError> {
     let Some(obj) = value.as_object() else {
-        return Err(ToolError {
-            kind: errors::INVALID_INPUT,
-            message: "style must be an object".to_string(),
+        return Ok(ListItemSpec {
+            text: value.as_str().unwrap_or("").to_string(),
+            level: 0,
         });
     };
-    let font_name = obj
-        .get("font_name")
+    let text = obj
+        .get("text")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| ToolError {
+            kind: errors::INVALID_INPUT,
+            message: format!("list.items[{index}].text is required"),
+        })?
+        .to_string();
+    let level = match obj.get("level") {
+        None => 0,
+        Some(level) => level
+            .as_u64()
+            .filter(|level| *level <= MAX_LIST_LEVEL)
+            .ok_or_else(|| ToolError {
+                kind: errors::INVALID_INPUT,
+                message: format!(
+                    "list.items[{index}].level must be an integer from 0 to {MAX_LIST_LEVEL}"
+                ),
+            })? as u32,
+    };
+    Ok(ListItemSpec { text, level })
+}
+
+/// Renders a `chart` block to a PNG and returns it as an image block, so both
+/// writers embed it like any other picture.
+fn parse_chart_block(obj: &Map<String, Value>) -> Result<BlockSpec, ToolError> {
+    use crate::tools::chart::{self, ChartData, ChartType};
+
+    let chart_type = obj
+        .get("chart_type")
+        .and_then(|v| v.as_str())
+        .map(|s| {
+            ChartType::parse(s).ok_or_else(|| ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "chart.chart_type must be bar, line, or pie".to_string(),
+            })
+        })
+        .transpose()?
+        .unwrap_or(ChartType::Bar);
+
+    let Some(rows_array) = obj.get("rows").and_then(|v| v.as_array()) else {
+        return Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "chart.rows must be an array of [label, value, ...] rows".to_string(),
+        });
+    };
+    let header_row = obj
+        .get("header_row")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let data_rows = &rows_array[usize::from(header_row).min(rows_array.len())..];
+    if data_rows.is_empty() {
+        return Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "chart.rows must contain at least one data row".to_string(),
+        });
+    }
+    let (max_rows, max_cols) = table_limits();
+    if data_rows.len() > max_rows {
+        return Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: format!(
+                "chart has {} rows, exceeding the limit of {max_rows}",
+                data_rows.len()
+            ),
+        });
+    }
+
+    let mut series: Vec<Vec<f64>> = Vec::new();
+    for (r, row_value) in data_rows.iter().enumerate() {
+        let row = usize::from(header_row) + r;
+        let Some(cells) = row_value.as_array() else {
+            return Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "chart.rows items must be arrays".to_string(),
+            });
+        };
+        if cells.len() < 2 || cells.len() > max_cols {
+            return Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: format!(
+                    "chart.rows[{row}] must have a label and 1 to {} values",
+                    max_cols - 1
+                ),
+            });
+        }
+        if series.is_empty() {
+            series = vec![Vec::with_capacity(data_rows.len()); cells.len() - 1];
+        } else if cells.len() - 1 != series.len() {
+            return Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "all chart rows must have the same column count".to_string(),
+            });
+        }
+        for (c, cell) in cells.iter().enumerate().skip(1) {
+            let value = match cell {
+                Value::Number(number) => number.as_f64(),
+                Value::String(text) => text.trim().parse::<f64>().ok(),
+                _ => None,
+            }
+            .filter(|value| value.is_finite())
+            .ok_or_else(|| ToolError {
+                kind: errors::INVALID_INPUT,
+                message: format!("chart.rows[{row}][{c}] is not numeric: {cell}"),
+            })?;
+            if chart_type == ChartType::Pie && value < 0.0 {
+                return Err(ToolError {
+                    kind: errors::INVALID_INPUT,
+                    message: format!("chart.rows[{row}][{c}]: pie values must not be negative"),
+                });
+            }
+            series[c - 1].push(value);
+        }
+    }
+    if chart_type == ChartType::Pie && series[0].iter().sum::<f64>() <= 0.0 {
+        return Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "pie chart values must not all be zero".to_string(),
+        });
+    }
+
+    let width_mm = obj
+        .get("width_mm")
+        .and_then(|v| v.as_u64())
+        .and_then(|v| u32::try_from(v).ok())
+        .unwrap_or(DEFAULT_CHART_WIDTH_MM);
+    let height_mm = obj
+        .get("height_mm")
+        .and_then(|v| v.as_u64())
+        .and_then(|v| u32::try_from(v).ok())
+        .unwrap_or(width_mm * chart::CHART_HEIGHT / chart::CHART_WIDTH);
+    let caption = obj
+        .get("caption")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+
+    let data = chart::render_png(chart_type, &ChartData { series });
+    Ok(BlockSpec::Image {
+        source: ImageSource::Base64 {
+            data,
+            mime_type: "image/png".to_string(),
+        },
+        width_mm: Some(width_mm),
+        height_mm: Some(height_mm),
+        caption,
+        align: None,
+        wrap_text: None,
+    })
+}
+
+fn parse_text_style(value: &Value) -> Result<TextStyleSpec, ToolError> {
+    let Some(obj) = value.as_object() else {
+        return Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "style must be an object".to_string(),
+        });
+    };
+    let font_name = obj
+        .get("font_name")
         .and_then(|v| v.as_str())
         .map(|s| s.to_string());
     let font_size = obj
@@ -582,6 +1239,8 @@ fn parse_text_style(value: &Value) -> Result<TextStyleSpec, ToolError> {
         }
     };
 
+    let spacing = parse_paragraph_spacing(obj)?;
+
     Ok(TextStyleSpec {
         font_name,
         font_size,
@@ -589,9 +1248,70 @@ fn parse_text_style(value: &Value) -> Result<TextStyleSpec, ToolError> {
         italic,
         underline,
         color,
+        spacing,
+    })
+}
+
+fn parse_paragraph_spacing(obj: &Map<String, Value>) -> Result<ParagraphSpacing, ToolError> {
+    let line_spacing = match obj.get("line_spacing") {
+        None => None,
+        Some(value) => Some(parse_line_spacing(value).ok_or_else(|| {
+            ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "style.line_spacing must be a percentage (160 or \"160%\") or a fixed \
+                      height (\"12pt\", \"5mm\")"
+                    .to_string(),
+            }
+        })?),
+    };
+    let space = |field: &str| -> Result<Option<u32>, ToolError> {
+        match obj.get(field) {
+            None => Ok(None),
+            Some(value) => match value.as_f64() {
+                Some(points) if points >= 0.0 => Ok(Some((points * HWPUNIT_PER_PT).round() as u32)),
+                _ => Err(ToolError {
+                    kind: errors::INVALID_INPUT,
+                    message: format!("style.{field} must be a non-negative number of points"),
+                }),
+            },
+        }
+    };
+    Ok(ParagraphSpacing {
+        line_spacing,
+        space_before: space("space_before")?,
+        space_after: space("space_after")?,
     })
 }
 
+/// Numbers and `"N%"` are proportional spacing; `"Npt"` and `"Nmm"` are a fixed line height.
+fn parse_line_spacing(value: &Value) -> Option<LineSpacing> {
+    if let Some(percent) = value.as_u64() {
+        return u32::try_from(percent)
+            .ok()
+            .filter(|percent| *percent > 0)
+            .map(LineSpacing::Percent);
+    }
+    let text = value.as_str()?.trim();
+    if let Some(percent) = text.strip_suffix('%') {
+        return percent
+            .trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|percent| *percent > 0)
+            .map(LineSpacing::Percent);
+    }
+    let (number, scale) = if let Some(points) = text.strip_suffix("pt") {
+        (points, HWPUNIT_PER_PT)
+    } else if let Some(mm) = text.strip_suffix("mm") {
+        (mm, HWPUNIT_PER_MM)
+    } else {
+        return None;
+    };
+    let length = number.trim().parse::<f64>().ok()?;
+    (length > 0.0 && length.is_finite())
+        .then(|| LineSpacing::Fixed((length * scale).round() as u32))
+}
+
 fn parse_color(value: &str) -> Result<u32, String> {
     let trimmed = value.trim();
     let hex = trimmed
@@ -614,6 +1334,7 @@ fn parse_table_cell(value: &Value) -> Result<TableCellSpec, ToolError> {
             background_color: None,
             text_align: None,
             style: None,
+            cell_margin: None,
         });
     }
 
@@ -665,6 +1386,10 @@ fn parse_table_cell(value: &Value) -> Result<TableCellSpec, ToolError> {
         None => None,
         Some(v) => Some(parse_text_style(v)?),
     };
+    let cell_margin = match obj.get("cell_margin_mm") {
+        None => None,
+        Some(v) => Some(parse_cell_margin(v, "cell.cell_margin_mm")?),
+    };
 
     Ok(TableCellSpec {
         content,
@@ -673,13 +1398,82 @@ fn parse_table_cell(value: &Value) -> Result<TableCellSpec, ToolError> {
         background_color,
         text_align,
         style,
+        cell_margin,
     })
 }
 
+/// Parses `table.column_widths` (mm) into HWPUNIT, one positive width per column.
+fn parse_column_widths(value: &Value, cols: usize) -> Result<Vec<u32>, ToolError> {
+    let invalid = |message: String| ToolError {
+        kind: errors::INVALID_INPUT,
+        message,
+    };
+    let Some(widths) = value.as_array() else {
+        return Err(invalid(
+            "table.column_widths must be an array of numbers".to_string(),
+        ));
+    };
+    if widths.len() != cols {
+        return Err(invalid(format!(
+            "table.column_widths has {} entries but the table has {cols} columns",
+            widths.len()
+        )));
+    }
+    widths
+        .iter()
+        .enumerate()
+        .map(|(index, width)| {
+            width
+                .as_f64()
+                .filter(|mm| mm.is_finite() && *mm > 0.0)
+                .map(|mm| ((mm * HWPUNIT_PER_MM).round() as u32).max(1))
+                .ok_or_else(|| {
+                    invalid(format!(
+                        "table.column_widths[{index}] must be a positive number"
+                    ))
+                })
+        })
+        .collect()
+}
+
+/// Parses `{top, right, bottom, left}` in millimetres; omitted sides keep the default padding.
+fn parse_cell_margin(value: &Value, field: &str) -> Result<CellMargin, ToolError> {
+    let Some(obj) = value.as_object() else {
+        return Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: format!("{field} must be an object with top/right/bottom/left"),
+        });
+    };
+    let mut margin = CellMargin::default();
+    for (side, slot) in [
+        ("top", &mut margin.top),
+        ("right", &mut margin.right),
+        ("bottom", &mut margin.bottom),
+        ("left", &mut margin.left),
+    ] {
+        let Some(v) = obj.get(side) else {
+            continue;
+        };
+        match v.as_f64() {
+            Some(mm) if mm.is_finite() && mm >= 0.0 => *slot = mm,
+            _ => {
+                return Err(ToolError {
+                    kind: errors::INVALID_INPUT,
+                    message: format!("{field}.{side} must be a non-negative number"),
+                });
+            }
+        }
+    }
+    Ok(margin)
+}
+
 fn build_hwp(document: &DocumentSpec, warnings: &mut Vec<String>) -> Result<Vec<u8>, ToolError> {
     use hwpers::writer::style as hwp_style;
 
-    let mut writer = HwpWriter::new();
+    let mut writer = hwp_writer(document.default_spacing)?;
+    writer
+        .set_page_layout(document.page.layout())
+        .map_err(|error| map_hwp_error_with_stage(error, "set page layout"))?;
 
     if let Some(title) = &document.title {
         writer
@@ -705,29 +1499,42 @@ fn build_hwp(document: &DocumentSpec, warnings: &mut Vec<String>) -> Result<Vec<
         warnings.push("hwp: document.footer is not supported; ignoring".to_string());
     }
 
+    for block in &document.blocks {
+        if block
+            .text_style()
+            .is_some_and(|style| !style.spacing.is_empty())
+        {
+            warnings.push(
+                "hwp: per-block line_spacing/space_before/space_after are not supported by \
+                 hwpers 0.5.0 (every paragraph is written with paragraph shape 0); use \
+                 document.default_paragraph_style"
+                    .to_string(),
+            );
+            break;
+        }
+    }
+
+    let mut table_specs = Vec::new();
     for block in &document.blocks {
         match block {
-            BlockSpec::Paragraph { text, style } => {
+            BlockSpec::Paragraph { text, runs, .. } if !runs.is_empty() => {
+                // Every run gets its own range so an unstyled run after a styled one
+                // switches back to the default shape. Offsets count chars, not bytes.
+                let mut styled = hwp_style::StyledText::new(text.clone());
+                let mut start = 0;
+                for run in runs {
+                    let end = start + run.text.chars().count();
+                    let ts = apply_hwp_text_style(hwp_style::TextStyle::new(), &run.style);
+                    styled = styled.add_range(start, end, ts);
+                    start = end;
+                }
+                writer
+                    .add_styled_paragraph(&styled)
+                    .map_err(|error| map_hwp_error_with_stage(error, "add styled paragraph"))?;
+            }
+            BlockSpec::Paragraph { text, style, .. } => {
                 if let Some(style) = style {
-                    let mut ts = hwp_style::TextStyle::new();
-                    if let Some(font) = &style.font_name {
-                        ts = ts.font(font);
-                    }
-                    if let Some(size) = style.font_size {
-                        ts = ts.size(size);
-                    }
-                    if style.bold {
-                        ts = ts.bold();
-                    }
-                    if style.italic {
-                        ts = ts.italic();
-                    }
-                    if style.underline {
-                        ts = ts.underline();
-                    }
-                    if let Some(color) = style.color {
-                        ts = ts.color(color);
-                    }
+                    let ts = apply_hwp_text_style(hwp_style::TextStyle::new(), style);
                     let len = text.chars().count();
                     let styled = hwp_style::StyledText::new(text.clone()).add_range(0, len, ts);
                     writer
@@ -739,16 +1546,29 @@ fn build_hwp(document: &DocumentSpec, warnings: &mut Vec<String>) -> Result<Vec<
                         .map_err(|error| map_hwp_error_with_stage(error, "add paragraph"))?;
                 }
             }
-            BlockSpec::Heading { level, text } => {
-                writer
-                    .add_heading(text, *level)
-                    .map_err(|error| map_hwp_error_with_stage(error, "add heading"))?;
+            BlockSpec::Heading { level, text, style } => {
+                if let Some(style) = style {
+                    let base = hwp_style::HeadingStyle::for_level(*level).text_style;
+                    let ts = apply_hwp_text_style(base, style);
+                    let len = text.chars().count();
+                    let styled = hwp_style::StyledText::new(text.clone()).add_range(0, len, ts);
+                    writer
+                        .add_styled_paragraph(&styled)
+                        .map_err(|error| map_hwp_error_with_stage(error, "add heading"))?;
+                } else {
+                    writer
+                        .add_heading(text, *level)
+                        .map_err(|error| map_hwp_error_with_stage(error, "add heading"))?;
+                }
             }
             BlockSpec::Table {
                 rows,
                 header_row,
                 column_widths,
                 border_style,
+                layout,
+                fit_to_page,
+                cell_margin,
             } => {
                 let row_count = rows.len() as u32;
                 let col_count = rows
@@ -762,11 +1582,16 @@ fn build_hwp(document: &DocumentSpec, warnings: &mut Vec<String>) -> Result<Vec<
                     .add_table(row_count, col_count)
                     .set_header_row(*header_row);
 
-                if column_widths.is_some() {
-                    warnings.push(
-                        "hwp: column_widths is not supported by hwpers 0.5.0; ignoring".to_string(),
-                    );
-                }
+                table_specs.push(HwpTableSpec {
+                    column_widths: column_widths.clone(),
+                    layout: *layout,
+                    fit_to_page: *fit_to_page,
+                    cell_margin: *cell_margin,
+                    cell_margins: rows
+                        .iter()
+                        .map(|row| row.iter().map(|cell| cell.cell_margin).collect())
+                        .collect(),
+                });
 
                 if let Some(style) = border_style {
                     let border_line = match style {
@@ -786,11 +1611,9 @@ fn build_hwp(document: &DocumentSpec, warnings: &mut Vec<String>) -> Result<Vec<
                                 row_span as u16,
                                 col_span as u16,
                             );
-                        } else if cell.row_span.is_some() {
-                            let row_span = cell.row_span.unwrap();
+                        } else if let Some(row_span) = cell.row_span {
                             builder = builder.merge_cells(r as u32, c as u32, row_span as u16, 1);
-                        } else if cell.col_span.is_some() {
-                            let col_span = cell.col_span.unwrap();
+                        } else if let Some(col_span) = cell.col_span {
                             builder = builder.merge_cells(r as u32, c as u32, 1, col_span as u16);
                         }
 
@@ -835,10 +1658,11 @@ fn build_hwp(document: &DocumentSpec, warnings: &mut Vec<String>) -> Result<Vec<
                             "image/gif" => hwp_style::ImageFormat::Gif,
                             "image/bmp" => hwp_style::ImageFormat::Bmp,
                             _ => {
-                                return Err(ToolError {
-                                    kind: errors::INVALID_INPUT,
-                                    message: format!("unsupported image mimeType: {mime_type}"),
-                                });
+                                return Err(unconvertible_image_error(data, Some(mime_type))
+                                    .unwrap_or_else(|| ToolError {
+                                        kind: errors::INVALID_INPUT,
+                                        message: format!("unsupported image mimeType: {mime_type}"),
+                                    }));
                             }
                         };
                         (data.clone(), format)
@@ -863,20 +1687,23 @@ fn build_hwp(document: &DocumentSpec, warnings: &mut Vec<String>) -> Result<Vec<
                         } else {
                             None
                         }
-                        .ok_or_else(|| ToolError {
-                            kind: errors::INVALID_INPUT,
-                            message: "unable to detect image format from file".to_string(),
+                        .ok_or_else(|| {
+                            unconvertible_image_error(&data, None).unwrap_or_else(|| ToolError {
+                                kind: errors::INVALID_INPUT,
+                                message: "unable to detect image format from file".to_string(),
+                            })
                         })?;
                         (data, format)
                     }
                 };
 
+                let (width_mm, height_mm) = complete_image_size(&data, *width_mm, *height_mm)?;
                 let mut options = hwp_style::ImageOptions::new();
                 if let Some(w) = width_mm {
-                    options = options.width(*w);
+                    options = options.width(w);
                 }
                 if let Some(h) = height_mm {
-                    options = options.height(*h);
+                    options = options.height(h);
                 }
                 if let Some(caption) = caption {
                     options = options.caption(caption);
@@ -912,24 +1739,752 @@ fn build_hwp(document: &DocumentSpec, warnings: &mut Vec<String>) -> Result<Vec<
                     ListTypeSpec::Korean => hwp_style::ListType::Korean,
                 };
 
-                let items_ref: Vec<&str> = items.iter().map(|s| s.as_str()).collect();
+                // Each nested list restarts its numbering and ending it resumes the
+                // parent's count. hwpers gives nested items an indented paragraph
+                // shape, but its serializer writes every paragraph with shape 0.
+                if items.iter().any(|item| item.level > 0) {
+                    warnings.push(
+                        "hwp: list indentation is not written by hwpers 0.5.0; nested items are not indented"
+                            .to_string(),
+                    );
+                }
+                let list_error = |error| map_hwp_error_with_stage(error, "add list");
+                writer
+                    .start_list(hwp_list_type.clone())
+                    .map_err(list_error)?;
+                let mut depth = 0;
+                for item in items {
+                    while depth < item.level {
+                        writer
+                            .start_nested_list(hwp_list_type.clone())
+                            .map_err(list_error)?;
+                        depth += 1;
+                    }
+                    while depth > item.level {
+                        writer.end_list().map_err(list_error)?;
+                        depth -= 1;
+                    }
+                    writer.add_list_item(&item.text).map_err(list_error)?;
+                }
+                for _ in 0..=depth {
+                    writer.end_list().map_err(list_error)?;
+                }
+            }
+            BlockSpec::Equation { latex } => {
+                warnings.push(
+                    "hwp: equation objects are not supported; writing the markup as a monospace paragraph"
+                        .to_string(),
+                );
+                let ts = hwp_style::TextStyle::new().font(EQUATION_FONT);
+                let len = latex.chars().count();
+                let styled = hwp_style::StyledText::new(latex.clone()).add_range(0, len, ts);
+                writer
+                    .add_styled_paragraph(&styled)
+                    .map_err(|error| map_hwp_error_with_stage(error, "add equation"))?;
+            }
+            BlockSpec::Link { text, href } => {
+                // hwpers keeps hyperlinks on the paragraph but its serializer never
+                // writes them, so the target is spelled out after link-styled text.
+                warnings.push(format!(
+                    "hwp: hyperlinks are not supported; writing '{href}' in parentheses after the link text"
+                ));
+                let ts = hwp_style::TextStyle::new().color(LINK_COLOR).underline();
+                let len = text.chars().count();
+                let styled =
+                    hwp_style::StyledText::new(format!("{text} ({href})")).add_range(0, len, ts);
+                writer
+                    .add_styled_paragraph(&styled)
+                    .map_err(|error| map_hwp_error_with_stage(error, "add link"))?;
+            }
+            BlockSpec::Quote { text, style } => {
+                warnings.push(
+                    "hwp: quote borders and paragraph indents are not supported by hwpers 0.5.0; \
+                     indenting the quote with spaces"
+                        .to_string(),
+                );
+                let ts = apply_hwp_text_style(hwp_style::TextStyle::new(), style);
+                let quote = format!("{SPACE_INDENT}{text}");
+                let len = quote.chars().count();
+                let styled = hwp_style::StyledText::new(quote).add_range(0, len, ts);
                 writer
-                    .add_list(&items_ref, hwp_list_type)
-                    .map_err(|error| map_hwp_error_with_stage(error, "add list"))?;
+                    .add_styled_paragraph(&styled)
+                    .map_err(|error| map_hwp_error_with_stage(error, "add quote"))?;
             }
         }
     }
 
-    writer
+    let bytes = writer
         .to_bytes()
-        .map_err(|error| map_hwp_error_with_stage(error, "write document"))
+        .map_err(|error| map_hwp_error_with_stage(error, "write document"))?;
+    let tables = written_tables(&writer, &table_specs);
+    if document.page == PageSpec::default() && tables.is_empty() {
+        return Ok(bytes);
+    }
+    patch_hwp_section(bytes, document.page, &tables)
+}
+
+/// The settings of a table block that hwpers 0.5.0 drops and the table records carry instead.
+/// Column widths are in HWPUNIT, as given; they are fitted to the page once its printable
+/// width is known. `cell_margins` holds each cell's own `cell_margin_mm` by row and column.
+struct HwpTableSpec {
+    column_widths: Option<Vec<u32>>,
+    layout: TableLayout,
+    fit_to_page: bool,
+    cell_margin: Option<CellMargin>,
+    cell_margins: Vec<Vec<Option<CellMargin>>>,
+}
+
+/// A table hwpers 0.5.0 keeps in memory: the index of its anchor paragraph in the section, the
+/// control's instance id and the cells, whose paragraphs follow the anchor in order, with the
+/// block settings it was built from.
+struct WrittenTable<'a> {
+    paragraph: usize,
+    instance_id: u32,
+    table: &'a Table,
+    spec: &'a HwpTableSpec,
+}
+
+/// Pairs the tables in the written section with the table blocks, both in document order.
+fn written_tables<'a>(writer: &'a HwpWriter, specs: &'a [HwpTableSpec]) -> Vec<WrittenTable<'a>> {
+    let Some(section) = writer
+        .document()
+        .body_texts
+        .first()
+        .and_then(|body_text| body_text.sections.first())
+    else {
+        return Vec::new();
+    };
+    section
+        .paragraphs
+        .iter()
+        .enumerate()
+        .filter_map(|(paragraph, para)| Some((paragraph, para, para.table_data.as_ref()?)))
+        .zip(specs)
+        .map(|((paragraph, para, table), spec)| WrittenTable {
+            paragraph,
+            instance_id: para.ctrl_header.as_ref().map_or(0, |ctrl| ctrl.instance_id),
+            table,
+            spec,
+        })
+        .collect()
+}
+
+/// HWPTAG_PARA_HEADER starts with the character count u32, whose bit 31 marks the last
+/// paragraph in its list, then the control mask u32.
+const PARA_LAST_IN_LIST: u32 = 0x8000_0000;
+/// HWPTAG_PAGE_DEF: paper width and height u32s, margins (left and right first), then the
+/// attributes u32 at byte 36.
+const PAGE_DEF_LEFT_MARGIN: usize = 8;
+const PAGE_DEF_RIGHT_MARGIN: usize = 12;
+const PAGE_DEF_ATTRIBUTES: usize = 36;
+/// The text of a paragraph holding a table: the extended control character 11 wrapping the
+/// `tbl ` control id and four reserved code units, then the paragraph end.
+const TABLE_ANCHOR_TEXT: [u16; 9] = [0x0B, 0x6C20, 0x7462, 0, 0, 0, 0, 0x0B, 0x0D];
+/// Control mask bit of control character 11 (tables and drawing objects).
+const TABLE_CONTROL_MASK: u32 = 1 << 11;
+
+/// The hwpers 0.5.0 serializer writes the section's records from a fixed template, so what it
+/// drops is patched into the written stream: the paper size and orientation (it always writes
+/// an A4 portrait PAGE_DEF) and the table controls (it writes a table as an empty paragraph
+/// followed by one paragraph per cell).
+fn patch_hwp_section(
+    bytes: Vec<u8>,
+    page: PageSpec,
+    tables: &[WrittenTable],
+) -> Result<Vec<u8>, ToolError> {
+    const SECTION_STREAM: &str = "/BodyText/Section0";
+    let patch_error = |message: String| ToolError {
+        kind: errors::INTERNAL_ERROR,
+        message: format!("write document failed: {message}"),
+    };
+
+    let mut compound =
+        cfb::CompoundFile::open(Cursor::new(bytes)).map_err(|err| patch_error(err.to_string()))?;
+    let mut file_header = Vec::new();
+    let mut section = Vec::new();
+    compound
+        .open_stream("/FileHeader")
+        .and_then(|mut stream| stream.read_to_end(&mut file_header))
+        .and_then(|_| compound.open_stream(SECTION_STREAM))
+        .and_then(|mut stream| stream.read_to_end(&mut section))
+        .map_err(|err| patch_error(err.to_string()))?;
+    let compressed = FileHeader::parse(file_header)
+        .map_err(|err| patch_error(err.to_string()))?
+        .is_compressed();
+    if compressed {
+        section = decompress_stream(&section).map_err(|err| patch_error(err.to_string()))?;
+    }
+
+    let mut records = parse_records(section);
+    if page != PageSpec::default() {
+        set_page_def(&mut records, page).map_err(patch_error)?;
+    }
+    if !tables.is_empty() {
+        records = add_table_controls(records, tables).map_err(patch_error)?;
+    }
+    let mut section = write_records(&records);
+
+    if compressed {
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(&section)
+            .map_err(|err| patch_error(err.to_string()))?;
+        section = encoder
+            .finish()
+            .map_err(|err| patch_error(err.to_string()))?;
+    }
+    compound
+        .create_stream(SECTION_STREAM)
+        .and_then(|mut stream| stream.write_all(&section))
+        .and_then(|_| compound.flush())
+        .map_err(|err| patch_error(err.to_string()))?;
+    Ok(compound.into_inner().into_inner())
 }
 
-fn build_hwpx(document: &DocumentSpec, warnings: &mut Vec<String>) -> Result<Vec<u8>, ToolError> {
-    use hwpers::hwpx::{HwpxImage, HwpxTable, HwpxTextStyle};
+/// As in files saved by Hangul, width/height stay the unrotated paper size and landscape is
+/// bit 0 of the record's attributes.
+fn set_page_def(records: &mut [Record], page: PageSpec) -> Result<(), String> {
+    let paper = PageSpec {
+        landscape: false,
+        ..page
+    }
+    .layout();
+    let page_def = records
+        .iter_mut()
+        .find(|record| {
+            record.header.tag_id == HWPTAG_PAGE_DEF && record.data.len() >= PAGE_DEF_ATTRIBUTES + 4
+        })
+        .ok_or("no page definition record was written")?;
+    page_def.data[0..4].copy_from_slice(&paper.width.to_le_bytes());
+    page_def.data[4..8].copy_from_slice(&paper.height.to_le_bytes());
+    if page.landscape {
+        page_def.data[PAGE_DEF_ATTRIBUTES] |= 0x01;
+    } else {
+        page_def.data[PAGE_DEF_ATTRIBUTES] &= !0x01;
+    }
+    Ok(())
+}
+
+/// Turns each table's anchor paragraph into one holding a table control, the way Hangul stores
+/// it: a CTRL_HEADER one level below the paragraph, the TABLE record one level below that,
+/// then per cell a LIST_HEADER followed by the cell's paragraph moved down two levels.
+fn add_table_controls(
+    records: Vec<Record>,
+    tables: &[WrittenTable],
+) -> Result<Vec<Record>, String> {
+    let page_width = printable_width(&records).ok_or("no page definition record was written")?;
+    // hwpers writes every paragraph at level 0, the section definition first, so paragraph
+    // `n` of the section starts at PARA_HEADER `n + 1`.
+    let mut paragraphs: Vec<Vec<Record>> = Vec::new();
+    for record in records {
+        match paragraphs.last_mut() {
+            Some(paragraph) if record.header.tag_id != HWPTAG_PARA_HEADER => paragraph.push(record),
+            _ => paragraphs.push(vec![record]),
+        }
+    }
+
+    let mut patched = Vec::new();
+    let mut paragraphs = paragraphs.into_iter();
+    patched.extend(paragraphs.next().unwrap_or_default());
+    let mut tables = tables.iter().peekable();
+    let mut index = 0;
+    while let Some(mut paragraph) = paragraphs.next() {
+        let Some(table) = tables.next_if(|table| table.paragraph == index) else {
+            patched.extend(paragraph);
+            index += 1;
+            continue;
+        };
+        let cells: Vec<Vec<Record>> = paragraphs.by_ref().take(table.table.cells.len()).collect();
+        if cells.len() < table.table.cells.len() {
+            return Err(format!(
+                "table at paragraph {index} lost its cell paragraphs"
+            ));
+        }
+        index += 1 + cells.len();
+        let widths = cell_widths(table, page_width);
+        let margins = cell_margins(table);
+
+        let last_in_section = paragraphs.len() == 0;
+        for record in &mut paragraph {
+            match record.header.tag_id {
+                HWPTAG_PARA_HEADER if record.data.len() >= 8 => {
+                    let mut count = TABLE_ANCHOR_TEXT.len() as u32;
+                    if last_in_section {
+                        count |= PARA_LAST_IN_LIST;
+                    }
+                    let mask = u32_at(&record.data, 4) | TABLE_CONTROL_MASK;
+                    record.data[0..4].copy_from_slice(&count.to_le_bytes());
+                    record.data[4..8].copy_from_slice(&mask.to_le_bytes());
+                }
+                HWPTAG_PARA_TEXT => {
+                    record.data = TABLE_ANCHOR_TEXT
+                        .iter()
+                        .flat_map(|unit| unit.to_le_bytes())
+                        .collect();
+                }
+                _ => {}
+            }
+            record.header.size = record.data.len() as u32;
+        }
+        let level = paragraph[0].header.level;
+        patched.extend(paragraph);
+        patched.push(body_record(
+            HWPTAG_CTRL_HEADER,
+            level + 1,
+            table_ctrl_header(table, &widths),
+        ));
+        patched.push(body_record(
+            HWPTAG_TABLE,
+            level + 2,
+            table_record(table.table, table.spec.cell_margin),
+        ));
+        for (index, (cell, records)) in table.table.cells.iter().zip(cells).enumerate() {
+            patched.push(body_record(
+                HWPTAG_LIST_HEADER,
+                level + 2,
+                cell_list_header(cell, widths[index], margins[index]),
+            ));
+            for mut record in records {
+                if record.header.tag_id == HWPTAG_PARA_HEADER && record.data.len() >= 4 {
+                    let count = u32_at(&record.data, 0) | PARA_LAST_IN_LIST;
+                    record.data[0..4].copy_from_slice(&count.to_le_bytes());
+                }
+                record.header.level += 2;
+                patched.push(record);
+            }
+        }
+    }
+    Ok(patched)
+}
+
+/// The width between the side margins of the section's PAGE_DEF record. The paper width and
+/// height stay unrotated in landscape, so the height is the page width there.
+fn printable_width(records: &[Record]) -> Option<u32> {
+    let data = &records
+        .iter()
+        .find(|record| {
+            record.header.tag_id == HWPTAG_PAGE_DEF && record.data.len() >= PAGE_DEF_ATTRIBUTES + 4
+        })?
+        .data;
+    let landscape = data[PAGE_DEF_ATTRIBUTES] & 0x01 != 0;
+    let paper_width = u32_at(data, if landscape { 4 } else { 0 });
+    Some(
+        paper_width
+            .saturating_sub(u32_at(data, PAGE_DEF_LEFT_MARGIN))
+            .saturating_sub(u32_at(data, PAGE_DEF_RIGHT_MARGIN)),
+    )
+}
+
+/// Each cell's width, in cell order: the sum of the table's column widths over the columns it
+/// spans. The columns are the block's `column_widths` or else the widths hwpers gave the cells,
+/// fitted to the page like the HWPX writer does.
+fn cell_widths(table: &WrittenTable, printable_width: u32) -> Vec<u32> {
+    let spec = table.spec;
+    let columns = spec
+        .column_widths
+        .clone()
+        .unwrap_or_else(|| default_column_widths(table.table));
+    let columns = fit_column_widths(&columns, spec.layout, spec.fit_to_page, printable_width);
+    table
+        .table
+        .cells
+        .iter()
+        .map(|cell| {
+            let spanned: u32 = columns
+                .iter()
+                .skip(usize::from(cell.cell_address.1))
+                .take(usize::from(cell.col_span.max(1)))
+                .sum();
+            if spanned == 0 { cell.width } else { spanned }
+        })
+        .collect()
+}
+
+/// Each cell's inner margins, in cell order: its own `cell_margin_mm`, else the table's, else
+/// the padding hwpers gave it.
+fn cell_margins(table: &WrittenTable) -> Vec<[u16; 4]> {
+    let spec = table.spec;
+    table
+        .table
+        .cells
+        .iter()
+        .map(|cell| {
+            let (row, col) = cell.cell_address;
+            spec.cell_margins
+                .get(usize::from(row))
+                .and_then(|cells| cells.get(usize::from(col)))
+                .copied()
+                .flatten()
+                .or(spec.cell_margin)
+                .map_or(
+                    [
+                        cell.left_margin,
+                        cell.right_margin,
+                        cell.top_margin,
+                        cell.bottom_margin,
+                    ],
+                    |margin| margin.hwp_units(),
+                )
+        })
+        .collect()
+}
+
+/// Per column, the width hwpers gave a cell that starts there and spans only that column.
+fn default_column_widths(table: &Table) -> Vec<u32> {
+    (0..table.cols)
+        .map(|col| {
+            table
+                .cells
+                .iter()
+                .find(|cell| cell.cell_address.1 == col && cell.col_span <= 1)
+                .map_or(0, |cell| cell.width)
+        })
+        .collect()
+}
+
+fn u32_at(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap_or_default())
+}
+
+fn body_record(tag_id: u16, level: u8, data: Vec<u8>) -> Record {
+    Record {
+        header: RecordHeader {
+            tag_id,
+            level,
+            size: data.len() as u32,
+        },
+        data,
+    }
+}
+
+/// A table's CTRL_HEADER: the `tbl ` control id, then the common object properties (placed
+/// like a character, size, z-order, outer margins, instance id, page split setting and an
+/// empty description). `widths` holds each cell's width, in cell order.
+fn table_ctrl_header(table: &WrittenTable, widths: &[u32]) -> Vec<u8> {
+    let cells = &table.table.cells;
+    let width: u32 = cells
+        .iter()
+        .zip(widths)
+        .filter(|(cell, _)| cell.cell_address.0 == 0)
+        .map(|(_, width)| width)
+        .sum();
+    let height: u32 = cells
+        .iter()
+        .filter(|cell| cell.cell_address.1 == 0)
+        .map(|cell| cell.height)
+        .sum();
+    let mut data = b" lbt".to_vec();
+    data.extend_from_slice(&1u32.to_le_bytes());
+    data.extend_from_slice(&[0; 8]);
+    data.extend_from_slice(&width.to_le_bytes());
+    data.extend_from_slice(&height.to_le_bytes());
+    data.extend_from_slice(&[0; 12]);
+    data.extend_from_slice(&table.instance_id.to_le_bytes());
+    data.extend_from_slice(&[0; 6]);
+    data
+}
+
+/// A TABLE record: properties, row and column counts, cell spacing, inner margins (the
+/// block's `cell_margin_mm` when set), the number of cells in each row and the border fill id.
+fn table_record(table: &Table, cell_margin: Option<CellMargin>) -> Vec<u8> {
+    let mut data = table.properties.to_le_bytes().to_vec();
+    data.extend_from_slice(&table.rows.to_le_bytes());
+    data.extend_from_slice(&table.cols.to_le_bytes());
+    data.extend_from_slice(&table.cell_spacing.to_le_bytes());
+    let margins = cell_margin.map_or(
+        [
+            table.left_margin,
+            table.right_margin,
+            table.top_margin,
+            table.bottom_margin,
+        ]
+        .map(|margin| margin.clamp(0, i32::from(u16::MAX)) as u16),
+        |margin| margin.hwp_units(),
+    );
+    for margin in margins {
+        data.extend_from_slice(&margin.to_le_bytes());
+    }
+    for row in 0..table.rows {
+        let cells = table
+            .cells
+            .iter()
+            .filter(|cell| cell.cell_address.0 == row)
+            .count();
+        data.extend_from_slice(&(cells as u16).to_le_bytes());
+    }
+    data.extend_from_slice(&1u16.to_le_bytes());
+    data
+}
+
+/// A cell's LIST_HEADER: paragraph count, list properties, then the cell's address, spans,
+/// size, margins (left, right, top, bottom) and border fill id. hwpers counts border fills
+/// from 0 (the default fill), the file format from 1.
+fn cell_list_header(cell: &TableCell, width: u32, margins: [u16; 4]) -> Vec<u8> {
+    let mut data = 1u16.to_le_bytes().to_vec();
+    data.extend_from_slice(&[0; 6]);
+    let (row, col) = cell.cell_address;
+    for value in [col, row, cell.col_span, cell.row_span] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(&width.to_le_bytes());
+    data.extend_from_slice(&cell.height.to_le_bytes());
+    for margin in margins {
+        data.extend_from_slice(&margin.to_le_bytes());
+    }
+    data.extend_from_slice(&(cell.border_fill_id + 1).to_le_bytes());
+    data
+}
+
+/// hwpers 0.5.0 writes every HWP paragraph with paragraph shape 0 and gives no mutable access
+/// to a writer's shapes, so the document default is applied by editing shape 0 of a blank
+/// document and building on top of it.
+fn hwp_writer(default_spacing: ParagraphSpacing) -> Result<HwpWriter, ToolError> {
+    if default_spacing.is_empty() {
+        return Ok(HwpWriter::new());
+    }
+    let blank = HwpWriter::new()
+        .to_bytes()
+        .map_err(|error| map_hwp_error_with_stage(error, "apply default paragraph style"))?;
+    let mut document = HwpReader::from_bytes(&blank)
+        .map_err(|error| map_hwp_error_with_stage(error, "apply default paragraph style"))?;
+    for body_text in &mut document.body_texts {
+        for section in &mut body_text.sections {
+            section.paragraphs.clear();
+        }
+    }
+    let Some(shape) = document.doc_info.para_shapes.first_mut() else {
+        return Err(ToolError {
+            kind: errors::INTERNAL_ERROR,
+            message: "apply default paragraph style failed: blank document has no paragraph shape"
+                .to_string(),
+        });
+    };
+    match default_spacing.line_spacing {
+        Some(LineSpacing::Percent(percent)) => {
+            shape.line_space_type = 0;
+            shape.line_space = percent as i32;
+        }
+        Some(LineSpacing::Fixed(height)) => {
+            shape.line_space_type = 1;
+            shape.line_space = height as i32;
+        }
+        None => {}
+    }
+    if let Some(before) = default_spacing.space_before {
+        shape.top_para_space = before as i32;
+    }
+    if let Some(after) = default_spacing.space_after {
+        shape.bottom_para_space = after as i32;
+    }
+    Ok(HwpWriter::from_document(document))
+}
+
+fn apply_hwp_text_style(
+    mut ts: hwpers::style::TextStyle,
+    style: &TextStyleSpec,
+) -> hwpers::style::TextStyle {
+    if let Some(font) = &style.font_name {
+        ts = ts.font(font);
+    }
+    if let Some(size) = style.font_size {
+        ts = ts.size(size);
+    }
+    if style.bold {
+        ts = ts.bold();
+    }
+    if style.italic {
+        ts = ts.italic();
+    }
+    if style.underline {
+        ts = ts.underline();
+    }
+    if let Some(color) = style.color {
+        ts = ts.color(color);
+    }
+    ts
+}
+
+fn apply_hwpx_text_style(
+    mut ts: hwpers::hwpx::HwpxTextStyle,
+    style: &TextStyleSpec,
+) -> hwpers::hwpx::HwpxTextStyle {
+    if style.font_name.is_some() {
+        ts.font_name = style.font_name.clone();
+    }
+    if let Some(size) = style.font_size {
+        ts = ts.size(size);
+    }
+    if style.bold {
+        ts = ts.bold();
+    }
+    if style.italic {
+        ts = ts.italic();
+    }
+    if style.underline {
+        ts = ts.underline();
+    }
+    if let Some(color) = style.color {
+        ts = ts.color(color);
+    }
+    ts
+}
+
+/// Explains WebP and SVG images, which `hwpers` cannot embed and this build has no
+/// decoder or rasterizer to convert to PNG. Other bytes get the caller's own error.
+fn unconvertible_image_error(data: &[u8], mime_type: Option<&str>) -> Option<ToolError> {
+    let head = &data[..data.len().min(512)];
+    let text = String::from_utf8_lossy(head);
+    let text = text.trim_start();
+    let format = if mime_type == Some("image/webp")
+        || (head.starts_with(b"RIFF") && head.get(8..12) == Some(b"WEBP"))
+    {
+        "WebP"
+    } else if mime_type == Some("image/svg+xml")
+        || text.starts_with("<svg")
+        || (text.starts_with("<?xml") && text.contains("<svg"))
+    {
+        "SVG"
+    } else {
+        return None;
+    };
+    Some(ToolError {
+        kind: errors::INVALID_INPUT,
+        message: format!(
+            "{format} images are not supported: hwpers embeds only PNG, JPEG, GIF and BMP, \
+             and converting {format} to PNG is not available; convert the image to PNG first"
+        ),
+    })
+}
+
+/// Fills in a missing `width_mm`/`height_mm` from the image's own aspect ratio, so
+/// giving one dimension scales the image instead of pairing it with the 50mm default.
+fn complete_image_size(
+    data: &[u8],
+    width_mm: Option<u32>,
+    height_mm: Option<u32>,
+) -> Result<(Option<u32>, Option<u32>), ToolError> {
+    if width_mm.is_some() == height_mm.is_some() {
+        return Ok((width_mm, height_mm));
+    }
+    let (width_px, height_px) = image_pixel_size(data).ok_or_else(|| ToolError {
+        kind: errors::INVALID_INPUT,
+        message: "image dimensions could not be read; give both width_mm and height_mm".to_string(),
+    })?;
+    let scale = |length: u32, from: u32, to: u32| {
+        ((f64::from(length) * f64::from(to) / f64::from(from)).round() as u32).max(1)
+    };
+    Ok(match (width_mm, height_mm) {
+        (Some(w), None) => (Some(w), Some(scale(w, width_px, height_px))),
+        (None, Some(h)) => (Some(scale(h, height_px, width_px)), Some(h)),
+        _ => (width_mm, height_mm),
+    })
+}
+
+/// Reads the pixel size from a PNG, JPEG, GIF or BMP header.
+fn image_pixel_size(data: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |at: usize| {
+        Some(u32::from(u16::from_be_bytes(
+            data.get(at..at + 2)?.try_into().ok()?,
+        )))
+    };
+    let le16 = |at: usize| {
+        Some(u32::from(u16::from_le_bytes(
+            data.get(at..at + 2)?.try_into().ok()?,
+        )))
+    };
+    let size = if data.starts_with(&[0x89, b'P', b'N', b'G']) && data.get(12..16) == Some(b"IHDR") {
+        let be32 = |at: usize| Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?));
+        (be32(16)?, be32(20)?)
+    } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        (le16(6)?, le16(8)?)
+    } else if data.starts_with(b"BM") {
+        let le32 = |at: usize| Some(i32::from_le_bytes(data.get(at..at + 4)?.try_into().ok()?));
+        // Top-down bitmaps store a negative height.
+        (le32(18)?.unsigned_abs(), le32(22)?.unsigned_abs())
+    } else if data.starts_with(&[0xFF, 0xD8]) {
+        let mut at = 2;
+        loop {
+            if *data.get(at)? != 0xFF {
+                return None;
+            }
+            let marker = *data.get(at + 1)?;
+            match marker {
+                // Fill bytes before a marker.
+                0xFF => at += 1,
+                // Standalone markers carry no length.
+                0x01 | 0xD0..=0xD7 => at += 2,
+                // Start of frame, except DHT (C4), JPG (C8) and DAC (CC).
+                0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+                    break (be16(at + 7)?, be16(at + 5)?);
+                }
+                0xD9 | 0xDA => return None,
+                _ => at += 2 + be16(at + 2)? as usize,
+            }
+        }
+    } else {
+        return None;
+    };
+    (size.0 > 0 && size.1 > 0).then_some(size)
+}
+
+/// Scales column widths to the printable width: always for fixed layout,
+/// and only when they overflow for auto layout with `fit_to_page`.
+fn fit_column_widths(
+    widths: &[u32],
+    layout: TableLayout,
+    fit_to_page: bool,
+    printable_width: u32,
+) -> Vec<u32> {
+    let total: u64 = widths.iter().map(|w| u64::from(*w)).sum();
+    let should_scale = match layout {
+        TableLayout::Fixed => true,
+        TableLayout::Auto => fit_to_page && total > u64::from(printable_width),
+    };
+    if !should_scale || total == 0 {
+        return widths.to_vec();
+    }
+
+    let mut scaled: Vec<u32> = widths
+        .iter()
+        .map(|w| (u64::from(*w) * u64::from(printable_width) / total) as u32)
+        .collect();
+    let used: u32 = scaled.iter().sum();
+    if let Some(last) = scaled.last_mut() {
+        *last += printable_width.saturating_sub(used);
+    }
+    scaled
+}
+
+fn build_hwpx(
+    document: &DocumentSpec,
+    warnings: &mut Vec<String>,
+) -> Result<HwpxWriter, ToolError> {
+    use hwpers::hwpx::{HwpxImage, HwpxTable, HwpxTextStyle, StyledText as HwpxStyledText};
 
     let mut writer = HwpxWriter::new();
 
+    if document.page != PageSpec::default() {
+        warnings.push(
+            "hwpx: document.page is not supported by hwpers 0.5.0 (the HWPX writer always \
+             writes A4 portrait); ignoring"
+                .to_string(),
+        );
+    }
+
+    let has_spacing = !document.default_spacing.is_empty()
+        || document.blocks.iter().any(|block| {
+            block
+                .text_style()
+                .is_some_and(|style| !style.spacing.is_empty())
+        });
+    if has_spacing {
+        warnings.push(
+            "hwpx: line_spacing/space_before/space_after are not supported by hwpers 0.5.0 \
+             (one paragraph shape is written); ignoring"
+                .to_string(),
+        );
+    }
+
     if let Some(header) = &document.header {
         writer.add_header(header);
     }
@@ -952,25 +2507,23 @@ fn build_hwpx(document: &DocumentSpec, warnings: &mut Vec<String>) -> Result<Vec
 
     for block in &document.blocks {
         match block {
-            BlockSpec::Paragraph { text, style } => {
+            BlockSpec::Paragraph { runs, .. } if !runs.is_empty() => {
+                let runs = runs
+                    .iter()
+                    .map(|run| {
+                        HwpxStyledText::with_style(
+                            &run.text,
+                            apply_hwpx_text_style(HwpxTextStyle::new(), &run.style),
+                        )
+                    })
+                    .collect();
+                writer
+                    .add_mixed_styled_paragraph(runs)
+                    .map_err(|err| map_hwp_error_with_stage(err, "add styled paragraph"))?;
+            }
+            BlockSpec::Paragraph { text, style, .. } => {
                 if let Some(style) = style {
-                    let mut ts = HwpxTextStyle::new();
-                    ts.font_name = style.font_name.clone();
-                    if let Some(size) = style.font_size {
-                        ts = ts.size(size);
-                    }
-                    if style.bold {
-                        ts = ts.bold();
-                    }
-                    if style.italic {
-                        ts = ts.italic();
-                    }
-                    if style.underline {
-                        ts = ts.underline();
-                    }
-                    if let Some(color) = style.color {
-                        ts = ts.color(color);
-                    }
+                    let ts = apply_hwpx_text_style(HwpxTextStyle::new(), style);
                     writer
                         .add_styled_paragraph(text, ts)
                         .map_err(|err| map_hwp_error_with_stage(err, "add styled paragraph"))?;
@@ -980,7 +2533,7 @@ fn build_hwpx(document: &DocumentSpec, warnings: &mut Vec<String>) -> Result<Vec
                         .map_err(|err| map_hwp_error_with_stage(err, "add paragraph"))?;
                 }
             }
-            BlockSpec::Heading { level, text } => {
+            BlockSpec::Heading { level, text, style } => {
                 let size = match level {
                     1 => 24,
                     2 => 18,
@@ -988,7 +2541,11 @@ fn build_hwpx(document: &DocumentSpec, warnings: &mut Vec<String>) -> Result<Vec
                     4 => 12,
                     _ => 11,
                 };
-                let style = HwpxTextStyle::new().size(size).bold();
+                let mut heading_style = HwpxTextStyle::new().size(size).bold();
+                if let Some(style) = style {
+                    heading_style = apply_hwpx_text_style(heading_style, style);
+                }
+                let style = heading_style;
                 writer
                     .add_styled_paragraph(text, style)
                     .map_err(|err| map_hwp_error_with_stage(err, "add heading"))?;
@@ -998,47 +2555,64 @@ fn build_hwpx(document: &DocumentSpec, warnings: &mut Vec<String>) -> Result<Vec
                 header_row: _,
                 column_widths,
                 border_style,
+                layout,
+                fit_to_page,
+                cell_margin,
             } => {
                 let row_count = rows.len();
                 let col_count = rows.first().map(|r| r.len()).unwrap_or(0);
                 let mut table = HwpxTable::new(row_count, col_count);
-
-                if column_widths.is_some() {
-                    warnings.push("hwpx: column_widths is not supported; ignoring".to_string());
+                if let Some(widths) = column_widths {
+                    table.col_widths = widths.clone();
                 }
+                table.col_widths = fit_column_widths(
+                    &table.col_widths,
+                    *layout,
+                    *fit_to_page,
+                    HWPX_PRINTABLE_WIDTH,
+                );
                 if border_style.is_some() {
                     warnings.push("hwpx: border_style is not supported; ignoring".to_string());
                 }
+                if cell_margin.is_some() {
+                    warnings.push("hwpx: cell_margin_mm is not supported; ignoring".to_string());
+                }
 
+                // HwpxTable holds only cell text and column widths, so per-cell
+                // formatting cannot reach the XML. Each attribute is reported once
+                // with the cells it was set on.
+                let mut unsupported: [(&str, Vec<String>); 5] = [
+                    ("cell merging (row_span/col_span)", Vec::new()),
+                    ("cell background_color", Vec::new()),
+                    ("cell text_align", Vec::new()),
+                    ("cell style", Vec::new()),
+                    ("cell cell_margin_mm", Vec::new()),
+                ];
                 for (r, row) in rows.iter().enumerate() {
                     for (c, cell) in row.iter().enumerate() {
-                        if cell.row_span.is_some() || cell.col_span.is_some() {
-                            warnings.push(format!(
-                                "hwpx: cell merging (row_span/col_span) at ({}, {}) is not supported",
-                                r, c
-                            ));
-                        }
-                        if cell.background_color.is_some() {
-                            warnings.push(format!(
-                                "hwpx: cell background_color at ({}, {}) is not supported",
-                                r, c
-                            ));
-                        }
-                        if cell.text_align.is_some() {
-                            warnings.push(format!(
-                                "hwpx: cell text_align at ({}, {}) is not supported",
-                                r, c
-                            ));
-                        }
-                        if cell.style.is_some() {
-                            warnings.push(format!(
-                                "hwpx: cell style at ({}, {}) is not supported",
-                                r, c
-                            ));
+                        let set = [
+                            cell.row_span.is_some() || cell.col_span.is_some(),
+                            cell.background_color.is_some(),
+                            cell.text_align.is_some(),
+                            cell.style.is_some(),
+                            cell.cell_margin.is_some(),
+                        ];
+                        for ((_, cells), set) in unsupported.iter_mut().zip(set) {
+                            if set {
+                                cells.push(format!("({r}, {c})"));
+                            }
                         }
                         table.set_cell(r, c, &cell.content);
                     }
                 }
+                for (attribute, cells) in unsupported {
+                    if !cells.is_empty() {
+                        warnings.push(format!(
+                            "hwpx: {attribute} at {} is not supported by hwpers 0.5.0; ignoring",
+                            cells.join(", ")
+                        ));
+                    }
+                }
                 writer
                     .add_table(table)
                     .map_err(|err| map_hwp_error_with_stage(err, "add table"))?;
@@ -1059,13 +2633,17 @@ fn build_hwpx(document: &DocumentSpec, warnings: &mut Vec<String>) -> Result<Vec
                     })?,
                 };
 
+                if let Some(error) = unconvertible_image_error(&data, None) {
+                    return Err(error);
+                }
+                let (width_mm, height_mm) = complete_image_size(&data, *width_mm, *height_mm)?;
                 let mut image = HwpxImage::from_bytes(data).ok_or_else(|| ToolError {
                     kind: errors::INVALID_INPUT,
                     message: "unsupported image format bytes".to_string(),
                 })?;
 
                 if let (Some(w), Some(h)) = (width_mm, height_mm) {
-                    image = image.with_size(*w, *h);
+                    image = image.with_size(w, h);
                 }
 
                 writer
@@ -1097,96 +2675,132 @@ fn build_hwpx(document: &DocumentSpec, warnings: &mut Vec<String>) -> Result<Vec
                     "hwpx: list type '{}' is not fully supported; using basic formatting",
                     list_type_name
                 ));
-                for (idx, item) in items.iter().enumerate() {
+                if items.iter().any(|item| item.level > 0) {
+                    warnings.push(
+                        "hwpx: list indentation is not supported; nested items are indented with spaces"
+                            .to_string(),
+                    );
+                }
+                // Numbering restarts whenever a list goes one level deeper.
+                let mut counters: Vec<usize> = Vec::new();
+                for item in items {
+                    let level = item.level as usize;
+                    counters.resize(level + 1, 0);
+                    counters[level] += 1;
+                    let idx = counters[level] - 1;
                     let prefix = match list_type {
-                        ListTypeSpec::Bullet => "•".to_string(),
+                        ListTypeSpec::Bullet => match level {
+                            0 => "•",
+                            1 => "◦",
+                            _ => "▪",
+                        }
+                        .to_string(),
                         ListTypeSpec::Numbered => format!("{}.", idx + 1),
-                        ListTypeSpec::Alphabetic => format!("{}.", (b'a' + idx as u8) as char),
+                        ListTypeSpec::Alphabetic => {
+                            format!("{}.", (b'a' + (idx % 26) as u8) as char)
+                        }
                         ListTypeSpec::Roman => format!("{}.", idx + 1),
                         ListTypeSpec::Korean => format!("{}.", idx + 1),
                     };
                     writer
-                        .add_paragraph(&format!("{} {}", prefix, item))
+                        .add_paragraph(&format!(
+                            "{}{} {}",
+                            SPACE_INDENT.repeat(level),
+                            prefix,
+                            item.text
+                        ))
                         .map_err(|err| map_hwp_error_with_stage(err, "add list item"))?;
                 }
             }
+            BlockSpec::Equation { latex } => {
+                warnings.push(
+                    "hwpx: equation objects are not supported; writing the markup as a monospace paragraph"
+                        .to_string(),
+                );
+                let mut style = HwpxTextStyle::new();
+                style.font_name = Some(EQUATION_FONT.to_string());
+                writer
+                    .add_styled_paragraph(latex, style)
+                    .map_err(|err| map_hwp_error_with_stage(err, "add equation"))?;
+            }
+            BlockSpec::Link { text, href } => {
+                writer
+                    .add_hyperlink(text, href)
+                    .map_err(|err| map_hwp_error_with_stage(err, "add link"))?;
+            }
+            BlockSpec::Quote { text, style } => {
+                warnings.push(
+                    "hwpx: quote borders and paragraph indents are not supported by hwpers 0.5.0; \
+                     indenting the quote with spaces"
+                        .to_string(),
+                );
+                let ts = apply_hwpx_text_style(HwpxTextStyle::new(), style);
+                writer
+                    .add_styled_paragraph(&format!("{SPACE_INDENT}{text}"), ts)
+                    .map_err(|err| map_hwp_error_with_stage(err, "add quote"))?;
+            }
         }
     }
 
-    writer
-        .to_bytes()
-        .map_err(|error| map_hwp_error_with_stage(error, "write document"))
+    Ok(writer)
 }
 
-fn write_output(path: &str, bytes: &[u8]) -> Result<OutputResource, ToolError> {
-    fs::write(path, bytes).map_err(|err| ToolError {
-        kind: errors::INTERNAL_ERROR,
-        message: format!("failed to write output: {err}"),
-    })?;
+#[cfg(test)]
+mod tests {
+    use super::{complete_image_size, image_pixel_size};
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut data = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 13];
+        data.extend_from_slice(b"IHDR");
+        data.extend_from_slice(&width.to_be_bytes());
+        data.extend_from_slice(&height.to_be_bytes());
+        data.extend_from_slice(&[8, 6, 0, 0, 0]);
+        data
+    }
 
-    let uri = format!("file://{path}");
-    let name = Path::new(path)
-        .file_name()
-        .and_then(|value| value.to_str())
-        .unwrap_or("document");
+    #[test]
+    fn reads_pixel_size_from_image_headers() {
+        assert_eq!(image_pixel_size(&png(640, 480)), Some((640, 480)));
 
-    let content = vec![
-        json!({
-            "type": "text",
-            "text": format!("document written to {path}")
-        }),
-        json!({
-            "type": "resource_link",
-            "uri": uri,
-            "name": name,
-            "mimeType": "application/octet-stream"
-        }),
-    ];
+        let mut gif = b"GIF89a".to_vec();
+        gif.extend_from_slice(&[0x20, 0x03, 0x58, 0x02]);
+        assert_eq!(image_pixel_size(&gif), Some((800, 600)));
 
-    Ok(OutputResource {
-        path: path.to_string(),
-        uri: format!("file://{path}"),
-        content,
-    })
-}
+        let mut bmp = vec![0; 26];
+        bmp[..2].copy_from_slice(b"BM");
+        bmp[18..22].copy_from_slice(&300i32.to_le_bytes());
+        bmp[22..26].copy_from_slice(&(-200i32).to_le_bytes());
+        assert_eq!(image_pixel_size(&bmp), Some((300, 200)));
 
-fn map_hwp_error(error: HwpError) -> ToolError {
-    match error {
-        HwpError::UnsupportedVersion(message) => {
-            if message.contains("Password-encrypted") {
-                ToolError {
-                    kind: errors::ENCRYPTED,
-                    message,
-                }
-            } else {
-                ToolError {
-                    kind: errors::PARSE_FAILED,
-                    message,
-                }
-            }
-        }
-        HwpError::InvalidInput(message) => ToolError {
-            kind: errors::INVALID_INPUT,
-            message,
-        },
-        HwpError::Io(err) => ToolError {
-            kind: errors::INVALID_INPUT,
-            message: err.to_string(),
-        },
-        HwpError::InvalidFormat(message)
-        | HwpError::Cfb(message)
-        | HwpError::CompressionError(message)
-        | HwpError::ParseError(message)
-        | HwpError::EncodingError(message)
-        | HwpError::NotFound(message) => ToolError {
-            kind: errors::PARSE_FAILED,
-            message,
-        },
+        // SOI, a JFIF APP0 segment, then a baseline SOF0 with 120x90.
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00];
+        jpeg.extend_from_slice(&[0xFF, 0xC0, 0x00, 0x11, 0x08, 0x00, 0x5A, 0x00, 0x78]);
+        assert_eq!(image_pixel_size(&jpeg), Some((120, 90)));
+
+        assert_eq!(image_pixel_size(&png(0, 10)), None);
+        assert_eq!(image_pixel_size(b"not an image"), None);
+        assert_eq!(image_pixel_size(&[0xFF, 0xD8, 0xFF, 0xDA]), None);
     }
-}
 
-fn map_hwp_error_with_stage(error: HwpError, stage: &str) -> ToolError {
-    let mut mapped = map_hwp_error(error);
-    mapped.message = format!("{stage} failed: {}", mapped.message);
-    mapped
+    #[test]
+    fn completes_a_single_image_dimension_from_the_aspect_ratio() {
+        let wide = png(400, 100);
+        assert!(matches!(
+            complete_image_size(&wide, Some(80), None),
+            Ok((Some(80), Some(20)))
+        ));
+        assert!(matches!(
+            complete_image_size(&wide, None, Some(10)),
+            Ok((Some(40), Some(10)))
+        ));
+        assert!(matches!(
+            complete_image_size(&wide, Some(30), Some(30)),
+            Ok((Some(30), Some(30)))
+        ));
+        assert!(matches!(
+            complete_image_size(b"opaque", None, None),
+            Ok((None, None))
+        ));
+        assert!(complete_image_size(b"opaque", Some(30), None).is_err());
+    }
 }
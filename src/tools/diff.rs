@@ -0,0 +1,264 @@
+use crate::input::load_input;
+use crate::mcp::errors;
+use crate::tools::{check_arguments, error_result, parse_document};
+use serde_json::{Value, json};
+
+/// Largest LCS table (left × right items, after trimming the common prefix/suffix) computed.
+const MAX_LCS_CELLS: usize = 16_000_000;
+/// Replaced paragraphs longer than this (in chars) skip the char-level diff.
+const MAX_INLINE_CHARS: usize = 2_000;
+
+pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let Some(args_obj) = args.as_object() else {
+        return error_result(errors::INVALID_INPUT, "arguments must be an object", None);
+    };
+
+    let mut paragraphs = Vec::new();
+    let mut sides = serde_json::Map::new();
+    let mut warnings = Vec::new();
+    for side in ["left", "right"] {
+        let Some(input) = args_obj.get(side) else {
+            return error_result(errors::INVALID_INPUT, format!("{side} is required"), None);
+        };
+        let payload = match load_input(input) {
+            Ok(payload) => payload,
+            Err(err) => return error_result(err.kind, format!("{side}: {}", err.message), None),
+        };
+        let parsed = match parse_document(&payload.bytes, payload.format) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                return error_result(
+                    err.kind,
+                    format!("{side}: {}", err.message),
+                    Some(payload.source.as_str()),
+                );
+            }
+        };
+        warnings.extend(
+            parsed
+                .warnings
+                .into_iter()
+                .map(|warning| format!("{side}: {warning}")),
+        );
+        let lines = paragraph_lines(&parsed.document);
+        sides.insert(
+            side.to_string(),
+            json!({
+                "format": parsed.format.as_str(),
+                "detected_format": parsed.format.as_str(),
+                "detection_method": payload.detection_method,
+                "paragraph_count": lines.len()
+            }),
+        );
+        paragraphs.push(lines);
+    }
+    let right = paragraphs.pop().unwrap_or_default();
+    let left = paragraphs.pop().unwrap_or_default();
+
+    let left_texts: Vec<&str> = left.iter().map(|(_, text)| text.as_str()).collect();
+    let right_texts: Vec<&str> = right.iter().map(|(_, text)| text.as_str()).collect();
+    let Some(ops) = diff_ops(&left_texts, &right_texts) else {
+        return error_result(
+            errors::TOO_LARGE,
+            format!(
+                "documents differ too much to diff: {} x {} changed paragraphs exceeds {MAX_LCS_CELLS} comparisons",
+                left.len(),
+                right.len()
+            ),
+            None,
+        );
+    };
+
+    let mut diff = Vec::with_capacity(ops.len());
+    let (mut added, mut removed, mut unchanged) = (0, 0, 0);
+    let mut index = 0;
+    while index < ops.len() {
+        match ops[index] {
+            Op::Equal(l, r) => {
+                unchanged += 1;
+                diff.push(json!({
+                    "op": "unchanged",
+                    "left_index": left[l].0,
+                    "right_index": right[r].0,
+                    "text": left[l].1
+                }));
+                index += 1;
+            }
+            _ => {
+                // A run of removals and additions; removed and added paragraphs are paired in
+                // order so each pair also gets a char-level diff.
+                let end = ops[index..]
+                    .iter()
+                    .position(|op| matches!(op, Op::Equal(..)))
+                    .map_or(ops.len(), |offset| index + offset);
+                let run = &ops[index..end];
+                let deleted: Vec<usize> = run
+                    .iter()
+                    .filter_map(|op| match op {
+                        Op::Delete(l) => Some(*l),
+                        _ => None,
+                    })
+                    .collect();
+                let inserted: Vec<usize> = run
+                    .iter()
+                    .filter_map(|op| match op {
+                        Op::Insert(r) => Some(*r),
+                        _ => None,
+                    })
+                    .collect();
+                for &l in &deleted {
+                    removed += 1;
+                    diff.push(json!({
+                        "op": "removed",
+                        "left_index": left[l].0,
+                        "right_index": Value::Null,
+                        "text": left[l].1
+                    }));
+                }
+                for (position, &r) in inserted.iter().enumerate() {
+                    added += 1;
+                    let mut entry = json!({
+                        "op": "added",
+                        "left_index": Value::Null,
+                        "right_index": right[r].0,
+                        "text": right[r].1
+                    });
+                    if let Some(&l) = deleted.get(position)
+                        && let Some(changes) = char_changes(&left[l].1, &right[r].1)
+                    {
+                        entry["replaces_left_index"] = json!(left[l].0);
+                        entry["changes"] = changes;
+                    }
+                    diff.push(entry);
+                }
+                index = end;
+            }
+        }
+    }
+
+    let summary = format!("{added} added, {removed} removed, {unchanged} unchanged paragraphs");
+    json!({
+        "content": [{"type": "text", "text": summary}],
+        "structuredContent": {
+            "diff": diff,
+            "counts": {
+                "added": added,
+                "removed": removed,
+                "unchanged": unchanged
+            },
+            "left": sides.remove("left"),
+            "right": sides.remove("right"),
+            "warnings": warnings
+        },
+        "isError": false
+    })
+}
+
+/// Non-empty paragraphs with their document-wide paragraph index (counted across sections).
+fn paragraph_lines(document: &hwpers::HwpDocument) -> Vec<(usize, String)> {
+    document
+        .sections()
+        .flat_map(|section| section.paragraphs.iter())
+        .enumerate()
+        .filter_map(|(index, paragraph)| {
+            let text = paragraph.text.as_ref()?.content.trim_end().to_string();
+            (!text.trim().is_empty()).then_some((index, text))
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+/// LCS diff of `left` against `right`. The common prefix and suffix are matched up front so the
+/// table only covers the changed middle; `None` when that middle is still too large.
+fn diff_ops<T: PartialEq>(left: &[T], right: &[T]) -> Option<Vec<Op>> {
+    let prefix = left
+        .iter()
+        .zip(right)
+        .take_while(|(left, right)| left == right)
+        .count();
+    let suffix = left[prefix..]
+        .iter()
+        .rev()
+        .zip(right[prefix..].iter().rev())
+        .take_while(|(left, right)| left == right)
+        .count();
+    let left_mid = &left[prefix..left.len() - suffix];
+    let right_mid = &right[prefix..right.len() - suffix];
+    let (n, m) = (left_mid.len(), right_mid.len());
+    if (n + 1).saturating_mul(m + 1) > MAX_LCS_CELLS {
+        return None;
+    }
+
+    // lengths[i][j]: LCS length of left_mid[i..] and right_mid[j..].
+    let width = m + 1;
+    let mut lengths = vec![0u32; (n + 1) * width];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i * width + j] = if left_mid[i] == right_mid[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut ops: Vec<Op> = (0..prefix).map(|index| Op::Equal(index, index)).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && left_mid[i] == right_mid[j] {
+            ops.push(Op::Equal(prefix + i, prefix + j));
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lengths[i * width + j + 1] >= lengths[(i + 1) * width + j]) {
+            ops.push(Op::Insert(prefix + j));
+            j += 1;
+        } else {
+            ops.push(Op::Delete(prefix + i));
+            i += 1;
+        }
+    }
+    ops.extend(
+        (0..suffix)
+            .map(|offset| Op::Equal(left.len() - suffix + offset, right.len() - suffix + offset)),
+    );
+    Some(ops)
+}
+
+/// Char-level diff of a replaced paragraph, as `{op, text}` runs. Works on `char`s so Hangul
+/// syllables and other multi-byte characters are never split.
+fn char_changes(left: &str, right: &str) -> Option<Value> {
+    let left: Vec<char> = left.chars().collect();
+    let right: Vec<char> = right.chars().collect();
+    if left.len() > MAX_INLINE_CHARS || right.len() > MAX_INLINE_CHARS {
+        return None;
+    }
+    let ops = diff_ops(&left, &right)?;
+
+    let mut runs: Vec<(&'static str, String)> = Vec::new();
+    for op in ops {
+        let (kind, ch) = match op {
+            Op::Equal(l, _) => ("unchanged", left[l]),
+            Op::Delete(l) => ("removed", left[l]),
+            Op::Insert(r) => ("added", right[r]),
+        };
+        match runs.last_mut() {
+            Some((last, text)) if *last == kind => text.push(ch),
+            _ => runs.push((kind, ch.to_string())),
+        }
+    }
+    Some(Value::Array(
+        runs.into_iter()
+            .map(|(op, text)| json!({"op": op, "text": text}))
+            .collect(),
+    ))
+}
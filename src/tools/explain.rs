@@ -0,0 +1,436 @@
+use crate::input::{InputFormat, load_input};
+use crate::mcp::contracts::limits;
+use crate::mcp::errors;
+use crate::tools::records::HwpSections;
+use crate::tools::{
+    ToolError, WorkerOutcome, check_arguments, error_result, map_hwp_error, run_with_timeout,
+};
+use hwpers::parser::FileHeader;
+use hwpers::parser::body_text::BodyTextParser;
+use hwpers::parser::doc_info::DocInfoParser;
+use hwpers::reader::CfbReader;
+use hwpers::{HwpError, HwpReader, HwpWriter, HwpxReader, HwpxWriter};
+use serde_json::{Value, json};
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Leading bytes of an HWP 3.x file (Hangul 97 and earlier), which is neither CFB nor ZIP.
+const HWP_V3_SIGNATURE: &[u8] = b"HWP Document File V3";
+
+pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let payload = match load_input(args) {
+        Ok(payload) => payload,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let to_format = match parse_to(args.get("to")) {
+        Ok(to_format) => to_format,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    // The worker records each stage before entering it, so a timeout or panic can still be
+    // pinned to the stage that never finished.
+    let stage = Arc::new(Mutex::new("format detection".to_string()));
+    let worker_stage = Arc::clone(&stage);
+    let bytes = payload.bytes.clone();
+    let format = payload.format;
+    let max_parse_ms = limits().max_parse_ms;
+    let diagnosis = match run_with_timeout(Duration::from_millis(max_parse_ms), move || {
+        diagnose(&bytes, format, to_format, &worker_stage)
+    }) {
+        WorkerOutcome::Finished(diagnosis) => diagnosis,
+        WorkerOutcome::TimedOut => Diagnosis::failed(Failure {
+            stage: current_stage(&stage),
+            kind: errors::TIMEOUT,
+            variant: None,
+            backend_error: format!("parse timed out after {max_parse_ms} ms"),
+            remedy: "the document is very large or malformed in a way that makes the parser \
+                     loop; split it in Hangul or check whether the file is damaged"
+                .to_string(),
+        }),
+        WorkerOutcome::Panicked => Diagnosis::failed(Failure {
+            stage: current_stage(&stage),
+            kind: errors::PARSE_FAILED,
+            variant: None,
+            backend_error: "parser panicked on this input".to_string(),
+            remedy: "the file contains data the parser does not guard against; re-save it in \
+                     Hangul, and report the file if it opens there without problems"
+                .to_string(),
+        }),
+    };
+
+    let detected_format = diagnosis.format.map(|format| format.as_str());
+    let text = match &diagnosis.failure {
+        None => match to_format {
+            Some(to_format) => format!(
+                "no failure: parsed as {} and converted to {}",
+                detected_format.unwrap_or("unknown"),
+                to_format.as_str()
+            ),
+            None => format!(
+                "no failure: parsed as {} ({} sections)",
+                detected_format.unwrap_or("unknown"),
+                diagnosis.sections
+            ),
+        },
+        Some(failure) => format!(
+            "failed at stage '{}': {}\nsuggested remedy: {}",
+            failure.stage, failure.backend_error, failure.remedy
+        ),
+    };
+    let failure = diagnosis.failure.as_ref();
+
+    json!({
+        "content": [{
+            "type": "text",
+            "text": text
+        }],
+        "structuredContent": {
+            "ok": failure.is_none(),
+            "detected_format": detected_format,
+            "to": to_format.map(|to_format| to_format.as_str()),
+            "stages_passed": diagnosis.passed,
+            "stage": failure.map(|f| f.stage.as_str()),
+            "kind": failure.map(|f| f.kind),
+            "error_variant": failure.and_then(|f| f.variant),
+            "backend_error": failure.map(|f| f.backend_error.as_str()),
+            "remedy": failure.map(|f| f.remedy.as_str())
+        },
+        "isError": false
+    })
+}
+
+#[derive(Clone, Copy)]
+enum OutputFormat {
+    Hwp,
+    Hwpx,
+}
+
+impl OutputFormat {
+    fn as_str(&self) -> &'static str {
+        match self {
+            OutputFormat::Hwp => "hwp",
+            OutputFormat::Hwpx => "hwpx",
+        }
+    }
+}
+
+fn parse_to(value: Option<&Value>) -> Result<Option<OutputFormat>, ToolError> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    match value.as_str() {
+        Some("hwp") => Ok(Some(OutputFormat::Hwp)),
+        Some("hwpx") => Ok(Some(OutputFormat::Hwpx)),
+        Some(_) => Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "to must be hwp or hwpx".to_string(),
+        }),
+        None => Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "to must be a string".to_string(),
+        }),
+    }
+}
+
+struct Failure {
+    stage: String,
+    kind: &'static str,
+    variant: Option<&'static str>,
+    backend_error: String,
+    remedy: String,
+}
+
+struct Diagnosis {
+    format: Option<InputFormat>,
+    passed: Vec<String>,
+    sections: usize,
+    failure: Option<Failure>,
+}
+
+impl Diagnosis {
+    fn failed(failure: Failure) -> Self {
+        Diagnosis {
+            format: None,
+            passed: Vec::new(),
+            sections: 0,
+            failure: Some(failure),
+        }
+    }
+}
+
+fn current_stage(stage: &Mutex<String>) -> String {
+    stage
+        .lock()
+        .map(|stage| stage.clone())
+        .unwrap_or_else(|poisoned| poisoned.into_inner().clone())
+}
+
+/// Walks the same stages as the readers and writers, stopping at the first one that fails.
+fn diagnose(
+    bytes: &[u8],
+    format: InputFormat,
+    to_format: Option<OutputFormat>,
+    stage: &Mutex<String>,
+) -> Diagnosis {
+    let mut diagnosis = Diagnosis {
+        format: None,
+        passed: Vec::new(),
+        sections: 0,
+        failure: None,
+    };
+    let enter = |name: &str, passed: &mut Vec<String>| {
+        if let Ok(mut current) = stage.lock() {
+            passed.push(std::mem::replace(&mut *current, name.to_string()));
+        }
+    };
+
+    // `load_input` already read the format from the content; `Auto` means it could not.
+    let resolved = match format {
+        InputFormat::Auto => None,
+        settled => Some(settled),
+    };
+    let Some(resolved) = resolved else {
+        diagnosis.failure = Some(unknown_container(bytes));
+        return diagnosis;
+    };
+    diagnosis.format = Some(resolved);
+
+    let document = match resolved {
+        InputFormat::Hwpx => {
+            enter("hwpx package", &mut diagnosis.passed);
+            match HwpxReader::from_bytes(bytes) {
+                Ok(document) => document,
+                Err(error) => {
+                    let stage = hwpx_stage(&error);
+                    diagnosis.failure = Some(failure(stage, error, resolved));
+                    return diagnosis;
+                }
+            }
+        }
+        _ => match probe_hwp(bytes, &mut |name| enter(name, &mut diagnosis.passed)) {
+            Ok(document) => document,
+            Err((stage, error)) => {
+                diagnosis.failure = Some(failure(stage, error, resolved));
+                return diagnosis;
+            }
+        },
+    };
+    diagnosis.sections = document.sections().count();
+
+    if let Some(to_format) = to_format {
+        let stage_name = format!("convert to {}", to_format.as_str());
+        enter(&stage_name, &mut diagnosis.passed);
+        let written = match to_format {
+            OutputFormat::Hwp => HwpWriter::from_document(document).to_bytes(),
+            OutputFormat::Hwpx => HwpxWriter::from_document(document).to_bytes(),
+        };
+        if let Err(error) = written {
+            diagnosis.failure = Some(failure(stage_name, error, resolved));
+            return diagnosis;
+        }
+    }
+
+    enter("done", &mut diagnosis.passed);
+    diagnosis
+}
+
+/// Replays `HwpReader::from_bytes` one stream at a time so a failure names the stream it
+/// came from. The full reader still runs last, since it is the one tools actually use.
+fn probe_hwp(
+    bytes: &[u8],
+    enter: &mut dyn FnMut(&str),
+) -> Result<hwpers::HwpDocument, (String, HwpError)> {
+    let at = |stage: &str| {
+        let stage = stage.to_string();
+        move |error: HwpError| (stage, error)
+    };
+
+    enter("container open");
+    let mut reader = CfbReader::new(Cursor::new(bytes)).map_err(at("container open"))?;
+
+    enter("file header");
+    let header = reader
+        .read_stream("FileHeader")
+        .and_then(FileHeader::parse)
+        .map_err(at("file header"))?;
+    if header.is_encrypted() {
+        return Err((
+            "file header".to_string(),
+            HwpError::UnsupportedVersion(
+                "Password-encrypted documents are not supported".to_string(),
+            ),
+        ));
+    }
+
+    if header.is_distribute() {
+        // Distribution streams are encrypted; only the reader itself can decrypt them.
+        enter("distribution decrypt");
+        return HwpReader::from_bytes(bytes).map_err(at("distribution decrypt"));
+    }
+
+    enter("doc info");
+    reader
+        .read_stream("DocInfo")
+        .and_then(|data| DocInfoParser::parse(data, header.is_compressed()))
+        .map_err(at("doc info"))?;
+
+    let mut sections = HwpSections::open(bytes)
+        .map_err(|message| ("section 0".to_string(), HwpError::Cfb(message)))?;
+    let mut section_index = 0;
+    while let Some(data) = sections.next_stream() {
+        let stage = format!("section {section_index}");
+        enter(&stage);
+        data.map_err(HwpError::CompressionError)
+            .and_then(|data| BodyTextParser::parse(data, false))
+            .map_err(at(&stage))?;
+        section_index += 1;
+    }
+
+    enter("document assembly");
+    HwpReader::from_bytes(bytes).map_err(at("document assembly"))
+}
+
+/// The HWPX reader does not expose its stages, but each error names the package part.
+fn hwpx_stage(error: &HwpError) -> String {
+    let message = error.to_string();
+    if message.contains("Invalid HWPX archive") {
+        return "container open".to_string();
+    }
+    if message.contains("No section files") {
+        return "section 0".to_string();
+    }
+    if let Some(start) = message.find("Contents/section") {
+        let digits: String = message[start + "Contents/section".len()..]
+            .chars()
+            .take_while(|ch| ch.is_ascii_digit())
+            .collect();
+        if !digits.is_empty() {
+            return format!("section {digits}");
+        }
+    }
+    for part in ["version.xml", "header.xml"] {
+        if message.contains(part) {
+            return part.to_string();
+        }
+    }
+    "hwpx package".to_string()
+}
+
+fn unknown_container(bytes: &[u8]) -> Failure {
+    if bytes.starts_with(HWP_V3_SIGNATURE) {
+        return Failure {
+            stage: "format detection".to_string(),
+            kind: errors::UNSUPPORTED_FORMAT,
+            variant: None,
+            backend_error: "file starts with the HWP 3.x signature, not an OLE compound file \
+                            or ZIP package"
+                .to_string(),
+            remedy: "file may be HWP v3 (Hangul 97 or earlier); open it in Hangul and save it \
+                     as HWP 5.0 or HWPX"
+                .to_string(),
+        };
+    }
+    Failure {
+        stage: "format detection".to_string(),
+        kind: errors::UNSUPPORTED_FORMAT,
+        variant: None,
+        backend_error: "no OLE compound file (HWP) or ZIP (HWPX) signature".to_string(),
+        remedy: "check that the file is an HWP/HWPX document and was not truncated; pass \
+                 format explicitly if the signature is known to be damaged"
+            .to_string(),
+    }
+}
+
+fn failure(stage: String, error: HwpError, format: InputFormat) -> Failure {
+    let variant = variant_name(&error);
+    let remedy = remedy(&stage, &error, format);
+    let mapped = map_hwp_error(error);
+    Failure {
+        stage,
+        kind: mapped.kind,
+        variant: Some(variant),
+        backend_error: mapped.message,
+        remedy,
+    }
+}
+
+fn variant_name(error: &HwpError) -> &'static str {
+    match error {
+        HwpError::Io(_) => "Io",
+        HwpError::InvalidFormat(_) => "InvalidFormat",
+        HwpError::UnsupportedVersion(_) => "UnsupportedVersion",
+        HwpError::Cfb(_) => "Cfb",
+        HwpError::CompressionError(_) => "CompressionError",
+        HwpError::ParseError(_) => "ParseError",
+        HwpError::EncodingError(_) => "EncodingError",
+        HwpError::NotFound(_) => "NotFound",
+        HwpError::InvalidInput(_) => "InvalidInput",
+    }
+}
+
+fn remedy(stage: &str, error: &HwpError, format: InputFormat) -> String {
+    if let Some(target) = stage.strip_prefix("convert to ") {
+        let other = if target == "hwp" { "hwpx" } else { "hwp" };
+        return format!(
+            "the document was read but could not be written as {target}; try converting to \
+             {other} instead"
+        );
+    }
+    if let Some(section) = stage.strip_prefix("section ") {
+        return format!(
+            "section {section} contains content the parser cannot read; re-saving the file in \
+             a recent Hangul release often normalizes it"
+        );
+    }
+    match error {
+        HwpError::UnsupportedVersion(message) if message.contains("Password-encrypted") => {
+            "document is password-protected; remove the password in Hangul and save it again"
+                .to_string()
+        }
+        HwpError::UnsupportedVersion(_) => {
+            "document uses an HWP version the parser does not read; save it as HWP 5.0 or HWPX \
+             in a current Hangul release"
+                .to_string()
+        }
+        HwpError::Cfb(_) => "the OLE compound container is damaged or truncated; re-download \
+                             the file or re-save it in Hangul"
+            .to_string(),
+        HwpError::CompressionError(_) => {
+            "a compressed stream failed to inflate; the file is probably truncated or corrupted"
+                .to_string()
+        }
+        HwpError::Io(_) => "the input could not be read completely; check that the file is not \
+                            truncated"
+            .to_string(),
+        _ => match (format, stage) {
+            (_, "distribution decrypt") => {
+                "distribution document (배포용 문서) could not be decrypted; ask the author for \
+                 an editable copy"
+                    .to_string()
+            }
+            (InputFormat::Hwpx, "container open") => {
+                "file is not a valid ZIP package; it may be truncated, or an HWP file with an \
+                 .hwpx name (try format \"hwp\")"
+                    .to_string()
+            }
+            (InputFormat::Hwpx, _) => format!(
+                "HWPX package part {stage} is missing or malformed; the file may be incomplete \
+                 or written by a tool that does not follow OWPML"
+            ),
+            (_, "file header") => "FileHeader is missing or malformed; the file may be HWP v3 \
+                                   or not an HWP document at all"
+                .to_string(),
+            (_, "doc info") => "DocInfo (fonts, styles and other settings) is missing or \
+                                malformed; re-save the file in Hangul"
+                .to_string(),
+            _ => "re-save the file in Hangul; if it opens there, report it with the backend error"
+                .to_string(),
+        },
+    }
+}
@@ -0,0 +1,378 @@
+use crate::input::load_input;
+use crate::mcp::errors;
+use crate::tools::{ToolError, check_arguments, error_result, parse_document};
+use serde_json::{Value, json};
+
+const MONTHS: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let payload = match load_input(args) {
+        Ok(payload) => payload,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let date_order = match DateOrder::parse(args.get("date_order")) {
+        Ok(date_order) => date_order,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let parsed = match parse_document(&payload.bytes, payload.format) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return error_result(err.kind, err.message, Some(payload.source.as_str()));
+        }
+    };
+
+    let mut dates = Vec::new();
+    for (section_index, section) in parsed.document.sections().enumerate() {
+        for (paragraph_index, paragraph) in section.paragraphs.iter().enumerate() {
+            let Some(text) = &paragraph.text else {
+                continue;
+            };
+            for found in find_dates(&text.content, date_order) {
+                dates.push(json!({
+                    "raw": found.raw,
+                    "iso": found.iso,
+                    "section_index": section_index,
+                    "paragraph_index": paragraph_index
+                }));
+            }
+        }
+    }
+
+    let summary = match dates.len() {
+        1 => "found 1 date".to_string(),
+        count => format!("found {count} dates"),
+    };
+
+    json!({
+        "content": [{"type": "text", "text": summary}],
+        "structuredContent": {
+            "format": parsed.format.as_str(),
+            "detected_format": parsed.format.as_str(),
+            "detection_method": payload.detection_method,
+            "date_order": date_order.as_str(),
+            "dates": dates,
+            "warnings": parsed.warnings
+        },
+        "isError": false
+    })
+}
+
+/// How to read all-numeric dates with the year last (`03/05/2024`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum DateOrder {
+    Mdy,
+    Dmy,
+}
+
+impl DateOrder {
+    fn parse(value: Option<&Value>) -> Result<Self, ToolError> {
+        match value.map(|v| v.as_str()) {
+            None | Some(Some("mdy")) => Ok(DateOrder::Mdy),
+            Some(Some("dmy")) => Ok(DateOrder::Dmy),
+            Some(_) => Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "date_order must be mdy or dmy".to_string(),
+            }),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            DateOrder::Mdy => "mdy",
+            DateOrder::Dmy => "dmy",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct FoundDate {
+    raw: String,
+    iso: String,
+}
+
+/// Scans `text` for full calendar dates: `2024년 3월 5일`, `2024-03-05`, `2024. 3. 5.`,
+/// `2024/03/05`, `03/05/2024` (read with `order`), `March 5, 2024` and `5 March 2024`.
+fn find_dates(text: &str, order: DateOrder) -> Vec<FoundDate> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut found = Vec::new();
+    let mut pos = 0;
+    while pos < chars.len() {
+        let boundary = pos == 0 || !chars[pos - 1].is_alphanumeric();
+        let matched = if boundary {
+            match_date(&chars, pos, order)
+        } else {
+            None
+        };
+        match matched {
+            Some((end, (year, month, day))) => {
+                found.push(FoundDate {
+                    raw: chars[pos..end].iter().collect(),
+                    iso: format!("{year:04}-{month:02}-{day:02}"),
+                });
+                pos = end;
+            }
+            None => pos += 1,
+        }
+    }
+    found
+}
+
+type Ymd = (u32, u32, u32);
+
+fn match_date(chars: &[char], start: usize, order: DateOrder) -> Option<(usize, Ymd)> {
+    if chars[start].is_ascii_digit() {
+        match_year_first(chars, start)
+            .or_else(|| match_year_last(chars, start, order))
+            .or_else(|| match_day_month_name(chars, start))
+    } else {
+        match_month_name_first(chars, start)
+    }
+}
+
+/// `2024년 3월 5일`, `2024-03-05`, `2024/3/5`, `2024.03.05`, `2024. 3. 5.`
+fn match_year_first(chars: &[char], start: usize) -> Option<(usize, Ymd)> {
+    let mut pos = start;
+    let year = number(chars, &mut pos, 4, 4)?;
+    let after_year = pos;
+
+    skip_spaces(chars, &mut pos);
+    if chars.get(pos) == Some(&'년') {
+        pos += 1;
+        skip_spaces(chars, &mut pos);
+        let month = number(chars, &mut pos, 1, 2)?;
+        skip_spaces(chars, &mut pos);
+        expect(chars, &mut pos, '월')?;
+        skip_spaces(chars, &mut pos);
+        let day = number(chars, &mut pos, 1, 2)?;
+        skip_spaces(chars, &mut pos);
+        expect(chars, &mut pos, '일')?;
+        return valid(year, month, day).then_some((pos, (year, month, day)));
+    }
+
+    pos = after_year;
+    let separator = *chars.get(pos).filter(|ch| matches!(ch, '-' | '/' | '.'))?;
+    pos += 1;
+    let dotted = separator == '.';
+    if dotted {
+        skip_spaces(chars, &mut pos);
+    }
+    let month = number(chars, &mut pos, 1, 2)?;
+    expect(chars, &mut pos, separator)?;
+    if dotted {
+        skip_spaces(chars, &mut pos);
+    }
+    let day = number(chars, &mut pos, 1, 2)?;
+    if dotted && chars.get(pos) == Some(&'.') {
+        pos += 1;
+    }
+    valid(year, month, day).then_some((pos, (year, month, day)))
+}
+
+/// `03/05/2024`, `05-03-2024`, `5.3.2024`; month/day order comes from `order` unless only
+/// one reading is a valid date.
+fn match_year_last(chars: &[char], start: usize, order: DateOrder) -> Option<(usize, Ymd)> {
+    let mut pos = start;
+    let first = number(chars, &mut pos, 1, 2)?;
+    let separator = *chars.get(pos).filter(|ch| matches!(ch, '-' | '/' | '.'))?;
+    pos += 1;
+    let second = number(chars, &mut pos, 1, 2)?;
+    expect(chars, &mut pos, separator)?;
+    let year = number(chars, &mut pos, 4, 4)?;
+
+    let (preferred, other) = match order {
+        DateOrder::Mdy => ((first, second), (second, first)),
+        DateOrder::Dmy => ((second, first), (first, second)),
+    };
+    [preferred, other]
+        .into_iter()
+        .find(|(month, day)| valid(year, *month, *day))
+        .map(|(month, day)| (pos, (year, month, day)))
+}
+
+/// `5 March 2024`, `5 Mar. 2024`
+fn match_day_month_name(chars: &[char], start: usize) -> Option<(usize, Ymd)> {
+    let mut pos = start;
+    let day = number(chars, &mut pos, 1, 2)?;
+    if !skip_spaces(chars, &mut pos) {
+        return None;
+    }
+    let month = month_name(chars, &mut pos)?;
+    skip_spaces(chars, &mut pos);
+    if chars.get(pos) == Some(&',') {
+        pos += 1;
+        skip_spaces(chars, &mut pos);
+    }
+    let year = number(chars, &mut pos, 4, 4)?;
+    valid(year, month, day).then_some((pos, (year, month, day)))
+}
+
+/// `March 5, 2024`, `Mar. 5th 2024`
+fn match_month_name_first(chars: &[char], start: usize) -> Option<(usize, Ymd)> {
+    let mut pos = start;
+    let month = month_name(chars, &mut pos)?;
+    if !skip_spaces(chars, &mut pos) {
+        return None;
+    }
+    let day = number(chars, &mut pos, 1, 2)?;
+    for suffix in ["st", "nd", "rd", "th"] {
+        if starts_with_ignore_case(chars, pos, suffix) {
+            pos += suffix.len();
+            break;
+        }
+    }
+    if chars.get(pos) == Some(&',') {
+        pos += 1;
+    }
+    if !skip_spaces(chars, &mut pos) && chars.get(pos - 1) != Some(&',') {
+        return None;
+    }
+    let year = number(chars, &mut pos, 4, 4)?;
+    valid(year, month, day).then_some((pos, (year, month, day)))
+}
+
+/// A full English month name or its three-letter abbreviation (`Sept` too), optionally
+/// followed by `.`; the word must end there.
+fn month_name(chars: &[char], pos: &mut usize) -> Option<u32> {
+    let word_end = chars[*pos..]
+        .iter()
+        .position(|ch| !ch.is_ascii_alphabetic())
+        .map_or(chars.len(), |len| *pos + len);
+    let word: String = chars[*pos..word_end]
+        .iter()
+        .map(|ch| ch.to_ascii_lowercase())
+        .collect();
+    if word.len() < 3 {
+        return None;
+    }
+    let index = MONTHS
+        .iter()
+        .position(|name| *name == word || (word.len() == 3 && name.starts_with(&word)))
+        .or_else(|| (word == "sept").then_some(8))?;
+    *pos = word_end;
+    if word.len() < MONTHS[index].len() && chars.get(*pos) == Some(&'.') {
+        *pos += 1;
+    }
+    Some(index as u32 + 1)
+}
+
+/// Reads `min..=max` ASCII digits that are not followed by another digit.
+fn number(chars: &[char], pos: &mut usize, min: usize, max: usize) -> Option<u32> {
+    let len = chars[*pos..]
+        .iter()
+        .take_while(|ch| ch.is_ascii_digit())
+        .count();
+    if len < min || len > max {
+        return None;
+    }
+    let value = chars[*pos..*pos + len]
+        .iter()
+        .fold(0, |acc, ch| acc * 10 + ch.to_digit(10).unwrap_or(0));
+    *pos += len;
+    Some(value)
+}
+
+fn expect(chars: &[char], pos: &mut usize, expected: char) -> Option<()> {
+    (chars.get(*pos) == Some(&expected)).then(|| *pos += 1)
+}
+
+/// Skips spaces and tabs; returns whether any were skipped.
+fn skip_spaces(chars: &[char], pos: &mut usize) -> bool {
+    let start = *pos;
+    while chars.get(*pos).is_some_and(|ch| *ch == ' ' || *ch == '\t') {
+        *pos += 1;
+    }
+    *pos > start
+}
+
+fn starts_with_ignore_case(chars: &[char], pos: usize, prefix: &str) -> bool {
+    prefix.chars().enumerate().all(|(offset, expected)| {
+        chars
+            .get(pos + offset)
+            .is_some_and(|ch| ch.eq_ignore_ascii_case(&expected))
+    }) && chars
+        .get(pos + prefix.len())
+        .is_none_or(|ch| !ch.is_alphanumeric())
+}
+
+fn valid(year: u32, month: u32, day: u32) -> bool {
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        _ => return false,
+    };
+    year > 0 && (1..=days).contains(&day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DateOrder, find_dates};
+
+    fn isos(text: &str, order: DateOrder) -> Vec<(String, String)> {
+        find_dates(text, order)
+            .into_iter()
+            .map(|found| (found.raw, found.iso))
+            .collect()
+    }
+
+    #[test]
+    fn finds_korean_numeric_and_english_dates() {
+        let text = "계약일 2024년 3월 5일, 만료 2025. 3. 4. 갱신 2024-12-31 / March 5, 2024 / 1st: 5 Sept 2023";
+        let found = isos(text, DateOrder::Mdy);
+        let expected = [
+            ("2024년 3월 5일", "2024-03-05"),
+            ("2025. 3. 4.", "2025-03-04"),
+            ("2024-12-31", "2024-12-31"),
+            ("March 5, 2024", "2024-03-05"),
+            ("5 Sept 2023", "2023-09-05"),
+        ];
+        assert_eq!(
+            found,
+            expected
+                .iter()
+                .map(|(raw, iso)| (raw.to_string(), iso.to_string()))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn year_last_dates_follow_date_order_unless_unambiguous() {
+        assert_eq!(isos("03/05/2024", DateOrder::Mdy)[0].1, "2024-03-05");
+        assert_eq!(isos("03/05/2024", DateOrder::Dmy)[0].1, "2024-05-03");
+        assert_eq!(isos("25/12/2024", DateOrder::Mdy)[0].1, "2024-12-25");
+    }
+
+    #[test]
+    fn rejects_invalid_and_embedded_numbers() {
+        assert!(
+            isos(
+                "2023-02-29 12024-01-01 2024-01-011 v2024-01-01",
+                DateOrder::Mdy
+            )
+            .is_empty()
+        );
+        assert_eq!(isos("2024-02-29", DateOrder::Mdy)[0].1, "2024-02-29");
+    }
+}
@@ -0,0 +1,283 @@
+use crate::input::{InputFormat, load_input};
+use crate::mcp::errors;
+use crate::tools::extract_rich::{ImageRenderContext, attach_image_data, mime_from_extension};
+use crate::tools::{Compress, ToolError, check_arguments, error_result, parse_document};
+use hwpers::HwpDocument;
+use hwpers::model::bin_data::BinData;
+use hwpers::parser::FileHeader;
+use hwpers::reader::CfbReader;
+use hwpers::utils::compression::decompress_stream;
+use serde_json::{Value, json};
+use std::io::{Cursor, Read};
+
+/// HWP BinData property bit for compressed storage data.
+const BIN_DATA_COMPRESSED: u16 = 0x04;
+
+pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let payload = match load_input(args) {
+        Ok(payload) => payload,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let output = match OutputMode::parse(args.get("output")) {
+        Ok(output) => output,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let compress = match Compress::parse(args.get("compress")) {
+        Ok(compress) => compress,
+        Err(message) => return error_result(errors::INVALID_INPUT, message, None),
+    };
+
+    let min_bytes = match args.get("min_bytes") {
+        None => 0,
+        Some(value) => match value.as_u64() {
+            Some(min_bytes) => min_bytes,
+            None => {
+                return error_result(
+                    errors::INVALID_INPUT,
+                    "min_bytes must be a non-negative integer",
+                    None,
+                );
+            }
+        },
+    };
+
+    let output_dir = match args.get("output_dir") {
+        None => None,
+        Some(value) => match value.as_str() {
+            Some(dir) if !dir.trim().is_empty() => Some(dir.to_string()),
+            _ => {
+                return error_result(
+                    errors::INVALID_INPUT,
+                    "output_dir must be a non-empty string",
+                    None,
+                );
+            }
+        },
+    };
+
+    let parsed = match parse_document(&payload.bytes, payload.format) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return error_result(err.kind, err.message, Some(payload.source.as_str()));
+        }
+    };
+
+    let mut warnings = parsed.warnings;
+    let mut total_inline_image_bytes: u64 = 0;
+    let mut images = Vec::new();
+    let mut skipped: u64 = 0;
+    let bins = match document_images(&parsed.document, parsed.format, &payload.bytes) {
+        Ok(bins) => bins,
+        Err(err) => {
+            warnings.push(format!("failed to read embedded images: {err}"));
+            Vec::new()
+        }
+    };
+
+    for bin in &bins {
+        let bin_id = bin.bin_id;
+        let bytes = match bin.get_data() {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                warnings.push(format!("failed to load image data bin_id={bin_id}: {err}"));
+                continue;
+            }
+        };
+        let bytes_len = bytes.len() as u64;
+        if bytes_len < min_bytes {
+            skipped += 1;
+            continue;
+        }
+
+        let mut image = json!({
+            "bin_id": bin_id,
+            "extension": bin.extension,
+            "mimeType": mime_from_extension(&bin.extension),
+            "bytes_len": bytes_len
+        });
+        let mut ctx = ImageRenderContext {
+            images_mode: output.as_str(),
+            max_image_bytes: 0,
+            total_inline_image_bytes: &mut total_inline_image_bytes,
+            source: &payload.source,
+            warnings: &mut warnings,
+            output_path: &output_dir,
+            compress,
+        };
+        if let Err(result) = attach_image_data(bin, &bytes, &mut image, &mut ctx) {
+            return result;
+        }
+        images.push(image);
+    }
+
+    let text = match (output, images.len()) {
+        (OutputMode::Resource, count) if count > 0 => {
+            format!("extracted {count} images to files")
+        }
+        (_, count) => format!("extracted {count} images"),
+    };
+
+    let mut result = json!({
+        "content": [{ "type": "text", "text": text }],
+        "structuredContent": {
+            "format": parsed.format.as_str(),
+            "detected_format": parsed.format.as_str(),
+            "detection_method": payload.detection_method,
+            "output": output.as_str(),
+            "count": images.len(),
+            "skipped": skipped,
+            "images": images,
+            "warnings": warnings
+        },
+        "isError": false
+    });
+    if matches!(output, OutputMode::Inline)
+        && let Some(encoding) = compress.encoding()
+    {
+        result["structuredContent"]["encoding"] = json!(encoding);
+    }
+    result
+}
+
+/// The document's embedded images: the ones the parser loaded, or, since the hwpers readers do
+/// not load BinData payloads for real files, the ones read from the container in `bytes`.
+pub(crate) fn document_images(
+    document: &HwpDocument,
+    format: InputFormat,
+    bytes: &[u8],
+) -> Result<Vec<BinData>, String> {
+    let loaded: Vec<BinData> = document
+        .get_images()
+        .into_iter()
+        .filter(|bin| !bin.data.is_empty())
+        .cloned()
+        .collect();
+    if !loaded.is_empty() {
+        return Ok(loaded);
+    }
+    match format {
+        InputFormat::Hwpx => hwpx_container_images(bytes),
+        _ => hwp_container_images(bytes),
+    }
+}
+
+/// Image streams under the HWP `BinData` storage, named `BIN{id:04X}.{ext}`.
+fn hwp_container_images(bytes: &[u8]) -> Result<Vec<BinData>, String> {
+    let mut reader = CfbReader::new(Cursor::new(bytes)).map_err(|err| err.to_string())?;
+    let header = reader
+        .read_stream("FileHeader")
+        .and_then(FileHeader::parse)
+        .map_err(|err| err.to_string())?;
+
+    let mut bins = Vec::new();
+    for stream in reader.list_streams() {
+        let Some(name) = stream.trim_start_matches('/').strip_prefix("BinData/") else {
+            continue;
+        };
+        let Some((stem, extension)) = name.rsplit_once('.') else {
+            continue;
+        };
+        let Some(bin_id) = stem
+            .strip_prefix("BIN")
+            .and_then(|id| u16::from_str_radix(id, 16).ok())
+        else {
+            continue;
+        };
+        let data = reader.read_stream(&stream).map_err(|err| err.to_string())?;
+        // Compressed documents deflate their storage streams too.
+        let properties = if header.is_compressed() && decompress_stream(&data).is_ok() {
+            BIN_DATA_COMPRESSED
+        } else {
+            0
+        };
+        let bin = BinData {
+            properties,
+            abs_name: String::new(),
+            rel_name: String::new(),
+            bin_id,
+            extension: extension.to_ascii_lowercase(),
+            data,
+        };
+        if bin.is_image() {
+            bins.push(bin);
+        }
+    }
+    bins.sort_by_key(|bin| bin.bin_id);
+    Ok(bins)
+}
+
+/// Image entries under `BinData/` in the HWPX package, numbered in name order.
+fn hwpx_container_images(bytes: &[u8]) -> Result<Vec<BinData>, String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).map_err(|err| err.to_string())?;
+    let mut names: Vec<String> = archive
+        .file_names()
+        .filter(|name| name.starts_with("BinData/"))
+        .map(|name| name.to_string())
+        .collect();
+    names.sort();
+
+    let mut bins = Vec::new();
+    for name in names {
+        let Some((_, extension)) = name.rsplit_once('.') else {
+            continue;
+        };
+        let mut data = Vec::new();
+        archive
+            .by_name(&name)
+            .and_then(|mut file| file.read_to_end(&mut data).map_err(Into::into))
+            .map_err(|err| format!("{name}: {err}"))?;
+        let bin = BinData {
+            properties: 0,
+            abs_name: String::new(),
+            rel_name: name.clone(),
+            bin_id: bins.len() as u16 + 1,
+            extension: extension.to_ascii_lowercase(),
+            data,
+        };
+        if bin.is_image() {
+            bins.push(bin);
+        }
+    }
+    Ok(bins)
+}
+
+#[derive(Clone, Copy)]
+enum OutputMode {
+    Inline,
+    Resource,
+}
+
+impl OutputMode {
+    fn parse(value: Option<&Value>) -> Result<Self, ToolError> {
+        let Some(value) = value else {
+            return Ok(OutputMode::Inline);
+        };
+        let Some(value) = value.as_str() else {
+            return Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "output must be a string".to_string(),
+            });
+        };
+        match value {
+            "inline" => Ok(OutputMode::Inline),
+            "resource" => Ok(OutputMode::Resource),
+            _ => Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "output must be inline or resource".to_string(),
+            }),
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            OutputMode::Inline => "inline",
+            OutputMode::Resource => "resource",
+        }
+    }
+}
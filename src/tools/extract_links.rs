@@ -0,0 +1,371 @@
+use crate::input::{InputFormat, load_input};
+use crate::tools::records::{
+    HWPTAG_CTRL_HEADER, HWPTAG_PARA_HEADER, HWPTAG_PARA_TEXT, HwpSections,
+};
+use crate::tools::{check_arguments, error_result, parse_document};
+use hwpers::parser::record::Record;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+
+/// `%hlk` (hyperlink field) as stored in a little-endian control id.
+const HYPERLINK_CTRL_ID: u32 = u32::from_le_bytes(*b"klh%");
+const FIELD_START: u16 = 0x03;
+const FIELD_END: u16 = 0x04;
+
+pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let payload = match load_input(args) {
+        Ok(payload) => payload,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let parsed = match parse_document(&payload.bytes, payload.format) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return error_result(err.kind, err.message, Some(payload.source.as_str()));
+        }
+    };
+
+    // hwpers drops hyperlink fields while parsing, so the links are read from the raw
+    // container; parsing above still validates the input and settles the format.
+    let mut warnings = parsed.warnings;
+    let links = if parsed.format == InputFormat::Hwpx {
+        hwpx_links(&payload.bytes)
+    } else if parsed.document.is_distribution_document() {
+        warnings.push(
+            "distribution document (배포용 문서): encrypted body is not scanned for links"
+                .to_string(),
+        );
+        Ok(Vec::new())
+    } else {
+        hwp_links(&payload.bytes)
+    };
+    let links = match links {
+        Ok(links) => links,
+        Err(message) => {
+            warnings.push(format!("link scan failed: {message}"));
+            Vec::new()
+        }
+    };
+
+    let summary = format!("found {} links", links.len());
+    let links: Vec<Value> = links
+        .into_iter()
+        .map(|link| {
+            json!({
+                "section_index": link.section_index,
+                "paragraph_index": link.paragraph_index,
+                "text": link.text,
+                "target": link.target,
+                "kind": link_kind(&link.target)
+            })
+        })
+        .collect();
+
+    json!({
+        "content": [{"type": "text", "text": summary}],
+        "structuredContent": {
+            "format": parsed.format.as_str(),
+            "detected_format": parsed.format.as_str(),
+            "detection_method": payload.detection_method,
+            "links": links,
+            "warnings": warnings
+        },
+        "isError": false
+    })
+}
+
+struct Link {
+    section_index: usize,
+    paragraph_index: usize,
+    text: String,
+    target: String,
+}
+
+fn link_kind(target: &str) -> &'static str {
+    let lower = target.to_ascii_lowercase();
+    if lower.starts_with("mailto:") {
+        "email"
+    } else if lower.starts_with("http://")
+        || lower.starts_with("https://")
+        || lower.starts_with("ftp://")
+    {
+        "url"
+    } else if target.starts_with('#') || target.starts_with('?') {
+        "bookmark"
+    } else {
+        "file"
+    }
+}
+
+/// Hyperlink field commands look like `https\://example.com;1;0;0;`: the target is the first
+/// `;`-separated part, with `\` escaping `:`, `;` and `\`.
+fn command_target(command: &str) -> String {
+    let mut target = String::new();
+    let mut chars = command.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    target.push(next);
+                }
+            }
+            ';' => break,
+            _ => target.push(ch),
+        }
+    }
+    target
+}
+
+/// Per-paragraph hyperlink pieces: anchor texts from PARA_TEXT and commands from the
+/// paragraph's `%hlk` control headers, both in document order.
+#[derive(Default)]
+struct HwpParagraphLinks {
+    texts: Vec<String>,
+    commands: Vec<String>,
+}
+
+fn hwp_links(bytes: &[u8]) -> Result<Vec<Link>, String> {
+    let mut links = Vec::new();
+    for (section_index, records) in HwpSections::open(bytes)?.enumerate() {
+        collect_hwp_section_links(section_index, records?, &mut links);
+    }
+    Ok(links)
+}
+
+fn collect_hwp_section_links(section_index: usize, records: Vec<Record>, links: &mut Vec<Link>) {
+    let mut paragraphs: Vec<HwpParagraphLinks> = Vec::new();
+    // Paragraph owning records at each nesting level; controls sit one level below it.
+    let mut owner_at_level: HashMap<u8, usize> = HashMap::new();
+
+    for record in records {
+        let level = record.header.level;
+        match record.tag_id() {
+            HWPTAG_PARA_HEADER => {
+                owner_at_level.insert(level, paragraphs.len());
+                paragraphs.push(HwpParagraphLinks::default());
+            }
+            HWPTAG_PARA_TEXT => {
+                if let Some(paragraph) = level
+                    .checked_sub(1)
+                    .and_then(|parent| owner_at_level.get(&parent))
+                    .and_then(|idx| paragraphs.get_mut(*idx))
+                {
+                    paragraph.texts.extend(field_texts(&record.data));
+                }
+            }
+            HWPTAG_CTRL_HEADER if record.data.len() >= 4 => {
+                let ctrl_id = u32::from_le_bytes([
+                    record.data[0],
+                    record.data[1],
+                    record.data[2],
+                    record.data[3],
+                ]);
+                if ctrl_id != HYPERLINK_CTRL_ID {
+                    continue;
+                }
+                let owner = level
+                    .checked_sub(1)
+                    .and_then(|parent| owner_at_level.get(&parent))
+                    .and_then(|idx| paragraphs.get_mut(*idx));
+                if let (Some(paragraph), Some(command)) = (owner, field_command(&record.data)) {
+                    paragraph.commands.push(command);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for (ordinal, paragraph) in paragraphs.into_iter().enumerate() {
+        // hwpers reads the first paragraph header of a section as the section definition, so
+        // indices shift down by one to line up with the other tools' paragraph_index.
+        let paragraph_index = ordinal.saturating_sub(1);
+        let mut texts = paragraph.texts.into_iter();
+        for command in paragraph.commands {
+            links.push(Link {
+                section_index,
+                paragraph_index,
+                text: texts.next().unwrap_or_default(),
+                target: command_target(&command),
+            });
+        }
+    }
+}
+
+/// Field control header: ctrl id (u32), properties (u32), extra properties (u8), then the
+/// command as a length-prefixed UTF-16 string.
+fn field_command(data: &[u8]) -> Option<String> {
+    let len = usize::from(u16::from_le_bytes([*data.get(9)?, *data.get(10)?]));
+    let raw = data.get(11..11 + len * 2)?;
+    let units: Vec<u16> = raw
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect();
+    Some(String::from_utf16_lossy(&units))
+}
+
+/// Returns the text between each `%hlk` field start and the next field end in a PARA_TEXT
+/// record. Inline and extended controls occupy eight code units; the first two after the
+/// code hold the control id.
+fn field_texts(data: &[u8]) -> Vec<String> {
+    let units: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect();
+    let mut texts = Vec::new();
+    let mut current: Option<Vec<u16>> = None;
+    let mut i = 0;
+    while i < units.len() {
+        let unit = units[i];
+        if unit < 32 {
+            let is_char_control = matches!(unit, 0 | 10 | 13 | 24..=31);
+            if unit == FIELD_START && i + 2 < units.len() {
+                let ctrl_id = u32::from(units[i + 1]) | (u32::from(units[i + 2]) << 16);
+                if ctrl_id == HYPERLINK_CTRL_ID {
+                    current = Some(Vec::new());
+                }
+            } else if unit == FIELD_END
+                && let Some(text) = current.take()
+            {
+                texts.push(String::from_utf16_lossy(&text));
+            }
+            i += if is_char_control { 1 } else { 8 };
+            continue;
+        }
+        if let Some(text) = current.as_mut() {
+            text.push(unit);
+        }
+        i += 1;
+    }
+    if let Some(text) = current {
+        texts.push(String::from_utf16_lossy(&text));
+    }
+    texts
+}
+
+fn hwpx_links(bytes: &[u8]) -> Result<Vec<Link>, String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).map_err(|err| err.to_string())?;
+    let mut links = Vec::new();
+    let mut section_index = 0;
+    loop {
+        let name = format!("Contents/section{section_index}.xml");
+        let Ok(mut file) = archive.by_name(&name) else {
+            break;
+        };
+        let mut xml = String::new();
+        file.read_to_string(&mut xml)
+            .map_err(|err| format!("{name}: {err}"))?;
+        collect_hwpx_section_links(section_index, &xml, &mut links);
+        section_index += 1;
+    }
+    Ok(links)
+}
+
+/// Walks the section XML tag by tag. Links come either from `<hp:hyperlink url=".."/>`
+/// controls (anchor text = rest of the run) or from `HYPERLINK` fields
+/// (`<hp:fieldBegin type="HYPERLINK">` with a `Command` parameter, text up to `<hp:fieldEnd>`).
+fn collect_hwpx_section_links(section_index: usize, xml: &str, links: &mut Vec<Link>) {
+    let mut paragraph_index: Option<usize> = None;
+    let mut paragraph_count = 0usize;
+    let mut open: Option<(Link, &'static str)> = None;
+    let mut field_command: Option<String> = None;
+    let mut in_command = false;
+    let mut in_text = false;
+
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        let text = &rest[..start];
+        if !text.is_empty() {
+            if in_command && let Some(command) = field_command.as_mut() {
+                command.push_str(&unescape_xml(text));
+            } else if in_text && let Some((link, _)) = open.as_mut() {
+                link.text.push_str(&unescape_xml(text));
+            }
+        }
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let name = tag
+            .trim_start_matches('/')
+            .split(|ch: char| ch.is_whitespace() || ch == '/')
+            .next()
+            .unwrap_or("");
+        let closing = tag.starts_with('/');
+        match (name, closing) {
+            ("hp:p", false) => {
+                paragraph_index = Some(paragraph_count);
+                paragraph_count += 1;
+            }
+            ("hp:t", false) => in_text = !tag.ends_with('/'),
+            ("hp:t", true) => in_text = false,
+            ("hp:hyperlink", false) => {
+                if let Some(url) = xml_attr(tag, "url") {
+                    open = Some((
+                        Link {
+                            section_index,
+                            paragraph_index: paragraph_index.unwrap_or(0),
+                            text: String::new(),
+                            target: url,
+                        },
+                        "hp:run",
+                    ));
+                }
+            }
+            ("hp:fieldBegin", false) if xml_attr(tag, "type").as_deref() == Some("HYPERLINK") => {
+                field_command = Some(String::new());
+            }
+            ("hp:stringParam", false) => {
+                in_command = field_command.is_some()
+                    && xml_attr(tag, "name").as_deref() == Some("Command")
+                    && !tag.ends_with('/');
+            }
+            ("hp:stringParam", true) => in_command = false,
+            ("hp:fieldBegin", true) => {
+                if let Some(command) = field_command.take() {
+                    open = Some((
+                        Link {
+                            section_index,
+                            paragraph_index: paragraph_index.unwrap_or(0),
+                            text: String::new(),
+                            target: command_target(&command),
+                        },
+                        "hp:fieldEnd",
+                    ));
+                }
+            }
+            ("hp:run", true) | ("hp:fieldEnd", false) => {
+                if open.as_ref().is_some_and(|(_, ends_at)| *ends_at == name)
+                    && let Some((link, _)) = open.take()
+                {
+                    links.push(link);
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some((link, _)) = open {
+        links.push(link);
+    }
+}
+
+fn xml_attr(tag: &str, name: &str) -> Option<String> {
+    let needle = format!(" {name}=\"");
+    let start = tag.find(&needle)? + needle.len();
+    let end = tag[start..].find('"')?;
+    Some(unescape_xml(&tag[start..start + end]))
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
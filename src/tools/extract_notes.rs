@@ -0,0 +1,445 @@
+use crate::input::{InputFormat, load_input};
+use crate::tools::records::{
+    HWPTAG_CTRL_HEADER, HWPTAG_PARA_HEADER, HWPTAG_PARA_TEXT, HwpSections,
+};
+use crate::tools::{check_arguments, error_result, parse_document};
+use hwpers::HwpDocument;
+use hwpers::model::ctrl_header::{ControlType, CtrlHeader};
+use hwpers::parser::record::Record;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+
+/// `fn  ` / `en  ` control ids as stored in a little-endian u32. hwpers' `ControlType`
+/// expects other ids, so both are checked.
+const FOOTNOTE_CTRL_ID: u32 = u32::from_le_bytes(*b"  nf");
+const ENDNOTE_CTRL_ID: u32 = u32::from_le_bytes(*b"  ne");
+
+pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let payload = match load_input(args) {
+        Ok(payload) => payload,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let parsed = match parse_document(&payload.bytes, payload.format) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return error_result(err.kind, err.message, Some(payload.source.as_str()));
+        }
+    };
+
+    // Parsing above validates the input and settles the format the notes are read in.
+    let mut warnings = parsed.warnings;
+    let notes = if parsed.format == InputFormat::Hwp && parsed.document.is_distribution_document() {
+        warnings.push(
+            "distribution document (배포용 문서): encrypted body is not scanned for notes"
+                .to_string(),
+        );
+        Ok(Vec::new())
+    } else {
+        document_notes(&parsed.document, parsed.format, &payload.bytes)
+    };
+    let notes = match notes {
+        Ok(notes) => notes,
+        Err(message) => {
+            warnings.push(format!("note scan failed: {message}"));
+            Vec::new()
+        }
+    };
+
+    let mut footnotes = Vec::new();
+    let mut endnotes = Vec::new();
+    for note in notes {
+        let (list, kind) = match note.kind {
+            NoteKind::Footnote => (&mut footnotes, "footnote"),
+            NoteKind::Endnote => (&mut endnotes, "endnote"),
+        };
+        let number = note.number;
+        let anchor = match note.anchor {
+            Some(anchor) => json!({
+                "section_index": anchor.section_index,
+                "paragraph_index": anchor.paragraph_index,
+                "text": anchor.text
+            }),
+            None => {
+                warnings.push(format!(
+                    "{kind} {number}: anchor paragraph not found; returning the note without an anchor"
+                ));
+                Value::Null
+            }
+        };
+        list.push(json!({
+            "number": number,
+            "anchor": anchor,
+            "text": note.text
+        }));
+    }
+
+    let summary = format!(
+        "found {} footnotes and {} endnotes",
+        footnotes.len(),
+        endnotes.len()
+    );
+    json!({
+        "content": [{"type": "text", "text": summary}],
+        "structuredContent": {
+            "format": parsed.format.as_str(),
+            "detected_format": parsed.format.as_str(),
+            "detection_method": payload.detection_method,
+            "footnotes": footnotes,
+            "endnotes": endnotes,
+            "warnings": warnings
+        },
+        "isError": false
+    })
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NoteKind {
+    Footnote,
+    Endnote,
+}
+
+pub(crate) struct Anchor {
+    pub(crate) section_index: usize,
+    pub(crate) paragraph_index: usize,
+    pub(crate) text: String,
+}
+
+pub(crate) struct Note {
+    pub(crate) kind: NoteKind,
+    /// Reference marker, counted in document order from the document's start number.
+    pub(crate) number: u64,
+    pub(crate) anchor: Option<Anchor>,
+    /// Paragraphs of the anchor's section that hold the note body. hwpers lists HWP note
+    /// bodies among the section's own paragraphs; HWPX ones stay inside their anchor.
+    pub(crate) body_paragraphs: Vec<usize>,
+    /// Char offset of the note control in its anchor paragraph's text, which is where the
+    /// HWPX reader folds the note body. Not tracked for HWP notes.
+    pub(crate) position: Option<usize>,
+    pub(crate) text: String,
+}
+
+/// Every footnote and endnote of `document`, numbered. hwpers drops note controls while
+/// parsing, so they are read from the container in `bytes`.
+pub(crate) fn document_notes(
+    document: &HwpDocument,
+    format: InputFormat,
+    bytes: &[u8],
+) -> Result<Vec<Note>, String> {
+    let mut notes = if format == InputFormat::Hwpx {
+        hwpx_notes(bytes)?
+    } else {
+        hwp_notes(bytes)?
+    };
+    let (footnote_start, endnote_start) = match document.doc_info.properties.as_ref() {
+        Some(properties) => (
+            properties.footnote_start_number.max(1),
+            properties.endnote_start_number.max(1),
+        ),
+        None => (1, 1),
+    };
+    let (mut footnotes, mut endnotes) = (0u64, 0u64);
+    for note in &mut notes {
+        let (count, start) = match note.kind {
+            NoteKind::Footnote => (&mut footnotes, footnote_start),
+            NoteKind::Endnote => (&mut endnotes, endnote_start),
+        };
+        note.number = u64::from(start) + *count;
+        *count += 1;
+    }
+    Ok(notes)
+}
+
+fn note_kind(header: &CtrlHeader) -> Option<NoteKind> {
+    match (header.ctrl_id, header.get_control_type()) {
+        (FOOTNOTE_CTRL_ID, _) | (_, ControlType::Footnote) => Some(NoteKind::Footnote),
+        (ENDNOTE_CTRL_ID, _) | (_, ControlType::Endnote) => Some(NoteKind::Endnote),
+        _ => None,
+    }
+}
+
+fn hwp_notes(bytes: &[u8]) -> Result<Vec<Note>, String> {
+    let mut notes = Vec::new();
+    for (section_index, records) in HwpSections::open(bytes)?.enumerate() {
+        collect_hwp_section_notes(section_index, records?, &mut notes);
+    }
+    Ok(notes)
+}
+
+/// A note control sits one level below the paragraph it is anchored in; its body paragraphs
+/// follow at deeper levels until a record at or above the control's level.
+fn collect_hwp_section_notes(section_index: usize, records: Vec<Record>, notes: &mut Vec<Note>) {
+    struct OpenNote {
+        kind: NoteKind,
+        level: u16,
+        owner: Option<usize>,
+        paragraphs: Vec<String>,
+        /// Section-wide indices of the body paragraphs.
+        body: Vec<usize>,
+    }
+
+    let mut paragraph_texts: Vec<String> = Vec::new();
+    // Paragraph owning records at each nesting level; controls sit one level below it.
+    let mut owner_at_level: HashMap<u16, usize> = HashMap::new();
+    let mut open: Option<OpenNote> = None;
+    let mut finished: Vec<OpenNote> = Vec::new();
+
+    for record in records {
+        let level = u16::from(record.header.level);
+        if open.as_ref().is_some_and(|note| level <= note.level)
+            && let Some(note) = open.take()
+        {
+            finished.push(note);
+        }
+
+        match record.tag_id() {
+            HWPTAG_PARA_HEADER => {
+                owner_at_level.insert(level, paragraph_texts.len());
+                if let Some(note) = open.as_mut() {
+                    note.paragraphs.push(String::new());
+                    note.body.push(paragraph_texts.len());
+                }
+                paragraph_texts.push(String::new());
+            }
+            HWPTAG_PARA_TEXT => {
+                let text = decode_para_text(&record.data);
+                if let Some(note) = open.as_mut() {
+                    if let Some(paragraph) = note.paragraphs.last_mut() {
+                        paragraph.push_str(&text);
+                    }
+                } else if let Some(paragraph) = level
+                    .checked_sub(1)
+                    .and_then(|parent| owner_at_level.get(&parent))
+                    .and_then(|idx| paragraph_texts.get_mut(*idx))
+                {
+                    paragraph.push_str(&text);
+                }
+            }
+            HWPTAG_CTRL_HEADER if open.is_none() => {
+                let Some(kind) = CtrlHeader::from_record(&record)
+                    .ok()
+                    .as_ref()
+                    .and_then(note_kind)
+                else {
+                    continue;
+                };
+                let owner = level
+                    .checked_sub(1)
+                    .and_then(|parent| owner_at_level.get(&parent))
+                    .copied();
+                open = Some(OpenNote {
+                    kind,
+                    level,
+                    owner,
+                    paragraphs: Vec::new(),
+                    body: Vec::new(),
+                });
+            }
+            _ => {}
+        }
+    }
+    finished.extend(open);
+
+    for note in finished {
+        let anchor = note.owner.map(|owner| Anchor {
+            section_index,
+            // hwpers reads the first paragraph header of a section as the section definition,
+            // so indices shift down by one to line up with the other tools' paragraph_index.
+            paragraph_index: owner.saturating_sub(1),
+            text: paragraph_texts
+                .get(owner)
+                .map(|text| text.trim().to_string())
+                .unwrap_or_default(),
+        });
+        notes.push(Note {
+            kind: note.kind,
+            number: 0,
+            anchor,
+            body_paragraphs: note
+                .body
+                .iter()
+                .filter_map(|index| index.checked_sub(1))
+                .collect(),
+            position: None,
+            text: note
+                .paragraphs
+                .iter()
+                .map(|paragraph| paragraph.trim())
+                .filter(|paragraph| !paragraph.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        });
+    }
+}
+
+/// Decodes PARA_TEXT, dropping controls. Char controls occupy one code unit; inline and
+/// extended controls occupy eight.
+fn decode_para_text(data: &[u8]) -> String {
+    let units: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect();
+    let mut text = Vec::with_capacity(units.len());
+    let mut i = 0;
+    while i < units.len() {
+        let unit = units[i];
+        if unit < 32 {
+            match unit {
+                10 => text.push(u16::from(b'\n')),
+                9 => text.push(u16::from(b'\t')),
+                30 | 31 => text.push(u16::from(b' ')),
+                _ => {}
+            }
+            i += if matches!(unit, 0 | 10 | 13 | 24..=31) {
+                1
+            } else {
+                8
+            };
+            continue;
+        }
+        text.push(unit);
+        i += 1;
+    }
+    String::from_utf16_lossy(&text)
+}
+
+fn hwpx_notes(bytes: &[u8]) -> Result<Vec<Note>, String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).map_err(|err| err.to_string())?;
+    let mut notes = Vec::new();
+    let mut section_index = 0;
+    loop {
+        let name = format!("Contents/section{section_index}.xml");
+        let Ok(mut file) = archive.by_name(&name) else {
+            break;
+        };
+        let mut xml = String::new();
+        file.read_to_string(&mut xml)
+            .map_err(|err| format!("{name}: {err}"))?;
+        collect_hwpx_section_notes(section_index, &xml, &mut notes);
+        section_index += 1;
+    }
+    Ok(notes)
+}
+
+/// Walks the section XML tag by tag. Notes are `<hp:footNote>`/`<hp:endNote>` elements inside a
+/// run; their body is the `<hp:t>` text of the paragraphs in their sub-list. Anchors are
+/// counted over top-level `<hp:p>` elements, as the HWPX reader does.
+fn collect_hwpx_section_notes(section_index: usize, xml: &str, notes: &mut Vec<Note>) {
+    let mut depth = 0usize;
+    let mut paragraph_count = 0usize;
+    let mut anchor_text = String::new();
+    let mut pending: Vec<(NoteKind, usize, String)> = Vec::new();
+    let mut open: Option<(NoteKind, &'static str, usize, String)> = None;
+    let mut in_text = false;
+
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        let text = &rest[..start];
+        if in_text && !text.is_empty() {
+            match open.as_mut() {
+                Some((_, _, _, body)) => body.push_str(&unescape_xml(text)),
+                None => anchor_text.push_str(&unescape_xml(text)),
+            }
+        }
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let name = tag
+            .trim_start_matches('/')
+            .split(|ch: char| ch.is_whitespace() || ch == '/')
+            .next()
+            .unwrap_or("");
+        let closing = tag.starts_with('/');
+        let self_closing = tag.ends_with('/');
+        match (name, closing) {
+            ("hp:p", false) => {
+                if let Some((_, _, _, body)) = open.as_mut()
+                    && !body.is_empty()
+                {
+                    body.push('\n');
+                }
+                if !self_closing {
+                    depth += 1;
+                }
+            }
+            ("hp:p", true) => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    for (kind, position, body) in pending.drain(..) {
+                        notes.push(Note {
+                            kind,
+                            number: 0,
+                            anchor: Some(Anchor {
+                                section_index,
+                                paragraph_index: paragraph_count,
+                                text: anchor_text.trim().to_string(),
+                            }),
+                            body_paragraphs: Vec::new(),
+                            position: Some(position),
+                            text: body.trim().to_string(),
+                        });
+                    }
+                    anchor_text.clear();
+                    paragraph_count += 1;
+                }
+            }
+            ("hp:t", false) => in_text = !self_closing,
+            ("hp:t", true) => in_text = false,
+            ("hp:footNote" | "hp:endNote", false) if open.is_none() && !self_closing => {
+                let (kind, ends_at) = match name {
+                    "hp:footNote" => (NoteKind::Footnote, "hp:footNote"),
+                    _ => (NoteKind::Endnote, "hp:endNote"),
+                };
+                // Text read so far belongs to the anchor, so its length is the control offset.
+                open = Some((kind, ends_at, anchor_text.chars().count(), String::new()));
+            }
+            ("hp:footNote" | "hp:endNote", true) => {
+                if open
+                    .as_ref()
+                    .is_some_and(|(_, ends_at, _, _)| *ends_at == name)
+                    && let Some((kind, _, position, body)) = open.take()
+                {
+                    if depth == 0 {
+                        notes.push(Note {
+                            kind,
+                            number: 0,
+                            anchor: None,
+                            body_paragraphs: Vec::new(),
+                            position: None,
+                            text: body.trim().to_string(),
+                        });
+                    } else {
+                        pending.push((kind, position, body));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    for (kind, _, body) in pending {
+        notes.push(Note {
+            kind,
+            number: 0,
+            anchor: None,
+            body_paragraphs: Vec::new(),
+            position: None,
+            text: body.trim().to_string(),
+        });
+    }
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
@@ -0,0 +1,232 @@
+use crate::input::load_input;
+use crate::mcp::contracts::limits;
+use crate::mcp::errors;
+use crate::mcp::resources;
+use crate::tools::{ToolError, check_arguments, error_result, parse_document};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use hwpers::render::renderer::{HwpRenderer, RenderOptions};
+use serde_json::{Value, json};
+use std::fs;
+use std::path::PathBuf;
+
+pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let payload = match load_input(args) {
+        Ok(payload) => payload,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let output = match OutputMode::parse(args.get("output")) {
+        Ok(output) => output,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let fallback_render = args
+        .get("fallback_render")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false);
+
+    let mut parsed = match parse_document(&payload.bytes, payload.format) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return error_result(err.kind, err.message, Some(payload.source.as_str()));
+        }
+    };
+
+    let preview = match parsed.document.preview_image.as_ref() {
+        Some(image) if !image.is_empty() => Some(Preview {
+            bytes: image.bytes().to_vec(),
+            extension: image.extension(),
+            mime_type: mime_from_extension(image.extension()),
+            source: "embedded",
+        }),
+        _ => None,
+    };
+
+    let preview = match preview {
+        Some(preview) => preview,
+        None if fallback_render => {
+            if ensure_page_defs(&mut parsed.document) {
+                parsed
+                    .warnings
+                    .push("missing page definition; default layout applied".to_string());
+            }
+            let renderer = HwpRenderer::new(&parsed.document, RenderOptions::default());
+            let Some(svg) = renderer.render().to_svg(0) else {
+                return error_result(
+                    errors::PARSE_FAILED,
+                    "document has no pages to render",
+                    None,
+                );
+            };
+            parsed
+                .warnings
+                .push("no embedded preview image; rendered page 1 as svg".to_string());
+            Preview {
+                bytes: svg.into_bytes(),
+                extension: "svg",
+                mime_type: "image/svg+xml",
+                source: "rendered",
+            }
+        }
+        None => {
+            return json!({
+                "content": [{"type": "text", "text": "no embedded preview image"}],
+                "structuredContent": {
+                    "format": parsed.format.as_str(),
+                    "detected_format": parsed.format.as_str(),
+                    "detection_method": payload.detection_method,
+                    "found": false,
+                    "warnings": parsed.warnings
+                },
+                "isError": false
+            });
+        }
+    };
+
+    let bytes_len = preview.bytes.len() as u64;
+    match output {
+        OutputMode::Inline => {
+            let max_output_bytes = limits().max_output_bytes;
+            if bytes_len > max_output_bytes {
+                return error_result(
+                    errors::TOO_LARGE,
+                    format!("output exceeds limit: {bytes_len} bytes (max {max_output_bytes})"),
+                    None,
+                );
+            }
+            json!({
+                "content": [{
+                    "type": "text",
+                    "text": format!("{} preview ({}, {bytes_len} bytes)", preview.source, preview.mime_type)
+                }],
+                "structuredContent": {
+                    "format": parsed.format.as_str(),
+                    "detected_format": parsed.format.as_str(),
+                    "detection_method": payload.detection_method,
+                    "found": true,
+                    "source": preview.source,
+                    "mimeType": preview.mime_type,
+                    "base64": STANDARD.encode(&preview.bytes),
+                    "bytes_len": bytes_len,
+                    "warnings": parsed.warnings
+                },
+                "isError": false
+            })
+        }
+        OutputMode::Resource => {
+            let path = match preview_path(preview.extension) {
+                Ok(path) => path,
+                Err(err) => {
+                    return error_result(
+                        errors::INTERNAL_ERROR,
+                        format!("failed to create output directory: {err}"),
+                        None,
+                    );
+                }
+            };
+            if let Err(err) = fs::write(&path, &preview.bytes) {
+                return error_result(
+                    errors::INTERNAL_ERROR,
+                    format!("failed to write preview output: {err}"),
+                    None,
+                );
+            }
+            resources::track(&path);
+            let path_string = path.to_string_lossy().to_string();
+            let uri = format!("file://{path_string}");
+            json!({
+                "content": [
+                    {
+                        "type": "text",
+                        "text": format!("{} preview written to {path_string}", preview.source)
+                    },
+                    {
+                        "type": "resource_link",
+                        "uri": uri,
+                        "name": "preview",
+                        "mimeType": preview.mime_type
+                    }
+                ],
+                "structuredContent": {
+                    "format": parsed.format.as_str(),
+                    "detected_format": parsed.format.as_str(),
+                    "detection_method": payload.detection_method,
+                    "found": true,
+                    "source": preview.source,
+                    "mimeType": preview.mime_type,
+                    "path": path_string,
+                    "uri": uri,
+                    "bytes_len": bytes_len,
+                    "warnings": parsed.warnings
+                },
+                "isError": false
+            })
+        }
+    }
+}
+
+struct Preview {
+    bytes: Vec<u8>,
+    extension: &'static str,
+    mime_type: &'static str,
+    source: &'static str,
+}
+
+enum OutputMode {
+    Inline,
+    Resource,
+}
+
+impl OutputMode {
+    fn parse(value: Option<&Value>) -> Result<Self, ToolError> {
+        let Some(value) = value else {
+            return Ok(OutputMode::Inline);
+        };
+        let Some(value) = value.as_str() else {
+            return Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "output must be a string".to_string(),
+            });
+        };
+        match value {
+            "inline" => Ok(OutputMode::Inline),
+            "resource" => Ok(OutputMode::Resource),
+            _ => Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "output must be inline or resource".to_string(),
+            }),
+        }
+    }
+}
+
+fn mime_from_extension(extension: &str) -> &'static str {
+    match extension {
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "bmp" => "image/bmp",
+        _ => "application/octet-stream",
+    }
+}
+
+fn preview_path(extension: &str) -> std::io::Result<PathBuf> {
+    let suffix = resources::unique_suffix();
+    Ok(resources::output_dir(None)?.join(format!("hwp-preview-{suffix}.{extension}")))
+}
+
+fn ensure_page_defs(document: &mut hwpers::HwpDocument) -> bool {
+    let mut updated = false;
+    for body_text in &mut document.body_texts {
+        for section in &mut body_text.sections {
+            if section.page_def.is_none() {
+                section.page_def = Some(hwpers::model::page_def::PageDef::new_default());
+                updated = true;
+            }
+        }
+    }
+    updated
+}
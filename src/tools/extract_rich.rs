@@ -1,20 +1,135 @@
-use crate::input::{InputFormat, load_input};
-use crate::mcp::contracts::MAX_OUTPUT_BYTES;
+use crate::input::{InputFormat, is_distribution_document, load_input};
+use crate::mcp::contracts::limits;
 use crate::mcp::errors;
-use crate::tools::error_result;
+use crate::mcp::resources;
+use crate::tools::extract_images::document_images;
+use crate::tools::records::{
+    HWPTAG_LIST_HEADER, HWPTAG_PARA_HEADER, HWPTAG_SHAPE_COMPONENT_PICTURE, HWPTAG_TABLE,
+    HwpSections,
+};
+use crate::tools::{
+    Compress, ToolError, WorkerOutcome, check_arguments, error_result, parse_document_blocking,
+    run_with_timeout,
+};
 use base64::Engine;
 use base64::engine::general_purpose::STANDARD;
 use hwpers::model::bin_data::BinData;
-use hwpers::{HwpError, HwpReader, HwpxReader};
+use hwpers::model::{Paragraph, Table};
+use hwpers::parser::record::Record;
 use serde_json::{Value, json};
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::io::{Cursor, Read};
 use std::path::PathBuf;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant};
+
+/// The tag hwpers 0.5.0 maps to `ParaHeader` (really HWPTAG_SHAPE_COMPONENT_ELLIPSE). It starts
+/// a paragraph only when the record holds the 18-byte paragraph header hwpers reads from it
+/// (control mask u32, para shape u16, style u8, column type u8, three u16 counts, instance id
+/// u32); a shorter one closes the open paragraph without starting another.
+const HWPERS_PARA_HEADER: u16 = 0x50;
+const HWPERS_PARA_HEADER_LEN: usize = 18;
+/// In a table, an HWPTAG_LIST_HEADER precedes each cell's paragraphs: paragraph count u16,
+/// u16, list properties u32, then the cell's column, row, column span and row span as u16s
+/// from byte 8.
+const CELL_ADDRESS_OFFSET: usize = 8;
+/// HWPTAG_TABLE: properties u32, then the row and column counts as u16s.
+const TABLE_ROWS_OFFSET: usize = 4;
+const TABLE_COLS_OFFSET: usize = 6;
+/// Where HWPTAG_SHAPE_COMPONENT_PICTURE keeps its BinItem id.
+const PICTURE_BIN_ID_OFFSET: usize = 71;
 
 pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let as_html = args
+        .get("as_html")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let summary_mode = match args.get("summary").map(|v| v.as_str()) {
+        None | Some(Some("counts")) => SummaryMode::Counts,
+        Some(Some("brief")) => SummaryMode::Brief,
+        Some(_) => {
+            return error_result(
+                errors::INVALID_INPUT,
+                "summary must be brief or counts",
+                None,
+            );
+        }
+    };
+    let compress = match Compress::parse(args.get("compress")) {
+        Ok(compress) => compress,
+        Err(message) => return error_result(errors::INVALID_INPUT, message, None),
+    };
+    if as_html && compress != Compress::None {
+        return error_result(
+            errors::INVALID_INPUT,
+            "compress cannot be combined with as_html, whose data: URIs must stay uncompressed",
+            None,
+        );
+    }
+    let images_inline = args.get("images").and_then(|v| v.as_str()) == Some("inline");
+    let extracted = match extract_blocks(args, compress) {
+        Ok(extracted) => extracted,
+        Err(tool_result) => return tool_result,
+    };
+    let blocks = extracted.blocks;
+
+    let mut content = vec![json!({
+        "type": "text",
+        "text": summarize_blocks(&blocks, summary_mode)
+    })];
+    let html = as_html.then(|| render_html(&blocks));
+    if let Some(html) = &html {
+        content.push(json!({"type": "text", "text": html}));
+    }
+
+    let mut result = json!({
+        "content": content,
+        "structuredContent": {
+            "format": extracted.format.as_str(),
+            "detected_format": extracted.format.as_str(),
+            "detection_method": extracted.detection_method,
+            "blocks": blocks,
+            "timed_out": extracted.timed_out,
+            "warnings": extracted.warnings
+        },
+        "isError": false
+    });
+    if let Some(html) = html
+        && let Some(structured) = result
+            .get_mut("structuredContent")
+            .and_then(|v| v.as_object_mut())
+    {
+        structured.insert("html".to_string(), json!(html));
+    }
+    if images_inline && let Some(encoding) = compress.encoding() {
+        result["structuredContent"]["encoding"] = json!(encoding);
+    }
+    result
+}
+
+/// The block model shared by `extract_rich` and the tools that serialize it.
+pub(crate) struct ExtractedBlocks {
+    pub(crate) document: hwpers::HwpDocument,
+    pub(crate) format: InputFormat,
+    pub(crate) detection_method: &'static str,
+    pub(crate) blocks: Vec<Value>,
+    pub(crate) timed_out: bool,
+    pub(crate) warnings: Vec<String>,
+}
+
+/// Loads, parses and walks the input into paragraph/table/image blocks, honouring the
+/// `images`, `max_image_bytes`, `output_path`, `timeout_ms` and `partial_on_timeout`
+/// arguments; inline images are compressed with `compress`. Errors come back as ready-made tool
+/// results.
+pub(crate) fn extract_blocks(args: &Value, compress: Compress) -> Result<ExtractedBlocks, Value> {
+    let started = Instant::now();
     let payload = match load_input(args) {
         Ok(payload) => payload,
-        Err(err) => return error_result(err.kind, err.message, None),
+        Err(err) => return Err(error_result(err.kind, err.message, None)),
     };
 
     let images_mode = args
@@ -29,84 +144,85 @@ pub fn call(args: &Value) -> Value {
         .get("output_path")
         .and_then(|v| v.as_str())
         .map(|s| s.to_string());
+    let timeout_ms = match parse_timeout_ms(args.get("timeout_ms")) {
+        Ok(timeout_ms) => timeout_ms,
+        Err(err) => return Err(error_result(err.kind, err.message, None)),
+    };
+    let partial_on_timeout = args
+        .get("partial_on_timeout")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    // Blocks are gathered without a deadline unless the caller sets timeout_ms.
+    let deadline = timeout_ms.map(|timeout_ms| started + Duration::from_millis(timeout_ms));
 
-    let parsed = match parse_document(&payload.bytes, payload.format) {
+    // The parse is held to the parse time limit, or to timeout_ms when one is set. A partial
+    // result still needs a parsed document, so with partial_on_timeout it gets the longer one.
+    let parse_timeout_ms = match timeout_ms {
+        Some(timeout_ms) if partial_on_timeout => timeout_ms.max(limits().max_parse_ms),
+        Some(timeout_ms) => timeout_ms,
+        None => limits().max_parse_ms,
+    };
+    let parse_deadline = started + Duration::from_millis(parse_timeout_ms);
+    let parsed = match parse_document(
+        &payload.bytes,
+        payload.format,
+        parse_deadline,
+        parse_timeout_ms,
+    ) {
         Ok(parsed) => parsed,
-        Err(err) => return error_result(err.kind, err.message, Some(payload.source.as_str())),
+        Err(err) if err.kind != errors::TIMEOUT && is_distribution_document(&payload.bytes) => {
+            return Err(error_result(
+                errors::ENCRYPTED,
+                format!(
+                    "distribution document (배포용 문서): content could not be decrypted for reading ({})",
+                    err.message
+                ),
+                Some(payload.source.as_str()),
+            ));
+        }
+        Err(err) => {
+            return Err(error_result(
+                err.kind,
+                err.message,
+                Some(payload.source.as_str()),
+            ));
+        }
     };
 
     let mut warnings = parsed.warnings;
+    if parsed.document.is_distribution_document() {
+        warnings.push(
+            "distribution document (배포용 문서): opened read-only; editing tools refuse it"
+                .to_string(),
+        );
+    }
     let mut blocks: Vec<Value> = Vec::new();
     let mut total_inline_image_bytes: u64 = 0;
-    let images = parsed.document.get_images();
-    let mut image_cursor: usize = 0;
+    let images = parsed.images;
+    let mut placed = vec![false; images.len()];
+    let mut timed_out = false;
+    let controls = parsed.controls;
+    let no_controls = SectionControls::default();
 
-    for (section_index, section) in parsed.document.sections().enumerate() {
+    'sections: for (section_index, section) in parsed.document.sections().enumerate() {
         let paragraphs = &section.paragraphs;
+        let controls = controls.get(section_index).unwrap_or(&no_controls);
         let mut i: usize = 0;
         while i < paragraphs.len() {
+            // Blocks are produced incrementally; always emit at least one before giving up.
+            if !blocks.is_empty() && deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                timed_out = true;
+                break 'sections;
+            }
             let paragraph = &paragraphs[i];
 
             // Prefer structured control data when available.
             if let Some(table) = paragraph.table_data.as_ref() {
-                let rows = usize::from(table.rows);
-                let cols = usize::from(table.cols);
-
-                let mut cells = table.cells.iter().collect::<Vec<_>>();
-                cells.sort_by_key(|cell| (cell.cell_address.0, cell.cell_address.1));
-
-                let cell_para_start = i.saturating_add(1);
-                let mut cell_texts: Vec<String> = Vec::with_capacity(cells.len());
-                for cell_idx in 0..cells.len() {
-                    let para_idx = cell_para_start + cell_idx;
-                    let text = paragraphs
-                        .get(para_idx)
-                        .map(paragraph_text)
-                        .unwrap_or_default();
-                    cell_texts.push(text);
-                }
-                if cell_para_start + cells.len() > paragraphs.len() {
-                    warnings.push(format!(
-                        "table at section {section_index} paragraph {i}: expected {} cell paragraphs but only {} remain",
-                        cells.len(),
-                        paragraphs.len().saturating_sub(cell_para_start)
-                    ));
-                }
-
-                let mut grid: Vec<Vec<String>> = Vec::with_capacity(rows);
-                for _ in 0..rows {
-                    grid.push(vec![String::new(); cols]);
-                }
-
-                let mut spans: Vec<Value> = Vec::new();
-                for (idx, cell) in cells.iter().enumerate() {
-                    let r = usize::from(cell.cell_address.0);
-                    let c = usize::from(cell.cell_address.1);
-                    if r < rows && c < cols {
-                        grid[r][c] = cell_texts.get(idx).cloned().unwrap_or_default();
-                    }
-                    if cell.row_span > 1 || cell.col_span > 1 {
-                        spans.push(json!({
-                            "row": cell.cell_address.0,
-                            "col": cell.cell_address.1,
-                            "row_span": cell.row_span,
-                            "col_span": cell.col_span
-                        }));
-                    }
-                }
-
-                blocks.push(json!({
-                    "type": "table",
-                    "section_index": section_index,
-                    "paragraph_index": i,
-                    "rows": grid,
-                    "spans": spans,
-                    "inferred": false,
-                    "cells_count": cells.len()
-                }));
-
+                let (block, cell_paragraphs) =
+                    table_block(section_index, i, table, paragraphs, &mut warnings);
+                blocks.push(block);
                 // Skip over the following cell paragraphs that belong to this table.
-                i = cell_para_start.saturating_add(cells.len());
+                i = i.saturating_add(1).saturating_add(cell_paragraphs);
                 continue;
             }
 
@@ -130,21 +246,23 @@ pub fn call(args: &Value) -> Value {
                             source: &payload.source,
                             warnings: &mut warnings,
                             output_path: &output_path,
+                            compress,
                         };
 
-                        if image_cursor < images.len() {
-                            let bin = images[image_cursor];
-                            image_cursor += 1;
-                            let block = match image_block_from_bin(
+                        let referenced = controls
+                            .pictures
+                            .get(&i)
+                            .or_else(|| controls.pictures.get(&(i + 1)))
+                            .copied();
+                        if let Some(index) = take_image(&images, &mut placed, referenced) {
+                            let bin = &images[index];
+                            let block = image_block_from_bin(
                                 section_index,
                                 i,
                                 bin,
                                 caption,
                                 &mut image_ctx,
-                            ) {
-                                Ok(block) => block,
-                                Err(tool_result) => return tool_result,
-                            };
+                            )?;
                             blocks.push(block);
                         } else {
                             warnings.push(
@@ -167,58 +285,11 @@ pub fn call(args: &Value) -> Value {
                     }
                 }
 
-                // Fallback: empty paragraph followed by multiple non-empty paragraphs -> infer a table.
-                let mut j = i + 1;
-                while j < paragraphs.len() {
-                    let t = paragraph_text(&paragraphs[j]);
-                    if t.trim().is_empty() {
-                        break;
-                    }
-                    j += 1;
-                }
-                let cell_count = j.saturating_sub(i + 1);
-                if cell_count >= 2 {
-                    let mut cells: Vec<String> = Vec::with_capacity(cell_count);
-                    for paragraph in paragraphs.iter().take(j).skip(i + 1) {
-                        cells.push(paragraph_text(paragraph).trim().to_string());
-                    }
-
-                    let (rows, cols) = infer_table_dims(cells.len());
-                    let mut rows_out: Vec<Vec<String>> = Vec::with_capacity(rows);
-                    for r in 0..rows {
-                        let mut row: Vec<String> = Vec::with_capacity(cols);
-                        for c in 0..cols {
-                            let idx = r * cols + c;
-                            row.push(cells.get(idx).cloned().unwrap_or_default());
-                        }
-                        rows_out.push(row);
-                    }
-
-                    blocks.push(json!({
-                        "type": "table",
-                        "section_index": section_index,
-                        "paragraph_index": i,
-                        "rows": rows_out,
-                        "inferred": true,
-                        "cells_count": cells.len()
-                    }));
-
-                    // If the next paragraph is an empty anchor for an image caption, don't consume it.
-                    if j < paragraphs.len()
-                        && paragraph_text(&paragraphs[j]).trim().is_empty()
-                        && j + 1 < paragraphs.len()
-                        && paragraph_text(&paragraphs[j + 1])
-                            .trim_start()
-                            .starts_with("그림:")
-                    {
-                        i = j;
-                    } else {
-                        i = j;
-                        if i < paragraphs.len() && paragraph_text(&paragraphs[i]).trim().is_empty()
-                        {
-                            i += 1;
-                        }
-                    }
+                if let Some((block, next)) =
+                    anchored_table(section_index, i, paragraphs, controls, &mut warnings)
+                {
+                    blocks.push(block);
+                    i = next;
                     continue;
                 }
 
@@ -245,23 +316,27 @@ pub fn call(args: &Value) -> Value {
                     source: &payload.source,
                     warnings: &mut warnings,
                     output_path: &output_path,
+                    compress,
                 };
 
-                if image_cursor < images.len() {
-                    let bin = images[image_cursor];
-                    image_cursor += 1;
-                    let block = match image_block_from_bin(
-                        section_index,
-                        i,
-                        bin,
-                        caption,
-                        &mut image_ctx,
-                    ) {
-                        Ok(block) => block,
-                        Err(tool_result) => return tool_result,
-                    };
+                // The picture may sit on the caption paragraph itself or, when the caption comes
+                // first, on the empty paragraph right after it.
+                let picture_follows = !controls.pictures.contains_key(&i)
+                    && controls.pictures.contains_key(&(i + 1))
+                    && paragraphs
+                        .get(i + 1)
+                        .is_some_and(|next| paragraph_text(next).trim().is_empty());
+                let referenced = controls
+                    .pictures
+                    .get(&i)
+                    .or_else(|| controls.pictures.get(&(i + 1)).filter(|_| picture_follows))
+                    .copied();
+                if let Some(index) = take_image(&images, &mut placed, referenced) {
+                    let bin = &images[index];
+                    let block =
+                        image_block_from_bin(section_index, i, bin, caption, &mut image_ctx)?;
                     blocks.push(block);
-                    i += 1;
+                    i += if picture_follows { 2 } else { 1 };
                     continue;
                 }
             }
@@ -276,10 +351,25 @@ pub fn call(args: &Value) -> Value {
         }
     }
 
+    if let Some(timeout_ms) = timeout_ms.filter(|_| timed_out) {
+        if !partial_on_timeout {
+            return Err(error_result(
+                errors::TIMEOUT,
+                format!(
+                    "extraction timed out after {timeout_ms} ms; set partial_on_timeout to return the blocks gathered so far"
+                ),
+                Some(payload.source.as_str()),
+            ));
+        }
+        warnings.push(format!(
+            "partial: extraction timed out after {timeout_ms} ms; returning the first {} blocks",
+            blocks.len()
+        ));
+    }
+
     // Any remaining embedded images without obvious anchors
-    while image_cursor < images.len() {
-        let bin = images[image_cursor];
-        image_cursor += 1;
+    while !timed_out && let Some(index) = take_image(&images, &mut placed, None) {
+        let bin = &images[index];
 
         let mut image_ctx = ImageRenderContext {
             images_mode,
@@ -288,87 +378,293 @@ pub fn call(args: &Value) -> Value {
             source: &payload.source,
             warnings: &mut warnings,
             output_path: &output_path,
+            compress,
         };
-        let block = match image_block_from_bin(0, 0, bin, None, &mut image_ctx) {
-            Ok(block) => block,
-            Err(tool_result) => return tool_result,
-        };
-        let mut block = block;
+        let mut block = image_block_from_bin(0, 0, bin, None, &mut image_ctx)?;
         if let Some(obj) = block.as_object_mut() {
             obj.insert("placement".to_string(), json!("unanchored"));
         }
         blocks.push(block);
     }
 
-    json!({
-        "content": [{
-            "type": "text",
-            "text": format!("extracted {} blocks", blocks.len())
-        }],
-        "structuredContent": {
-            "format": parsed.format.as_str(),
-            "blocks": blocks,
-            "warnings": warnings
-        },
-        "isError": false
+    Ok(ExtractedBlocks {
+        document: parsed.document,
+        format: parsed.format,
+        detection_method: payload.detection_method,
+        blocks,
+        timed_out,
+        warnings,
     })
 }
 
-struct ToolError {
-    kind: &'static str,
-    message: String,
+/// Renders blocks as a standalone HTML page. Images use a `data:` URI when inline bytes are
+/// present, the resource URI when written to disk, and an `<img>` without `src` otherwise.
+pub(crate) fn render_html(blocks: &[Value]) -> String {
+    let mut html = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>HWP preview</title>\n</head>\n<body>\n",
+    );
+    for block in blocks {
+        match block.get("type").and_then(|v| v.as_str()) {
+            Some("paragraph") => {
+                let text = block.get("text").and_then(|v| v.as_str()).unwrap_or("");
+                let text = text.trim_end_matches(|ch: char| ch.is_control());
+                if text.trim().is_empty() {
+                    continue;
+                }
+                html.push_str("<p>");
+                html.push_str(&escape_html(text).replace(['\r', '\n'], "<br>"));
+                html.push_str("</p>\n");
+            }
+            Some("table") => render_html_table(block, &mut html),
+            Some("image") => render_html_image(block, &mut html),
+            _ => {}
+        }
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn render_html_table(block: &Value, html: &mut String) {
+    let rows: Vec<Vec<&str>> = block
+        .get("rows")
+        .and_then(|v| v.as_array())
+        .map(|rows| {
+            rows.iter()
+                .map(|row| {
+                    row.as_array()
+                        .map(|cells| cells.iter().map(|c| c.as_str().unwrap_or("")).collect())
+                        .unwrap_or_default()
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // Cells covered by a span are skipped; the anchor cell carries rowspan/colspan.
+    // Spans come from the document as-is, so they are clamped to the grid: a corrupt
+    // 65535x65535 span must not expand into billions of covered cells.
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let mut spans: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+    let mut covered: HashSet<(usize, usize)> = HashSet::new();
+    for span in block
+        .get("spans")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+    {
+        let field = |name: &str| span.get(name).and_then(|v| v.as_u64()).unwrap_or(0) as usize;
+        let (row, col) = (field("row"), field("col"));
+        if row >= rows.len() || col >= width {
+            continue;
+        }
+        let row_span = field("row_span").clamp(1, rows.len() - row);
+        let col_span = field("col_span").clamp(1, width - col);
+        spans.insert((row, col), (row_span, col_span));
+        for r in row..row + row_span {
+            for c in col..col + col_span {
+                if (r, c) != (row, col) {
+                    covered.insert((r, c));
+                }
+            }
+        }
+    }
+
+    html.push_str("<table border=\"1\">\n");
+    for (r, row) in rows.iter().enumerate() {
+        html.push_str("<tr>");
+        for (c, cell) in row.iter().enumerate() {
+            if covered.contains(&(r, c)) {
+                continue;
+            }
+            html.push_str("<td");
+            if let Some((row_span, col_span)) = spans.get(&(r, c)) {
+                if *row_span > 1 {
+                    html.push_str(&format!(" rowspan=\"{row_span}\""));
+                }
+                if *col_span > 1 {
+                    html.push_str(&format!(" colspan=\"{col_span}\""));
+                }
+            }
+            html.push('>');
+            html.push_str(&escape_html(
+                cell.trim_end_matches(|ch: char| ch.is_control()),
+            ));
+            html.push_str("</td>");
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n");
+}
+
+fn render_html_image(block: &Value, html: &mut String) {
+    let caption = block.get("caption").and_then(|v| v.as_str());
+    let mime = block
+        .get("mimeType")
+        .and_then(|v| v.as_str())
+        .unwrap_or("application/octet-stream");
+    let src = if let Some(data) = block.get("base64").and_then(|v| v.as_str()) {
+        Some(format!("data:{mime};base64,{data}"))
+    } else {
+        block
+            .get("uri")
+            .and_then(|v| v.as_str())
+            .map(|uri| uri.to_string())
+    };
+
+    html.push_str("<figure><img");
+    if let Some(src) = src {
+        html.push_str(&format!(" src=\"{}\"", escape_html(&src)));
+    }
+    html.push_str(&format!(" alt=\"{}\">", escape_html(caption.unwrap_or(""))));
+    if let Some(caption) = caption {
+        html.push_str(&format!(
+            "<figcaption>{}</figcaption>",
+            escape_html(caption)
+        ));
+    }
+    html.push_str("</figure>\n");
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SummaryMode {
+    /// `extracted N blocks`
+    Brief,
+    /// `extracted N blocks: P paragraphs, T tables, I images`
+    Counts,
+}
+
+fn summarize_blocks(blocks: &[Value], mode: SummaryMode) -> String {
+    let total = blocks.len();
+    if mode == SummaryMode::Brief {
+        return format!("extracted {total} blocks");
+    }
+    let count = |kind: &str| {
+        blocks
+            .iter()
+            .filter(|block| block.get("type").and_then(|v| v.as_str()) == Some(kind))
+            .count()
+    };
+    let paragraphs = count("paragraph");
+    let tables = count("table");
+    let images = count("image");
+    format!(
+        "extracted {total} blocks: {paragraphs} {}, {tables} {}, {images} {}",
+        plural(paragraphs, "paragraph"),
+        plural(tables, "table"),
+        plural(images, "image")
+    )
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        noun.to_string()
+    } else {
+        format!("{noun}s")
+    }
+}
+
+fn parse_timeout_ms(value: Option<&Value>) -> Result<Option<u64>, ToolError> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    value.as_u64().map(Some).ok_or_else(|| ToolError {
+        kind: errors::INVALID_INPUT,
+        message: "timeout_ms must be a non-negative integer".to_string(),
+    })
 }
 
 struct ParsedDocument {
     document: hwpers::HwpDocument,
     format: InputFormat,
     warnings: Vec<String>,
+    images: Vec<BinData>,
+    controls: Vec<SectionControls>,
 }
 
-fn parse_document(bytes: &[u8], format: InputFormat) -> Result<ParsedDocument, ToolError> {
-    match format {
-        InputFormat::Hwp => HwpReader::from_bytes(bytes)
-            .map(|document| ParsedDocument {
-                document,
-                format,
-                warnings: Vec::new(),
-            })
-            .map_err(|error| map_hwp_error_with_format(error, format.as_str())),
-        InputFormat::Hwpx => HwpxReader::from_bytes(bytes)
-            .map(|document| ParsedDocument {
-                document,
-                format,
-                warnings: Vec::new(),
-            })
-            .map_err(|error| map_hwp_error_with_format(error, format.as_str())),
-        InputFormat::Auto => {
-            let hwp_result = HwpReader::from_bytes(bytes);
-            match hwp_result {
-                Ok(document) => Ok(ParsedDocument {
-                    document,
-                    format: InputFormat::Hwp,
-                    warnings: Vec::new(),
-                }),
-                Err(hwp_err) => match HwpxReader::from_bytes(bytes) {
-                    Ok(document) => Ok(ParsedDocument {
-                        document,
-                        format: InputFormat::Hwpx,
-                        warnings: vec!["auto format: hwp parse failed; hwpx succeeded".to_string()],
-                    }),
-                    Err(hwpx_err) => Err(ToolError {
-                        kind: errors::PARSE_FAILED,
-                        message: format!(
-                            "auto format parse failed (hwp: {}; hwpx: {})",
-                            hwp_err, hwpx_err
-                        ),
-                    }),
-                },
-            }
+impl From<crate::tools::ParsedDocument> for ParsedDocument {
+    fn from(parsed: crate::tools::ParsedDocument) -> Self {
+        Self {
+            document: parsed.document,
+            format: parsed.format,
+            warnings: parsed.warnings,
+            images: Vec::new(),
+            controls: Vec::new(),
+        }
+    }
+}
+
+impl ParsedDocument {
+    /// Reads what hwpers leaves out from the raw container: image payloads when the reader
+    /// loaded none, and the table and picture controls, keyed by the index of the anchor
+    /// paragraph that owns them.
+    fn read_container(&mut self, bytes: &[u8]) {
+        match document_images(&self.document, self.format, bytes) {
+            Ok(images) => self.images = images,
+            Err(err) => self
+                .warnings
+                .push(format!("failed to read embedded images: {err}")),
         }
+        self.controls = container_controls(bytes, self.format, &self.images);
     }
 }
 
-fn mime_from_extension(ext: &str) -> Option<&'static str> {
+/// The table and picture controls of each section of the HWP or HWPX container in `bytes`,
+/// keyed by the index of the anchor paragraph that owns them. HWPX pictures are matched to
+/// `images` by file name.
+pub(crate) fn container_controls(
+    bytes: &[u8],
+    format: InputFormat,
+    images: &[BinData],
+) -> Vec<SectionControls> {
+    match format {
+        InputFormat::Hwpx => hwpx_section_controls(bytes, images),
+        _ => hwp_section_controls(bytes),
+    }
+}
+
+/// Parses on a worker thread so a hostile input cannot wedge the server past the call's
+/// `timeout_ms` deadline.
+fn parse_document(
+    bytes: &[u8],
+    format: InputFormat,
+    deadline: Instant,
+    timeout_ms: u64,
+) -> Result<ParsedDocument, ToolError> {
+    let bytes = bytes.to_vec();
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    let parse = move || {
+        let mut parsed = ParsedDocument::from(parse_document_blocking(&bytes, format)?);
+        parsed.read_container(&bytes);
+        Ok(parsed)
+    };
+    match run_with_timeout(remaining, parse) {
+        WorkerOutcome::Finished(result) => result,
+        WorkerOutcome::TimedOut => Err(ToolError {
+            kind: errors::TIMEOUT,
+            message: format!("parse timed out after {timeout_ms} ms"),
+        }),
+        WorkerOutcome::Panicked => Err(ToolError {
+            kind: errors::PARSE_FAILED,
+            message: "parser panicked on this input".to_string(),
+        }),
+    }
+}
+
+pub(crate) fn mime_from_extension(ext: &str) -> Option<&'static str> {
     match ext.to_ascii_lowercase().as_str() {
         "png" => Some("image/png"),
         "jpg" | "jpeg" => Some("image/jpeg"),
@@ -378,47 +674,551 @@ fn mime_from_extension(ext: &str) -> Option<&'static str> {
     }
 }
 
+/// `MAX_TABLE_ROWS` / `MAX_TABLE_COLS` as configured.
+fn table_limits() -> (usize, usize) {
+    let limits = limits();
+    (
+        usize::try_from(limits.max_table_rows).unwrap_or(usize::MAX),
+        usize::try_from(limits.max_table_cols).unwrap_or(usize::MAX),
+    )
+}
+
+/// Rebuilds a table from its control data and the cell paragraphs that follow paragraph
+/// `paragraph_index`. Malformed tables (more cells than remaining paragraphs, addresses outside
+/// the declared grid, oversized dimensions) degrade to empty or dropped cells with a warning.
+/// Returns the block and how many cell paragraphs it consumed.
+fn table_block(
+    section_index: usize,
+    paragraph_index: usize,
+    table: &Table,
+    paragraphs: &[Paragraph],
+    warnings: &mut Vec<String>,
+) -> (Value, usize) {
+    let location = format!("table at section {section_index} paragraph {paragraph_index}");
+    let declared_rows = usize::from(table.rows);
+    let declared_cols = usize::from(table.cols);
+    let (max_rows, max_cols) = table_limits();
+    let rows = declared_rows.min(max_rows);
+    let cols = declared_cols.min(max_cols);
+    if rows != declared_rows || cols != declared_cols {
+        warnings.push(format!(
+            "{location}: declared {declared_rows}x{declared_cols} grid exceeds the {max_rows}x{max_cols} limit; truncated to {rows}x{cols}"
+        ));
+    }
+
+    let mut cells = table.cells.iter().collect::<Vec<_>>();
+    cells.sort_by_key(|cell| (cell.cell_address.0, cell.cell_address.1));
+
+    let cell_para_start = paragraph_index.saturating_add(1);
+    let available = paragraphs.len().saturating_sub(cell_para_start);
+    let consumed = cells.len().min(available);
+    if consumed < cells.len() {
+        warnings.push(format!(
+            "{location}: expected {} cell paragraphs but only {available} remain; missing cells are left empty",
+            cells.len()
+        ));
+    }
+
+    let mut grid: Vec<Vec<String>> = vec![vec![String::new(); cols]; rows];
+    let mut spans: Vec<Value> = Vec::new();
+    let mut out_of_range = 0usize;
+    for (idx, cell) in cells.iter().enumerate() {
+        let r = usize::from(cell.cell_address.0);
+        let c = usize::from(cell.cell_address.1);
+        let text = if idx < consumed {
+            paragraphs
+                .get(cell_para_start + idx)
+                .map(paragraph_text)
+                .unwrap_or_default()
+        } else {
+            String::new()
+        };
+        match grid.get_mut(r).and_then(|row| row.get_mut(c)) {
+            Some(slot) => *slot = text,
+            None => out_of_range += 1,
+        }
+        if cell.row_span > 1 || cell.col_span > 1 {
+            spans.push(json!({
+                "row": cell.cell_address.0,
+                "col": cell.cell_address.1,
+                "row_span": cell.row_span,
+                "col_span": cell.col_span
+            }));
+        }
+    }
+    if out_of_range > 0 {
+        warnings.push(format!(
+            "{location}: {out_of_range} of {} cells are outside the {rows}x{cols} grid; dropped",
+            cells.len()
+        ));
+    }
+
+    let block = json!({
+        "type": "table",
+        "section_index": section_index,
+        "paragraph_index": paragraph_index,
+        "rows": grid,
+        "spans": spans,
+        "inferred": false,
+        "cells_count": cells.len()
+    });
+    (block, consumed)
+}
+
+/// The table owned by the empty paragraph `index`, if any: the block and the index of the
+/// first paragraph after the table. A table control on the anchor lists its cells and how many
+/// paragraphs each one holds, so the table takes exactly those paragraphs; without one, the
+/// non-empty paragraphs up to the next empty one become a table only when they look tabular.
+/// An anchor followed by an image caption (`그림:`) holds no table.
+pub(crate) fn anchored_table(
+    section_index: usize,
+    index: usize,
+    paragraphs: &[Paragraph],
+    controls: &SectionControls,
+    warnings: &mut Vec<String>,
+) -> Option<(Value, usize)> {
+    let is_empty = |at: usize| {
+        paragraphs
+            .get(at)
+            .is_some_and(|paragraph| paragraph_text(paragraph).trim().is_empty())
+    };
+    let is_caption = |at: usize| {
+        paragraphs
+            .get(at)
+            .is_some_and(|paragraph| paragraph_text(paragraph).trim_start().starts_with("그림:"))
+    };
+    if !is_empty(index) || is_caption(index + 1) {
+        return None;
+    }
+
+    let declared = controls.tables.get(&index);
+    if let Some(table) = declared.filter(|table| !table.cells.is_empty()) {
+        let (block, consumed) =
+            declared_table_block(section_index, index, table, paragraphs, warnings);
+        return Some((block, index.saturating_add(1).saturating_add(consumed)));
+    }
+
+    let end = (index + 1..paragraphs.len())
+        .find(|&at| is_empty(at))
+        .unwrap_or(paragraphs.len());
+    let cells: Vec<String> = paragraphs[index + 1..end]
+        .iter()
+        .map(|paragraph| paragraph_text(paragraph).trim().to_string())
+        .collect();
+    // Without a declared grid, only paragraphs that look tabular become a table; anything else
+    // (a blank line before a few lines of prose) stays as paragraphs.
+    let rows = match declared {
+        Some(table) if !cells.is_empty() => grid_rows(&cells, table.cols),
+        Some(_) => return None,
+        None => delimited_rows(&cells)?,
+    };
+    let block = json!({
+        "type": "table",
+        "section_index": section_index,
+        "paragraph_index": index,
+        "rows": rows,
+        "inferred": true,
+        "cells_count": cells.len()
+    });
+    // The empty paragraph closing the table goes with it, unless it anchors an image caption.
+    let next = if is_empty(end) && !is_caption(end + 1) {
+        end + 1
+    } else {
+        end
+    };
+    Some((block, next))
+}
+
+/// The cell texts of a table block, row by row.
+pub(crate) fn block_rows(block: &Value) -> Vec<Vec<String>> {
+    block
+        .get("rows")
+        .and_then(|v| v.as_array())
+        .map(|rows| {
+            rows.iter()
+                .map(|row| {
+                    row.as_array()
+                        .map(|cells| {
+                            cells
+                                .iter()
+                                .map(|c| c.as_str().unwrap_or("").to_string())
+                                .collect()
+                        })
+                        .unwrap_or_default()
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Builds a table from the cells its control declares. Each cell takes its own number of the
+/// paragraphs after the anchor (joined with newlines), so merged cells and cells holding several
+/// paragraphs line up; returns the block and the number of paragraphs consumed.
+fn declared_table_block(
+    section_index: usize,
+    paragraph_index: usize,
+    table: &DeclaredTable,
+    paragraphs: &[Paragraph],
+    warnings: &mut Vec<String>,
+) -> (Value, usize) {
+    let location = format!("table at section {section_index} paragraph {paragraph_index}");
+    let (max_rows, max_cols) = table_limits();
+    let rows = table.rows.min(max_rows);
+    let cols = table.cols.min(max_cols);
+    if rows != table.rows || cols != table.cols {
+        warnings.push(format!(
+            "{location}: declared {}x{} grid exceeds the {max_rows}x{max_cols} limit; truncated to {rows}x{cols}",
+            table.rows, table.cols
+        ));
+    }
+
+    let cell_para_start = paragraph_index.saturating_add(1);
+    let available = paragraphs.len().saturating_sub(cell_para_start);
+    let expected: usize = table.cells.iter().map(|cell| cell.paragraphs).sum();
+    if expected > available {
+        warnings.push(format!(
+            "{location}: expected {expected} cell paragraphs but only {available} remain; missing cells are left empty"
+        ));
+    }
+
+    let mut grid: Vec<Vec<String>> = vec![vec![String::new(); cols]; rows];
+    let mut spans: Vec<Value> = Vec::new();
+    let mut out_of_range = 0usize;
+    let mut next = cell_para_start;
+    for cell in &table.cells {
+        let end = next.saturating_add(cell.paragraphs).min(paragraphs.len());
+        let text = paragraphs
+            .get(next..end)
+            .unwrap_or_default()
+            .iter()
+            .map(paragraph_text)
+            .collect::<Vec<_>>()
+            .join("\n");
+        next = end;
+        match grid.get_mut(cell.row).and_then(|row| row.get_mut(cell.col)) {
+            Some(slot) => *slot = text,
+            None => out_of_range += 1,
+        }
+        if cell.row_span > 1 || cell.col_span > 1 {
+            spans.push(json!({
+                "row": cell.row,
+                "col": cell.col,
+                "row_span": cell.row_span,
+                "col_span": cell.col_span
+            }));
+        }
+    }
+    if out_of_range > 0 {
+        warnings.push(format!(
+            "{location}: {out_of_range} of {} cells are outside the {rows}x{cols} grid; dropped",
+            table.cells.len()
+        ));
+    }
+
+    let block = json!({
+        "type": "table",
+        "section_index": section_index,
+        "paragraph_index": paragraph_index,
+        "rows": grid,
+        "spans": spans,
+        "inferred": true,
+        "cells_count": table.cells.len()
+    });
+    (block, next - cell_para_start)
+}
+
 fn paragraph_text(paragraph: &hwpers::model::paragraph::Paragraph) -> String {
     match &paragraph.text {
-        Some(text) => text.content.clone(),
+        Some(text) => normalize_paragraph_text(&text.content).to_string(),
         None => String::new(),
     }
 }
 
-fn infer_table_dims(cell_count: usize) -> (usize, usize) {
-    if cell_count == 0 {
-        return (0, 0);
+/// Drops the paragraph-end CR/LF the parsers leave on paragraph text; line breaks inside the
+/// paragraph are kept.
+fn normalize_paragraph_text(text: &str) -> &str {
+    text.trim_end_matches(['\r', '\n'])
+}
+
+/// Lays inferred cells out row by row, `cols` to a row (all in one row when the declared
+/// column count is zero).
+fn grid_rows(cells: &[String], cols: usize) -> Vec<Vec<String>> {
+    let cols = if cols == 0 { cells.len() } else { cols };
+    cells
+        .chunks(cols.max(1))
+        .map(|row| {
+            let mut row = row.to_vec();
+            row.resize(cols, String::new());
+            row
+        })
+        .collect()
+}
+
+/// Splits paragraphs into table rows when each of them (at least two) holds the same nonzero
+/// number of tab or `|` delimiters.
+fn delimited_rows(lines: &[String]) -> Option<Vec<Vec<String>>> {
+    if lines.len() < 2 {
+        return None;
     }
+    ['\t', '|'].into_iter().find_map(|delimiter| {
+        let count = lines[0].matches(delimiter).count();
+        (count > 0
+            && lines
+                .iter()
+                .all(|line| line.matches(delimiter).count() == count))
+        .then(|| {
+            lines
+                .iter()
+                .map(|line| {
+                    line.split(delimiter)
+                        .map(|cell| cell.trim().to_string())
+                        .collect()
+                })
+                .collect()
+        })
+    })
+}
+
+/// Picks the image for a caption anchor: the BinItem `referenced` by its picture control when
+/// that image is still unplaced, otherwise the first unplaced image in order.
+fn take_image(images: &[BinData], placed: &mut [bool], referenced: Option<u16>) -> Option<usize> {
+    let by_reference = referenced.and_then(|bin_id| {
+        images
+            .iter()
+            .zip(placed.iter())
+            .position(|(bin, placed)| !placed && bin.bin_id == bin_id)
+    });
+    let index = by_reference.or_else(|| placed.iter().position(|placed| !placed))?;
+    placed[index] = true;
+    Some(index)
+}
 
-    let mut best_rows = 1usize;
-    let mut best_cols = cell_count;
-    let mut best_diff = best_cols.saturating_sub(best_rows);
+/// Controls hwpers drops from a section, keyed by the index of their anchor paragraph.
+#[derive(Default)]
+pub(crate) struct SectionControls {
+    tables: HashMap<usize, DeclaredTable>,
+    /// BinItem id of the first picture.
+    pictures: HashMap<usize, u16>,
+}
+
+/// A table control as declared by its TABLE and LIST_HEADER records.
+#[derive(Debug, Default, PartialEq)]
+struct DeclaredTable {
+    rows: usize,
+    cols: usize,
+    cells: Vec<DeclaredCell>,
+}
 
-    let mut r = 1usize;
-    while r * r <= cell_count {
-        if cell_count % r == 0 {
-            let c = cell_count / r;
-            let (rows, cols) = if r <= c { (r, c) } else { (c, r) };
-            let diff = cols.saturating_sub(rows);
-            if diff < best_diff {
-                best_rows = rows;
-                best_cols = cols;
-                best_diff = diff;
+#[derive(Debug, PartialEq)]
+struct DeclaredCell {
+    row: usize,
+    col: usize,
+    row_span: usize,
+    col_span: usize,
+    /// Paragraphs hwpers lists for the cell, which follow the anchor in cell order.
+    paragraphs: usize,
+}
+
+/// Table and picture controls of each `BodyText/Section{n}` stream. Empty when the streams
+/// cannot be read (e.g. distribution documents).
+fn hwp_section_controls(bytes: &[u8]) -> Vec<SectionControls> {
+    let Ok(sections) = HwpSections::open(bytes) else {
+        return Vec::new();
+    };
+    sections
+        .map(|records| records.map(section_controls).unwrap_or_default())
+        .collect()
+}
+
+/// Replays how hwpers splits a section stream into paragraphs (see [`HWPTAG_PARA_HEADER`] and
+/// [`HWPERS_PARA_HEADER`]) so each TABLE and picture record is keyed by the paragraph that is
+/// open when it appears: the control's anchor. A table's cells are the LIST_HEADER records at
+/// the TABLE record's level until the control ends (a record at or above the control header's
+/// level); each cell holds the paragraphs hwpers starts until the next cell, including those of
+/// tables nested in it.
+fn section_controls(records: Vec<Record>) -> SectionControls {
+    let mut controls = SectionControls::default();
+    let mut seen_section_def = false;
+    let mut closed = 0usize;
+    let mut open = false;
+    // Anchor index and TABLE record level of the table whose cells are being read.
+    let mut table: Option<(usize, u8)> = None;
+    for record in records {
+        let level = record.header.level;
+        if table.is_some_and(|(_, table_level)| level < table_level) {
+            table = None;
+        }
+        match record.tag_id() {
+            HWPTAG_PARA_HEADER if !seen_section_def => seen_section_def = true,
+            HWPTAG_PARA_HEADER | HWPERS_PARA_HEADER => {
+                closed += usize::from(open);
+                open = record.tag_id() == HWPTAG_PARA_HEADER
+                    || record.data.len() >= HWPERS_PARA_HEADER_LEN;
+                if open
+                    && let Some(cell) = table
+                        .and_then(|(anchor, _)| controls.tables.get_mut(&anchor))
+                        .and_then(|declared| declared.cells.last_mut())
+                {
+                    cell.paragraphs += 1;
+                }
+            }
+            HWPTAG_TABLE
+                if open && table.is_none() && record.data.len() >= TABLE_COLS_OFFSET + 2 =>
+            {
+                let field = |offset: usize| {
+                    usize::from(u16::from_le_bytes([
+                        record.data[offset],
+                        record.data[offset + 1],
+                    ]))
+                };
+                if let std::collections::hash_map::Entry::Vacant(entry) =
+                    controls.tables.entry(closed)
+                {
+                    entry.insert(DeclaredTable {
+                        rows: field(TABLE_ROWS_OFFSET),
+                        cols: field(TABLE_COLS_OFFSET),
+                        cells: Vec::new(),
+                    });
+                    table = Some((closed, level));
+                }
+            }
+            HWPTAG_LIST_HEADER
+                if record.data.len() >= CELL_ADDRESS_OFFSET + 8
+                    && table.is_some_and(|(_, table_level)| level == table_level) =>
+            {
+                let field = |index: usize| {
+                    let offset = CELL_ADDRESS_OFFSET + index * 2;
+                    usize::from(u16::from_le_bytes([
+                        record.data[offset],
+                        record.data[offset + 1],
+                    ]))
+                };
+                if let Some(declared) =
+                    table.and_then(|(anchor, _)| controls.tables.get_mut(&anchor))
+                {
+                    declared.cells.push(DeclaredCell {
+                        col: field(0),
+                        row: field(1),
+                        col_span: field(2).max(1),
+                        row_span: field(3).max(1),
+                        paragraphs: 0,
+                    });
+                }
+            }
+            HWPTAG_SHAPE_COMPONENT_PICTURE
+                if open && record.data.len() >= PICTURE_BIN_ID_OFFSET + 2 =>
+            {
+                let bin_id = u16::from_le_bytes([
+                    record.data[PICTURE_BIN_ID_OFFSET],
+                    record.data[PICTURE_BIN_ID_OFFSET + 1],
+                ]);
+                controls.pictures.entry(closed).or_insert(bin_id);
+            }
+            _ => {}
+        }
+    }
+    controls
+}
+
+/// Picture references of each `Contents/section{n}.xml`, counted over top-level `<hp:p>`
+/// elements as the HWPX reader does. A `binaryItemIDRef` is resolved through the package
+/// manifest (`<opf:item href>`) or the header's `<hh:binDataItem src>` to the `BinData/` entry
+/// it names, and from there to that image's `bin_id`; an id matching an entry's file stem is
+/// taken as naming it directly.
+fn hwpx_section_controls(bytes: &[u8], images: &[BinData]) -> Vec<SectionControls> {
+    let Ok(mut archive) = zip::ZipArchive::new(Cursor::new(bytes)) else {
+        return Vec::new();
+    };
+    let mut manifest = String::new();
+    for name in ["Contents/content.hpf", "Contents/header.xml"] {
+        if let Ok(mut file) = archive.by_name(name) {
+            let _ = file.read_to_string(&mut manifest);
+        }
+    }
+    let resolve = |item_id: &str| {
+        let href = xml_tags(&manifest)
+            .filter(|tag| xml_attribute(tag, "id") == Some(item_id))
+            .find_map(|tag| {
+                if tag.starts_with("opf:item ") {
+                    xml_attribute(tag, "href")
+                } else if tag.starts_with("hh:binDataItem ") {
+                    xml_attribute(tag, "src")
+                } else {
+                    None
+                }
+            });
+        images.iter().find_map(|bin| {
+            let stem = bin
+                .rel_name
+                .rsplit('/')
+                .next()
+                .and_then(|name| name.split('.').next());
+            (href == Some(bin.rel_name.as_str()) || stem == Some(item_id)).then_some(bin.bin_id)
+        })
+    };
+
+    let mut sections = Vec::new();
+    loop {
+        let name = format!("Contents/section{}.xml", sections.len());
+        let Ok(mut file) = archive.by_name(&name) else {
+            break;
+        };
+        let mut xml = String::new();
+        if file.read_to_string(&mut xml).is_err() {
+            sections.push(SectionControls::default());
+            continue;
+        }
+        let mut controls = SectionControls::default();
+        let mut depth = 0usize;
+        let mut paragraph_count = 0usize;
+        for tag in xml_tags(&xml) {
+            let name = tag
+                .trim_start_matches('/')
+                .split(|ch: char| ch.is_whitespace() || ch == '/')
+                .next()
+                .unwrap_or("");
+            match (name, tag.starts_with('/')) {
+                ("hp:p", false) if !tag.ends_with('/') => depth += 1,
+                ("hp:p", false) => paragraph_count += usize::from(depth == 0),
+                ("hp:p", true) => {
+                    depth = depth.saturating_sub(1);
+                    paragraph_count += usize::from(depth == 0);
+                }
+                ("hp:img" | "hc:img", false) if depth > 0 => {
+                    if let Some(bin_id) = xml_attribute(tag, "binaryItemIDRef").and_then(resolve) {
+                        controls.pictures.entry(paragraph_count).or_insert(bin_id);
+                    }
+                }
+                _ => {}
             }
         }
-        r += 1;
+        sections.push(controls);
     }
+    sections
+}
 
-    (best_rows, best_cols)
+/// The inside of each `<...>` tag in `xml`, in document order.
+fn xml_tags(xml: &str) -> impl Iterator<Item = &str> {
+    xml.split('<')
+        .skip(1)
+        .filter_map(|chunk| chunk.split_once('>').map(|(tag, _)| tag))
 }
 
-struct ImageRenderContext<'a> {
-    images_mode: &'a str,
-    max_image_bytes: u64,
-    total_inline_image_bytes: &'a mut u64,
-    source: &'a str,
-    warnings: &'a mut Vec<String>,
-    output_path: &'a Option<String>,
+/// Value of attribute `name` in a tag's inside, without unescaping.
+fn xml_attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let start = tag.find(&format!(" {name}=\""))? + name.len() + 3;
+    let len = tag[start..].find('"')?;
+    Some(&tag[start..start + len])
+}
+
+pub(crate) struct ImageRenderContext<'a> {
+    pub(crate) images_mode: &'a str,
+    pub(crate) max_image_bytes: u64,
+    pub(crate) total_inline_image_bytes: &'a mut u64,
+    pub(crate) source: &'a str,
+    pub(crate) warnings: &'a mut Vec<String>,
+    pub(crate) output_path: &'a Option<String>,
+    pub(crate) compress: Compress,
 }
 
 fn image_block_from_bin(
@@ -452,6 +1252,21 @@ fn image_block_from_bin(
         obj.insert("caption".to_string(), json!(caption));
     }
 
+    attach_image_data(bin, &bytes, &mut block, ctx)?;
+    Ok(block)
+}
+
+/// Adds the image bytes to `block` according to `ctx.images_mode`: `base64` when inline
+/// (compressed with `ctx.compress` and counted against the output limit after compression),
+/// `path`/`uri` of a written file for resource.
+pub(crate) fn attach_image_data(
+    bin: &BinData,
+    bytes: &[u8],
+    block: &mut Value,
+    ctx: &mut ImageRenderContext<'_>,
+) -> Result<(), Value> {
+    let bin_id = bin.bin_id;
+    let bytes_len = bytes.len() as u64;
     match ctx.images_mode {
         "none" => {}
         "metadata" => {}
@@ -462,19 +1277,21 @@ fn image_block_from_bin(
                     ctx.max_image_bytes
                 ));
             } else {
-                *ctx.total_inline_image_bytes += bytes_len;
-                if *ctx.total_inline_image_bytes > MAX_OUTPUT_BYTES {
+                let payload = ctx.compress.apply(bytes);
+                *ctx.total_inline_image_bytes += payload.len() as u64;
+                let max_output_bytes = limits().max_output_bytes;
+                if *ctx.total_inline_image_bytes > max_output_bytes {
                     return Err(error_result(
                         errors::TOO_LARGE,
                         format!(
-                            "inline images exceed output limit: {} bytes (max {MAX_OUTPUT_BYTES})",
+                            "inline images exceed output limit: {} bytes (max {max_output_bytes})",
                             *ctx.total_inline_image_bytes
                         ),
                         Some(ctx.source),
                     ));
                 }
                 if let Some(obj) = block.as_object_mut() {
-                    obj.insert("base64".to_string(), json!(STANDARD.encode(&bytes)));
+                    obj.insert("base64".to_string(), json!(STANDARD.encode(&payload)));
                 }
             }
         }
@@ -484,7 +1301,7 @@ fn image_block_from_bin(
             } else {
                 bin.extension.as_str()
             };
-            let path = write_image_file(bin_id, ext, &bytes, ctx.output_path).map_err(|err| {
+            let path = write_image_file(bin_id, ext, bytes, ctx.output_path).map_err(|err| {
                 error_result(
                     errors::INTERNAL_ERROR,
                     format!("failed to write image bin_id={bin_id}: {err}"),
@@ -506,7 +1323,7 @@ fn image_block_from_bin(
         }
     }
 
-    Ok(block)
+    Ok(())
 }
 
 fn write_image_file(
@@ -515,65 +1332,123 @@ fn write_image_file(
     bytes: &[u8],
     output_path: &Option<String>,
 ) -> Result<PathBuf, String> {
-    let mut path = if let Some(custom_path) = output_path {
-        let custom = PathBuf::from(custom_path);
-        fs::create_dir_all(&custom).map_err(|e| e.to_string())?;
-        custom
-    } else {
-        let mut temp = std::env::temp_dir();
-        temp.push("mcp-hwp");
-        fs::create_dir_all(&temp).map_err(|e| e.to_string())?;
-        temp
-    };
+    let mut path = match output_path {
+        Some(custom_path) => resources::output_dir(Some(custom_path)),
+        None => resources::output_dir(None).and_then(|dir| {
+            let dir = dir.join("mcp-hwp");
+            fs::create_dir_all(&dir)?;
+            Ok(dir)
+        }),
+    }
+    .map_err(|e| e.to_string())?;
 
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map_err(|e| e.to_string())?
-        .as_millis();
-    let pid = std::process::id();
-    let filename = format!("image-{pid}-{now}-{bin_id}.{ext}");
-    path.push(filename);
+    let suffix = resources::unique_suffix();
+    path.push(format!("image-{suffix}-{bin_id}.{ext}"));
     fs::write(&path, bytes).map_err(|e| e.to_string())?;
+    if output_path.is_none() {
+        resources::track(&path);
+    }
     Ok(path)
 }
 
-fn map_hwp_error(error: HwpError) -> ToolError {
-    match error {
-        HwpError::UnsupportedVersion(message) => {
-            if message.contains("Password-encrypted") {
-                ToolError {
-                    kind: errors::ENCRYPTED,
-                    message,
-                }
-            } else {
-                ToolError {
-                    kind: errors::PARSE_FAILED,
-                    message,
-                }
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hwpers::model::ParaText;
+
+    fn text_paragraph(text: &str) -> Paragraph {
+        Paragraph {
+            text: Some(ParaText {
+                content: text.to_string(),
+            }),
+            ..Paragraph::default()
         }
-        HwpError::InvalidInput(message) => ToolError {
-            kind: errors::INVALID_INPUT,
-            message,
-        },
-        HwpError::Io(err) => ToolError {
-            kind: errors::INVALID_INPUT,
-            message: err.to_string(),
-        },
-        HwpError::InvalidFormat(message)
-        | HwpError::Cfb(message)
-        | HwpError::CompressionError(message)
-        | HwpError::ParseError(message)
-        | HwpError::EncodingError(message)
-        | HwpError::NotFound(message) => ToolError {
-            kind: errors::PARSE_FAILED,
-            message,
-        },
     }
-}
 
-fn map_hwp_error_with_format(error: HwpError, format: &str) -> ToolError {
-    let mut mapped = map_hwp_error(error);
-    mapped.message = format!("{format} parse failed: {}", mapped.message);
-    mapped
+    #[test]
+    fn table_with_more_cells_than_paragraphs_warns_without_panicking() {
+        let mut table = Table::new_default(2, 2);
+        for (row, col) in [(0, 0), (0, 1), (1, 0), (1, 1), (5, 7)] {
+            table.create_cell(row, col, 1000, 1000);
+        }
+        let paragraphs = vec![
+            Paragraph {
+                table_data: Some(table.clone()),
+                ..Paragraph::default()
+            },
+            text_paragraph("A"),
+            text_paragraph("B"),
+        ];
+
+        let mut warnings = Vec::new();
+        let (block, consumed) = table_block(0, 0, &table, &paragraphs, &mut warnings);
+
+        assert_eq!(consumed, 2);
+        assert_eq!(block["rows"], json!([["A", "B"], ["", ""]]));
+        assert_eq!(block["cells_count"], json!(5));
+        assert_eq!(
+            warnings,
+            vec![
+                "table at section 0 paragraph 0: expected 5 cell paragraphs but only 2 remain; missing cells are left empty",
+                "table at section 0 paragraph 0: 1 of 5 cells are outside the 2x2 grid; dropped",
+            ]
+        );
+    }
+
+    #[test]
+    fn html_table_clamps_spans_to_the_grid() {
+        let block = json!({
+            "rows": [["a", "b"], ["c", "d"]],
+            "spans": [
+                {"row": 0, "col": 0, "row_span": 65535, "col_span": 65535},
+                {"row": 40000, "col": 0, "row_span": 65535, "col_span": 1}
+            ]
+        });
+        let mut html = String::new();
+        render_html_table(&block, &mut html);
+        assert_eq!(
+            html,
+            "<table border=\"1\">\n<tr><td rowspan=\"2\" colspan=\"2\">a</td></tr>\n<tr></tr>\n</table>\n"
+        );
+    }
+
+    #[test]
+    fn grid_rows_fills_declared_columns() {
+        let cells: Vec<String> = ["a", "b", "c", "d", "e"].map(String::from).to_vec();
+        assert_eq!(
+            grid_rows(&cells, 2),
+            [vec!["a", "b"], vec!["c", "d"], vec!["e", ""]]
+        );
+        assert_eq!(grid_rows(&cells[..4], 4), [vec!["a", "b", "c", "d"]]);
+        assert_eq!(grid_rows(&cells[..2], 0), [vec!["a", "b"]]);
+    }
+
+    #[test]
+    fn paragraph_text_drops_trailing_line_endings() {
+        assert_eq!(paragraph_text(&text_paragraph("본문\r\n")), "본문");
+        assert_eq!(
+            paragraph_text(&text_paragraph("그림: 로고\r")),
+            "그림: 로고"
+        );
+        assert_eq!(paragraph_text(&text_paragraph("a\nb\r")), "a\nb");
+        assert_eq!(paragraph_text(&text_paragraph("\r")), "");
+    }
+
+    #[test]
+    fn only_tabular_paragraphs_are_split_on_delimiters() {
+        let lines = |texts: &[&str]| texts.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            delimited_rows(&lines(&["a\tb", "1\t2"])),
+            Some(vec![
+                vec!["a".to_string(), "b".to_string()],
+                vec!["1".to_string(), "2".to_string()]
+            ])
+        );
+        assert_eq!(delimited_rows(&lines(&["a | b", "c"])), None);
+        assert_eq!(delimited_rows(&lines(&["a | b"])), None);
+        assert_eq!(
+            delimited_rows(&lines(&["Dear team,", "see you soon."])),
+            None
+        );
+    }
 }
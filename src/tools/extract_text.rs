@@ -1,10 +1,26 @@
-use crate::input::{InputFormat, load_input};
+use crate::input::{InputFormat, is_distribution_document, load_input};
+use crate::mcp::contracts::limits;
 use crate::mcp::errors;
-use crate::tools::error_result;
-use hwpers::{HwpError, HwpReader, HwpxReader};
-use serde_json::{Value, json};
+use crate::mcp::resources;
+use crate::tools::extract_images::document_images;
+use crate::tools::extract_notes::{Note, document_notes};
+use crate::tools::extract_rich::{SectionControls, anchored_table, block_rows, container_controls};
+use crate::tools::{
+    ToolError, WorkerOutcome, check_arguments, error_result, parse_document_blocking,
+    run_with_timeout, write_file_atomically,
+};
+use hwpers::model::bin_data::BinData;
+use serde_json::{Map, Value, json};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::time::{Duration, Instant};
 
 pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let started = Instant::now();
     let payload = match load_input(args) {
         Ok(payload) => payload,
         Err(err) => return error_result(err.kind, err.message, None),
@@ -19,97 +35,1093 @@ pub fn call(args: &Value) -> Value {
         .and_then(|value| value.as_bool())
         .unwrap_or(false);
     let max_chars = args.get("max_chars").and_then(|value| value.as_u64());
+    let footnotes = match FootnoteMode::parse(args.get("footnotes")) {
+        Ok(footnotes) => footnotes,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+    let table_format = match TableFormat::parse(args.get("table_format")) {
+        Ok(table_format) => table_format,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+    let representations = match parse_representations(args.get("representations")) {
+        Ok(representations) => representations,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+    let output_path = match parse_output_path(args.get("output_path")) {
+        Ok(output_path) => output_path,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+    let granularity = match Granularity::parse(args.get("granularity")) {
+        Ok(granularity) => granularity,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+    let by_section = granularity == Granularity::Section
+        || args
+            .get("by_section")
+            .and_then(|value| value.as_bool())
+            .unwrap_or(false);
+    let ocr = args
+        .get("ocr")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false);
+    let timeout_ms = match parse_timeout_ms(args.get("timeout_ms")) {
+        Ok(timeout_ms) => timeout_ms,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+    let partial_on_timeout = args
+        .get("partial_on_timeout")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false);
+    // Text is gathered without a deadline unless the caller sets timeout_ms.
+    let deadline = timeout_ms.map(|timeout_ms| started + Duration::from_millis(timeout_ms));
 
-    let document = match parse_document(&payload.bytes, payload.format) {
-        Ok(document) => document,
+    // The parse is held to the parse time limit, or to timeout_ms when one is set. A partial
+    // result still needs a parsed document, so with partial_on_timeout it gets the longer one.
+    let parse_timeout_ms = match timeout_ms {
+        Some(timeout_ms) if partial_on_timeout => timeout_ms.max(limits().max_parse_ms),
+        Some(timeout_ms) => timeout_ms,
+        None => limits().max_parse_ms,
+    };
+    let parse_deadline = started + Duration::from_millis(parse_timeout_ms);
+    let mut parsed = match parse_document(
+        &payload.bytes,
+        payload.format,
+        parse_deadline,
+        parse_timeout_ms,
+        table_format != TableFormat::Ignore || representations.contains(&Representation::Markdown),
+        footnotes.is_some(),
+    ) {
+        Ok(parsed) => parsed,
+        Err(err) if err.kind != errors::TIMEOUT && is_distribution_document(&payload.bytes) => {
+            return error_result(
+                errors::ENCRYPTED,
+                format!(
+                    "distribution document (배포용 문서): content could not be decrypted for reading ({})",
+                    err.message
+                ),
+                Some(payload.source.as_str()),
+            );
+        }
         Err(err) => {
             return error_result(err.kind, err.message, Some(payload.source.as_str()));
         }
     };
+    if parsed.document.is_distribution_document() {
+        parsed.warnings.push(
+            "distribution document (배포용 문서): opened read-only; editing tools refuse it"
+                .to_string(),
+        );
+    }
+
+    let notes = footnotes.map(|mode| NoteLayout::new(mode, std::mem::take(&mut parsed.notes)));
+
+    let gathered = gather_text(
+        &parsed.document,
+        &parsed.controls,
+        notes.as_ref(),
+        deadline,
+        table_format,
+        &mut parsed.warnings,
+    );
+    let timed_out = gathered.paragraph_limit.is_some();
+    if let Some(timeout_ms) = timeout_ms.filter(|_| timed_out) {
+        if !partial_on_timeout {
+            return error_result(
+                errors::TIMEOUT,
+                format!(
+                    "extraction timed out after {timeout_ms} ms; set partial_on_timeout to return the text gathered so far"
+                ),
+                Some(payload.source.as_str()),
+            );
+        }
+        parsed.warnings.push(format!(
+            "partial: extraction timed out after {timeout_ms} ms; returning text from the first {} paragraphs",
+            gathered.paragraphs
+        ));
+    }
+    // Sections are taken from the document text before OCR could replace it.
+    let sections: Option<Vec<Value>> = by_section.then(|| {
+        gathered
+            .sections()
+            .map(|(section_index, text)| {
+                let normalized = normalize_text(
+                    text,
+                    include_newlines,
+                    normalize_whitespace,
+                    table_format == TableFormat::Tsv,
+                );
+                json!({
+                    "section_index": section_index,
+                    "text": apply_max_chars(normalized, max_chars)
+                })
+            })
+            .collect()
+    });
+    let paragraphs = (granularity == Granularity::Paragraph)
+        .then(|| paragraph_spans(&parsed.document, gathered.paragraph_limit));
+    let mut document_text = gathered.text;
+    let mut text_source = "document";
+    let mut ocr_report: Option<Value> = None;
+    // Only a document without text can be image-based, so only then are the images read.
+    let images = if document_text.trim().is_empty() {
+        match document_images(&parsed.document, parsed.format, &payload.bytes) {
+            Ok(images) => images,
+            Err(err) => {
+                parsed
+                    .warnings
+                    .push(format!("failed to read embedded images: {err}"));
+                Vec::new()
+            }
+        }
+    } else {
+        Vec::new()
+    };
+    let image_count = images.len();
+    let likely_scanned = image_count > 0;
+    if likely_scanned {
+        parsed.warnings.push(format!(
+            "no text found; document appears to be image-based ({image_count} images); use hwp.extract_rich to get the images for OCR"
+        ));
+    }
+    if ocr {
+        if timed_out {
+            parsed
+                .warnings
+                .push("ocr: skipped; extraction timed out".to_string());
+        } else if likely_scanned {
+            if let Some(recognized) = run_ocr(&images, &mut parsed.warnings) {
+                document_text = recognized.text;
+                text_source = "ocr";
+                ocr_report = Some(json!({
+                    "backend": recognized.backend,
+                    "images": recognized.images
+                }));
+            }
+        } else {
+            parsed
+                .warnings
+                .push("ocr: skipped; document has extractable text".to_string());
+        }
+    }
 
-    let text = document.extract_text();
-    let normalized = normalize_text(&text, include_newlines, normalize_whitespace);
-    let truncated = apply_max_chars(normalized, max_chars);
+    // Every representation is derived from the single parse above.
+    let mut structured = Map::new();
+    let mut primary: Option<String> = None;
+    let mut written: Option<OutputResource> = None;
+    for representation in &representations {
+        let rendered = match representation {
+            Representation::Text => {
+                let normalized = normalize_text(
+                    &document_text,
+                    include_newlines,
+                    normalize_whitespace,
+                    table_format == TableFormat::Tsv,
+                );
+                apply_max_chars(normalized, max_chars)
+            }
+            Representation::Markdown => apply_max_chars(
+                render_markdown(
+                    &parsed.document,
+                    &parsed.controls,
+                    notes.as_ref(),
+                    gathered.paragraph_limit,
+                    &mut parsed.warnings,
+                ),
+                max_chars,
+            ),
+        };
+        if primary.is_none() {
+            // With output_path the first representation goes to the file instead of the response.
+            if let Some(path) = output_path.as_deref() {
+                let output = match write_output(path, &rendered, *representation) {
+                    Ok(output) => output,
+                    Err(err) => return error_result(err.kind, err.message, None),
+                };
+                structured.insert("path".to_string(), json!(output.path));
+                structured.insert("uri".to_string(), json!(output.uri));
+                structured.insert("bytes_len".to_string(), json!(output.bytes_len));
+                written = Some(output);
+                primary = Some(String::new());
+                continue;
+            }
+            primary = Some(rendered.clone());
+        }
+        structured.insert(representation.as_str().to_string(), json!(rendered));
+    }
+    if let Some(sections) = sections {
+        structured.insert("sections".to_string(), json!(sections));
+    }
+    if let Some(paragraphs) = paragraphs {
+        structured.insert("paragraphs".to_string(), json!(paragraphs));
+    }
+    structured.insert("detected_format".to_string(), json!(parsed.format.as_str()));
+    structured.insert(
+        "detection_method".to_string(),
+        json!(payload.detection_method),
+    );
+    structured.insert("source".to_string(), json!(text_source));
+    if let Some(ocr_report) = ocr_report {
+        structured.insert("ocr".to_string(), ocr_report);
+    }
+    structured.insert("likely_scanned".to_string(), json!(likely_scanned));
+    structured.insert("timed_out".to_string(), json!(timed_out));
+    structured.insert("warnings".to_string(), json!(parsed.warnings));
+
+    let content = match written {
+        Some(output) => output.content,
+        None => vec![json!({"type": "text", "text": primary.unwrap_or_default()})],
+    };
 
     json!({
-        "content": [{"type": "text", "text": truncated}],
-        "structuredContent": {"text": truncated},
+        "content": content,
+        "structuredContent": structured,
         "isError": false
     })
 }
 
-struct ToolError {
-    kind: &'static str,
-    message: String,
+struct OutputResource {
+    path: String,
+    uri: String,
+    bytes_len: u64,
+    content: Vec<Value>,
+}
+
+fn parse_output_path(value: Option<&Value>) -> Result<Option<String>, ToolError> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    let Some(path) = value.as_str() else {
+        return Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "output_path must be a string".to_string(),
+        });
+    };
+    if path.trim().is_empty() {
+        return Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "output_path must not be empty".to_string(),
+        });
+    }
+    Ok(Some(path.to_string()))
+}
+
+fn write_output(
+    path: &str,
+    text: &str,
+    representation: Representation,
+) -> Result<OutputResource, ToolError> {
+    write_file_atomically(Path::new(path), text.as_bytes()).map_err(|err| ToolError {
+        kind: errors::INTERNAL_ERROR,
+        message: format!("failed to write output: {err}"),
+    })?;
+    let bytes_len = text.len() as u64;
+
+    let mime_type = match representation {
+        Representation::Text => "text/plain",
+        Representation::Markdown => "text/markdown",
+    };
+    let uri = resources::register(path, mime_type);
+    let name = Path::new(path)
+        .file_name()
+        .and_then(|value| value.to_str())
+        .unwrap_or("extracted");
+
+    let content = vec![
+        json!({
+            "type": "text",
+            "text": format!("extracted {} written to {path} ({bytes_len} bytes)", representation.as_str())
+        }),
+        json!({
+            "type": "resource_link",
+            "uri": uri,
+            "name": name,
+            "mimeType": mime_type
+        }),
+    ];
+
+    Ok(OutputResource {
+        path: path.to_string(),
+        uri,
+        bytes_len,
+        content,
+    })
+}
+
+struct GatheredText {
+    text: String,
+    /// Byte offset in `text` where each section reached starts.
+    section_starts: Vec<usize>,
+    paragraphs: usize,
+    /// Document-wide paragraph count reached before the deadline, if it elapsed.
+    paragraph_limit: Option<usize>,
+}
+
+impl GatheredText {
+    /// The text of each section reached, with its section index.
+    fn sections(&self) -> impl Iterator<Item = (usize, &str)> {
+        let ends = self
+            .section_starts
+            .iter()
+            .skip(1)
+            .copied()
+            .chain(std::iter::once(self.text.len()));
+        self.section_starts
+            .iter()
+            .zip(ends)
+            .enumerate()
+            .map(|(index, (&start, end))| (index, &self.text[start..end]))
+    }
 }
 
-fn parse_document(bytes: &[u8], format: InputFormat) -> Result<hwpers::HwpDocument, ToolError> {
-    match format {
-        InputFormat::Hwp => HwpReader::from_bytes(bytes)
-            .map_err(|error| map_hwp_error_with_format(error, format.as_str())),
-        InputFormat::Hwpx => HwpxReader::from_bytes(bytes)
-            .map_err(|error| map_hwp_error_with_format(error, format.as_str())),
-        InputFormat::Auto => {
-            let hwp_result = HwpReader::from_bytes(bytes);
-            match hwp_result {
-                Ok(doc) => Ok(doc),
-                Err(hwp_err) => match HwpxReader::from_bytes(bytes) {
-                    Ok(doc) => Ok(doc),
-                    Err(hwpx_err) => Err(ToolError {
-                        kind: errors::PARSE_FAILED,
-                        message: format!(
-                            "auto format parse failed (hwp: {}; hwpx: {})",
-                            hwp_err, hwpx_err
-                        ),
-                    }),
-                },
+/// Collects paragraph text (as `HwpDocument::extract_text` does) until the deadline, if any,
+/// passes.
+/// Unless `table_format` is `Ignore`, each table and its cell paragraphs are replaced by
+/// the rendered table. With `notes`, footnotes and endnotes are placed per their layout.
+/// At least one paragraph is always processed.
+fn gather_text(
+    document: &hwpers::HwpDocument,
+    controls: &[SectionControls],
+    notes: Option<&NoteLayout>,
+    deadline: Option<Instant>,
+    table_format: TableFormat,
+    warnings: &mut Vec<String>,
+) -> GatheredText {
+    let mut text = String::new();
+    let mut section_starts = Vec::new();
+    let mut paragraphs = 0usize;
+    let mut paragraph_limit = None;
+    let mut trailing = Vec::new();
+    let no_controls = SectionControls::default();
+    'sections: for (section_index, section) in document.sections().enumerate() {
+        let controls = controls.get(section_index).unwrap_or(&no_controls);
+        section_starts.push(text.len());
+        let section_paragraphs = &section.paragraphs;
+        let mut i = 0;
+        while i < section_paragraphs.len() {
+            if paragraphs > 0 && deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                paragraph_limit = Some(paragraphs);
+                break 'sections;
             }
+            let paragraph = &section_paragraphs[i];
+            if notes.is_some_and(|notes| notes.is_body(section_index, i)) {
+                paragraphs += 1;
+                i += 1;
+                continue;
+            }
+            if table_format != TableFormat::Ignore
+                && let Some((grid, next)) =
+                    table_at(section_index, section_paragraphs, i, controls, warnings)
+            {
+                if grid.first().is_some_and(|row| !row.is_empty()) {
+                    let rendered = match table_format {
+                        TableFormat::Markdown => markdown_table(&grid),
+                        _ => tsv_table(&grid),
+                    };
+                    text.push_str(&rendered);
+                    text.push('\n');
+                }
+                paragraphs += next - i;
+                i = next;
+                continue;
+            }
+            if let Some(paragraph_text) = &paragraph.text {
+                match notes {
+                    Some(notes) => {
+                        let (content, lines) = notes.annotate(
+                            section_index,
+                            i,
+                            &paragraph_text.content,
+                            &mut trailing,
+                            warnings,
+                        );
+                        text.push_str(&content);
+                        text.push('\n');
+                        for line in lines {
+                            text.push_str(&line);
+                            text.push('\n');
+                        }
+                    }
+                    None => {
+                        text.push_str(&paragraph_text.content);
+                        text.push('\n');
+                    }
+                }
+            }
+            paragraphs += 1;
+            i += 1;
         }
     }
+    if let Some(notes) = notes {
+        trailing.extend(notes.unanchored.iter().cloned());
+    }
+    if !trailing.is_empty() && !text.is_empty() {
+        text.push('\n');
+    }
+    for line in trailing {
+        text.push_str(&line);
+        text.push('\n');
+    }
+    GatheredText {
+        text,
+        section_starts,
+        paragraphs,
+        paragraph_limit,
+    }
+}
+
+/// Footnotes and endnotes placed per the `footnotes` option. hwpers lists HWP note bodies
+/// among the section's paragraphs, so those paragraphs are left out and each note is placed
+/// from the container scan instead.
+struct NoteLayout {
+    mode: FootnoteMode,
+    notes: Vec<Note>,
+    /// Notes anchored at each (section, paragraph), as indices into `notes`.
+    anchored: HashMap<(usize, usize), Vec<usize>>,
+    bodies: HashSet<(usize, usize)>,
+    /// Lines of notes whose anchor was not found; they close the text unless omitted.
+    unanchored: Vec<String>,
 }
 
-fn map_hwp_error(error: HwpError) -> ToolError {
-    match error {
-        HwpError::UnsupportedVersion(message) => {
-            if message.contains("Password-encrypted") {
-                ToolError {
-                    kind: errors::ENCRYPTED,
-                    message,
+impl NoteLayout {
+    fn new(mode: FootnoteMode, notes: Vec<Note>) -> Self {
+        let mut anchored: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        let mut bodies = HashSet::new();
+        let mut unanchored = Vec::new();
+        for (index, note) in notes.iter().enumerate() {
+            let Some(anchor) = &note.anchor else {
+                if mode != FootnoteMode::Omit {
+                    unanchored.push(format!("[{}] {}", note.number, note.text));
                 }
+                continue;
+            };
+            anchored
+                .entry((anchor.section_index, anchor.paragraph_index))
+                .or_default()
+                .push(index);
+            bodies.extend(
+                note.body_paragraphs
+                    .iter()
+                    .map(|paragraph| (anchor.section_index, *paragraph)),
+            );
+        }
+        NoteLayout {
+            mode,
+            notes,
+            anchored,
+            bodies,
+            unanchored,
+        }
+    }
+
+    fn is_body(&self, section_index: usize, paragraph_index: usize) -> bool {
+        self.bodies.contains(&(section_index, paragraph_index))
+    }
+
+    /// The paragraph `content` with a `[n]` marker per note anchored in it, and the
+    /// `[n] text` lines of those notes: returned to follow the paragraph when inline, queued
+    /// in `trailing` as endnotes, dropped with the markers when omitted.
+    fn annotate(
+        &self,
+        section_index: usize,
+        paragraph_index: usize,
+        content: &str,
+        trailing: &mut Vec<String>,
+        warnings: &mut Vec<String>,
+    ) -> (String, Vec<String>) {
+        let Some(indices) = self.anchored.get(&(section_index, paragraph_index)) else {
+            return (content.to_string(), Vec::new());
+        };
+        let (body, ending) = match content.strip_suffix('\r') {
+            Some(body) => (body, "\r"),
+            None => (content, ""),
+        };
+        let chars: Vec<char> = body.chars().collect();
+        let mut annotated = String::new();
+        // Markers of notes without a known position close the paragraph.
+        let mut closing = String::new();
+        let mut cursor = 0;
+        // Chars of folded note bodies skipped so far; positions count the text without them.
+        let mut folded = 0;
+        let mut lines = Vec::new();
+        for note in indices.iter().map(|index| &self.notes[*index]) {
+            let marker = if self.mode == FootnoteMode::Omit {
+                String::new()
             } else {
-                ToolError {
-                    kind: errors::PARSE_FAILED,
-                    message,
+                format!("[{}]", note.number)
+            };
+            let at = note
+                .position
+                .map(|position| position + folded)
+                .filter(|at| (cursor..=chars.len()).contains(at));
+            let mut located = false;
+            match at {
+                Some(at) => {
+                    annotated.extend(&chars[cursor..at]);
+                    cursor = at;
+                    // The HWPX reader may fold the note body into its anchor paragraph's text
+                    // at the control.
+                    let rest: String = chars[at..].iter().collect();
+                    let body_start = rest.trim_start();
+                    if !note.text.is_empty() && body_start.starts_with(&note.text) {
+                        let skipped = rest[..rest.len() - body_start.len()].chars().count()
+                            + note.text.chars().count();
+                        cursor += skipped;
+                        folded += skipped;
+                        located = true;
+                    }
+                    annotated.push_str(&marker);
                 }
+                None => closing.push_str(&marker),
+            }
+            if !located
+                && !note.text.is_empty()
+                && note.body_paragraphs.is_empty()
+                && body.contains(&note.text)
+            {
+                let warning = format!(
+                    "note [{}] at section {section_index} paragraph {paragraph_index}: could not locate the note text in its anchor paragraph; it is left in place",
+                    note.number
+                );
+                if !warnings.contains(&warning) {
+                    warnings.push(warning);
+                }
+            }
+            if self.mode == FootnoteMode::Omit {
+                continue;
             }
+            let line = format!("[{}] {}", note.number, note.text);
+            match self.mode {
+                FootnoteMode::Inline => lines.push(line),
+                _ => trailing.push(line),
+            }
+        }
+        annotated.extend(&chars[cursor..]);
+        annotated.push_str(&closing);
+        annotated.push_str(ending);
+        (annotated, lines)
+    }
+}
+
+/// Every paragraph (up to `paragraph_limit` document-wide) with its char span in the plain
+/// document text: paragraph contents each followed by a newline, as `text` is returned with
+/// the default options. A trailing paragraph break (`\r`) folds into that newline, so it is
+/// left out of the span. Offsets count `chars()`, like `search_text`'s `char_offset`, so a
+/// match at `char_offset` lies at `char_start + char_offset` in the document.
+fn paragraph_spans(document: &hwpers::HwpDocument, paragraph_limit: Option<usize>) -> Vec<Value> {
+    let mut spans = Vec::new();
+    let mut offset = 0usize;
+    let paragraphs = document
+        .sections()
+        .enumerate()
+        .flat_map(|(section_index, section)| {
+            section
+                .paragraphs
+                .iter()
+                .enumerate()
+                .map(move |(paragraph_index, paragraph)| {
+                    (section_index, paragraph_index, paragraph)
+                })
+        })
+        .take(paragraph_limit.unwrap_or(usize::MAX));
+    for (section_index, paragraph_index, paragraph) in paragraphs {
+        let text = paragraph
+            .text
+            .as_ref()
+            .map(|text| text.content.as_str())
+            .unwrap_or("");
+        let text = text.strip_suffix('\r').unwrap_or(text);
+        let char_start = offset;
+        let char_end = char_start + text.chars().count();
+        if paragraph.text.is_some() {
+            offset = char_end + 1;
+        }
+        spans.push(json!({
+            "section_index": section_index,
+            "paragraph_index": paragraph_index,
+            "char_start": char_start,
+            "char_end": char_end,
+            "text": text
+        }));
+    }
+    spans
+}
+
+fn parse_timeout_ms(value: Option<&Value>) -> Result<Option<u64>, ToolError> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    value.as_u64().map(Some).ok_or_else(|| ToolError {
+        kind: errors::INVALID_INPUT,
+        message: "timeout_ms must be a non-negative integer".to_string(),
+    })
+}
+
+struct RecognizedText {
+    text: String,
+    backend: &'static str,
+    images: Vec<Value>,
+}
+
+#[cfg(feature = "ocr")]
+fn run_ocr(images: &[BinData], warnings: &mut Vec<String>) -> Option<RecognizedText> {
+    let Some(backend) = crate::tools::ocr::detect_backend() else {
+        warnings
+            .push("ocr: no OCR backend found (install tesseract with kor/eng data)".to_string());
+        return None;
+    };
+
+    let mut texts = Vec::new();
+    let mut recognized = Vec::new();
+    for (index, bin) in images.iter().enumerate() {
+        let bytes = match bin.get_data() {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                warnings.push(format!("ocr: image {index}: {err}"));
+                continue;
+            }
+        };
+        match backend.recognize(&bytes) {
+            Ok(result) => {
+                recognized.push(json!({
+                    "index": index,
+                    "confidence": result.confidence,
+                    "chars": result.text.chars().count()
+                }));
+                texts.push(result.text);
+            }
+            Err(message) => warnings.push(format!("ocr: image {index}: {message}")),
+        }
+    }
+
+    if recognized.is_empty() {
+        return None;
+    }
+    Some(RecognizedText {
+        text: texts.join("\n\n"),
+        backend: backend.name(),
+        images: recognized,
+    })
+}
+
+#[cfg(not(feature = "ocr"))]
+fn run_ocr(_images: &[BinData], warnings: &mut Vec<String>) -> Option<RecognizedText> {
+    warnings.push("ocr: not available in this build (enable the ocr feature)".to_string());
+    None
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Representation {
+    Text,
+    Markdown,
+}
+
+impl Representation {
+    fn as_str(self) -> &'static str {
+        match self {
+            Representation::Text => "text",
+            Representation::Markdown => "markdown",
         }
-        HwpError::InvalidInput(message) => ToolError {
+    }
+}
+
+fn parse_representations(value: Option<&Value>) -> Result<Vec<Representation>, ToolError> {
+    let Some(value) = value else {
+        return Ok(vec![Representation::Text]);
+    };
+    let Some(items) = value.as_array() else {
+        return Err(ToolError {
             kind: errors::INVALID_INPUT,
-            message,
-        },
-        HwpError::Io(err) => ToolError {
+            message: "representations must be an array".to_string(),
+        });
+    };
+    let mut representations = Vec::with_capacity(items.len());
+    for item in items {
+        let representation = match item.as_str() {
+            Some("text") => Representation::Text,
+            Some("markdown") => Representation::Markdown,
+            _ => {
+                return Err(ToolError {
+                    kind: errors::INVALID_INPUT,
+                    message: "representations items must be text or markdown".to_string(),
+                });
+            }
+        };
+        if !representations.contains(&representation) {
+            representations.push(representation);
+        }
+    }
+    if representations.is_empty() {
+        return Err(ToolError {
             kind: errors::INVALID_INPUT,
-            message: err.to_string(),
-        },
-        HwpError::InvalidFormat(message)
-        | HwpError::Cfb(message)
-        | HwpError::CompressionError(message)
-        | HwpError::ParseError(message)
-        | HwpError::EncodingError(message)
-        | HwpError::NotFound(message) => ToolError {
-            kind: errors::PARSE_FAILED,
-            message,
-        },
+            message: "representations must not be empty".to_string(),
+        });
+    }
+    Ok(representations)
+}
+
+/// Longest paragraph (in chars) still considered a heading candidate.
+const MAX_HEADING_CHARS: usize = 80;
+
+fn render_markdown(
+    document: &hwpers::HwpDocument,
+    controls: &[SectionControls],
+    notes: Option<&NoteLayout>,
+    paragraph_limit: Option<usize>,
+    warnings: &mut Vec<String>,
+) -> String {
+    let body_size = body_font_size(document);
+    let mut blocks: Vec<String> = Vec::new();
+    let mut trailing = Vec::new();
+    let mut seen = 0usize;
+    let no_controls = SectionControls::default();
+
+    'sections: for (section_index, section) in document.sections().enumerate() {
+        let paragraphs = &section.paragraphs;
+        let controls = controls.get(section_index).unwrap_or(&no_controls);
+        let mut i = 0;
+        while i < paragraphs.len() {
+            if paragraph_limit.is_some_and(|limit| seen + i >= limit) {
+                break 'sections;
+            }
+            let paragraph = &paragraphs[i];
+            if notes.is_some_and(|notes| notes.is_body(section_index, i)) {
+                i += 1;
+                continue;
+            }
+
+            if let Some((grid, next)) = table_at(section_index, paragraphs, i, controls, warnings) {
+                if grid.first().is_some_and(|row| !row.is_empty()) {
+                    blocks.push(markdown_table(&grid));
+                }
+                i = next;
+                continue;
+            }
+
+            let text = paragraph_text(paragraph);
+            let (text, lines) = match notes {
+                Some(notes) => notes.annotate(section_index, i, &text, &mut trailing, warnings),
+                None => (text, Vec::new()),
+            };
+            let text = text.trim();
+            if !text.is_empty() {
+                match heading_level(document, paragraph, body_size, text) {
+                    Some(level) => blocks.push(format!("{} {text}", "#".repeat(level))),
+                    None => blocks.push(text.to_string()),
+                }
+            }
+            blocks.extend(lines);
+            i += 1;
+        }
+        seen += paragraphs.len();
+    }
+    blocks.extend(trailing);
+    if let Some(notes) = notes {
+        blocks.extend(notes.unanchored.iter().cloned());
+    }
+
+    let mut markdown = blocks.join("\n\n");
+    if !markdown.is_empty() {
+        markdown.push('\n');
+    }
+    markdown
+}
+
+fn paragraph_text(paragraph: &hwpers::model::paragraph::Paragraph) -> String {
+    match &paragraph.text {
+        Some(text) => text.content.clone(),
+        None => String::new(),
+    }
+}
+
+fn paragraph_char_shape<'a>(
+    document: &'a hwpers::HwpDocument,
+    paragraph: &hwpers::model::paragraph::Paragraph,
+) -> Option<&'a hwpers::model::CharShape> {
+    let id = paragraph
+        .char_shapes
+        .as_ref()
+        .and_then(|shapes| shapes.char_positions.first())
+        .map(|position| usize::from(position.char_shape_id))
+        .unwrap_or(0);
+    document.get_char_shape(id)
+}
+
+/// The most common leading font size across text paragraphs, used as the body size.
+fn body_font_size(document: &hwpers::HwpDocument) -> i32 {
+    let mut counts: HashMap<i32, usize> = HashMap::new();
+    for section in document.sections() {
+        for paragraph in &section.paragraphs {
+            if paragraph_text(paragraph).trim().is_empty() {
+                continue;
+            }
+            if let Some(shape) = paragraph_char_shape(document, paragraph) {
+                *counts.entry(shape.base_size).or_default() += 1;
+            }
+        }
+    }
+    counts
+        .into_iter()
+        .max_by_key(|(size, count)| (*count, -*size))
+        .map(|(size, _)| size)
+        .unwrap_or(0)
+}
+
+/// Infers a heading level from the paragraph's font size relative to the body text.
+fn heading_level(
+    document: &hwpers::HwpDocument,
+    paragraph: &hwpers::model::paragraph::Paragraph,
+    body_size: i32,
+    text: &str,
+) -> Option<usize> {
+    if body_size <= 0 || text.chars().count() > MAX_HEADING_CHARS {
+        return None;
+    }
+    let shape = paragraph_char_shape(document, paragraph)?;
+    let ratio = shape.base_size * 100 / body_size;
+    if ratio >= 200 {
+        Some(1)
+    } else if ratio >= 150 {
+        Some(2)
+    } else if ratio >= 120 {
+        Some(3)
+    } else if ratio > 100 && shape.is_bold() {
+        Some(4)
+    } else {
+        None
+    }
+}
+
+/// The table starting at paragraph `index` of a section as a grid, with the index of the first
+/// paragraph after it. Documents built in memory keep hwpers' `table_data`; parsed ones get
+/// the table `hwp.extract_rich` finds at the anchor paragraph (its table control, or delimited
+/// paragraphs).
+fn table_at(
+    section_index: usize,
+    paragraphs: &[hwpers::model::paragraph::Paragraph],
+    index: usize,
+    controls: &SectionControls,
+    warnings: &mut Vec<String>,
+) -> Option<(Vec<Vec<String>>, usize)> {
+    if let Some(table) = paragraphs[index].table_data.as_ref() {
+        let (grid, consumed) = table_grid(table, paragraphs, index);
+        let consumed = consumed.min(paragraphs.len() - index - 1);
+        return Some((grid, index + 1 + consumed));
+    }
+    anchored_table(section_index, index, paragraphs, controls, warnings)
+        .map(|(block, next)| (block_rows(&block), next))
+}
+
+/// Lays out the cells of the table in paragraph `index`, filled from the cell paragraphs
+/// that follow it. Returns the grid and how many cell paragraphs the table owns.
+fn table_grid(
+    table: &hwpers::model::Table,
+    paragraphs: &[hwpers::model::paragraph::Paragraph],
+    index: usize,
+) -> (Vec<Vec<String>>, usize) {
+    let rows = usize::from(table.rows);
+    let cols = usize::from(table.cols);
+    let mut cells = table.cells.iter().collect::<Vec<_>>();
+    cells.sort_by_key(|cell| (cell.cell_address.0, cell.cell_address.1));
+
+    let mut grid = vec![vec![String::new(); cols]; rows];
+    for (idx, cell) in cells.iter().enumerate() {
+        let r = usize::from(cell.cell_address.0);
+        let c = usize::from(cell.cell_address.1);
+        if r < rows && c < cols {
+            grid[r][c] = paragraphs
+                .get(index + 1 + idx)
+                .map(paragraph_text)
+                .unwrap_or_default();
+        }
+    }
+    (grid, cells.len())
+}
+
+/// One line per row with tab-separated cells; tabs and line breaks inside a cell become spaces.
+fn tsv_table(grid: &[Vec<String>]) -> String {
+    grid.iter()
+        .map(|row| {
+            row.iter()
+                .map(|cell| {
+                    cell.trim()
+                        .replace("\r\n", " ")
+                        .replace(['\t', '\r', '\n'], " ")
+                })
+                .collect::<Vec<_>>()
+                .join("\t")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn markdown_table(grid: &[Vec<String>]) -> String {
+    let escape = |cell: &str| {
+        cell.trim()
+            .replace('|', "\\|")
+            .replace("\r\n", " ")
+            .replace(['\r', '\n'], " ")
+    };
+    let cols = grid.first().map(|row| row.len()).unwrap_or(0);
+    let mut lines = Vec::with_capacity(grid.len() + 1);
+    for (index, row) in grid.iter().enumerate() {
+        let cells: Vec<String> = row.iter().map(|cell| escape(cell)).collect();
+        lines.push(format!("| {} |", cells.join(" | ")));
+        if index == 0 {
+            lines.push(format!("|{}", " --- |".repeat(cols)));
+        }
+    }
+    lines.join("\n")
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TableFormat {
+    Ignore,
+    Tsv,
+    Markdown,
+}
+
+impl TableFormat {
+    fn parse(value: Option<&Value>) -> Result<Self, ToolError> {
+        let Some(value) = value else {
+            return Ok(TableFormat::Ignore);
+        };
+        match value.as_str() {
+            Some("ignore") => Ok(TableFormat::Ignore),
+            Some("tsv") => Ok(TableFormat::Tsv),
+            Some("markdown") => Ok(TableFormat::Markdown),
+            _ => Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "table_format must be ignore, tsv, or markdown".to_string(),
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Granularity {
+    Document,
+    Section,
+    Paragraph,
+}
+
+impl Granularity {
+    fn parse(value: Option<&Value>) -> Result<Self, ToolError> {
+        let Some(value) = value else {
+            return Ok(Granularity::Document);
+        };
+        match value.as_str() {
+            Some("document") => Ok(Granularity::Document),
+            Some("section") => Ok(Granularity::Section),
+            Some("paragraph") => Ok(Granularity::Paragraph),
+            _ => Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "granularity must be document, section, or paragraph".to_string(),
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FootnoteMode {
+    Inline,
+    Endnotes,
+    Omit,
+}
+
+impl FootnoteMode {
+    fn parse(value: Option<&Value>) -> Result<Option<Self>, ToolError> {
+        let Some(value) = value else {
+            return Ok(None);
+        };
+        match value.as_str() {
+            Some("inline") => Ok(Some(FootnoteMode::Inline)),
+            Some("endnotes") => Ok(Some(FootnoteMode::Endnotes)),
+            Some("omit") => Ok(Some(FootnoteMode::Omit)),
+            _ => Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "footnotes must be inline, endnotes, or omit".to_string(),
+            }),
+        }
+    }
+}
+
+struct ParsedDocument {
+    document: hwpers::HwpDocument,
+    format: InputFormat,
+    warnings: Vec<String>,
+    /// Table controls hwpers drops, per section; read only when tables are rendered.
+    controls: Vec<SectionControls>,
+    /// Footnotes and endnotes hwpers drops; read only when `footnotes` is set.
+    notes: Vec<Note>,
+}
+
+impl From<crate::tools::ParsedDocument> for ParsedDocument {
+    fn from(parsed: crate::tools::ParsedDocument) -> Self {
+        Self {
+            document: parsed.document,
+            format: parsed.format,
+            warnings: parsed.warnings,
+            controls: Vec::new(),
+            notes: Vec::new(),
+        }
     }
 }
 
-fn map_hwp_error_with_format(error: HwpError, format: &str) -> ToolError {
-    let mut mapped = map_hwp_error(error);
-    mapped.message = format!("{format} parse failed: {}", mapped.message);
-    mapped
+/// Parses on a worker thread so a hostile input cannot wedge the server past the call's
+/// `timeout_ms` deadline.
+fn parse_document(
+    bytes: &[u8],
+    format: InputFormat,
+    deadline: Instant,
+    timeout_ms: u64,
+    read_tables: bool,
+    read_notes: bool,
+) -> Result<ParsedDocument, ToolError> {
+    let bytes = bytes.to_vec();
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    let parse = move || {
+        let mut parsed = ParsedDocument::from(parse_document_blocking(&bytes, format)?);
+        if read_tables {
+            parsed.controls = container_controls(&bytes, parsed.format, &[]);
+        }
+        if read_notes {
+            if parsed.format == InputFormat::Hwp && parsed.document.is_distribution_document() {
+                parsed.warnings.push(
+                    "footnotes: distribution document (배포용 문서): encrypted body is not scanned for notes"
+                        .to_string(),
+                );
+            } else {
+                match document_notes(&parsed.document, parsed.format, &bytes) {
+                    Ok(notes) => parsed.notes = notes,
+                    Err(message) => parsed
+                        .warnings
+                        .push(format!("footnotes: note scan failed: {message}")),
+                }
+            }
+        }
+        Ok(parsed)
+    };
+    match run_with_timeout(remaining, parse) {
+        WorkerOutcome::Finished(result) => result,
+        WorkerOutcome::TimedOut => Err(ToolError {
+            kind: errors::TIMEOUT,
+            message: format!("parse timed out after {timeout_ms} ms"),
+        }),
+        WorkerOutcome::Panicked => Err(ToolError {
+            kind: errors::PARSE_FAILED,
+            message: "parser panicked on this input".to_string(),
+        }),
+    }
 }
 
-fn normalize_text(text: &str, include_newlines: bool, normalize_whitespace: bool) -> String {
+/// With `keep_tabs` (TSV tables), whitespace normalization runs per tab-separated field so
+/// the cell separators survive.
+fn normalize_text(
+    text: &str,
+    include_newlines: bool,
+    normalize_whitespace: bool,
+    keep_tabs: bool,
+) -> String {
     let mut output = text.replace("\r\n", "\n").replace('\r', "\n");
 
     if !include_newlines {
@@ -117,10 +1129,20 @@ fn normalize_text(text: &str, include_newlines: bool, normalize_whitespace: bool
     }
 
     if normalize_whitespace {
+        let collapse = |text: &str| text.split_whitespace().collect::<Vec<_>>().join(" ");
         if include_newlines {
             let lines: Vec<String> = output
                 .lines()
-                .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+                .map(|line| {
+                    if keep_tabs {
+                        line.split('\t')
+                            .map(collapse)
+                            .collect::<Vec<_>>()
+                            .join("\t")
+                    } else {
+                        collapse(line)
+                    }
+                })
                 .collect();
             output = lines.join("\n");
         } else {
@@ -138,3 +1160,100 @@ fn apply_max_chars(text: String, max_chars: Option<u64>) -> String {
     let limit = usize::try_from(max_chars).unwrap_or(usize::MAX);
     text.chars().take(limit).collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::extract_notes::{Anchor, NoteKind};
+    use hwpers::HwpWriter;
+
+    // The hwpers readers do not rebuild `table_data`, so this covers documents assembled in
+    // memory; parsed ones go through the table controls (see tests/stdio_extract_text.rs).
+    fn writer_with_table() -> HwpWriter {
+        let mut writer = HwpWriter::new();
+        writer.add_paragraph("Before").expect("paragraph");
+        writer
+            .add_simple_table(&[vec!["Name", "Qty"], vec!["Apple", "3"]])
+            .expect("table");
+        writer.add_paragraph("After").expect("paragraph");
+        writer
+    }
+
+    fn gathered(table_format: TableFormat) -> String {
+        let writer = writer_with_table();
+        gather_text(
+            writer.document(),
+            &[],
+            None,
+            None,
+            table_format,
+            &mut Vec::new(),
+        )
+        .text
+    }
+
+    #[test]
+    fn table_format_renders_tables_inline() {
+        assert!(gathered(TableFormat::Tsv).contains("Before\nName\tQty\nApple\t3\nAfter\n"));
+        assert!(
+            gathered(TableFormat::Markdown)
+                .contains("Before\n| Name | Qty |\n| --- | --- |\n| Apple | 3 |\nAfter\n")
+        );
+
+        let ignored = gathered(TableFormat::Ignore);
+        assert!(ignored.contains("Name\nQty\nApple\n3\n"), "{ignored}");
+    }
+
+    #[test]
+    fn normalize_whitespace_keeps_tsv_tabs() {
+        let text = "a  b\tc   d\nplain\ttext";
+        assert_eq!(
+            normalize_text(text, true, true, true),
+            "a b\tc d\nplain\ttext"
+        );
+        assert_eq!(
+            normalize_text(text, true, true, false),
+            "a b c d\nplain text"
+        );
+    }
+
+    fn anchored_note(number: u64, position: Option<usize>, text: &str) -> Note {
+        Note {
+            kind: NoteKind::Footnote,
+            number,
+            anchor: Some(Anchor {
+                section_index: 0,
+                paragraph_index: 0,
+                text: String::new(),
+            }),
+            body_paragraphs: Vec::new(),
+            position,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn annotate_strips_folded_note_text_only_at_the_control() {
+        let layout = NoteLayout::new(
+            FootnoteMode::Inline,
+            vec![anchored_note(1, Some(19), "Ibid.")],
+        );
+        let (mut trailing, mut warnings) = (Vec::new(), Vec::new());
+        let (content, lines) = layout.annotate(
+            0,
+            0,
+            "Ibid. appears firstIbid.\r",
+            &mut trailing,
+            &mut warnings,
+        );
+        assert_eq!(content, "Ibid. appears first[1]\r");
+        assert_eq!(lines, ["[1] Ibid."]);
+        assert!(warnings.is_empty(), "{warnings:?}");
+
+        let layout = NoteLayout::new(FootnoteMode::Inline, vec![anchored_note(2, None, "Ibid.")]);
+        let (content, _) =
+            layout.annotate(0, 0, "Ibid. appears first", &mut trailing, &mut warnings);
+        assert_eq!(content, "Ibid. appears first[2]");
+        assert_eq!(warnings.len(), 1, "{warnings:?}");
+    }
+}
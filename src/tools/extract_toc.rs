@@ -0,0 +1,213 @@
+use crate::input::load_input;
+use crate::mcp::errors;
+use crate::tools::{ToolError, check_arguments, error_result, parse_document};
+use hwpers::HwpDocument;
+use hwpers::model::paragraph::Paragraph;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+/// Longest paragraph (in chars) still considered a heading candidate.
+const MAX_HEADING_CHARS: usize = 80;
+
+pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let max_level = match parse_max_level(args.get("max_level")) {
+        Ok(max_level) => max_level,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let payload = match load_input(args) {
+        Ok(payload) => payload,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let parsed = match parse_document(&payload.bytes, payload.format) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return error_result(err.kind, err.message, Some(payload.source.as_str()));
+        }
+    };
+
+    let mut warnings = parsed.warnings;
+    let toc = toc_entries(&parsed.document, max_level);
+    if toc.is_empty() {
+        warnings.push("no heading paragraphs were found".to_string());
+    }
+
+    let summary = toc
+        .iter()
+        .map(|entry| {
+            let level = entry["level"].as_u64().unwrap_or(1) as usize;
+            let text = entry["text"].as_str().unwrap_or("");
+            format!("{}{text}", "  ".repeat(level.saturating_sub(1)))
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    json!({
+        "content": [{"type": "text", "text": summary}],
+        "structuredContent": {
+            "format": parsed.format.as_str(),
+            "detected_format": parsed.format.as_str(),
+            "detection_method": payload.detection_method,
+            "max_level": max_level,
+            "toc": toc,
+            "warnings": warnings
+        },
+        "isError": false
+    })
+}
+
+/// Headings in document order as a flat list; clients rebuild the tree from `level`.
+/// Paragraphs with an outline/heading style take their level from the style name; other
+/// paragraphs fall back to the short-bold-text heuristic.
+fn toc_entries(document: &HwpDocument, max_level: Option<usize>) -> Vec<Value> {
+    let body_size = body_font_size(document);
+    let mut entries = Vec::new();
+    for (section_index, section) in document.sections().enumerate() {
+        for (paragraph_index, paragraph) in section.paragraphs.iter().enumerate() {
+            let text = paragraph_text(paragraph);
+            let text = text.trim();
+            if text.is_empty() {
+                continue;
+            }
+            let (level, source) = match style_heading_level(document, paragraph) {
+                Some(level) => (level, "style"),
+                None => match heading_level(document, paragraph, body_size, text) {
+                    Some(level) => (level, "heuristic"),
+                    None => continue,
+                },
+            };
+            if max_level.is_some_and(|max_level| level > max_level) {
+                continue;
+            }
+            entries.push(json!({
+                "level": level,
+                "text": text,
+                "section_index": section_index,
+                "paragraph_index": paragraph_index,
+                "source": source
+            }));
+        }
+    }
+    entries
+}
+
+fn style_heading_level(document: &HwpDocument, paragraph: &Paragraph) -> Option<usize> {
+    let style = document.get_style(usize::from(paragraph.style_id))?;
+    level_from_style_name(&style.name).or_else(|| level_from_style_name(&style.english_name))
+}
+
+/// Levels from Hangul's built-in `개요 N` / `Outline N` styles and the common `제목 N` /
+/// `Heading N` names.
+fn level_from_style_name(name: &str) -> Option<usize> {
+    let name = name.trim();
+    let number = ["개요", "제목", "Outline", "Heading"]
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix))?;
+    match number.trim().parse::<usize>() {
+        Ok(level @ 1..=10) => Some(level),
+        _ => None,
+    }
+}
+
+fn paragraph_text(paragraph: &Paragraph) -> String {
+    match &paragraph.text {
+        Some(text) => text.content.clone(),
+        None => String::new(),
+    }
+}
+
+fn paragraph_char_shape<'a>(
+    document: &'a HwpDocument,
+    paragraph: &Paragraph,
+) -> Option<&'a hwpers::model::CharShape> {
+    let id = paragraph
+        .char_shapes
+        .as_ref()
+        .and_then(|shapes| shapes.char_positions.first())
+        .map(|position| usize::from(position.char_shape_id))
+        .unwrap_or(0);
+    document.get_char_shape(id)
+}
+
+/// The most common leading font size across text paragraphs, used as the body size.
+fn body_font_size(document: &HwpDocument) -> i32 {
+    let mut counts: HashMap<i32, usize> = HashMap::new();
+    for section in document.sections() {
+        for paragraph in &section.paragraphs {
+            if paragraph_text(paragraph).trim().is_empty() {
+                continue;
+            }
+            if let Some(shape) = paragraph_char_shape(document, paragraph) {
+                *counts.entry(shape.base_size).or_default() += 1;
+            }
+        }
+    }
+    counts
+        .into_iter()
+        .max_by_key(|(size, count)| (*count, -*size))
+        .map(|(size, _)| size)
+        .unwrap_or(0)
+}
+
+/// Short bold paragraphs become headings; the level follows the font size relative to the body.
+fn heading_level(
+    document: &HwpDocument,
+    paragraph: &Paragraph,
+    body_size: i32,
+    text: &str,
+) -> Option<usize> {
+    if text.chars().count() > MAX_HEADING_CHARS || text.contains(['\r', '\n']) {
+        return None;
+    }
+    let shape = paragraph_char_shape(document, paragraph)?;
+    if !shape.is_bold() {
+        return None;
+    }
+    let ratio = if body_size > 0 {
+        shape.base_size * 100 / body_size
+    } else {
+        100
+    };
+    if ratio >= 200 {
+        Some(1)
+    } else if ratio >= 150 {
+        Some(2)
+    } else if ratio >= 120 {
+        Some(3)
+    } else {
+        Some(4)
+    }
+}
+
+fn parse_max_level(value: Option<&Value>) -> Result<Option<usize>, ToolError> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    match value.as_u64() {
+        Some(level @ 1..=10) => Ok(Some(level as usize)),
+        _ => Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "max_level must be an integer between 1 and 10".to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn style_names_map_to_levels() {
+        assert_eq!(level_from_style_name("개요 1"), Some(1));
+        assert_eq!(level_from_style_name("Outline 3"), Some(3));
+        assert_eq!(level_from_style_name("Heading 2"), Some(2));
+        assert_eq!(level_from_style_name("제목 10"), Some(10));
+        assert_eq!(level_from_style_name("바탕글"), None);
+        assert_eq!(level_from_style_name("Outline"), None);
+        assert_eq!(level_from_style_name("Heading 0"), None);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::input::load_input;
+use crate::mcp::errors;
+use crate::tools::{ToolError, check_arguments, error_result, parse_document};
+use serde_json::{Value, json};
+use std::collections::HashSet;
+
+pub(crate) const DEFAULT_SHINGLE_SIZE: usize = 3;
+pub(crate) const SIGNATURE_HASHES: usize = 64;
+
+pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let payload = match load_input(args) {
+        Ok(payload) => payload,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let shingle_size = match shingle_size_from_args(args) {
+        Ok(size) => size,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let parsed = match parse_document(&payload.bytes, payload.format) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return error_result(err.kind, err.message, Some(payload.source.as_str()));
+        }
+    };
+
+    let signature = Signature::from_text(&parsed.document.extract_text(), shingle_size);
+    let summary = format!(
+        "minhash signature over {} shingles (shingle_size={shingle_size})",
+        signature.shingle_count
+    );
+
+    json!({
+        "content": [{"type": "text", "text": summary}],
+        "structuredContent": {
+            "format": parsed.format.as_str(),
+            "detected_format": parsed.format.as_str(),
+            "detection_method": payload.detection_method,
+            "algorithm": "minhash",
+            "shingle_size": shingle_size as u64,
+            "token_count": signature.token_count as u64,
+            "shingle_count": signature.shingle_count as u64,
+            "signature": signature.to_hex(),
+            "warnings": parsed.warnings
+        },
+        "isError": false
+    })
+}
+
+/// MinHash signature over word shingles of normalized document text.
+pub(crate) struct Signature {
+    pub(crate) values: Vec<u64>,
+    pub(crate) token_count: usize,
+    pub(crate) shingle_count: usize,
+}
+
+impl Signature {
+    pub(crate) fn from_text(text: &str, shingle_size: usize) -> Self {
+        let tokens = tokenize(text);
+        let shingles = shingles(&tokens, shingle_size);
+
+        let mut values = vec![u64::MAX; SIGNATURE_HASHES];
+        for shingle in &shingles {
+            let base = fnv1a64(shingle.as_bytes());
+            for (seed, slot) in values.iter_mut().enumerate() {
+                let hashed = splitmix64(base ^ (seed as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+                if hashed < *slot {
+                    *slot = hashed;
+                }
+            }
+        }
+
+        Self {
+            values,
+            token_count: tokens.len(),
+            shingle_count: shingles.len(),
+        }
+    }
+
+    /// Estimated Jaccard similarity in `0.0..=1.0`.
+    pub(crate) fn similarity(&self, other: &Signature) -> f64 {
+        if self.shingle_count == 0 && other.shingle_count == 0 {
+            return 1.0;
+        }
+        if self.shingle_count == 0 || other.shingle_count == 0 {
+            return 0.0;
+        }
+        let matching = self
+            .values
+            .iter()
+            .zip(other.values.iter())
+            .filter(|(left, right)| left == right)
+            .count();
+        matching as f64 / self.values.len() as f64
+    }
+
+    fn to_hex(&self) -> Vec<String> {
+        self.values
+            .iter()
+            .map(|value| format!("{value:016x}"))
+            .collect()
+    }
+}
+
+pub(crate) fn shingle_size_from_args(args: &Value) -> Result<usize, ToolError> {
+    let Some(value) = args.get("shingle_size") else {
+        return Ok(DEFAULT_SHINGLE_SIZE);
+    };
+    match value.as_u64() {
+        Some(size) if size >= 1 => Ok(usize::try_from(size).unwrap_or(usize::MAX)),
+        _ => Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "shingle_size must be a positive integer".to_string(),
+        }),
+    }
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.chars()
+        .map(|ch| {
+            if ch.is_alphanumeric() {
+                ch.to_lowercase().next().unwrap_or(ch)
+            } else {
+                ' '
+            }
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .map(str::to_string)
+        .collect()
+}
+
+fn shingles(tokens: &[String], shingle_size: usize) -> HashSet<String> {
+    if tokens.is_empty() {
+        return HashSet::new();
+    }
+    if tokens.len() <= shingle_size {
+        return HashSet::from([tokens.join(" ")]);
+    }
+    tokens
+        .windows(shingle_size)
+        .map(|window| window.join(" "))
+        .collect()
+}
+
+fn fnv1a64(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+fn splitmix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
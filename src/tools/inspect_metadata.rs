@@ -1,17 +1,62 @@
-use crate::input::{InputFormat, load_input};
+use crate::input::{InputFormat, InputPayload, is_distribution_document, load_input};
 use crate::mcp::errors;
-use crate::tools::error_result;
-use hwpers::{HwpError, HwpReader, HwpxReader};
+use crate::tools::{
+    ToolError, check_arguments, error_result, map_hwp_error_with_format, parse_document,
+};
+use hwpers::parser::header::FileHeader;
+use hwpers::reader::CfbReader;
 use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::io::{Cursor, Read};
+
+// Property ids of the OLE SummaryInformation / DocumentSummaryInformation sets.
+const PID_TITLE: u32 = 0x02;
+const PID_SUBJECT: u32 = 0x03;
+const PID_AUTHOR: u32 = 0x04;
+const PID_KEYWORDS: u32 = 0x05;
+const PID_COMMENTS: u32 = 0x06;
+const PID_LAST_SAVED_BY: u32 = 0x08;
+const PID_CREATED: u32 = 0x0C;
+const PID_MODIFIED: u32 = 0x0D;
+const PID_COMPANY: u32 = 0x0F;
+
+const VT_LPSTR: u32 = 0x1E;
+const VT_LPWSTR: u32 = 0x1F;
+const VT_FILETIME: u32 = 0x40;
+
+/// Seconds between the FILETIME epoch (1601-01-01) and the Unix epoch.
+const FILETIME_UNIX_OFFSET_SECS: i64 = 11_644_473_600;
 
 pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
     let payload = match load_input(args) {
         Ok(payload) => payload,
         Err(err) => return error_result(err.kind, err.message, None),
     };
 
-    let parsed = match parse_document(&payload.bytes, payload.format) {
+    let counts = args
+        .get("counts")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(true);
+    if !counts {
+        return header_only(&payload);
+    }
+
+    let mut parsed = match parse_document(&payload.bytes, payload.format) {
         Ok(parsed) => parsed,
+        Err(err) if err.kind != errors::TIMEOUT && is_distribution_document(&payload.bytes) => {
+            return error_result(
+                errors::ENCRYPTED,
+                format!(
+                    "distribution document (배포용 문서): content could not be decrypted for reading ({})",
+                    err.message
+                ),
+                Some(payload.source.as_str()),
+            );
+        }
         Err(err) => {
             return error_result(err.kind, err.message, Some(payload.source.as_str()));
         }
@@ -24,10 +69,26 @@ pub fn call(args: &Value) -> Value {
         .map(|section| section.paragraphs.len() as u64)
         .sum::<u64>();
 
+    let fonts = font_table(&parsed.document);
+    if fonts.is_empty() {
+        parsed
+            .warnings
+            .push("fonts: document declares no font faces".to_string());
+    }
+
+    let summary = match parsed.format {
+        InputFormat::Hwpx => hwpx_summary(&payload.bytes),
+        _ => hwp_summary(Some(&parsed.document), &payload.bytes),
+    };
+
     let mut structured = json!({
         "format": parsed.format.as_str(),
+        "detected_format": parsed.format.as_str(),
+        "detection_method": payload.detection_method,
         "sections": sections,
         "paragraphs": paragraphs,
+        "summary": summary.to_json(),
+        "fonts": fonts,
         "warnings": parsed.warnings,
     });
 
@@ -36,6 +97,10 @@ pub fn call(args: &Value) -> Value {
             "encrypted".to_string(),
             json!(parsed.document.is_encrypted()),
         );
+        obj.insert(
+            "distribution".to_string(),
+            json!(parsed.document.is_distribution_document()),
+        );
         obj.insert(
             "compressed".to_string(),
             json!(parsed.document.header.is_compressed()),
@@ -55,97 +120,354 @@ pub fn call(args: &Value) -> Value {
     })
 }
 
-struct ToolError {
-    kind: &'static str,
-    message: String,
-}
-
-struct ParsedDocument {
-    document: hwpers::HwpDocument,
-    format: InputFormat,
-    warnings: Vec<String>,
-}
-
-fn parse_document(bytes: &[u8], format: InputFormat) -> Result<ParsedDocument, ToolError> {
-    match format {
-        InputFormat::Hwp => HwpReader::from_bytes(bytes)
-            .map(|document| ParsedDocument {
-                document,
-                format,
-                warnings: Vec::new(),
-            })
-            .map_err(|error| map_hwp_error_with_format(error, format.as_str())),
-        InputFormat::Hwpx => HwpxReader::from_bytes(bytes)
-            .map(|document| ParsedDocument {
-                document,
-                format,
-                warnings: Vec::new(),
-            })
-            .map_err(|error| map_hwp_error_with_format(error, format.as_str())),
-        InputFormat::Auto => {
-            let hwp_result = HwpReader::from_bytes(bytes);
-            match hwp_result {
-                Ok(document) => Ok(ParsedDocument {
-                    document,
-                    format: InputFormat::Hwp,
-                    warnings: Vec::new(),
-                }),
-                Err(hwp_err) => match HwpxReader::from_bytes(bytes) {
-                    Ok(document) => Ok(ParsedDocument {
-                        document,
-                        format: InputFormat::Hwpx,
-                        warnings: vec!["auto format: hwp parse failed; hwpx succeeded".to_string()],
-                    }),
-                    Err(hwpx_err) => Err(ToolError {
-                        kind: errors::PARSE_FAILED,
-                        message: format!(
-                            "auto format parse failed (hwp: {}; hwpx: {})",
-                            hwp_err, hwpx_err
-                        ),
-                    }),
-                },
+/// `counts: false`: reads only the file header and the summary properties, skipping the
+/// DocInfo and BodyText parse, so the cost no longer grows with the document body.
+fn header_only(payload: &InputPayload) -> Value {
+    let (bytes, format, source) = (&payload.bytes, payload.format, payload.source.as_str());
+    if format == InputFormat::Auto {
+        return error_result(
+            errors::PARSE_FAILED,
+            "input is neither an HWP (CFB) nor an HWPX (ZIP) container",
+            Some(source),
+        );
+    }
+
+    let header = match format {
+        InputFormat::Hwpx => hwpx_file_header(bytes),
+        _ => hwp_file_header(bytes),
+    };
+    let header = match header {
+        Ok(header) => header,
+        Err(err) => return error_result(err.kind, err.message, Some(source)),
+    };
+    let summary = match format {
+        InputFormat::Hwpx => hwpx_summary(bytes),
+        _ => hwp_summary(None, bytes),
+    };
+
+    let version = header.version_string();
+    json!({
+        "content": [{"type": "text", "text": format!("{} {version}", format.as_str())}],
+        "structuredContent": {
+            "format": format.as_str(),
+            "detected_format": format.as_str(),
+            "detection_method": payload.detection_method,
+            "version": version,
+            "compressed": header.is_compressed(),
+            "encrypted": header.is_encrypted(),
+            "distribution": header.is_distribute(),
+            "summary": summary.to_json(),
+            "warnings": []
+        },
+        "isError": false
+    })
+}
+
+fn hwp_file_header(bytes: &[u8]) -> Result<FileHeader, ToolError> {
+    CfbReader::new(Cursor::new(bytes))
+        .and_then(|mut reader| reader.read_stream("FileHeader"))
+        .and_then(FileHeader::parse)
+        .map_err(|error| map_hwp_error_with_format(error, "hwp"))
+}
+
+/// Mirrors how `HwpxReader` derives its header: default flags, version from `version.xml`.
+fn hwpx_file_header(bytes: &[u8]) -> Result<FileHeader, ToolError> {
+    let Some(xml) = read_zip_text(bytes, "version.xml") else {
+        return Err(ToolError {
+            kind: errors::PARSE_FAILED,
+            message: "hwpx parse failed: version.xml not found".to_string(),
+        });
+    };
+    let version = hwpers::hwpx::parse_version(&xml).map_err(|err| ToolError {
+        kind: errors::PARSE_FAILED,
+        message: format!("hwpx parse failed: invalid version.xml: {err}"),
+    })?;
+
+    let mut header = FileHeader::new_default();
+    let parts: Vec<u8> = version
+        .version
+        .as_deref()
+        .or(version.xml_version.as_deref())
+        .unwrap_or("5.0")
+        .split('.')
+        .filter_map(|part| part.parse().ok())
+        .collect();
+    if !parts.is_empty() {
+        let part = |index: usize, default: u8| parts.get(index).copied().unwrap_or(default);
+        header.set_version(part(0, 5), part(1, 0), part(2, 0), part(3, 0));
+    } else if let Some(major) = &version.major {
+        let minor = version
+            .minor
+            .as_ref()
+            .and_then(|minor| minor.parse().ok())
+            .unwrap_or(0);
+        header.set_version(major.parse().unwrap_or(5), minor, 0, 0);
+    }
+    Ok(header)
+}
+
+/// Lists the DocInfo font faces once per name. HWP repeats the table for each script
+/// (Hangul, Latin, Hanja, ...), so the same face usually appears several times.
+fn font_table(document: &hwpers::HwpDocument) -> Vec<Value> {
+    let mut seen = std::collections::HashSet::new();
+    let mut fonts = Vec::new();
+    for face in &document.doc_info.face_names {
+        let name = face.font_name.trim();
+        if name.is_empty() || !seen.insert(name.to_string()) {
+            continue;
+        }
+        let substitute = Some(face.substitute_font_name.trim()).filter(|name| !name.is_empty());
+        let substitute_type = match (substitute, face.substitute_font_type) {
+            (None, _) => None,
+            (Some(_), 1) => Some("ttf"),
+            (Some(_), 2) => Some("hft"),
+            (Some(_), _) => Some("unknown"),
+        };
+        fonts.push(json!({
+            "name": name,
+            "substitute": substitute,
+            "substitute_type": substitute_type,
+        }));
+    }
+    fonts
+}
+
+/// Document properties; absent fields serialize as `null` so the shape stays stable.
+#[derive(Default)]
+struct Summary {
+    title: Option<String>,
+    subject: Option<String>,
+    author: Option<String>,
+    keywords: Option<String>,
+    comments: Option<String>,
+    last_saved_by: Option<String>,
+    company: Option<String>,
+    created: Option<String>,
+    modified: Option<String>,
+}
+
+impl Summary {
+    fn to_json(&self) -> Value {
+        json!({
+            "title": self.title,
+            "subject": self.subject,
+            "author": self.author,
+            "keywords": self.keywords,
+            "comments": self.comments,
+            "last_saved_by": self.last_saved_by,
+            "company": self.company,
+            "created": self.created,
+            "modified": self.modified,
+        })
+    }
+}
+
+enum PropertyValue {
+    Text(String),
+    FileTime(u64),
+}
+
+/// Takes what `hwpers` decoded from `\x05HwpSummaryInformation`, then fills the gaps from the
+/// raw property sets: `hwpers` only reads 8-bit strings while Hangul writes UTF-16 ones, and
+/// the company name lives in `\x05DocumentSummaryInformation`.
+fn hwp_summary(document: Option<&hwpers::HwpDocument>, bytes: &[u8]) -> Summary {
+    let mut summary = Summary::default();
+    if let Some(info) = document.and_then(|document| document.summary_info()) {
+        summary.title = non_empty(info.title.clone());
+        summary.subject = non_empty(info.subject.clone());
+        summary.author = non_empty(info.author.clone());
+        summary.keywords = non_empty(info.keywords.clone());
+        summary.comments = non_empty(info.comments.clone());
+        summary.last_saved_by = non_empty(info.last_saved_by.clone());
+        summary.created = info
+            .creation_date
+            .and_then(|value| u64::try_from(value).ok())
+            .and_then(filetime_to_iso8601);
+        summary.modified = info
+            .last_saved_date
+            .and_then(|value| u64::try_from(value).ok())
+            .and_then(filetime_to_iso8601);
+    }
+
+    let Ok(mut reader) = CfbReader::new(Cursor::new(bytes)) else {
+        return summary;
+    };
+    if let Ok(data) = reader.read_stream("\x05HwpSummaryInformation") {
+        let properties = read_property_set(&data);
+        let text = |id: u32| match properties.get(&id) {
+            Some(PropertyValue::Text(text)) => non_empty(Some(text.clone())),
+            _ => None,
+        };
+        let time = |id: u32| match properties.get(&id) {
+            Some(PropertyValue::FileTime(value)) => filetime_to_iso8601(*value),
+            _ => None,
+        };
+        summary.title = summary.title.take().or_else(|| text(PID_TITLE));
+        summary.subject = summary.subject.take().or_else(|| text(PID_SUBJECT));
+        summary.author = summary.author.take().or_else(|| text(PID_AUTHOR));
+        summary.keywords = summary.keywords.take().or_else(|| text(PID_KEYWORDS));
+        summary.comments = summary.comments.take().or_else(|| text(PID_COMMENTS));
+        summary.last_saved_by = summary
+            .last_saved_by
+            .take()
+            .or_else(|| text(PID_LAST_SAVED_BY));
+        summary.created = summary.created.take().or_else(|| time(PID_CREATED));
+        summary.modified = summary.modified.take().or_else(|| time(PID_MODIFIED));
+    }
+    if let Ok(data) = reader.read_stream("\x05DocumentSummaryInformation")
+        && let Some(PropertyValue::Text(company)) = read_property_set(&data).get(&PID_COMPANY)
+    {
+        summary.company = non_empty(Some(company.clone()));
+    }
+    summary
+}
+
+/// Reads the first section of an OLE property set stream, keeping string and FILETIME values.
+fn read_property_set(data: &[u8]) -> BTreeMap<u32, PropertyValue> {
+    let mut properties = BTreeMap::new();
+    let read_u32 = |offset: usize| -> Option<u32> {
+        let bytes = data.get(offset..offset.checked_add(4)?)?;
+        Some(u32::from_le_bytes(bytes.try_into().ok()?))
+    };
+    if data.get(0..2) != Some(&[0xFE, 0xFF][..]) {
+        return properties;
+    }
+    let Some(section) = read_u32(44).map(|offset| offset as usize) else {
+        return properties;
+    };
+    let count = read_u32(section + 4).unwrap_or(0) as usize;
+    for index in 0..count.min(1024) {
+        let entry = section + 8 + index * 8;
+        let (Some(id), Some(offset)) = (read_u32(entry), read_u32(entry + 4)) else {
+            break;
+        };
+        let value = section + offset as usize;
+        let Some(kind) = read_u32(value) else {
+            continue;
+        };
+        let parsed = match kind {
+            VT_LPSTR => read_u32(value + 4).and_then(|len| {
+                let bytes = data.get(value + 8..value + 8 + len as usize)?;
+                let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+                Some(PropertyValue::Text(
+                    String::from_utf8_lossy(&bytes[..end]).into_owned(),
+                ))
+            }),
+            VT_LPWSTR => read_u32(value + 4).and_then(|chars| {
+                let bytes = data.get(value + 8..value + 8 + chars as usize * 2)?;
+                let units: Vec<u16> = bytes
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                    .take_while(|&unit| unit != 0)
+                    .collect();
+                Some(PropertyValue::Text(String::from_utf16_lossy(&units)))
+            }),
+            VT_FILETIME => {
+                let low = read_u32(value + 4);
+                let high = read_u32(value + 8);
+                low.zip(high).map(|(low, high)| {
+                    PropertyValue::FileTime((u64::from(high) << 32) | u64::from(low))
+                })
             }
+            _ => None,
+        };
+        if let Some(parsed) = parsed {
+            properties.insert(id, parsed);
         }
     }
+    properties
 }
 
-fn map_hwp_error(error: HwpError) -> ToolError {
-    match error {
-        HwpError::UnsupportedVersion(message) => {
-            if message.contains("Password-encrypted") {
-                ToolError {
-                    kind: errors::ENCRYPTED,
-                    message,
-                }
-            } else {
-                ToolError {
-                    kind: errors::PARSE_FAILED,
-                    message,
-                }
+/// HWPX keeps its properties in the `<opf:metadata>` block of `Contents/content.hpf`.
+fn hwpx_summary(bytes: &[u8]) -> Summary {
+    let Some(hpf) = read_zip_text(bytes, "Contents/content.hpf") else {
+        return Summary::default();
+    };
+    let meta = |name: &str| non_empty(opf_meta(&hpf, name));
+    Summary {
+        title: non_empty(element_text(&hpf, "opf:title")),
+        subject: meta("subject"),
+        author: meta("creator"),
+        keywords: meta("keyword"),
+        comments: meta("description"),
+        last_saved_by: meta("lastsaveby"),
+        company: None,
+        created: meta("CreatedDate"),
+        modified: meta("ModifiedDate"),
+    }
+}
+
+fn read_zip_text(bytes: &[u8], name: &str) -> Option<String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).ok()?;
+    let mut file = archive.by_name(name).ok()?;
+    let mut text = String::new();
+    file.read_to_string(&mut text).ok()?;
+    Some(text)
+}
+
+fn element_text(xml: &str, tag: &str) -> Option<String> {
+    let open = format!("<{tag}>");
+    let start = xml.find(&open)? + open.len();
+    let end = xml[start..].find('<')?;
+    Some(unescape_xml(&xml[start..start + end]))
+}
+
+fn opf_meta(xml: &str, name: &str) -> Option<String> {
+    let needle = format!("name=\"{name}\"");
+    let mut rest = xml;
+    while let Some(start) = rest.find("<opf:meta ") {
+        let tag_end = rest[start..].find('>')? + start;
+        let tag = &rest[start..tag_end];
+        if tag.contains(&needle) {
+            if tag.ends_with('/') {
+                return None;
             }
+            let text_end = rest[tag_end..].find('<')? + tag_end;
+            return Some(unescape_xml(&rest[tag_end + 1..text_end]));
         }
-        HwpError::InvalidInput(message) => ToolError {
-            kind: errors::INVALID_INPUT,
-            message,
-        },
-        HwpError::Io(err) => ToolError {
-            kind: errors::INVALID_INPUT,
-            message: err.to_string(),
-        },
-        HwpError::InvalidFormat(message)
-        | HwpError::Cfb(message)
-        | HwpError::CompressionError(message)
-        | HwpError::ParseError(message)
-        | HwpError::EncodingError(message)
-        | HwpError::NotFound(message) => ToolError {
-            kind: errors::PARSE_FAILED,
-            message,
-        },
+        rest = &rest[tag_end..];
     }
+    None
 }
 
-fn map_hwp_error_with_format(error: HwpError, format: &str) -> ToolError {
-    let mut mapped = map_hwp_error(error);
-    mapped.message = format!("{format} parse failed: {}", mapped.message);
-    mapped
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Formats a FILETIME (100 ns ticks since 1601-01-01 UTC) as `YYYY-MM-DDTHH:MM:SSZ`.
+fn filetime_to_iso8601(filetime: u64) -> Option<String> {
+    if filetime == 0 {
+        return None;
+    }
+    let secs = (filetime / 10_000_000) as i64 - FILETIME_UNIX_OFFSET_SECS;
+    let days = secs.div_euclid(86_400);
+    let time = secs.rem_euclid(86_400);
+
+    // Civil-from-days (proleptic Gregorian), after Howard Hinnant.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    Some(format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    ))
 }
@@ -0,0 +1,132 @@
+use crate::input::{InputFormat, load_input};
+use crate::tools::{check_arguments, error_result, parse_document};
+use serde_json::{Value, json};
+
+pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let payload = match load_input(args) {
+        Ok(payload) => payload,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let mut parsed = match parse_document(&payload.bytes, payload.format) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return error_result(err.kind, err.message, Some(payload.source.as_str()));
+        }
+    };
+
+    let doc_info = &parsed.document.doc_info;
+    let para_styles: Vec<Value> = doc_info
+        .styles
+        .iter()
+        .enumerate()
+        .map(|(id, style)| {
+            let para_shape = doc_info.para_shapes.get(usize::from(style.para_shape_id));
+            json!({
+                "id": id,
+                "name": style.name,
+                "english_name": style.english_name,
+                "kind": if style.properties & 0x07 == 1 { "character" } else { "paragraph" },
+                "para_shape_id": style.para_shape_id,
+                "char_shape_id": style.char_shape_id,
+                "next_style_id": style.next_style_id,
+                "alignment": para_shape.map(|shape| alignment_name(shape, parsed.format)),
+                "line_spacing": para_shape.map(line_spacing),
+            })
+        })
+        .collect();
+    let char_shapes: Vec<Value> = doc_info
+        .char_shapes
+        .iter()
+        .enumerate()
+        .map(|(id, shape)| {
+            let font_id = shape.face_name_ids[0];
+            let font_name = doc_info
+                .face_names
+                .get(usize::from(font_id))
+                .map(|face| face.font_name.clone());
+            json!({
+                "id": id,
+                "font_id": font_id,
+                "font_name": font_name,
+                "size_pt": f64::from(shape.base_size) / 100.0,
+                "bold": shape.is_bold(),
+                "italic": shape.is_italic(),
+                "underline": shape.is_underline(),
+                "color": format!("#{:06X}", shape.text_color & 0xFF_FFFF),
+            })
+        })
+        .collect();
+
+    if para_styles.is_empty() {
+        let message = match parsed.format {
+            InputFormat::Hwpx => "para_styles: the hwpx reader does not expose style definitions",
+            _ => "para_styles: document declares no styles",
+        };
+        parsed.warnings.push(message.to_string());
+    }
+    if char_shapes.is_empty() {
+        parsed
+            .warnings
+            .push("char_shapes: document declares no character shapes".to_string());
+    }
+
+    json!({
+        "content": [{
+            "type": "text",
+            "text": format!(
+                "{} paragraph style(s), {} character shape(s)",
+                para_styles.len(),
+                char_shapes.len()
+            )
+        }],
+        "structuredContent": {
+            "format": parsed.format.as_str(),
+            "detected_format": parsed.format.as_str(),
+            "detection_method": payload.detection_method,
+            "para_styles": para_styles,
+            "char_shapes": char_shapes,
+            "warnings": parsed.warnings
+        },
+        "isError": false
+    })
+}
+
+/// HWP stores alignment in bits 2-4 of `properties1` (0 justify, 1 left, 2 right, 3 center,
+/// 4 distribute, 5 divide); the `hwpers` HWPX reader packs its own left/center/right/justify order.
+fn alignment_name(shape: &hwpers::model::ParaShape, format: InputFormat) -> &'static str {
+    let value = shape.get_alignment();
+    match format {
+        InputFormat::Hwpx => match value {
+            1 => "center",
+            2 => "right",
+            3 => "justify",
+            _ => "left",
+        },
+        _ => match value {
+            0 => "justify",
+            1 => "left",
+            2 => "right",
+            3 => "center",
+            4 => "distribute",
+            5 => "divide",
+            _ => "unknown",
+        },
+    }
+}
+
+/// Percent spacing is a percentage of the font size; the other types are HWPUNIT lengths.
+fn line_spacing(shape: &hwpers::model::ParaShape) -> Value {
+    let kind = match shape.line_space_type {
+        0 => "percent",
+        1 => "fixed",
+        2 => "space_only",
+        3 => "at_least",
+        _ => "unknown",
+    };
+    json!({ "type": kind, "value": shape.line_space })
+}
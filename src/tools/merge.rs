@@ -0,0 +1,399 @@
+use crate::input::{is_distribution_document, load_input};
+use crate::mcp::contracts::limits;
+use crate::mcp::errors;
+use crate::tools::{
+    ToolError, check_arguments, error_result, map_hwp_error_with_stage, parse_document,
+    write_file_atomically,
+};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use hwpers::model::Section;
+use hwpers::{HwpDocument, HwpWriter, HwpxWriter};
+use serde_json::{Value, json};
+use std::path::Path;
+
+/// HWP paragraph divide-sort flag for a page break before the paragraph.
+const DIVIDE_PAGE_BREAK: u8 = 0x04;
+
+pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let Some(args_obj) = args.as_object() else {
+        return error_result(errors::INVALID_INPUT, "arguments must be an object", None);
+    };
+
+    let to_format = match OutputFormat::parse(args.get("to")) {
+        Ok(to_format) => to_format,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let separator = match Separator::parse(args.get("separator")) {
+        Ok(separator) => separator,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let output_path = match parse_output_path(args.get("output_path")) {
+        Ok(path) => path,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let Some(inputs) = args_obj.get("inputs").and_then(|value| value.as_array()) else {
+        return error_result(errors::INVALID_INPUT, "inputs must be an array", None);
+    };
+    if inputs.len() < 2 {
+        return error_result(
+            errors::INVALID_INPUT,
+            "inputs must contain at least 2 documents",
+            None,
+        );
+    }
+
+    let mut merged: Option<HwpDocument> = None;
+    let mut formats = Vec::new();
+    let mut detections = Vec::new();
+    let mut warnings = Vec::new();
+    for (idx, input) in inputs.iter().enumerate() {
+        let payload = match load_input(input) {
+            Ok(payload) => payload,
+            Err(err) => {
+                return error_result(err.kind, format!("inputs[{idx}]: {}", err.message), None);
+            }
+        };
+        if is_distribution_document(&payload.bytes) {
+            return error_result(
+                errors::ENCRYPTED,
+                format!(
+                    "inputs[{idx}]: distribution document (배포용 문서) is read-only and cannot be merged"
+                ),
+                Some(payload.source.as_str()),
+            );
+        }
+        let parsed = match parse_document(&payload.bytes, payload.format) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                return error_result(
+                    err.kind,
+                    format!("inputs[{idx}]: {}", err.message),
+                    Some(payload.source.as_str()),
+                );
+            }
+        };
+        formats.push(parsed.format);
+        detections.push(json!({
+            "detected_format": parsed.format.as_str(),
+            "detection_method": payload.detection_method
+        }));
+        warnings.extend(
+            parsed
+                .warnings
+                .into_iter()
+                .map(|warning| format!("inputs[{idx}]: {warning}")),
+        );
+        match merged.as_mut() {
+            Some(target) => append_document(target, parsed.document, separator),
+            None => merged = Some(parsed.document),
+        }
+    }
+    let Some(merged) = merged else {
+        return error_result(errors::INTERNAL_ERROR, "no documents merged", None);
+    };
+
+    if formats.iter().any(|format| *format != formats[0]) {
+        warnings.push(format!(
+            "inputs mix hwp and hwpx formats; inputs[0] ({}) was used as the base document",
+            formats[0].as_str()
+        ));
+    }
+
+    if separator == Separator::PageBreak {
+        warnings.push(
+            "separator page_break: hwpers writers do not persist page breaks; documents are concatenated"
+                .to_string(),
+        );
+    }
+
+    let section_count = merged.sections().count() as u64;
+    let output_bytes = match to_format {
+        OutputFormat::Hwp => HwpWriter::from_document(merged)
+            .to_bytes()
+            .map_err(|error| map_hwp_error_with_stage(error, "merge to hwp")),
+        OutputFormat::Hwpx => HwpxWriter::from_document(merged)
+            .to_bytes()
+            .map_err(|error| map_hwp_error_with_stage(error, "merge to hwpx")),
+    };
+
+    let output_bytes = match output_bytes {
+        Ok(bytes) => bytes,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let bytes_len = output_bytes.len() as u64;
+    let documents = inputs.len() as u64;
+
+    match output_path {
+        Some(path) => match write_output(&path, &output_bytes) {
+            Ok(output) => json!({
+                "content": output.content,
+                "structuredContent": {
+                    "to": to_format.as_str(),
+                    "separator": separator.as_str(),
+                    "documents": documents,
+                    "sections": section_count,
+                    "inputs": detections,
+                    "path": output.path,
+                    "uri": output.uri,
+                    "bytes_len": bytes_len,
+                    "warnings": warnings
+                },
+                "isError": false
+            }),
+            Err(err) => error_result(err.kind, err.message, None),
+        },
+        None => {
+            let max_output_bytes = limits().max_output_bytes;
+            if bytes_len > max_output_bytes {
+                return error_result(
+                    errors::TOO_LARGE,
+                    format!("output exceeds limit: {bytes_len} bytes (max {max_output_bytes})"),
+                    None,
+                );
+            }
+            let base64 = STANDARD.encode(&output_bytes);
+            json!({
+                "content": [{
+                    "type": "text",
+                    "text": format!(
+                        "merged {documents} documents into {} ({bytes_len} bytes)",
+                        to_format.as_str()
+                    )
+                }],
+                "structuredContent": {
+                    "to": to_format.as_str(),
+                    "separator": separator.as_str(),
+                    "documents": documents,
+                    "sections": section_count,
+                    "inputs": detections,
+                    "base64": base64,
+                    "bytes_len": bytes_len,
+                    "warnings": warnings
+                },
+                "isError": false
+            })
+        }
+    }
+}
+
+/// Appends `source` to `target`, remapping shape and binary ids so the
+/// appended paragraphs keep referring to their own formatting.
+fn append_document(target: &mut HwpDocument, mut source: HwpDocument, separator: Separator) {
+    let char_shape_offset = target.doc_info.char_shapes.len() as u16;
+    let para_shape_offset = target.doc_info.para_shapes.len() as u16;
+    let bin_id_offset = target
+        .doc_info
+        .bin_data
+        .iter()
+        .map(|bin| bin.bin_id)
+        .max()
+        .unwrap_or(0);
+
+    for body_text in &mut source.body_texts {
+        for section in &mut body_text.sections {
+            for paragraph in &mut section.paragraphs {
+                paragraph.para_shape_id = paragraph.para_shape_id.saturating_add(para_shape_offset);
+                if let Some(char_shapes) = paragraph.char_shapes.as_mut() {
+                    for position in &mut char_shapes.char_positions {
+                        position.char_shape_id =
+                            position.char_shape_id.saturating_add(char_shape_offset);
+                    }
+                }
+                if let Some(picture) = paragraph.picture_data.as_mut() {
+                    picture.bin_item_id = picture.bin_item_id.saturating_add(bin_id_offset);
+                }
+            }
+        }
+    }
+
+    target
+        .doc_info
+        .char_shapes
+        .append(&mut source.doc_info.char_shapes);
+    target
+        .doc_info
+        .para_shapes
+        .append(&mut source.doc_info.para_shapes);
+    for mut bin in source.doc_info.bin_data.drain(..) {
+        bin.bin_id = bin.bin_id.saturating_add(bin_id_offset);
+        target.doc_info.bin_data.push(bin);
+    }
+
+    match separator {
+        Separator::SectionBreak => {
+            target.body_texts.append(&mut source.body_texts);
+            let section_count = target.sections().count() as u16;
+            if let Some(properties) = target.doc_info.properties.as_mut() {
+                properties.section_count = section_count;
+            }
+        }
+        Separator::None | Separator::PageBreak => {
+            let mut paragraphs = source
+                .body_texts
+                .into_iter()
+                .flat_map(|body_text| body_text.sections)
+                .flat_map(|section| section.paragraphs)
+                .collect::<Vec<_>>();
+            if separator == Separator::PageBreak
+                && let Some(first) = paragraphs.first_mut()
+            {
+                first.column_type |= DIVIDE_PAGE_BREAK;
+            }
+            match target
+                .body_texts
+                .last_mut()
+                .and_then(|body_text| body_text.sections.last_mut())
+            {
+                Some(section) => section.paragraphs.append(&mut paragraphs),
+                None => target.body_texts.push(hwpers::parser::body_text::BodyText {
+                    sections: vec![Section {
+                        paragraphs,
+                        section_def: None,
+                        page_def: None,
+                    }],
+                }),
+            }
+        }
+    }
+}
+
+struct OutputResource {
+    path: String,
+    uri: String,
+    content: Vec<Value>,
+}
+
+enum OutputFormat {
+    Hwp,
+    Hwpx,
+}
+
+impl OutputFormat {
+    fn parse(value: Option<&Value>) -> Result<Self, ToolError> {
+        let Some(value) = value else {
+            return Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "to is required".to_string(),
+            });
+        };
+        let Some(value) = value.as_str() else {
+            return Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "to must be a string".to_string(),
+            });
+        };
+        match value {
+            "hwp" => Ok(OutputFormat::Hwp),
+            "hwpx" => Ok(OutputFormat::Hwpx),
+            _ => Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "to must be hwp or hwpx".to_string(),
+            }),
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            OutputFormat::Hwp => "hwp",
+            OutputFormat::Hwpx => "hwpx",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Separator {
+    None,
+    PageBreak,
+    SectionBreak,
+}
+
+impl Separator {
+    fn parse(value: Option<&Value>) -> Result<Self, ToolError> {
+        let Some(value) = value else {
+            return Ok(Separator::SectionBreak);
+        };
+        let Some(value) = value.as_str() else {
+            return Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "separator must be a string".to_string(),
+            });
+        };
+        match value {
+            "none" => Ok(Separator::None),
+            "page_break" => Ok(Separator::PageBreak),
+            "section_break" => Ok(Separator::SectionBreak),
+            _ => Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "separator must be none, page_break, or section_break".to_string(),
+            }),
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Separator::None => "none",
+            Separator::PageBreak => "page_break",
+            Separator::SectionBreak => "section_break",
+        }
+    }
+}
+
+fn parse_output_path(value: Option<&Value>) -> Result<Option<String>, ToolError> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    let Some(path) = value.as_str() else {
+        return Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "output_path must be a string".to_string(),
+        });
+    };
+    if path.trim().is_empty() {
+        return Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "output_path must not be empty".to_string(),
+        });
+    }
+    Ok(Some(path.to_string()))
+}
+
+fn write_output(path: &str, bytes: &[u8]) -> Result<OutputResource, ToolError> {
+    write_file_atomically(Path::new(path), bytes).map_err(|err| ToolError {
+        kind: errors::INTERNAL_ERROR,
+        message: format!("failed to write output: {err}"),
+    })?;
+
+    let uri = format!("file://{path}");
+    let name = Path::new(path)
+        .file_name()
+        .and_then(|value| value.to_str())
+        .unwrap_or("merged");
+
+    let content = vec![
+        json!({
+            "type": "text",
+            "text": format!("merged output written to {path}")
+        }),
+        json!({
+            "type": "resource_link",
+            "uri": uri,
+            "name": name,
+            "mimeType": "application/octet-stream"
+        }),
+    ];
+
+    Ok(OutputResource {
+        path: path.to_string(),
+        uri: format!("file://{path}"),
+        content,
+    })
+}
@@ -1,13 +1,56 @@
+use crate::input::InputFormat;
+use crate::mcp::contracts::limits;
+use crate::mcp::errors;
+use hwpers::{HwpDocument, HwpError, HwpReader, HwpWriter, HwpxReader, HwpxWriter};
 use serde_json::json;
+use std::cell::RefCell;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, mpsc};
+use std::thread;
+use std::time::Duration;
 
+pub mod batch;
+pub(crate) mod chart;
+pub mod cleanup;
 pub mod convert;
+pub mod count_stats;
 pub mod create_document;
 pub mod create_rich_document;
+pub mod diff;
+pub mod explain;
+pub mod extract_dates;
+pub mod extract_images;
+pub mod extract_links;
+pub mod extract_notes;
+pub mod extract_preview;
 pub mod extract_rich;
 pub mod extract_text;
+pub mod extract_toc;
+pub mod fingerprint;
 pub mod inspect_metadata;
+pub mod list_styles;
+pub mod merge;
+#[cfg(feature = "ocr")]
+pub(crate) mod ocr;
+pub mod page_count;
+pub(crate) mod pattern;
+pub(crate) mod raster;
+pub(crate) mod records;
+pub mod redact;
 pub mod render_svg;
+pub mod replace_text;
+pub mod search_text;
+pub mod server_info;
+pub mod similarity;
+pub mod split;
 pub mod summarize_structure;
+pub mod to_html;
+pub mod to_json;
+pub mod to_markdown;
+pub mod validate;
 
 pub fn error_result(
     kind: &'static str,
@@ -17,6 +60,7 @@ pub fn error_result(
     let message = message.into();
     let mut error = json!({
         "kind": kind,
+        "code": crate::mcp::errors::code(kind),
         "message": message,
     });
 
@@ -32,3 +76,547 @@ pub fn error_result(
         "isError": true
     })
 }
+
+/// Rejects tool `arguments` that are not a JSON object, so a string or array payload gets a
+/// precise error instead of a misleading "field is required" from the first lookup.
+pub(crate) fn check_arguments(args: &serde_json::Value) -> Option<serde_json::Value> {
+    let kind = match args {
+        serde_json::Value::Object(_) => return None,
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "a boolean",
+        serde_json::Value::Number(_) => "a number",
+        serde_json::Value::String(_) => "a string",
+        serde_json::Value::Array(_) => "an array",
+    };
+    Some(error_result(
+        crate::mcp::errors::INVALID_INPUT,
+        format!("arguments must be an object, got {kind}"),
+        None,
+    ))
+}
+
+/// Outcome of [`run_with_timeout`].
+pub(crate) enum WorkerOutcome<T> {
+    Finished(T),
+    TimedOut,
+    Panicked,
+}
+
+/// Runs `work` on a worker thread and waits at most `timeout` for it. A worker that overruns
+/// is left detached (its result is dropped when it finishes) so the caller can keep serving.
+pub(crate) fn run_with_timeout<T: Send + 'static>(
+    timeout: Duration,
+    work: impl FnOnce() -> T + Send + 'static,
+) -> WorkerOutcome<T> {
+    let (sender, receiver) = mpsc::channel();
+    let spawned = thread::Builder::new()
+        .name("mcp-hwp-parse".to_string())
+        .spawn(move || {
+            let _ = sender.send(work());
+        });
+    if spawned.is_err() {
+        return WorkerOutcome::Panicked;
+    }
+    match receiver.recv_timeout(timeout) {
+        Ok(value) => WorkerOutcome::Finished(value),
+        Err(mpsc::RecvTimeoutError::Timeout) => WorkerOutcome::TimedOut,
+        Err(mpsc::RecvTimeoutError::Disconnected) => WorkerOutcome::Panicked,
+    }
+}
+
+/// A failed tool step: an error kind from [`crate::mcp::errors`] and its message.
+pub(crate) struct ToolError {
+    pub(crate) kind: &'static str,
+    pub(crate) message: String,
+}
+
+/// A document read by [`parse_document`], with the format it was read as. How that format was
+/// settled is reported by the input payload.
+pub(crate) struct ParsedDocument {
+    pub(crate) document: HwpDocument,
+    pub(crate) format: InputFormat,
+    pub(crate) warnings: Vec<String>,
+}
+
+/// Parses on a worker thread so a hostile input cannot wedge the server past the parse time limit.
+pub(crate) fn parse_document(
+    bytes: &[u8],
+    format: InputFormat,
+) -> Result<ParsedDocument, ToolError> {
+    let timeout_ms = limits().max_parse_ms;
+    let bytes = bytes.to_vec();
+    match run_with_timeout(Duration::from_millis(timeout_ms), move || {
+        parse_document_blocking(&bytes, format)
+    }) {
+        WorkerOutcome::Finished(result) => result,
+        WorkerOutcome::TimedOut => Err(ToolError {
+            kind: errors::TIMEOUT,
+            message: format!("parse timed out after {timeout_ms} ms"),
+        }),
+        WorkerOutcome::Panicked => Err(ToolError {
+            kind: errors::PARSE_FAILED,
+            message: "parser panicked on this input".to_string(),
+        }),
+    }
+}
+
+/// Parses `bytes` on the calling thread as `format`, which `load_input` settles from the
+/// content; `Auto` is left only when the content is inconclusive, and tries HWP and then HWPX.
+pub(crate) fn parse_document_blocking(
+    bytes: &[u8],
+    format: InputFormat,
+) -> Result<ParsedDocument, ToolError> {
+    let parsed = |document: HwpDocument, format: InputFormat| ParsedDocument {
+        document,
+        format,
+        warnings: Vec::new(),
+    };
+    match format {
+        InputFormat::Hwp => HwpReader::from_bytes(bytes)
+            .map(|document| parsed(document, format))
+            .map_err(|error| map_hwp_error_with_format(error, format.as_str())),
+        InputFormat::Hwpx => HwpxReader::from_bytes(bytes)
+            .map(|document| parsed(document, format))
+            .map_err(|error| map_hwp_error_with_format(error, format.as_str())),
+        InputFormat::Auto => match HwpReader::from_bytes(bytes) {
+            Ok(document) => Ok(parsed(document, InputFormat::Hwp)),
+            Err(hwp_err) => match HwpxReader::from_bytes(bytes) {
+                Ok(document) => {
+                    let mut document = parsed(document, InputFormat::Hwpx);
+                    document
+                        .warnings
+                        .push("auto format: hwp parse failed; hwpx succeeded".to_string());
+                    Ok(document)
+                }
+                Err(hwpx_err) => Err(ToolError {
+                    kind: errors::PARSE_FAILED,
+                    message: format!(
+                        "auto format parse failed (hwp: {}; hwpx: {})",
+                        hwp_err, hwpx_err
+                    ),
+                }),
+            },
+        },
+    }
+}
+
+pub(crate) fn map_hwp_error(error: HwpError) -> ToolError {
+    match error {
+        HwpError::UnsupportedVersion(message) => {
+            if message.contains("Password-encrypted") {
+                ToolError {
+                    kind: errors::ENCRYPTED,
+                    message,
+                }
+            } else {
+                ToolError {
+                    kind: errors::PARSE_FAILED,
+                    message,
+                }
+            }
+        }
+        HwpError::InvalidInput(message) => ToolError {
+            kind: errors::INVALID_INPUT,
+            message,
+        },
+        HwpError::Io(err) => ToolError {
+            kind: errors::INVALID_INPUT,
+            message: err.to_string(),
+        },
+        HwpError::InvalidFormat(message)
+        | HwpError::Cfb(message)
+        | HwpError::CompressionError(message)
+        | HwpError::ParseError(message)
+        | HwpError::EncodingError(message)
+        | HwpError::NotFound(message) => ToolError {
+            kind: errors::PARSE_FAILED,
+            message,
+        },
+    }
+}
+
+pub(crate) fn map_hwp_error_with_format(error: HwpError, format: &str) -> ToolError {
+    let mut mapped = map_hwp_error(error);
+    mapped.message = format!("{format} parse failed: {}", mapped.message);
+    mapped
+}
+
+pub(crate) fn map_hwp_error_with_stage(error: HwpError, stage: &str) -> ToolError {
+    let mut mapped = map_hwp_error(error);
+    mapped.message = format!("{stage} failed: {}", mapped.message);
+    mapped
+}
+
+thread_local! {
+    static CANCELLED: RefCell<Option<Arc<AtomicBool>>> = const { RefCell::new(None) };
+}
+
+/// Runs `work` with `flag` as this thread's cancellation flag, so long tools called inside it
+/// (including through `hwp.batch`) can stop early once the flag is set.
+pub(crate) fn with_cancellation<T>(flag: Arc<AtomicBool>, work: impl FnOnce() -> T) -> T {
+    let previous = CANCELLED.with(|current| current.replace(Some(flag)));
+    let result = work();
+    CANCELLED.with(|current| current.replace(previous));
+    result
+}
+
+/// Whether the request running on this thread was cancelled; always `false` outside
+/// [`with_cancellation`].
+pub(crate) fn is_cancelled() -> bool {
+    CANCELLED.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .is_some_and(|flag| flag.load(Ordering::Relaxed))
+    })
+}
+
+/// Result returned by a tool that stopped because its request was cancelled. The stdio server
+/// drops it instead of answering.
+pub(crate) fn cancelled_result() -> serde_json::Value {
+    error_result(errors::CANCELLED, "request cancelled", None)
+}
+
+/// Writes `path` through a temporary sibling: `fill` writes the temporary file, which is fsynced
+/// and then renamed over `path`, so a crash or a process watching `path` never sees a
+/// half-written output. The temporary file is removed when any step fails.
+pub(crate) fn write_atomically<E: From<io::Error>>(
+    path: &Path,
+    fill: impl FnOnce(&Path) -> Result<(), E>,
+) -> Result<(), E> {
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "output".to_string());
+    let temp = dir.join(format!(
+        ".{file_name}.{}.tmp",
+        crate::mcp::resources::unique_suffix()
+    ));
+
+    let result = fill(&temp).and_then(|()| {
+        fs::OpenOptions::new().write(true).open(&temp)?.sync_all()?;
+        replace_file(&temp, path)?;
+        Ok(())
+    });
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+        return result;
+    }
+    // Persist the rename itself; not every platform or filesystem can open a directory.
+    #[cfg(unix)]
+    if let Ok(dir) = fs::File::open(dir) {
+        let _ = dir.sync_all();
+    }
+    result
+}
+
+/// [`write_atomically`] for bytes already in memory.
+pub(crate) fn write_file_atomically(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    write_atomically(path, |temp| fs::write(temp, bytes))
+}
+
+/// Renames `from` over `to`. Windows can refuse to replace an existing file (for example one
+/// another process holds open without delete sharing), so there the old file is removed and
+/// the rename retried.
+fn replace_file(from: &Path, to: &Path) -> io::Result<()> {
+    match fs::rename(from, to) {
+        Ok(()) => Ok(()),
+        #[cfg(windows)]
+        Err(_) if to.is_file() => {
+            fs::remove_file(to)?;
+            fs::rename(from, to)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// A document written to `output_path`, with the content items that announce it.
+pub(crate) struct OutputResource {
+    pub(crate) path: String,
+    pub(crate) uri: String,
+    pub(crate) bytes_len: u64,
+    pub(crate) content: Vec<serde_json::Value>,
+}
+
+/// A finished document. HWP is serialized up front; HWPX keeps its writer so an
+/// `output_path` can be written entry by entry instead of buffering the whole ZIP.
+/// Text targets carry the rendered text and its MIME type.
+pub(crate) enum BuiltDocument {
+    Bytes(Vec<u8>),
+    Hwpx(Box<HwpxWriter>),
+    Text(String, &'static str),
+}
+
+impl BuiltDocument {
+    pub(crate) fn into_bytes(self) -> Result<Vec<u8>, ToolError> {
+        match self {
+            BuiltDocument::Bytes(bytes) => Ok(bytes),
+            BuiltDocument::Text(text, _) => Ok(text.into_bytes()),
+            BuiltDocument::Hwpx(writer) => writer
+                .to_bytes()
+                .map_err(|error| map_hwp_error_with_stage(error, "write document")),
+        }
+    }
+}
+
+/// Target of the editing tools. `to` is optional there; without it the edited document
+/// keeps its input format.
+pub(crate) enum EditedFormat {
+    Hwp,
+    Hwpx,
+}
+
+impl EditedFormat {
+    pub(crate) fn parse(value: Option<&serde_json::Value>) -> Result<Option<Self>, ToolError> {
+        let Some(value) = value else {
+            return Ok(None);
+        };
+        let Some(value) = value.as_str() else {
+            return Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "to must be a string".to_string(),
+            });
+        };
+        match value {
+            "hwp" => Ok(Some(EditedFormat::Hwp)),
+            "hwpx" => Ok(Some(EditedFormat::Hwpx)),
+            _ => Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "to must be hwp or hwpx".to_string(),
+            }),
+        }
+    }
+
+    /// The format `to` named, or else the one the document was read in.
+    pub(crate) fn or_input(to: Option<Self>, input: InputFormat) -> Self {
+        to.unwrap_or(match input {
+            InputFormat::Hwpx => EditedFormat::Hwpx,
+            _ => EditedFormat::Hwp,
+        })
+    }
+
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            EditedFormat::Hwp => "hwp",
+            EditedFormat::Hwpx => "hwpx",
+        }
+    }
+
+    pub(crate) fn build(&self, document: HwpDocument) -> Result<BuiltDocument, ToolError> {
+        match self {
+            EditedFormat::Hwp => HwpWriter::from_document(document)
+                .to_bytes()
+                .map(BuiltDocument::Bytes)
+                .map_err(|error| map_hwp_error_with_stage(error, "write hwp")),
+            EditedFormat::Hwpx => Ok(BuiltDocument::Hwpx(Box::new(HwpxWriter::from_document(
+                document,
+            )))),
+        }
+    }
+}
+
+pub(crate) fn parse_output_path(
+    value: Option<&serde_json::Value>,
+) -> Result<Option<String>, ToolError> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    let Some(path) = value.as_str() else {
+        return Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "output_path must be a string".to_string(),
+        });
+    };
+    if path.trim().is_empty() {
+        return Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "output_path must not be empty".to_string(),
+        });
+    }
+    Ok(Some(path.to_string()))
+}
+
+/// Writes `document` to `path` atomically and registers it as a resource. The text content
+/// item reads `{summary}; output written to {path}`.
+pub(crate) fn write_output(
+    path: &str,
+    document: BuiltDocument,
+    summary: &str,
+) -> Result<OutputResource, ToolError> {
+    let write_error = |err: io::Error| ToolError {
+        kind: errors::INTERNAL_ERROR,
+        message: format!("failed to write output: {err}"),
+    };
+    let mime_type = match &document {
+        BuiltDocument::Bytes(_) => crate::mcp::resources::HWP_MIME_TYPE,
+        BuiltDocument::Hwpx(_) => crate::mcp::resources::HWPX_MIME_TYPE,
+        BuiltDocument::Text(_, mime_type) => mime_type,
+    };
+    match document {
+        BuiltDocument::Bytes(bytes) => {
+            write_file_atomically(Path::new(path), &bytes).map_err(write_error)?
+        }
+        BuiltDocument::Text(text, _) => {
+            write_file_atomically(Path::new(path), text.as_bytes()).map_err(write_error)?
+        }
+        BuiltDocument::Hwpx(writer) => {
+            write_atomically(Path::new(path), |temp| writer.save_to_file(temp)).map_err(
+                |error| match error {
+                    HwpError::Io(err) => write_error(err),
+                    error => map_hwp_error_with_stage(error, "write document"),
+                },
+            )?
+        }
+    }
+    let bytes_len = fs::metadata(path).map_err(write_error)?.len();
+
+    let uri = crate::mcp::resources::register(path, mime_type);
+    let name = Path::new(path)
+        .file_name()
+        .and_then(|value| value.to_str())
+        .unwrap_or("output");
+
+    let content = vec![
+        json!({
+            "type": "text",
+            "text": format!("{summary}; output written to {path}")
+        }),
+        json!({
+            "type": "resource_link",
+            "uri": uri,
+            "name": name,
+            "mimeType": mime_type
+        }),
+    ];
+
+    Ok(OutputResource {
+        path: path.to_string(),
+        uri,
+        bytes_len,
+        content,
+    })
+}
+
+/// The `compress` argument of tools that return base64 payloads. Gzip is applied before base64
+/// encoding and the output limit is checked against the compressed size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Compress {
+    None,
+    Gzip,
+}
+
+impl Compress {
+    pub(crate) fn parse(value: Option<&serde_json::Value>) -> Result<Self, String> {
+        match value {
+            None | Some(serde_json::Value::Null) => Ok(Compress::None),
+            Some(value) => match value.as_str() {
+                Some("none") => Ok(Compress::None),
+                Some("gzip") => Ok(Compress::Gzip),
+                _ => Err("compress must be none or gzip".to_string()),
+            },
+        }
+    }
+
+    /// The `encoding` reported next to compressed payloads.
+    pub(crate) fn encoding(self) -> Option<&'static str> {
+        match self {
+            Compress::None => None,
+            Compress::Gzip => Some("gzip"),
+        }
+    }
+
+    pub(crate) fn apply(self, bytes: &[u8]) -> Vec<u8> {
+        match self {
+            Compress::None => bytes.to_vec(),
+            Compress::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                // Writing into a Vec cannot fail.
+                let _ = io::Write::write_all(&mut encoder, bytes);
+                encoder.finish().unwrap_or_default()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn entries(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .expect("read_dir")
+            .map(|entry| {
+                entry
+                    .expect("entry")
+                    .file_name()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn write_atomically_replaces_existing_file() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("out.hwp");
+        fs::write(&path, b"old contents").expect("seed");
+
+        write_file_atomically(&path, b"new").expect("write");
+
+        assert_eq!(fs::read(&path).expect("read"), b"new");
+        assert_eq!(entries(dir.path()), vec!["out.hwp".to_string()]);
+    }
+
+    #[test]
+    fn write_atomically_keeps_original_when_fill_fails() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("out.hwp");
+        fs::write(&path, b"old contents").expect("seed");
+
+        let result = write_atomically(&path, |temp| {
+            fs::write(temp, b"partial")?;
+            Err(io::Error::other("writer failed"))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(fs::read(&path).expect("read"), b"old contents");
+        assert_eq!(entries(dir.path()), vec!["out.hwp".to_string()]);
+    }
+
+    #[test]
+    fn cancellation_flag_is_scoped_to_the_call() {
+        let flag = Arc::new(AtomicBool::new(false));
+        assert!(!is_cancelled());
+        with_cancellation(Arc::clone(&flag), || {
+            assert!(!is_cancelled());
+            flag.store(true, Ordering::Relaxed);
+            assert!(is_cancelled());
+        });
+        assert!(!is_cancelled());
+    }
+
+    #[test]
+    fn gzip_round_trips() {
+        let bytes = "반복 ".repeat(1000).into_bytes();
+        let compressed = Compress::Gzip.apply(&bytes);
+        assert!(compressed.len() < bytes.len());
+
+        let mut decoded = Vec::new();
+        io::Read::read_to_end(
+            &mut flate2::read::GzDecoder::new(&compressed[..]),
+            &mut decoded,
+        )
+        .expect("gunzip");
+        assert_eq!(decoded, bytes);
+        assert_eq!(Compress::None.apply(&bytes), bytes);
+        assert!(Compress::parse(Some(&json!("brotli"))).is_err());
+    }
+}
@@ -0,0 +1,109 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Languages passed to the OCR engine (Korean first, then English).
+const OCR_LANGUAGES: &str = "kor+eng";
+
+pub(crate) struct OcrResult {
+    pub text: String,
+    /// Mean word confidence in the 0-100 range, if the engine reported any words.
+    pub confidence: Option<f64>,
+}
+
+pub(crate) trait OcrBackend {
+    fn name(&self) -> &'static str;
+    fn recognize(&self, image: &[u8]) -> Result<OcrResult, String>;
+}
+
+/// Returns the first OCR backend that is usable on this machine.
+pub(crate) fn detect_backend() -> Option<Box<dyn OcrBackend>> {
+    let available = Command::new("tesseract")
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false);
+    if available {
+        Some(Box::new(Tesseract))
+    } else {
+        None
+    }
+}
+
+struct Tesseract;
+
+impl OcrBackend for Tesseract {
+    fn name(&self) -> &'static str {
+        "tesseract"
+    }
+
+    fn recognize(&self, image: &[u8]) -> Result<OcrResult, String> {
+        let mut child = Command::new("tesseract")
+            .args(["stdin", "stdout", "-l", OCR_LANGUAGES, "tsv"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| format!("failed to start tesseract: {err}"))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(image)
+                .map_err(|err| format!("failed to send image to tesseract: {err}"))?;
+        }
+        let output = child
+            .wait_with_output()
+            .map_err(|err| format!("tesseract failed: {err}"))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("tesseract failed: {}", stderr.trim()));
+        }
+        Ok(parse_tsv(&String::from_utf8_lossy(&output.stdout)))
+    }
+}
+
+/// Rebuilds line-broken text and the mean word confidence from tesseract's TSV output.
+fn parse_tsv(tsv: &str) -> OcrResult {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current_line: Option<(String, String, String)> = None;
+    let mut confidence_sum = 0.0;
+    let mut words = 0usize;
+
+    // Columns: level page block par line word left top width height conf text
+    for row in tsv.lines().skip(1) {
+        let fields: Vec<&str> = row.split('\t').collect();
+        if fields.len() < 12 || fields[0] != "5" {
+            continue;
+        }
+        let word = fields[11].trim();
+        if word.is_empty() {
+            continue;
+        }
+        let line_key = (
+            fields[2].to_string(),
+            fields[3].to_string(),
+            fields[4].to_string(),
+        );
+        if current_line.as_ref() != Some(&line_key) || lines.is_empty() {
+            lines.push(String::new());
+            current_line = Some(line_key);
+        }
+        if let Some(line) = lines.last_mut() {
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        if let Ok(confidence) = fields[10].parse::<f64>()
+            && confidence >= 0.0
+        {
+            confidence_sum += confidence;
+            words += 1;
+        }
+    }
+
+    OcrResult {
+        text: lines.join("\n"),
+        confidence: (words > 0).then(|| confidence_sum / words as f64),
+    }
+}
@@ -0,0 +1,64 @@
+use crate::input::load_input;
+use crate::tools::{check_arguments, error_result, parse_document};
+use hwpers::render::renderer::{HwpRenderer, RenderOptions};
+use serde_json::{Value, json};
+
+pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let payload = match load_input(args) {
+        Ok(payload) => payload,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let mut parsed = match parse_document(&payload.bytes, payload.format) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return error_result(err.kind, err.message, Some(payload.source.as_str()));
+        }
+    };
+
+    if ensure_page_defs(&mut parsed.document) {
+        parsed
+            .warnings
+            .push("missing page definition; default layout applied".to_string());
+    }
+
+    let renderer = HwpRenderer::new(&parsed.document, RenderOptions::default());
+    let page_count = renderer.render().pages.len();
+    if page_count == 0 {
+        parsed
+            .warnings
+            .push("renderer produced no pages".to_string());
+    }
+
+    json!({
+        "content": [{
+            "type": "text",
+            "text": format!("{page_count} page(s)")
+        }],
+        "structuredContent": {
+            "format": parsed.format.as_str(),
+            "detected_format": parsed.format.as_str(),
+            "detection_method": payload.detection_method,
+            "page_count": page_count,
+            "warnings": parsed.warnings
+        },
+        "isError": false
+    })
+}
+
+fn ensure_page_defs(document: &mut hwpers::HwpDocument) -> bool {
+    let mut updated = false;
+    for body_text in &mut document.body_texts {
+        for section in &mut body_text.sections {
+            if section.page_def.is_none() {
+                section.page_def = Some(hwpers::model::page_def::PageDef::new_default());
+                updated = true;
+            }
+        }
+    }
+    updated
+}
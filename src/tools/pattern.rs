@@ -0,0 +1,111 @@
+//! Regular-expression search over paragraph text, on top of the `regex` crate.
+//!
+//! `regex` matches in time linear in the input and never recurses per character, so long
+//! paragraphs and pathological patterns cannot stall or overflow the worker thread.
+//! Look-around and backreferences are not supported.
+
+use regex::{Regex, RegexBuilder};
+
+/// Upper bound on the compiled program, so a huge counted repetition is rejected up front.
+const MAX_COMPILED_BYTES: usize = 1 << 20;
+
+pub(crate) struct Pattern {
+    regex: Regex,
+}
+
+impl Pattern {
+    pub(crate) fn new(source: &str, case_sensitive: bool) -> Result<Self, String> {
+        let regex = RegexBuilder::new(source)
+            .case_insensitive(!case_sensitive)
+            .size_limit(MAX_COMPILED_BYTES)
+            .build()
+            .map_err(|err| match err {
+                regex::Error::Syntax(message) => message
+                    .lines()
+                    .rev()
+                    .find(|line| line.starts_with("error:"))
+                    .unwrap_or(&message)
+                    .trim_start_matches("error:")
+                    .trim()
+                    .to_string(),
+                other => other.to_string(),
+            })?;
+        Ok(Self { regex })
+    }
+
+    /// Non-overlapping, non-empty matches as `(start, end)` char offsets into `text`.
+    pub(crate) fn find_all(&self, text: &[char]) -> Vec<(usize, usize)> {
+        let haystack: String = text.iter().collect();
+        // Byte offset -> char offset, with one extra entry for the end of the text.
+        let mut char_at = vec![0usize; haystack.len() + 1];
+        for (index, (offset, ch)) in haystack.char_indices().enumerate() {
+            char_at[offset..offset + ch.len_utf8()].fill(index);
+        }
+        char_at[haystack.len()] = text.len();
+        self.regex
+            .find_iter(&haystack)
+            .filter(|found| !found.is_empty())
+            .map(|found| (char_at[found.start()], char_at[found.end()]))
+            .collect()
+    }
+}
+
+/// Simple case folding: the first char of the lowercase mapping.
+pub(crate) fn fold(ch: char) -> char {
+    ch.to_lowercase().next().unwrap_or(ch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pattern;
+
+    fn find(pattern: &str, text: &str, case_sensitive: bool) -> Vec<String> {
+        let chars: Vec<char> = text.chars().collect();
+        Pattern::new(pattern, case_sensitive)
+            .expect("valid pattern")
+            .find_all(&chars)
+            .into_iter()
+            .map(|(start, end)| chars[start..end].iter().collect())
+            .collect()
+    }
+
+    #[test]
+    fn matches_classes_quantifiers_and_alternation() {
+        assert_eq!(
+            find(r"\d{4}-\d\d", "on 2024-03 and 2025-12", true),
+            ["2024-03", "2025-12"]
+        );
+        assert_eq!(
+            find("제(\\d+)조", "제1조 제12조", true),
+            ["제1조", "제12조"]
+        );
+        assert_eq!(find("cat|dog", "Cat dog", false), ["Cat", "dog"]);
+        assert_eq!(find("a.*?b", "axxbyyb", true), ["axxb"]);
+        assert_eq!(find("[^a-c ]+", "abc xyz", true), ["xyz"]);
+        assert_eq!(find("^ab", "abab", true), ["ab"]);
+        assert_eq!(find("(?:ab)+$", "xabab", true), ["abab"]);
+    }
+
+    #[test]
+    fn rejects_invalid_patterns() {
+        for pattern in ["(", "a)", "[a-", "*a", "a{3,1}", r"\q", "(?=a)"] {
+            assert!(Pattern::new(pattern, true).is_err(), "{pattern}");
+        }
+    }
+
+    #[test]
+    fn reports_char_offsets_for_multibyte_text() {
+        let chars: Vec<char> = "가나 다라".chars().collect();
+        let pattern = Pattern::new("다라", true).expect("valid pattern");
+        assert_eq!(pattern.find_all(&chars), [(3, 5)]);
+    }
+
+    #[test]
+    fn matches_long_text_without_backtracking() {
+        let text: Vec<char> = "a".repeat(20_000).chars().collect();
+        let pattern = Pattern::new("(a*)*b", true).expect("valid pattern");
+        assert!(pattern.find_all(&text).is_empty());
+        let pattern = Pattern::new("a+", true).expect("valid pattern");
+        assert_eq!(pattern.find_all(&text), [(0, 20_000)]);
+    }
+}
@@ -0,0 +1,114 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+use std::thread;
+
+/// Generic families handed to `resvg`, which otherwise falls back to Latin-only defaults.
+/// `rsvg-convert` resolves fonts through fontconfig, which already covers Hangul.
+const SANS_SERIF_FAMILY: &str = "Noto Sans CJK KR";
+const SERIF_FAMILY: &str = "Noto Serif CJK KR";
+
+pub(crate) trait RasterBackend {
+    fn name(&self) -> &'static str;
+    /// Rasterizes one SVG document to PNG bytes, scaling its intrinsic size by `scale`.
+    fn rasterize(&self, svg: &str, scale: f64) -> Result<Vec<u8>, String>;
+}
+
+/// The rasterizer program found on the first render, so later renders skip the
+/// `--version` probes.
+static DETECTED: OnceLock<Option<&'static str>> = OnceLock::new();
+
+/// Returns the first SVG rasterizer that is usable on this machine.
+pub(crate) fn detect_backend() -> Option<Box<dyn RasterBackend>> {
+    let program = DETECTED.get_or_init(|| {
+        ["resvg", "rsvg-convert"]
+            .into_iter()
+            .find(|program| available(program))
+    });
+    match (*program)? {
+        "resvg" => Some(Box::new(Resvg)),
+        _ => Some(Box::new(RsvgConvert)),
+    }
+}
+
+fn available(program: &str) -> bool {
+    Command::new(program)
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+struct Resvg;
+
+impl RasterBackend for Resvg {
+    fn name(&self) -> &'static str {
+        "resvg"
+    }
+
+    fn rasterize(&self, svg: &str, scale: f64) -> Result<Vec<u8>, String> {
+        let zoom = scale.to_string();
+        run(
+            "resvg",
+            &[
+                "--zoom",
+                &zoom,
+                "--sans-serif-family",
+                SANS_SERIF_FAMILY,
+                "--serif-family",
+                SERIF_FAMILY,
+                "-c",
+                "-",
+            ],
+            svg,
+        )
+    }
+}
+
+struct RsvgConvert;
+
+impl RasterBackend for RsvgConvert {
+    fn name(&self) -> &'static str {
+        "rsvg-convert"
+    }
+
+    fn rasterize(&self, svg: &str, scale: f64) -> Result<Vec<u8>, String> {
+        let zoom = scale.to_string();
+        run("rsvg-convert", &["--zoom", &zoom, "--format", "png"], svg)
+    }
+}
+
+fn run(program: &str, args: &[&str], svg: &str) -> Result<Vec<u8>, String> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("failed to start {program}: {err}"))?;
+    // Feed stdin from its own thread: a large SVG can fill the stdin pipe while the
+    // child blocks on a full stdout pipe that nobody is reading yet.
+    let writer = child.stdin.take().map(|mut stdin| {
+        let svg = svg.to_owned();
+        thread::spawn(move || stdin.write_all(svg.as_bytes()))
+    });
+    let output = child
+        .wait_with_output()
+        .map_err(|err| format!("{program} failed: {err}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("{program} failed: {}", stderr.trim()));
+    }
+    if let Some(writer) = writer {
+        writer
+            .join()
+            .map_err(|_| format!("failed to send svg to {program}"))?
+            .map_err(|err| format!("failed to send svg to {program}: {err}"))?;
+    }
+    if !output.stdout.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return Err(format!("{program} did not produce a png"));
+    }
+    Ok(output.stdout)
+}
@@ -0,0 +1,183 @@
+//! Raw record access to the `BodyText/Section{N}` streams of an HWP file.
+//!
+//! hwpers 0.5.0 reads several body records under the wrong tag ids (controls, tables,
+//! pictures, page definitions) and keeps no field or note data, so tools that need them walk
+//! the section records themselves through [`HwpSections`].
+
+use crate::mcp::contracts::limits;
+use flate2::read::{DeflateDecoder, ZlibDecoder};
+use hwpers::parser::FileHeader;
+use hwpers::parser::record::Record;
+use hwpers::reader::{CfbReader, StreamReader};
+use std::io::{Cursor, Read};
+
+/// Body record tag ids, counted from HWPTAG_BEGIN (0x10).
+const HWPTAG_BEGIN: u16 = 0x10;
+/// hwpers 0.5.0 takes the first one in a section as the section definition and starts an
+/// empty paragraph at every later one.
+pub(crate) const HWPTAG_PARA_HEADER: u16 = HWPTAG_BEGIN + 50;
+pub(crate) const HWPTAG_PARA_TEXT: u16 = HWPTAG_BEGIN + 51;
+pub(crate) const HWPTAG_CTRL_HEADER: u16 = HWPTAG_BEGIN + 55;
+pub(crate) const HWPTAG_LIST_HEADER: u16 = HWPTAG_BEGIN + 56;
+pub(crate) const HWPTAG_PAGE_DEF: u16 = HWPTAG_BEGIN + 57;
+pub(crate) const HWPTAG_TABLE: u16 = HWPTAG_BEGIN + 61;
+pub(crate) const HWPTAG_SHAPE_COMPONENT_PICTURE: u16 = HWPTAG_BEGIN + 69;
+
+/// The `BodyText/Section{N}` streams of an HWP file, in order. Iterating yields each section's
+/// records; a section whose records stop parsing early yields the records before the damage.
+pub(crate) struct HwpSections<'a> {
+    reader: CfbReader<Cursor<&'a [u8]>>,
+    compressed: bool,
+    index: usize,
+    /// Bytes left before the inflated sections together pass the input limit.
+    budget: u64,
+}
+
+impl<'a> HwpSections<'a> {
+    /// Fails when `bytes` is not a CFB container with a readable `FileHeader`, or when the
+    /// body is encrypted (distribution documents), since its records cannot be scanned then.
+    pub(crate) fn open(bytes: &'a [u8]) -> Result<Self, String> {
+        let mut reader = CfbReader::new(Cursor::new(bytes)).map_err(|err| err.to_string())?;
+        let header = reader
+            .read_stream("FileHeader")
+            .and_then(FileHeader::parse)
+            .map_err(|err| err.to_string())?;
+        if header.is_distribute() {
+            return Err(
+                "distribution document bodies are encrypted; their records cannot be scanned"
+                    .to_string(),
+            );
+        }
+        Ok(Self {
+            reader,
+            compressed: header.is_compressed(),
+            index: 0,
+            budget: limits().max_input_bytes,
+        })
+    }
+
+    /// The next section stream, inflated when the document is compressed. Inflating past the
+    /// input limit (summed over all sections) is an error, so a deflate bomb cannot exhaust
+    /// memory.
+    pub(crate) fn next_stream(&mut self) -> Option<Result<Vec<u8>, String>> {
+        let name = format!("BodyText/Section{}", self.index);
+        if !self.reader.stream_exists(&name) {
+            return None;
+        }
+        self.index += 1;
+        let data = match self.reader.read_stream(&name) {
+            Ok(data) => data,
+            Err(err) => return Some(Err(format!("{name}: {err}"))),
+        };
+        let data = if self.compressed {
+            inflate(&data, self.budget)
+        } else {
+            Some(data)
+        };
+        Some(match data {
+            Some(data) if data.len() as u64 <= self.budget => {
+                self.budget -= data.len() as u64;
+                Ok(data)
+            }
+            _ => Err(format!(
+                "{name}: sections inflate past the input limit of {} bytes",
+                limits().max_input_bytes
+            )),
+        })
+    }
+}
+
+impl Iterator for HwpSections<'_> {
+    type Item = Result<Vec<Record>, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_stream().map(|data| data.map(parse_records))
+    }
+}
+
+/// Splits a section stream into records, stopping at the first one that does not parse.
+pub(crate) fn parse_records(data: Vec<u8>) -> Vec<Record> {
+    let mut stream = StreamReader::new(data);
+    let mut records = Vec::new();
+    while stream.remaining() >= 4 {
+        let Ok(record) = Record::parse(&mut stream) else {
+            break;
+        };
+        records.push(record);
+    }
+    records
+}
+
+/// Serializes records back into a section stream, the inverse of [`parse_records`].
+pub(crate) fn write_records(records: &[Record]) -> Vec<u8> {
+    let mut data = Vec::new();
+    for record in records {
+        let tag_and_level =
+            u32::from(record.header.tag_id & 0x3FF) | (u32::from(record.header.level) << 10);
+        let size = record.data.len() as u32;
+        if size >= 0xFFF {
+            data.extend_from_slice(&(tag_and_level | (0xFFF << 20)).to_le_bytes());
+            data.extend_from_slice(&size.to_le_bytes());
+        } else {
+            data.extend_from_slice(&(tag_and_level | (size << 20)).to_le_bytes());
+        }
+        data.extend_from_slice(&record.data);
+    }
+    data
+}
+
+/// Inflates raw deflate, falling back to zlib and then to the bytes as stored, like hwpers'
+/// `decompress_stream`. Returns `None` once the output would pass `budget` bytes.
+fn inflate(data: &[u8], budget: u64) -> Option<Vec<u8>> {
+    let bounded = |reader: &mut dyn Read| {
+        let mut inflated = Vec::new();
+        reader
+            .take(budget + 1)
+            .read_to_end(&mut inflated)
+            .ok()
+            .map(|_| inflated)
+    };
+    let inflated = bounded(&mut DeflateDecoder::new(data))
+        .or_else(|| bounded(&mut ZlibDecoder::new(data)))
+        .unwrap_or_else(|| data.to_vec());
+    (inflated.len() as u64 <= budget).then_some(inflated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inflate_stops_at_the_budget() {
+        let mut encoder =
+            flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+        std::io::Write::write_all(&mut encoder, &[0u8; 4096]).expect("deflate");
+        let compressed = encoder.finish().expect("deflate");
+
+        assert_eq!(
+            inflate(&compressed, 4096).map(|data| data.len()),
+            Some(4096)
+        );
+        assert_eq!(inflate(&compressed, 4095), None);
+    }
+
+    #[test]
+    fn write_records_round_trips_extended_sizes() {
+        let record = |tag_id, level, data: Vec<u8>| Record {
+            header: hwpers::parser::record::RecordHeader {
+                tag_id,
+                level,
+                size: data.len() as u32,
+            },
+            data,
+        };
+        let records = vec![record(0x42, 0, vec![1; 24]), record(0x43, 1, vec![2; 5000])];
+
+        let parsed = parse_records(write_records(&records));
+        assert_eq!(parsed.len(), 2);
+        for (parsed, record) in parsed.iter().zip(&records) {
+            assert_eq!(parsed.header, record.header);
+            assert_eq!(parsed.data, record.data);
+        }
+    }
+}
@@ -0,0 +1,323 @@
+use crate::input::{is_distribution_document, load_input};
+use crate::mcp::contracts::limits;
+use crate::mcp::errors;
+use crate::tools::pattern::{Pattern, fold};
+use crate::tools::{
+    EditedFormat, ToolError, check_arguments, error_result, parse_document, parse_output_path,
+    write_output,
+};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde_json::{Value, json};
+
+const DEFAULT_MASK: char = '█';
+
+pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let payload = match load_input(args) {
+        Ok(payload) => payload,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let options = match RedactOptions::parse(args) {
+        Ok(options) => options,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let to_format = match EditedFormat::parse(args.get("to")) {
+        Ok(to_format) => to_format,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let output_path = match parse_output_path(args.get("output_path")) {
+        Ok(path) => path,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    if is_distribution_document(&payload.bytes) {
+        return error_result(
+            errors::ENCRYPTED,
+            "distribution document (배포용 문서) is read-only and cannot be edited",
+            Some(payload.source.as_str()),
+        );
+    }
+
+    let parsed = match parse_document(&payload.bytes, payload.format) {
+        Ok(parsed) => parsed,
+        Err(err) => return error_result(err.kind, err.message, Some(payload.source.as_str())),
+    };
+
+    let detected_format = parsed.format.as_str();
+    let detection_method = payload.detection_method;
+    let warnings = parsed.warnings;
+    let mut document = parsed.document;
+    let counts = redact_document(&mut document, &options);
+    let redactions: u64 = counts.iter().sum();
+    let matched_patterns: Vec<Value> = options
+        .patterns
+        .iter()
+        .zip(&counts)
+        .filter(|(_, count)| **count > 0)
+        .map(|(pattern, count)| {
+            json!({
+                "pattern": pattern.source,
+                "regex": pattern.is_regex(),
+                "count": count
+            })
+        })
+        .collect();
+
+    let to_format = EditedFormat::or_input(to_format, parsed.format);
+    let output = match to_format.build(document) {
+        Ok(output) => output,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    match output_path {
+        Some(path) => {
+            match write_output(&path, output, &format!("redacted {redactions} matches")) {
+                Ok(output) => json!({
+                    "content": output.content,
+                    "structuredContent": {
+                        "to": to_format.as_str(),
+                        "detected_format": detected_format,
+                        "detection_method": detection_method,
+                        "redactions": redactions,
+                        "matched_patterns": matched_patterns,
+                        "path": output.path,
+                        "uri": output.uri,
+                        "bytes_len": output.bytes_len,
+                        "warnings": warnings
+                    },
+                    "isError": false
+                }),
+                Err(err) => error_result(err.kind, err.message, None),
+            }
+        }
+        None => {
+            let output_bytes = match output.into_bytes() {
+                Ok(bytes) => bytes,
+                Err(err) => return error_result(err.kind, err.message, None),
+            };
+            let bytes_len = output_bytes.len() as u64;
+            let max_output_bytes = limits().max_output_bytes;
+            if bytes_len > max_output_bytes {
+                return error_result(
+                    errors::TOO_LARGE,
+                    format!("output exceeds limit: {bytes_len} bytes (max {max_output_bytes})"),
+                    None,
+                );
+            }
+            let base64 = STANDARD.encode(&output_bytes);
+            json!({
+                "content": [{
+                    "type": "text",
+                    "text": format!(
+                        "redacted {redactions} matches ({}, {bytes_len} bytes)",
+                        to_format.as_str()
+                    )
+                }],
+                "structuredContent": {
+                    "to": to_format.as_str(),
+                    "detected_format": detected_format,
+                    "detection_method": detection_method,
+                    "redactions": redactions,
+                    "matched_patterns": matched_patterns,
+                    "base64": base64,
+                    "bytes_len": bytes_len,
+                    "warnings": warnings
+                },
+                "isError": false
+            })
+        }
+    }
+}
+
+struct RedactPattern {
+    source: String,
+    matcher: Matcher,
+}
+
+impl RedactPattern {
+    fn is_regex(&self) -> bool {
+        matches!(self.matcher, Matcher::Regex(_))
+    }
+}
+
+enum Matcher {
+    Literal(Vec<char>),
+    Regex(Pattern),
+}
+
+impl Matcher {
+    /// Non-overlapping matches as `(start, end)` char offsets.
+    fn find_all(&self, text: &[char], case_sensitive: bool) -> Vec<(usize, usize)> {
+        match self {
+            Matcher::Regex(pattern) => pattern.find_all(text),
+            Matcher::Literal(query) => {
+                let eq = |a: &char, b: &char| a == b || (!case_sensitive && fold(*a) == fold(*b));
+                let mut found = Vec::new();
+                let mut start = 0;
+                while start + query.len() <= text.len() {
+                    if text[start..start + query.len()]
+                        .iter()
+                        .zip(query)
+                        .all(|(a, b)| eq(a, b))
+                    {
+                        found.push((start, start + query.len()));
+                        start += query.len();
+                    } else {
+                        start += 1;
+                    }
+                }
+                found
+            }
+        }
+    }
+}
+
+struct RedactOptions {
+    patterns: Vec<RedactPattern>,
+    case_sensitive: bool,
+    mask: char,
+}
+
+impl RedactOptions {
+    fn parse(args: &Value) -> Result<Self, ToolError> {
+        let invalid = |message: &str| ToolError {
+            kind: errors::INVALID_INPUT,
+            message: message.to_string(),
+        };
+        let case_sensitive = match args.get("case_sensitive") {
+            None => false,
+            Some(value) => value
+                .as_bool()
+                .ok_or_else(|| invalid("case_sensitive must be a boolean"))?,
+        };
+        let mask = match args.get("mask") {
+            None => DEFAULT_MASK,
+            Some(value) => {
+                let mut chars = value
+                    .as_str()
+                    .ok_or_else(|| invalid("mask must be a string"))?
+                    .chars();
+                match (chars.next(), chars.next()) {
+                    (Some(mask), None) if !mask.is_control() => mask,
+                    _ => return Err(invalid("mask must be exactly one printable character")),
+                }
+            }
+        };
+        let items = match args.get("patterns") {
+            None => return Err(invalid("patterns is required")),
+            Some(value) => value
+                .as_array()
+                .ok_or_else(|| invalid("patterns must be an array"))?,
+        };
+        if items.is_empty() {
+            return Err(invalid("patterns must not be empty"));
+        }
+
+        let mut patterns = Vec::with_capacity(items.len());
+        for (index, item) in items.iter().enumerate() {
+            // A bare string is a literal; `{ "regex": ... }` opts into regular expressions.
+            let (source, regex) = match item {
+                Value::String(literal) => (literal.as_str(), false),
+                Value::Object(object) => match (object.get("literal"), object.get("regex")) {
+                    (Some(Value::String(literal)), None) => (literal.as_str(), false),
+                    (None, Some(Value::String(regex))) => (regex.as_str(), true),
+                    _ => {
+                        return Err(ToolError {
+                            kind: errors::INVALID_INPUT,
+                            message: format!(
+                                "patterns[{index}] must have exactly one of literal or regex (a string)"
+                            ),
+                        });
+                    }
+                },
+                _ => {
+                    return Err(ToolError {
+                        kind: errors::INVALID_INPUT,
+                        message: format!("patterns[{index}] must be a string or an object"),
+                    });
+                }
+            };
+            if source.is_empty() {
+                return Err(ToolError {
+                    kind: errors::INVALID_INPUT,
+                    message: format!("patterns[{index}] must not be empty"),
+                });
+            }
+            let matcher = if regex {
+                Matcher::Regex(Pattern::new(source, case_sensitive).map_err(|message| {
+                    ToolError {
+                        kind: errors::INVALID_INPUT,
+                        message: format!("patterns[{index}]: invalid regex: {message}"),
+                    }
+                })?)
+            } else {
+                Matcher::Literal(source.chars().collect())
+            };
+            patterns.push(RedactPattern {
+                source: source.to_string(),
+                matcher,
+            });
+        }
+        Ok(RedactOptions {
+            patterns,
+            case_sensitive,
+            mask,
+        })
+    }
+}
+
+/// Masks every match in every paragraph of every section, which includes the table cell
+/// paragraphs the parser lists after their table, and in text boxes. Returns the match count
+/// per pattern.
+fn redact_document(document: &mut hwpers::HwpDocument, options: &RedactOptions) -> Vec<u64> {
+    let mut counts = vec![0u64; options.patterns.len()];
+    let paragraphs = document
+        .body_texts
+        .iter_mut()
+        .flat_map(|body| body.sections.iter_mut())
+        .flat_map(|section| section.paragraphs.iter_mut());
+    for paragraph in paragraphs {
+        if let Some(text) = paragraph.text.as_mut() {
+            text.content = redact_text(&text.content, options, &mut counts);
+        }
+        if let Some(text_box) = paragraph.text_box_data.as_mut() {
+            text_box.text = redact_text(&text_box.text, options, &mut counts);
+        }
+    }
+    counts
+}
+
+/// Every char covered by a match becomes the mask, so the text keeps its length and char-shape
+/// runs and hyperlink ranges stay aligned. Control chars inside a match are kept.
+fn redact_text(text: &str, options: &RedactOptions, counts: &mut [u64]) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut masked = vec![false; chars.len()];
+    for (pattern, count) in options.patterns.iter().zip(counts.iter_mut()) {
+        let found = pattern.matcher.find_all(&chars, options.case_sensitive);
+        *count += found.len() as u64;
+        for (start, end) in found {
+            masked[start..end].fill(true);
+        }
+    }
+    if !masked.contains(&true) {
+        return text.to_string();
+    }
+    chars
+        .iter()
+        .zip(masked)
+        .map(|(&ch, masked)| {
+            if masked && !ch.is_control() {
+                options.mask
+            } else {
+                ch
+            }
+        })
+        .collect()
+}
@@ -1,15 +1,95 @@
 use crate::input::{InputFormat, load_input};
-use crate::mcp::contracts::MAX_SVG_OUTPUT_BYTES;
+use crate::mcp::contracts::limits;
 use crate::mcp::errors;
-use crate::tools::error_result;
-use hwpers::render::renderer::{HwpRenderer, RenderOptions};
-use hwpers::{HwpError, HwpReader, HwpxReader};
+use crate::mcp::resources;
+use crate::tools::records::{HWPTAG_PAGE_DEF, HwpSections};
+use crate::tools::{
+    ToolError, cancelled_result, check_arguments, error_result, is_cancelled, parse_document,
+};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use hwpers::model::page_def::PageDef;
+use hwpers::parser::record::Record;
+use hwpers::render::renderer::{HwpRenderer, RenderElement, RenderOptions, RenderResult};
 use serde_json::{Value, json};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// Upper bound on the pages a `range` string may expand to.
+const MAX_RANGE_PAGES: u64 = 10_000;
+
+/// Resolution the renderer's SVG coordinates are expressed in.
+const SVG_DPI: f64 = 96.0;
+const MAX_RASTER_SCALE: f64 = 8.0;
+
+const LINE_NUMBER_FONT: &str = "sans-serif";
+const LINE_NUMBER_FONT_SIZE: f32 = 8.0;
+const LINE_NUMBER_COLOR: u32 = 0x808080;
+
+const SERIF_FALLBACK: &str =
+    "'Noto Serif CJK KR', 'Noto Serif KR', 'Nanum Myeongjo', AppleMyungjo, serif";
+const SANS_FALLBACK: &str =
+    "'Noto Sans CJK KR', 'Noto Sans KR', 'Nanum Gothic', 'Apple SD Gothic Neo', sans-serif";
+
+/// Common Korean document fonts and the generic family whose fallbacks stand in for them.
+const DEFAULT_FONT_MAP: &[(&str, &str)] = &[
+    ("함초롬바탕", SERIF_FALLBACK),
+    ("한컴바탕", SERIF_FALLBACK),
+    ("바탕", SERIF_FALLBACK),
+    ("바탕체", SERIF_FALLBACK),
+    ("궁서", SERIF_FALLBACK),
+    ("궁서체", SERIF_FALLBACK),
+    ("신명조", SERIF_FALLBACK),
+    ("HY신명조", SERIF_FALLBACK),
+    ("휴먼명조", SERIF_FALLBACK),
+    ("함초롬돋움", SANS_FALLBACK),
+    ("한컴돋움", SANS_FALLBACK),
+    ("돋움", SANS_FALLBACK),
+    ("돋움체", SANS_FALLBACK),
+    ("굴림", SANS_FALLBACK),
+    ("굴림체", SANS_FALLBACK),
+    ("맑은 고딕", SANS_FALLBACK),
+    ("중고딕", SANS_FALLBACK),
+    ("HY헤드라인M", SANS_FALLBACK),
+    ("휴먼고딕", SANS_FALLBACK),
+];
+
+static SERVER_FONT_MAP: OnceLock<BTreeMap<String, String>> = OnceLock::new();
+
+/// Installs the `serve --font-map` table, consulted after a call's own `font_map`.
+pub fn set_font_map(map: BTreeMap<String, String>) {
+    let _ = SERVER_FONT_MAP.set(map);
+}
+
+/// Reads a font map object (`{ "font name": "replacement font-family" }`).
+pub fn parse_font_map(value: &Value) -> Result<BTreeMap<String, String>, String> {
+    let Some(object) = value.as_object() else {
+        return Err("font_map must be an object of font name to replacement font".to_string());
+    };
+    let mut map = BTreeMap::new();
+    for (from, to) in object {
+        match to.as_str().map(str::trim) {
+            Some(to) if !to.is_empty() => {
+                map.insert(from.trim().to_string(), to.to_string());
+            }
+            _ => return Err(format!("font_map.{from} must be a non-empty string")),
+        }
+    }
+    Ok(map)
+}
 
 pub fn call(args: &Value) -> Value {
+    call_with_progress(args, &mut |_, _| {})
+}
+
+/// Like [`call`], reporting `(pages done, total pages)` to `progress` as each page finishes
+/// rendering (and rasterizing, for `as: "png"`). A cancelled request stops between pages.
+pub fn call_with_progress(args: &Value, progress: &mut dyn FnMut(u64, u64)) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
     let payload = match load_input(args) {
         Ok(payload) => payload,
         Err(err) => return error_result(err.kind, err.message, None),
@@ -25,6 +105,31 @@ pub fn call(args: &Value) -> Value {
         Err(err) => return error_result(err.kind, err.message, None),
     };
 
+    let output_dir = match parse_output_dir(args.get("output_dir")) {
+        Ok(output_dir) => output_dir,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let image_format = match ImageFormat::parse(args.get("as")) {
+        Ok(image_format) => image_format,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let scale = match parse_scale(args) {
+        Ok(scale) => scale,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let line_numbers = match LineNumbers::parse(args) {
+        Ok(line_numbers) => line_numbers,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let font_map = match args.get("font_map").map(parse_font_map).transpose() {
+        Ok(font_map) => font_map.unwrap_or_default(),
+        Err(message) => return error_result(errors::INVALID_INPUT, message, None),
+    };
+
     let mut parsed = match parse_document(&payload.bytes, payload.format) {
         Ok(parsed) => parsed,
         Err(err) => {
@@ -32,17 +137,46 @@ pub fn call(args: &Value) -> Value {
         }
     };
 
-    if ensure_page_defs(&mut parsed.document) {
+    let page_defs = if parsed.format == InputFormat::Hwp {
+        hwp_page_defs(&payload.bytes)
+    } else {
+        Vec::new()
+    };
+    if ensure_page_defs(&mut parsed.document, &page_defs) {
         parsed
             .warnings
             .push("missing page definition; default layout applied".to_string());
     }
 
     let renderer = HwpRenderer::new(&parsed.document, RenderOptions::default());
-    let render_result = renderer.render();
+    let mut render_result = renderer.render();
+    if let Some(line_numbers) = &line_numbers
+        && add_line_numbers(&mut render_result, line_numbers) == 0
+    {
+        parsed
+            .warnings
+            .push("line_numbers: renderer produced no text lines to number".to_string());
+    }
+    substitute_fonts(&mut render_result, &font_map, &mut parsed.warnings);
 
+    let pages = match pages {
+        Some(pages) => pages,
+        None => {
+            if render_result.pages.is_empty() {
+                parsed
+                    .warnings
+                    .push("renderer produced no pages".to_string());
+            }
+            (1..=render_result.pages.len() as u64).collect()
+        }
+    };
+
+    let total = pages.len() as u64;
     let mut rendered_pages = Vec::new();
     for page in pages {
+        if is_cancelled() {
+            return cancelled_result();
+        }
         let page_index = match usize::try_from(page.saturating_sub(1)) {
             Ok(index) => index,
             Err(_) => return error_result(errors::INVALID_INPUT, "page index out of range", None),
@@ -54,7 +188,42 @@ pub fn call(args: &Value) -> Value {
                 None,
             );
         };
-        rendered_pages.push(RenderedPage { page, svg });
+        rendered_pages.push(RenderedPage {
+            page,
+            svg,
+            png: None,
+        });
+        if image_format == ImageFormat::Svg {
+            progress(rendered_pages.len() as u64, total);
+        }
+    }
+
+    let mut rasterizer = None;
+    if image_format == ImageFormat::Png {
+        let Some(backend) = crate::tools::raster::detect_backend() else {
+            return error_result(
+                errors::UNSUPPORTED_FORMAT,
+                "png output needs an SVG rasterizer; install resvg or rsvg-convert",
+                None,
+            );
+        };
+        for (done, rendered) in (1..).zip(&mut rendered_pages) {
+            if is_cancelled() {
+                return cancelled_result();
+            }
+            match backend.rasterize(&rendered.svg, scale) {
+                Ok(png) => rendered.png = Some(png),
+                Err(message) => {
+                    return error_result(
+                        errors::INTERNAL_ERROR,
+                        format!("page {}: {message}", rendered.page),
+                        None,
+                    );
+                }
+            }
+            progress(done, total);
+        }
+        rasterizer = Some(backend.name());
     }
 
     if let Err(err) = enforce_size_limit(&rendered_pages) {
@@ -63,7 +232,7 @@ pub fn call(args: &Value) -> Value {
 
     let structured_pages = match output {
         OutputMode::Inline => render_inline(&rendered_pages),
-        OutputMode::Resource => match render_resource(&rendered_pages) {
+        OutputMode::Resource => match render_resource(&rendered_pages, output_dir.as_deref()) {
             Ok(pages) => pages,
             Err(err) => return error_result(err.kind, err.message, None),
         },
@@ -72,36 +241,81 @@ pub fn call(args: &Value) -> Value {
     let content = match output {
         OutputMode::Inline => vec![json!({
             "type": "text",
-            "text": format!("rendered {} page(s) as svg", rendered_pages.len())
+            "text": format!(
+                "rendered {} page(s) as {}",
+                rendered_pages.len(),
+                image_format.extension()
+            )
         })],
-        OutputMode::Resource => build_resource_content(&structured_pages),
+        OutputMode::Resource => build_resource_content(&structured_pages, image_format),
     };
 
+    let mut structured = json!({
+        "format": parsed.format.as_str(),
+        "detected_format": parsed.format.as_str(),
+        "detection_method": payload.detection_method,
+        "pages": structured_pages,
+        "warnings": parsed.warnings
+    });
+    if let Some(rasterizer) = rasterizer {
+        structured["rasterizer"] = json!(rasterizer);
+    }
+
     json!({
         "content": content,
-        "structuredContent": {
-            "format": parsed.format.as_str(),
-            "pages": structured_pages,
-            "warnings": parsed.warnings
-        },
+        "structuredContent": structured,
         "isError": false
     })
 }
 
-struct ToolError {
-    kind: &'static str,
-    message: String,
+struct RenderedPage {
+    page: u64,
+    svg: String,
+    /// Rasterized page when `as` is `png`.
+    png: Option<Vec<u8>>,
 }
 
-struct ParsedDocument {
-    document: hwpers::HwpDocument,
-    format: InputFormat,
-    warnings: Vec<String>,
+impl RenderedPage {
+    fn bytes(&self) -> &[u8] {
+        self.png.as_deref().unwrap_or(self.svg.as_bytes())
+    }
 }
 
-struct RenderedPage {
-    page: u64,
-    svg: String,
+struct LineNumbers {
+    start: u64,
+    interval: u64,
+}
+
+impl LineNumbers {
+    fn parse(args: &Value) -> Result<Option<Self>, ToolError> {
+        let enabled = match args.get("line_numbers") {
+            None => false,
+            Some(value) => value.as_bool().ok_or_else(|| ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "line_numbers must be a boolean".to_string(),
+            })?,
+        };
+        let start = match args.get("line_number_start") {
+            None => 1,
+            Some(value) => value.as_u64().ok_or_else(|| ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "line_number_start must be a non-negative integer".to_string(),
+            })?,
+        };
+        let interval = match args.get("line_number_interval") {
+            None => 1,
+            Some(value) => match value.as_u64() {
+                Some(interval) if interval >= 1 => interval,
+                _ => {
+                    return Err(ToolError {
+                        kind: errors::INVALID_INPUT,
+                        message: "line_number_interval must be an integer >= 1".to_string(),
+                    });
+                }
+            },
+        };
+        Ok(enabled.then_some(LineNumbers { start, interval }))
+    }
 }
 
 enum OutputMode {
@@ -131,50 +345,76 @@ impl OutputMode {
     }
 }
 
-fn parse_document(bytes: &[u8], format: InputFormat) -> Result<ParsedDocument, ToolError> {
-    match format {
-        InputFormat::Hwp => HwpReader::from_bytes(bytes)
-            .map(|document| ParsedDocument {
-                document,
-                format,
-                warnings: Vec::new(),
-            })
-            .map_err(|error| map_hwp_error_with_format(error, format.as_str())),
-        InputFormat::Hwpx => HwpxReader::from_bytes(bytes)
-            .map(|document| ParsedDocument {
-                document,
-                format,
-                warnings: Vec::new(),
-            })
-            .map_err(|error| map_hwp_error_with_format(error, format.as_str())),
-        InputFormat::Auto => {
-            let hwp_result = HwpReader::from_bytes(bytes);
-            match hwp_result {
-                Ok(document) => Ok(ParsedDocument {
-                    document,
-                    format: InputFormat::Hwp,
-                    warnings: Vec::new(),
-                }),
-                Err(hwp_err) => match HwpxReader::from_bytes(bytes) {
-                    Ok(document) => Ok(ParsedDocument {
-                        document,
-                        format: InputFormat::Hwpx,
-                        warnings: vec!["auto format: hwp parse failed; hwpx succeeded".to_string()],
-                    }),
-                    Err(hwpx_err) => Err(ToolError {
-                        kind: errors::PARSE_FAILED,
-                        message: format!(
-                            "auto format parse failed (hwp: {}; hwpx: {})",
-                            hwp_err, hwpx_err
-                        ),
-                    }),
-                },
-            }
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ImageFormat {
+    Svg,
+    Png,
+}
+
+impl ImageFormat {
+    fn parse(value: Option<&Value>) -> Result<Self, ToolError> {
+        let Some(value) = value else {
+            return Ok(ImageFormat::Svg);
+        };
+        match value.as_str() {
+            Some("svg") => Ok(ImageFormat::Svg),
+            Some("png") => Ok(ImageFormat::Png),
+            _ => Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "as must be svg or png".to_string(),
+            }),
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Svg => "svg",
+            ImageFormat::Png => "png",
+        }
+    }
+
+    fn mime_type(self) -> &'static str {
+        match self {
+            ImageFormat::Svg => "image/svg+xml",
+            ImageFormat::Png => "image/png",
         }
     }
 }
 
-fn parse_pages(args: &Value) -> Result<Vec<u64>, ToolError> {
+/// Reads the raster multiplier from `scale`, or from `dpi` relative to the SVG's 96 dpi.
+fn parse_scale(args: &Value) -> Result<f64, ToolError> {
+    let invalid = |message: &str| ToolError {
+        kind: errors::INVALID_INPUT,
+        message: message.to_string(),
+    };
+    match (args.get("scale"), args.get("dpi")) {
+        (Some(_), Some(_)) => Err(invalid("scale and dpi cannot be combined")),
+        (Some(value), None) => match value.as_f64() {
+            Some(scale) if scale > 0.0 && scale <= MAX_RASTER_SCALE => Ok(scale),
+            _ => Err(invalid("scale must be a number in (0, 8]")),
+        },
+        (None, Some(value)) => match value.as_f64() {
+            Some(dpi) if dpi > 0.0 && dpi / SVG_DPI <= MAX_RASTER_SCALE => Ok(dpi / SVG_DPI),
+            _ => Err(invalid("dpi must be a number in (0, 768]")),
+        },
+        (None, None) => Ok(1.0),
+    }
+}
+
+/// Returns `None` when `all` is set, meaning every rendered page.
+fn parse_pages(args: &Value) -> Result<Option<Vec<u64>>, ToolError> {
+    match args.get("all") {
+        None => {}
+        Some(Value::Bool(true)) => return Ok(None),
+        Some(Value::Bool(false)) => {}
+        Some(_) => {
+            return Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "all must be a boolean".to_string(),
+            });
+        }
+    }
+
     let mut pages = Vec::new();
     let mut seen = HashSet::new();
 
@@ -218,19 +458,167 @@ fn parse_pages(args: &Value) -> Result<Vec<u64>, ToolError> {
         }
     }
 
+    if let Some(value) = args.get("range") {
+        let Some(range) = value.as_str() else {
+            return Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "range must be a string such as \"1-3,5\"".to_string(),
+            });
+        };
+        for page in parse_page_range(range)? {
+            if seen.insert(page) {
+                pages.push(page);
+            }
+        }
+    }
+
     if pages.is_empty() {
         pages.push(1);
     }
 
+    Ok(Some(pages))
+}
+
+/// Expands a comma-separated list of pages and inclusive ranges (`"1-3,5,9-10"`) in order.
+fn parse_page_range(range: &str) -> Result<Vec<u64>, ToolError> {
+    let invalid = |message: String| ToolError {
+        kind: errors::INVALID_INPUT,
+        message,
+    };
+    let parse_page = |text: &str, part: &str| -> Result<u64, ToolError> {
+        let page = text
+            .trim()
+            .parse::<u64>()
+            .map_err(|_| invalid(format!("range: invalid page '{part}'")))?;
+        if page == 0 {
+            return Err(invalid(format!("range: pages must be >= 1 ('{part}')")));
+        }
+        Ok(page)
+    };
+
+    let mut pages = Vec::new();
+    for part in range.split(',').map(str::trim) {
+        if part.is_empty() {
+            return Err(invalid("range: empty entry".to_string()));
+        }
+        let (start, end) = match part.split_once('-') {
+            Some((start, end)) => (parse_page(start, part)?, parse_page(end, part)?),
+            None => {
+                let page = parse_page(part, part)?;
+                (page, page)
+            }
+        };
+        if start > end {
+            return Err(invalid(format!("range: reversed range '{part}'")));
+        }
+        if pages.len() as u64 + (end - start + 1) > MAX_RANGE_PAGES {
+            return Err(invalid(format!(
+                "range: expands to more than {MAX_RANGE_PAGES} pages"
+            )));
+        }
+        pages.extend(start..=end);
+    }
     Ok(pages)
 }
 
+/// Labels every text baseline in the left margin, counting lines continuously across pages.
+/// Returns the number of lines found.
+fn add_line_numbers(result: &mut RenderResult, options: &LineNumbers) -> u64 {
+    let mut line = 0u64;
+    for page in &mut result.pages {
+        let mut baselines = Vec::new();
+        let mut left = i32::MAX;
+        for element in &page.elements {
+            if let RenderElement::Text { x, y, text, .. } = element
+                && !text.trim().is_empty()
+            {
+                baselines.push(*y);
+                left = left.min(*x);
+            }
+        }
+        baselines.sort_unstable();
+        baselines.dedup();
+
+        let x = (left / 2).max(0);
+        for y in baselines {
+            let number = options.start + line;
+            line += 1;
+            if !number.is_multiple_of(options.interval) {
+                continue;
+            }
+            page.elements.push(RenderElement::Text {
+                x,
+                y,
+                text: number.to_string(),
+                font_family: LINE_NUMBER_FONT.to_string(),
+                font_size: LINE_NUMBER_FONT_SIZE,
+                color: LINE_NUMBER_COLOR,
+                bold: false,
+                italic: false,
+                underline: false,
+            });
+        }
+    }
+    line
+}
+
+/// Rewrites text font families through the call's `font_map`, then the server map, then the
+/// built-in Korean defaults (which keep the original font first and append fallbacks).
+fn substitute_fonts(
+    result: &mut RenderResult,
+    font_map: &BTreeMap<String, String>,
+    warnings: &mut Vec<String>,
+) {
+    let server_map = SERVER_FONT_MAP.get();
+    let mut substituted: BTreeMap<String, String> = BTreeMap::new();
+    for page in &mut result.pages {
+        for element in &mut page.elements {
+            let RenderElement::Text { font_family, .. } = element else {
+                continue;
+            };
+            let name = font_family.trim();
+            let replacement = font_map
+                .get(name)
+                .or_else(|| server_map.and_then(|map| map.get(name)))
+                .cloned()
+                .or_else(|| {
+                    DEFAULT_FONT_MAP
+                        .iter()
+                        .find(|(font, _)| *font == name)
+                        .map(|(font, fallback)| format!("'{font}', {fallback}"))
+                });
+            let Some(replacement) = replacement else {
+                continue;
+            };
+            substituted
+                .entry(name.to_string())
+                .or_insert_with(|| replacement.clone());
+            // The renderer writes font-family unescaped.
+            *font_family = replacement
+                .replace('&', "&amp;")
+                .replace('"', "&quot;")
+                .replace('<', "&lt;");
+        }
+    }
+    for (from, to) in substituted {
+        warnings.push(format!("font substituted: '{from}' -> {to}"));
+    }
+}
+
 fn enforce_size_limit(pages: &[RenderedPage]) -> Result<(), ToolError> {
-    let size: u64 = pages.iter().map(|page| page.svg.len() as u64).sum();
-    if size > MAX_SVG_OUTPUT_BYTES {
+    let size: u64 = pages.iter().map(|page| page.bytes().len() as u64).sum();
+    let max_svg_output_bytes = limits().max_svg_output_bytes;
+    if size > max_svg_output_bytes {
+        let label = if pages.iter().any(|page| page.png.is_some()) {
+            "png"
+        } else {
+            "svg"
+        };
         return Err(ToolError {
             kind: errors::TOO_LARGE,
-            message: format!("svg output exceeds limit: {size} bytes (max {MAX_SVG_OUTPUT_BYTES})"),
+            message: format!(
+                "{label} output exceeds limit: {size} bytes (max {max_svg_output_bytes})"
+            ),
         });
     }
     Ok(())
@@ -239,20 +627,47 @@ fn enforce_size_limit(pages: &[RenderedPage]) -> Result<(), ToolError> {
 fn render_inline(pages: &[RenderedPage]) -> Vec<Value> {
     pages
         .iter()
-        .map(|page| json!({"page": page.page, "svg": page.svg}))
+        .map(|page| match &page.png {
+            Some(png) => json!({
+                "page": page.page,
+                "mimeType": "image/png",
+                "base64": STANDARD.encode(png)
+            }),
+            None => json!({"page": page.page, "svg": page.svg}),
+        })
         .collect()
 }
 
-fn render_resource(pages: &[RenderedPage]) -> Result<Vec<Value>, ToolError> {
+fn render_resource(
+    pages: &[RenderedPage],
+    output_dir: Option<&str>,
+) -> Result<Vec<Value>, ToolError> {
+    let dir = resources::output_dir(output_dir).map_err(|err| ToolError {
+        kind: errors::INTERNAL_ERROR,
+        message: format!("failed to create output directory: {err}"),
+    })?;
+    let suffix = resources::unique_suffix();
     let mut output = Vec::new();
     for page in pages {
-        let path = svg_path_for_page(page.page);
-        fs::write(&path, page.svg.as_bytes()).map_err(|err| ToolError {
+        let extension = if page.png.is_some() { "png" } else { "svg" };
+        let path = dir.join(format!(
+            "hwp-render-{suffix}-page-{}.{extension}",
+            page.page
+        ));
+        fs::write(&path, page.bytes()).map_err(|err| ToolError {
             kind: errors::INTERNAL_ERROR,
-            message: format!("failed to write svg output: {err}"),
+            message: format!("failed to write {extension} output: {err}"),
         })?;
+        if output_dir.is_none() {
+            resources::track(&path);
+        }
         let path_string = path.to_string_lossy().to_string();
-        let uri = format!("file://{path_string}");
+        let mime_type = if page.png.is_some() {
+            "image/png"
+        } else {
+            "image/svg+xml"
+        };
+        let uri = resources::register(&path_string, mime_type);
         output.push(json!({
             "page": page.page,
             "path": path_string,
@@ -262,11 +677,15 @@ fn render_resource(pages: &[RenderedPage]) -> Result<Vec<Value>, ToolError> {
     Ok(output)
 }
 
-fn build_resource_content(pages: &[Value]) -> Vec<Value> {
+fn build_resource_content(pages: &[Value], image_format: ImageFormat) -> Vec<Value> {
     let mut content = Vec::new();
     content.push(json!({
         "type": "text",
-        "text": format!("rendered {} page(s) as svg resources", pages.len())
+        "text": format!(
+            "rendered {} page(s) as {} resources",
+            pages.len(),
+            image_format.extension()
+        )
     }));
     for page in pages {
         let uri = page
@@ -281,68 +700,97 @@ fn build_resource_content(pages: &[Value]) -> Vec<Value> {
             "type": "resource_link",
             "uri": uri,
             "name": format!("page-{page_number}"),
-            "mimeType": "image/svg+xml"
+            "mimeType": image_format.mime_type()
         }));
     }
     content
 }
 
-fn svg_path_for_page(page: u64) -> PathBuf {
-    let pid = std::process::id();
-    let filename = format!("hwp-render-{pid}-page-{page}.svg");
-    std::env::temp_dir().join(filename)
+fn parse_output_dir(value: Option<&Value>) -> Result<Option<String>, ToolError> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    match value.as_str() {
+        Some(dir) if !dir.trim().is_empty() => Ok(Some(dir.to_string())),
+        _ => Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "output_dir must be a non-empty string".to_string(),
+        }),
+    }
 }
 
-fn map_hwp_error(error: HwpError) -> ToolError {
-    match error {
-        HwpError::UnsupportedVersion(message) => {
-            if message.contains("Password-encrypted") {
-                ToolError {
-                    kind: errors::ENCRYPTED,
-                    message,
-                }
-            } else {
-                ToolError {
-                    kind: errors::PARSE_FAILED,
-                    message,
+/// Fills in sections without a page definition, from `page_defs` (indexed like the document's
+/// sections) when available and with the default A4 layout otherwise. Returns whether the
+/// default was needed.
+fn ensure_page_defs(document: &mut hwpers::HwpDocument, page_defs: &[Option<PageDef>]) -> bool {
+    let mut updated = false;
+    let sections = document
+        .body_texts
+        .iter_mut()
+        .flat_map(|body_text| body_text.sections.iter_mut());
+    for (index, section) in sections.enumerate() {
+        if section.page_def.is_none() {
+            section.page_def = match page_defs.get(index).cloned().flatten() {
+                Some(page_def) => Some(page_def),
+                None => {
+                    updated = true;
+                    Some(PageDef::new_default())
                 }
-            }
+            };
         }
-        HwpError::InvalidInput(message) => ToolError {
-            kind: errors::INVALID_INPUT,
-            message,
-        },
-        HwpError::Io(err) => ToolError {
-            kind: errors::INVALID_INPUT,
-            message: err.to_string(),
-        },
-        HwpError::InvalidFormat(message)
-        | HwpError::Cfb(message)
-        | HwpError::CompressionError(message)
-        | HwpError::ParseError(message)
-        | HwpError::EncodingError(message)
-        | HwpError::NotFound(message) => ToolError {
-            kind: errors::PARSE_FAILED,
-            message,
-        },
     }
+    updated
 }
 
-fn map_hwp_error_with_format(error: HwpError, format: &str) -> ToolError {
-    let mut mapped = map_hwp_error(error);
-    mapped.message = format!("{format} parse failed: {}", mapped.message);
-    mapped
+/// hwpers 0.5.0 looks for PAGE_DEF under the wrong tag id, so HWP sections come back without a
+/// page definition. This reads the first PAGE_DEF record of each `BodyText/Section{N}` stream
+/// instead. Landscape pages (attribute bit 0) store the unrotated paper size, so width and
+/// height are swapped for them.
+fn hwp_page_defs(bytes: &[u8]) -> Vec<Option<PageDef>> {
+    let Ok(sections) = HwpSections::open(bytes) else {
+        return Vec::new();
+    };
+    sections
+        .map(|records| records.ok().and_then(|records| section_page_def(&records)))
+        .collect()
 }
 
-fn ensure_page_defs(document: &mut hwpers::HwpDocument) -> bool {
-    let mut updated = false;
-    for body_text in &mut document.body_texts {
-        for section in &mut body_text.sections {
-            if section.page_def.is_none() {
-                section.page_def = Some(hwpers::model::page_def::PageDef::new_default());
-                updated = true;
-            }
+fn section_page_def(records: &[Record]) -> Option<PageDef> {
+    for record in records {
+        if record.tag_id() != HWPTAG_PAGE_DEF || record.data.len() < 40 {
+            continue;
+        }
+        let field = |index: usize| {
+            let start = index * 4;
+            u32::from_le_bytes([
+                record.data[start],
+                record.data[start + 1],
+                record.data[start + 2],
+                record.data[start + 3],
+            ])
+        };
+        let (width, height) = (field(0), field(1));
+        if width == 0 || height == 0 {
+            return None;
         }
+        let properties = field(9);
+        let (width, height) = if properties & 0x01 != 0 {
+            (height, width)
+        } else {
+            (width, height)
+        };
+        let mut page_def = PageDef::new_default();
+        page_def.width = width;
+        page_def.height = height;
+        page_def.left_margin = field(2);
+        page_def.right_margin = field(3);
+        page_def.top_margin = field(4);
+        page_def.bottom_margin = field(5);
+        page_def.header_margin = field(6);
+        page_def.footer_margin = field(7);
+        page_def.gutter_margin = field(8);
+        page_def.properties = properties;
+        return Some(page_def);
     }
-    updated
+    None
 }
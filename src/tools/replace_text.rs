@@ -0,0 +1,402 @@
+use crate::input::{is_distribution_document, load_input};
+use crate::mcp::contracts::limits;
+use crate::mcp::errors;
+use crate::tools::{
+    EditedFormat, ToolError, check_arguments, error_result, parse_document, parse_output_path,
+    write_output,
+};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use hwpers::model::paragraph::Paragraph;
+use serde_json::{Value, json};
+
+pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let payload = match load_input(args) {
+        Ok(payload) => payload,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let options = match ReplaceOptions::parse(args) {
+        Ok(options) => options,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let to_format = match EditedFormat::parse(args.get("to")) {
+        Ok(to_format) => to_format,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let output_path = match parse_output_path(args.get("output_path")) {
+        Ok(path) => path,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    if is_distribution_document(&payload.bytes) {
+        return error_result(
+            errors::ENCRYPTED,
+            "distribution document (배포용 문서) is read-only and cannot be edited",
+            Some(payload.source.as_str()),
+        );
+    }
+
+    let parsed = match parse_document(&payload.bytes, payload.format) {
+        Ok(parsed) => parsed,
+        Err(err) => return error_result(err.kind, err.message, Some(payload.source.as_str())),
+    };
+
+    let detected_format = parsed.format.as_str();
+    let detection_method = payload.detection_method;
+    let mut warnings = parsed.warnings;
+    let mut document = parsed.document;
+    let replacements = replace_in_document(&mut document, &options, &mut warnings);
+
+    let to_format = EditedFormat::or_input(to_format, parsed.format);
+    let output = match to_format.build(document) {
+        Ok(output) => output,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    match output_path {
+        Some(path) => match write_output(
+            &path,
+            output,
+            &format!("replaced {replacements} occurrences"),
+        ) {
+            Ok(output) => json!({
+                "content": output.content,
+                "structuredContent": {
+                    "to": to_format.as_str(),
+                    "detected_format": detected_format,
+                    "detection_method": detection_method,
+                    "replacements": replacements,
+                    "path": output.path,
+                    "uri": output.uri,
+                    "bytes_len": output.bytes_len,
+                    "warnings": warnings
+                },
+                "isError": false
+            }),
+            Err(err) => error_result(err.kind, err.message, None),
+        },
+        None => {
+            let output_bytes = match output.into_bytes() {
+                Ok(bytes) => bytes,
+                Err(err) => return error_result(err.kind, err.message, None),
+            };
+            let bytes_len = output_bytes.len() as u64;
+            let max_output_bytes = limits().max_output_bytes;
+            if bytes_len > max_output_bytes {
+                return error_result(
+                    errors::TOO_LARGE,
+                    format!("output exceeds limit: {bytes_len} bytes (max {max_output_bytes})"),
+                    None,
+                );
+            }
+            let base64 = STANDARD.encode(&output_bytes);
+            json!({
+                "content": [{
+                    "type": "text",
+                    "text": format!(
+                        "replaced {replacements} occurrences ({}, {bytes_len} bytes)",
+                        to_format.as_str()
+                    )
+                }],
+                "structuredContent": {
+                    "to": to_format.as_str(),
+                    "detected_format": detected_format,
+                    "detection_method": detection_method,
+                    "replacements": replacements,
+                    "base64": base64,
+                    "bytes_len": bytes_len,
+                    "warnings": warnings
+                },
+                "isError": false
+            })
+        }
+    }
+}
+
+struct ReplaceOptions {
+    find: Vec<char>,
+    replace: Vec<char>,
+    max_replacements: Option<u64>,
+}
+
+impl ReplaceOptions {
+    fn parse(args: &Value) -> Result<Self, ToolError> {
+        let find = match args.get("find") {
+            None => {
+                return Err(ToolError {
+                    kind: errors::INVALID_INPUT,
+                    message: "find is required".to_string(),
+                });
+            }
+            Some(value) => value.as_str().ok_or_else(|| ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "find must be a string".to_string(),
+            })?,
+        };
+        if find.is_empty() {
+            return Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "find must not be empty".to_string(),
+            });
+        }
+        let replace = match args.get("replace") {
+            None => {
+                return Err(ToolError {
+                    kind: errors::INVALID_INPUT,
+                    message: "replace is required".to_string(),
+                });
+            }
+            Some(value) => value.as_str().ok_or_else(|| ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "replace must be a string".to_string(),
+            })?,
+        };
+        let max_replacements = match args.get("max_replacements") {
+            None => None,
+            Some(value) => Some(value.as_u64().ok_or_else(|| ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "max_replacements must be a non-negative integer".to_string(),
+            })?),
+        };
+        Ok(ReplaceOptions {
+            find: find.chars().collect(),
+            replace: replace.chars().collect(),
+            max_replacements,
+        })
+    }
+}
+
+/// Replaces `find` in every body paragraph, in reading order, until `max_replacements` is used
+/// up. Returns the number of occurrences replaced.
+fn replace_in_document(
+    document: &mut hwpers::HwpDocument,
+    options: &ReplaceOptions,
+    warnings: &mut Vec<String>,
+) -> u64 {
+    let mut replaced = 0u64;
+    let mut capped = 0u64;
+    let sections = document
+        .body_texts
+        .iter_mut()
+        .flat_map(|body| body.sections.iter_mut());
+    for (section_index, section) in sections.enumerate() {
+        for (paragraph_index, paragraph) in section.paragraphs.iter_mut().enumerate() {
+            let outcome = replace_in_paragraph(
+                paragraph,
+                options,
+                options
+                    .max_replacements
+                    .map(|max| max.saturating_sub(replaced)),
+            );
+            replaced += outcome.replaced;
+            capped += outcome.capped;
+            for offset in outcome.spanning {
+                warnings.push(format!(
+                    "section {section_index} paragraph {paragraph_index} offset {offset}: \
+                     match spans multiple styled runs; skipped"
+                ));
+            }
+            for offset in outcome.hyperlinked {
+                warnings.push(format!(
+                    "section {section_index} paragraph {paragraph_index} offset {offset}: \
+                     match crosses a hyperlink boundary; skipped"
+                ));
+            }
+        }
+    }
+    if capped > 0 {
+        warnings.push(format!(
+            "max_replacements reached: {capped} further occurrences left unchanged"
+        ));
+    }
+    replaced
+}
+
+#[derive(Default)]
+struct ParagraphOutcome {
+    replaced: u64,
+    capped: u64,
+    spanning: Vec<usize>,
+    hyperlinked: Vec<usize>,
+}
+
+/// Replaces non-overlapping occurrences inside one paragraph. Char-shape runs and hyperlink
+/// ranges after a replacement are shifted by the length change; an occurrence that would cut
+/// through a run or hyperlink boundary is reported and left alone.
+fn replace_in_paragraph(
+    paragraph: &mut Paragraph,
+    options: &ReplaceOptions,
+    budget: Option<u64>,
+) -> ParagraphOutcome {
+    let mut outcome = ParagraphOutcome::default();
+    let Some(text) = paragraph.text.as_mut() else {
+        return outcome;
+    };
+    let chars: Vec<char> = text.content.chars().collect();
+    let find = &options.find;
+    if chars.len() < find.len() {
+        return outcome;
+    }
+
+    let run_starts: Vec<usize> = paragraph
+        .char_shapes
+        .as_ref()
+        .map(|shapes| {
+            shapes
+                .char_positions
+                .iter()
+                .map(|position| position.position as usize)
+                .collect()
+        })
+        .unwrap_or_default();
+    let link_bounds: Vec<usize> = paragraph
+        .hyperlinks
+        .iter()
+        .flat_map(|link| {
+            let start = link.start_position as usize;
+            [start, start + link.length as usize]
+        })
+        .collect();
+    let cuts = |bounds: &[usize], start: usize, end: usize| {
+        bounds.iter().any(|&bound| start < bound && bound < end)
+    };
+
+    let mut accepted: Vec<usize> = Vec::new();
+    let mut index = 0;
+    while index + find.len() <= chars.len() {
+        if chars[index..index + find.len()] != find[..] {
+            index += 1;
+            continue;
+        }
+        let end = index + find.len();
+        if cuts(&run_starts, index, end) {
+            outcome.spanning.push(index);
+        } else if cuts(&link_bounds, index, end) {
+            outcome.hyperlinked.push(index);
+        } else if budget.is_some_and(|budget| outcome.replaced >= budget) {
+            outcome.capped += 1;
+        } else {
+            accepted.push(index);
+            outcome.replaced += 1;
+        }
+        index = end;
+    }
+    if accepted.is_empty() {
+        return outcome;
+    }
+
+    let mut content = String::with_capacity(text.content.len());
+    let mut cursor = 0;
+    for &start in &accepted {
+        content.extend(&chars[cursor..start]);
+        content.extend(&options.replace);
+        cursor = start + find.len();
+    }
+    content.extend(&chars[cursor..]);
+    text.content = content;
+
+    // A position at or after the end of an accepted match moves by the length change of
+    // every match before it.
+    let delta = options.replace.len() as i64 - find.len() as i64;
+    let shift = |position: u32| {
+        let before = accepted
+            .iter()
+            .filter(|&&start| start + find.len() <= position as usize)
+            .count() as i64;
+        (position as i64 + before * delta).max(0) as u32
+    };
+    if let Some(shapes) = paragraph.char_shapes.as_mut() {
+        for position in &mut shapes.char_positions {
+            position.position = shift(position.position);
+        }
+    }
+    for link in &mut paragraph.hyperlinks {
+        let start = shift(link.start_position);
+        let end = shift(link.start_position + link.length);
+        link.start_position = start;
+        link.length = end - start;
+    }
+    paragraph.line_segments = None;
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ReplaceOptions, replace_in_paragraph};
+    use hwpers::model::paragraph::{ParaText, Paragraph};
+    use hwpers::model::{CharPositionShape, ParaCharShape};
+
+    fn styled_paragraph(text: &str, runs: &[(u32, u16)]) -> Paragraph {
+        Paragraph {
+            text: Some(ParaText {
+                content: text.to_string(),
+            }),
+            char_shapes: Some(ParaCharShape {
+                char_positions: runs
+                    .iter()
+                    .map(|&(position, char_shape_id)| CharPositionShape {
+                        position,
+                        char_shape_id,
+                    })
+                    .collect(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn options(find: &str, replace: &str, max_replacements: Option<u64>) -> ReplaceOptions {
+        ReplaceOptions {
+            find: find.chars().collect(),
+            replace: replace.chars().collect(),
+            max_replacements,
+        }
+    }
+
+    fn run_starts(paragraph: &Paragraph) -> Vec<u32> {
+        paragraph
+            .char_shapes
+            .as_ref()
+            .map(|shapes| shapes.char_positions.iter().map(|p| p.position).collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn replacement_shifts_later_runs() {
+        // "a-b" | "BOLD" | "-a-b"
+        let mut paragraph = styled_paragraph("a-bBOLD-a-b", &[(0, 0), (3, 1), (7, 0)]);
+        let outcome = replace_in_paragraph(&mut paragraph, &options("a", "xyz", None), None);
+        assert_eq!(outcome.replaced, 2);
+        assert_eq!(paragraph.text.as_ref().unwrap().content, "xyz-bBOLD-xyz-b");
+        assert_eq!(run_starts(&paragraph), vec![0, 5, 9]);
+    }
+
+    #[test]
+    fn match_across_runs_is_skipped() {
+        let mut paragraph =
+            styled_paragraph("plainBOLD plainBOLD", &[(0, 0), (5, 1), (9, 0), (15, 1)]);
+        let outcome = replace_in_paragraph(&mut paragraph, &options("nB", "", None), None);
+        assert_eq!(outcome.replaced, 0);
+        assert_eq!(outcome.spanning, vec![4, 14]);
+        assert_eq!(
+            paragraph.text.as_ref().unwrap().content,
+            "plainBOLD plainBOLD"
+        );
+        assert_eq!(run_starts(&paragraph), vec![0, 5, 9, 15]);
+    }
+
+    #[test]
+    fn budget_limits_replacements() {
+        let mut paragraph = styled_paragraph("x x x", &[(0, 0)]);
+        let outcome = replace_in_paragraph(&mut paragraph, &options("x", "yy", Some(2)), Some(2));
+        assert_eq!(outcome.replaced, 2);
+        assert_eq!(outcome.capped, 1);
+        assert_eq!(paragraph.text.as_ref().unwrap().content, "yy yy x");
+    }
+}
@@ -0,0 +1,146 @@
+use crate::input::load_input;
+use crate::mcp::errors;
+use crate::tools::pattern::{Pattern, fold};
+use crate::tools::{check_arguments, error_result, parse_document};
+use serde_json::{Value, json};
+
+/// Characters of context kept on each side of a match.
+const CONTEXT_CHARS: usize = 40;
+/// Matches beyond this are dropped with a warning.
+const MAX_MATCHES: usize = 10_000;
+
+pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let payload = match load_input(args) {
+        Ok(payload) => payload,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let query = match args.get("query").and_then(|v| v.as_str()) {
+        Some(query) if !query.is_empty() => query,
+        _ => return error_result(errors::INVALID_INPUT, "query is required", None),
+    };
+    let case_sensitive = args
+        .get("case_sensitive")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let use_regex = args.get("regex").and_then(|v| v.as_bool()).unwrap_or(false);
+    let matcher = if use_regex {
+        match Pattern::new(query, case_sensitive) {
+            Ok(pattern) => Matcher::Regex(pattern),
+            Err(message) => {
+                return error_result(
+                    errors::INVALID_INPUT,
+                    format!("invalid regex: {message}"),
+                    None,
+                );
+            }
+        }
+    } else {
+        Matcher::Literal(query.chars().collect())
+    };
+
+    let parsed = match parse_document(&payload.bytes, payload.format) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return error_result(err.kind, err.message, Some(payload.source.as_str()));
+        }
+    };
+
+    let mut warnings = parsed.warnings;
+    let mut matches = Vec::new();
+    let mut total = 0usize;
+    for (section_index, section) in parsed.document.sections().enumerate() {
+        for (paragraph_index, paragraph) in section.paragraphs.iter().enumerate() {
+            let Some(text) = &paragraph.text else {
+                continue;
+            };
+            let chars: Vec<char> = text.content.chars().collect();
+            let found = matcher.find_all(&chars, case_sensitive);
+            total += found.len();
+            for (start, end) in found {
+                if matches.len() == MAX_MATCHES {
+                    break;
+                }
+                matches.push(json!({
+                    "section_index": section_index,
+                    "paragraph_index": paragraph_index,
+                    "char_offset": start,
+                    "text": chars[start..end].iter().collect::<String>(),
+                    "context": context(&chars, start, end)
+                }));
+            }
+        }
+    }
+    if total > matches.len() {
+        warnings.push(format!(
+            "returning the first {MAX_MATCHES} of {total} matches"
+        ));
+    }
+
+    let summary = match total {
+        1 => format!("found 1 match for {query:?}"),
+        count => format!("found {count} matches for {query:?}"),
+    };
+
+    json!({
+        "content": [{"type": "text", "text": summary}],
+        "structuredContent": {
+            "format": parsed.format.as_str(),
+            "detected_format": parsed.format.as_str(),
+            "detection_method": payload.detection_method,
+            "total_matches": total,
+            "matches": matches,
+            "warnings": warnings
+        },
+        "isError": false
+    })
+}
+
+enum Matcher {
+    Literal(Vec<char>),
+    Regex(Pattern),
+}
+
+impl Matcher {
+    /// Non-overlapping matches as `(start, end)` char offsets.
+    fn find_all(&self, text: &[char], case_sensitive: bool) -> Vec<(usize, usize)> {
+        match self {
+            Matcher::Regex(pattern) => pattern.find_all(text),
+            Matcher::Literal(query) => {
+                let eq = |a: &char, b: &char| a == b || (!case_sensitive && fold(*a) == fold(*b));
+                let mut found = Vec::new();
+                let mut start = 0;
+                while start + query.len() <= text.len() {
+                    if text[start..start + query.len()]
+                        .iter()
+                        .zip(query)
+                        .all(|(a, b)| eq(a, b))
+                    {
+                        found.push((start, start + query.len()));
+                        start += query.len();
+                    } else {
+                        start += 1;
+                    }
+                }
+                found
+            }
+        }
+    }
+}
+
+/// Up to `CONTEXT_CHARS` on either side of the match, with control characters (paragraph
+/// terminators, inline control markers) shown as spaces.
+fn context(chars: &[char], start: usize, end: usize) -> String {
+    let from = start.saturating_sub(CONTEXT_CHARS);
+    let to = (end + CONTEXT_CHARS).min(chars.len());
+    chars[from..to]
+        .iter()
+        .map(|ch| if ch.is_control() { ' ' } else { *ch })
+        .collect::<String>()
+        .trim_end()
+        .to_string()
+}
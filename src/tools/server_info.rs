@@ -0,0 +1,54 @@
+use crate::mcp::contracts::{HWPERS_VERSION, limits};
+use crate::tools::check_arguments;
+use serde_json::{Value, json};
+
+/// Formats accepted by the `format` argument of the parsing tools.
+const INPUT_FORMATS: [&str; 2] = ["hwp", "hwpx"];
+/// Formats any tool can produce: `convert` targets plus the HTML, JSON, SVG and PNG renderers.
+const OUTPUT_FORMATS: [&str; 8] = ["hwp", "hwpx", "txt", "md", "html", "json", "svg", "png"];
+
+pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let limits = limits();
+    let server = json!({
+        "name": env!("CARGO_PKG_NAME"),
+        "version": env!("CARGO_PKG_VERSION"),
+        "hwpers_version": HWPERS_VERSION,
+        "limits": {
+            "max_input_bytes": limits.max_input_bytes,
+            "max_output_bytes": limits.max_output_bytes,
+            "max_svg_output_bytes": limits.max_svg_output_bytes,
+            "max_parse_ms": limits.max_parse_ms,
+            "max_table_rows": limits.max_table_rows,
+            "max_table_cols": limits.max_table_cols
+        },
+        "formats": {
+            "input": INPUT_FORMATS,
+            "output": OUTPUT_FORMATS
+        }
+    });
+
+    let text = format!(
+        "{} {} (hwpers {HWPERS_VERSION})\ninput limit: {} bytes\noutput limit: {} bytes ({} for \
+         SVG)\nparse timeout: {} ms\ntable limit: {} rows x {} columns\ninput formats: {}\noutput formats: {}",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        limits.max_input_bytes,
+        limits.max_output_bytes,
+        limits.max_svg_output_bytes,
+        limits.max_parse_ms,
+        limits.max_table_rows,
+        limits.max_table_cols,
+        INPUT_FORMATS.join(", "),
+        OUTPUT_FORMATS.join(", ")
+    );
+
+    json!({
+        "content": [{"type": "text", "text": text}],
+        "structuredContent": { "server": server },
+        "isError": false
+    })
+}
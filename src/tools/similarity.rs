@@ -0,0 +1,73 @@
+use crate::input::load_input;
+use crate::mcp::errors;
+use crate::tools::fingerprint::{Signature, shingle_size_from_args};
+use crate::tools::{check_arguments, error_result, parse_document};
+use serde_json::{Value, json};
+
+pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let shingle_size = match shingle_size_from_args(args) {
+        Ok(size) => size,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let mut signatures = Vec::new();
+    let mut sides = serde_json::Map::new();
+    let mut warnings = Vec::new();
+    for side in ["left", "right"] {
+        let Some(input) = args.get(side) else {
+            return error_result(errors::INVALID_INPUT, format!("{side} is required"), None);
+        };
+        let payload = match load_input(input) {
+            Ok(payload) => payload,
+            Err(err) => return error_result(err.kind, format!("{side}: {}", err.message), None),
+        };
+        let parsed = match parse_document(&payload.bytes, payload.format) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                return error_result(
+                    err.kind,
+                    format!("{side}: {}", err.message),
+                    Some(payload.source.as_str()),
+                );
+            }
+        };
+        warnings.extend(
+            parsed
+                .warnings
+                .into_iter()
+                .map(|warning| format!("{side}: {warning}")),
+        );
+        let signature = Signature::from_text(&parsed.document.extract_text(), shingle_size);
+        sides.insert(
+            side.to_string(),
+            json!({
+                "format": parsed.format.as_str(),
+                "detected_format": parsed.format.as_str(),
+                "detection_method": payload.detection_method,
+                "token_count": signature.token_count as u64,
+                "shingle_count": signature.shingle_count as u64
+            }),
+        );
+        signatures.push(signature);
+    }
+
+    let score = signatures[0].similarity(&signatures[1]);
+    let summary = format!("similarity: {score:.3}");
+
+    json!({
+        "content": [{"type": "text", "text": summary}],
+        "structuredContent": {
+            "similarity": score,
+            "algorithm": "minhash",
+            "shingle_size": shingle_size as u64,
+            "left": sides.remove("left"),
+            "right": sides.remove("right"),
+            "warnings": warnings
+        },
+        "isError": false
+    })
+}
@@ -0,0 +1,577 @@
+use crate::input::{InputFormat, is_distribution_document, load_input};
+use crate::mcp::contracts::limits;
+use crate::mcp::errors;
+use crate::tools::records::{HWPTAG_PARA_HEADER, HwpSections};
+use crate::tools::{
+    ToolError, check_arguments, error_result, map_hwp_error_with_stage, parse_document,
+    write_file_atomically,
+};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use hwpers::model::paragraph::Paragraph;
+use hwpers::{HwpDocument, HwpWriter, HwpxWriter};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Cursor, Read};
+use std::ops::Range;
+use std::path::Path;
+
+/// Longest paragraph (in chars) still considered a heading candidate.
+const MAX_HEADING_CHARS: usize = 80;
+
+/// Offset of the divide-sort byte in PARA_HEADER: char count (u32), control mask (u32),
+/// para shape id (u16), style id (u8).
+const PARA_HEADER_DIVIDE_SORT: usize = 11;
+/// HWP paragraph divide-sort flag for a page break before the paragraph.
+const DIVIDE_PAGE_BREAK: u8 = 0x04;
+
+pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let payload = match load_input(args) {
+        Ok(payload) => payload,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let by = match SplitBy::parse(args.get("by")) {
+        Ok(by) => by,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let level = match parse_level(args.get("level")) {
+        Ok(level) => level,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let every = match parse_every(args.get("every")) {
+        Ok(every) => every,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let output_dir = match parse_output_dir(args.get("output_dir")) {
+        Ok(output_dir) => output_dir,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    if is_distribution_document(&payload.bytes) {
+        return error_result(
+            errors::ENCRYPTED,
+            "distribution document (배포용 문서) is read-only and cannot be split",
+            Some(payload.source.as_str()),
+        );
+    }
+
+    let parsed = match parse_document(&payload.bytes, payload.format) {
+        Ok(parsed) => parsed,
+        Err(err) => return error_result(err.kind, err.message, Some(payload.source.as_str())),
+    };
+
+    let to_format = match OutputFormat::parse(args.get("to"), parsed.format) {
+        Ok(to_format) => to_format,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let mut warnings = parsed.warnings;
+    let ranges = match by {
+        SplitBy::Heading => heading_ranges(&parsed.document, level),
+        SplitBy::PageBreak => {
+            // hwpers does not keep paragraph break flags, so they are read from the raw file.
+            let breaks = match parsed.format {
+                InputFormat::Hwpx => hwpx_page_breaks(&payload.bytes),
+                _ => hwp_page_breaks(&payload.bytes),
+            };
+            match breaks {
+                Ok(breaks) => page_break_ranges(&parsed.document, &breaks),
+                Err(err) => {
+                    return error_result(
+                        errors::PARSE_FAILED,
+                        format!("page break scan failed: {err}"),
+                        Some(payload.source.as_str()),
+                    );
+                }
+            }
+        }
+        SplitBy::Section => section_ranges(&parsed.document, every),
+    };
+    match by {
+        SplitBy::Heading if ranges.iter().all(|range| range.heading.is_none()) => {
+            warnings.push(format!(
+                "no headings at level {level} or above were found; returning the whole document as one part"
+            ));
+        }
+        SplitBy::PageBreak if ranges.len() == 1 => {
+            warnings.push(
+                "no page breaks were found; returning the whole document as one part".to_string(),
+            );
+        }
+        _ => {}
+    }
+
+    let mut parts = Vec::with_capacity(ranges.len());
+    let mut total_bytes: u64 = 0;
+    for (index, range) in ranges.iter().enumerate() {
+        // Paragraphs are not Clone, so each part is carved out of a fresh parse.
+        let mut document = match parse_document(&payload.bytes, parsed.format) {
+            Ok(reparsed) => reparsed.document,
+            Err(err) => return error_result(err.kind, err.message, Some(payload.source.as_str())),
+        };
+        retain_paragraphs(&mut document, &range.paragraphs);
+
+        let output_bytes = match to_format {
+            OutputFormat::Hwp => HwpWriter::from_document(document)
+                .to_bytes()
+                .map_err(|error| map_hwp_error_with_stage(error, "split to hwp")),
+            OutputFormat::Hwpx => HwpxWriter::from_document(document)
+                .to_bytes()
+                .map_err(|error| map_hwp_error_with_stage(error, "split to hwpx")),
+        };
+        let output_bytes = match output_bytes {
+            Ok(bytes) => bytes,
+            Err(err) => return error_result(err.kind, err.message, None),
+        };
+        let bytes_len = output_bytes.len() as u64;
+
+        let mut part = json!({
+            "index": index,
+            "heading": range.heading,
+            "paragraphs": range.paragraphs.len(),
+            "bytes_len": bytes_len
+        });
+        let Some(obj) = part.as_object_mut() else {
+            return error_result(errors::INTERNAL_ERROR, "failed to build part", None);
+        };
+        match &output_dir {
+            Some(dir) => {
+                let path = Path::new(dir).join(format!("part-{index}.{}", to_format.as_str()));
+                if let Err(err) = write_file_atomically(&path, &output_bytes) {
+                    return error_result(
+                        errors::INTERNAL_ERROR,
+                        format!("failed to write output: {err}"),
+                        None,
+                    );
+                }
+                let path = path.to_string_lossy().to_string();
+                obj.insert("uri".to_string(), json!(format!("file://{path}")));
+                obj.insert("path".to_string(), json!(path));
+            }
+            None => {
+                total_bytes = total_bytes.saturating_add(bytes_len);
+                let max_output_bytes = limits().max_output_bytes;
+                if total_bytes > max_output_bytes {
+                    return error_result(
+                        errors::TOO_LARGE,
+                        format!(
+                            "output exceeds limit: {total_bytes} bytes (max {max_output_bytes}); use output_dir"
+                        ),
+                        None,
+                    );
+                }
+                obj.insert("base64".to_string(), json!(STANDARD.encode(&output_bytes)));
+            }
+        }
+        parts.push(part);
+    }
+
+    let mut structured = json!({
+        "to": to_format.as_str(),
+        "by": by.as_str(),
+        "detected_format": parsed.format.as_str(),
+        "detection_method": payload.detection_method,
+        "parts": parts,
+        "warnings": warnings
+    });
+    if let Some(obj) = structured.as_object_mut() {
+        match by {
+            SplitBy::Heading => {
+                obj.insert("level".to_string(), json!(level));
+            }
+            SplitBy::Section => {
+                obj.insert("every".to_string(), json!(every));
+            }
+            SplitBy::PageBreak => {}
+        }
+    }
+
+    json!({
+        "content": [{
+            "type": "text",
+            "text": format!("split into {} parts ({})", parts.len(), to_format.as_str())
+        }],
+        "structuredContent": structured,
+        "isError": false
+    })
+}
+
+struct PartRange {
+    heading: Option<String>,
+    /// Document-wide paragraph indices (counted across all sections).
+    paragraphs: Range<usize>,
+}
+
+/// Cuts before every heading at `level` or above; text ahead of the first heading
+/// becomes a leading part without a heading.
+fn heading_ranges(document: &HwpDocument, level: usize) -> Vec<PartRange> {
+    let body_size = body_font_size(document);
+    let paragraphs: Vec<&Paragraph> = document
+        .sections()
+        .flat_map(|section| section.paragraphs.iter())
+        .collect();
+
+    let mut ranges: Vec<PartRange> = Vec::new();
+    let mut start = 0;
+    let mut heading: Option<String> = None;
+    for (index, paragraph) in paragraphs.iter().enumerate() {
+        let text = paragraph_text(paragraph);
+        let text = text.trim();
+        let is_cut = !text.is_empty()
+            && heading_level(document, paragraph, body_size, text)
+                .is_some_and(|found| found <= level);
+        if !is_cut {
+            continue;
+        }
+        let has_content = paragraphs[start..index]
+            .iter()
+            .any(|paragraph| !paragraph_text(paragraph).trim().is_empty());
+        if heading.is_some() || has_content {
+            ranges.push(PartRange {
+                heading: heading.take(),
+                paragraphs: start..index,
+            });
+        }
+        start = index;
+        heading = Some(text.to_string());
+    }
+    ranges.push(PartRange {
+        heading,
+        paragraphs: start..paragraphs.len(),
+    });
+    ranges
+}
+
+/// Cuts before every paragraph flagged with a page break (`breaks` holds one flag per
+/// document-wide paragraph index). A document without page breaks comes back as a single part.
+fn page_break_ranges(document: &HwpDocument, breaks: &[bool]) -> Vec<PartRange> {
+    let paragraph_count = document
+        .sections()
+        .map(|section| section.paragraphs.len())
+        .sum::<usize>();
+
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    for index in 0..paragraph_count {
+        if index > start && breaks.get(index).copied().unwrap_or(false) {
+            ranges.push(PartRange {
+                heading: None,
+                paragraphs: start..index,
+            });
+            start = index;
+        }
+    }
+    ranges.push(PartRange {
+        heading: None,
+        paragraphs: start..paragraph_count,
+    });
+    ranges
+}
+
+/// Page break flags for every paragraph header in the BodyText sections, in document order.
+fn hwp_page_breaks(bytes: &[u8]) -> Result<Vec<bool>, String> {
+    let mut breaks = Vec::new();
+    for records in HwpSections::open(bytes)? {
+        let mut section_breaks = Vec::new();
+        for record in records? {
+            if record.tag_id() == HWPTAG_PARA_HEADER {
+                let divide_sort = record
+                    .data
+                    .get(PARA_HEADER_DIVIDE_SORT)
+                    .copied()
+                    .unwrap_or(0);
+                section_breaks.push(divide_sort & DIVIDE_PAGE_BREAK != 0);
+            }
+        }
+        // hwpers reads the first paragraph header of a section as the section definition.
+        breaks.extend(section_breaks.into_iter().skip(1));
+    }
+    Ok(breaks)
+}
+
+/// `pageBreak` flags of the top-level `<hp:p>` elements in each section, in document order.
+/// Paragraphs nested in tables are skipped, as the HWPX reader does.
+fn hwpx_page_breaks(bytes: &[u8]) -> Result<Vec<bool>, String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).map_err(|err| err.to_string())?;
+    let mut breaks = Vec::new();
+    let mut section_index = 0;
+    loop {
+        let name = format!("Contents/section{section_index}.xml");
+        let Ok(mut file) = archive.by_name(&name) else {
+            break;
+        };
+        let mut xml = String::new();
+        file.read_to_string(&mut xml)
+            .map_err(|err| format!("{name}: {err}"))?;
+
+        let mut depth = 0usize;
+        let mut rest = xml.as_str();
+        while let Some(start) = rest.find('<') {
+            let Some(end) = rest[start..].find('>') else {
+                break;
+            };
+            let tag = &rest[start + 1..start + end];
+            rest = &rest[start + end + 1..];
+
+            let name = tag
+                .trim_start_matches('/')
+                .split(|ch: char| ch.is_whitespace() || ch == '/')
+                .next()
+                .unwrap_or("");
+            if name != "hp:p" {
+                continue;
+            }
+            if tag.starts_with('/') {
+                depth = depth.saturating_sub(1);
+                continue;
+            }
+            if depth == 0 {
+                breaks.push(tag.contains("pageBreak=\"1\""));
+            }
+            if !tag.ends_with('/') {
+                depth += 1;
+            }
+        }
+        section_index += 1;
+    }
+    Ok(breaks)
+}
+
+/// Groups every `every` consecutive sections into one part.
+fn section_ranges(document: &HwpDocument, every: usize) -> Vec<PartRange> {
+    let section_lengths: Vec<usize> = document
+        .sections()
+        .map(|section| section.paragraphs.len())
+        .collect();
+
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    for chunk in section_lengths.chunks(every) {
+        let end = start + chunk.iter().sum::<usize>();
+        ranges.push(PartRange {
+            heading: None,
+            paragraphs: start..end,
+        });
+        start = end;
+    }
+    if ranges.is_empty() {
+        ranges.push(PartRange {
+            heading: None,
+            paragraphs: 0..0,
+        });
+    }
+    ranges
+}
+
+/// Drops every paragraph outside `keep` (document-wide indices) and any section left empty.
+fn retain_paragraphs(document: &mut HwpDocument, keep: &Range<usize>) {
+    let mut index = 0;
+    for body_text in &mut document.body_texts {
+        for section in &mut body_text.sections {
+            let paragraphs = std::mem::take(&mut section.paragraphs);
+            for paragraph in paragraphs {
+                if keep.contains(&index) {
+                    section.paragraphs.push(paragraph);
+                }
+                index += 1;
+            }
+        }
+        body_text
+            .sections
+            .retain(|section| !section.paragraphs.is_empty());
+    }
+    document
+        .body_texts
+        .retain(|body_text| !body_text.sections.is_empty());
+    let section_count = document.sections().count() as u16;
+    if let Some(properties) = document.doc_info.properties.as_mut() {
+        properties.section_count = section_count;
+    }
+}
+
+fn paragraph_text(paragraph: &Paragraph) -> String {
+    match &paragraph.text {
+        Some(text) => text.content.clone(),
+        None => String::new(),
+    }
+}
+
+fn paragraph_char_shape<'a>(
+    document: &'a HwpDocument,
+    paragraph: &Paragraph,
+) -> Option<&'a hwpers::model::CharShape> {
+    let id = paragraph
+        .char_shapes
+        .as_ref()
+        .and_then(|shapes| shapes.char_positions.first())
+        .map(|position| usize::from(position.char_shape_id))
+        .unwrap_or(0);
+    document.get_char_shape(id)
+}
+
+/// The most common leading font size across text paragraphs, used as the body size.
+fn body_font_size(document: &HwpDocument) -> i32 {
+    let mut counts: HashMap<i32, usize> = HashMap::new();
+    for section in document.sections() {
+        for paragraph in &section.paragraphs {
+            if paragraph_text(paragraph).trim().is_empty() {
+                continue;
+            }
+            if let Some(shape) = paragraph_char_shape(document, paragraph) {
+                *counts.entry(shape.base_size).or_default() += 1;
+            }
+        }
+    }
+    counts
+        .into_iter()
+        .max_by_key(|(size, count)| (*count, -*size))
+        .map(|(size, _)| size)
+        .unwrap_or(0)
+}
+
+/// Infers a heading level from the paragraph's font size relative to the body text.
+fn heading_level(
+    document: &HwpDocument,
+    paragraph: &Paragraph,
+    body_size: i32,
+    text: &str,
+) -> Option<usize> {
+    if body_size <= 0 || text.chars().count() > MAX_HEADING_CHARS {
+        return None;
+    }
+    let shape = paragraph_char_shape(document, paragraph)?;
+    let ratio = shape.base_size * 100 / body_size;
+    if ratio >= 200 {
+        Some(1)
+    } else if ratio >= 150 {
+        Some(2)
+    } else if ratio >= 120 {
+        Some(3)
+    } else if ratio > 100 && shape.is_bold() {
+        Some(4)
+    } else {
+        None
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SplitBy {
+    Heading,
+    PageBreak,
+    Section,
+}
+
+impl SplitBy {
+    fn parse(value: Option<&Value>) -> Result<Self, ToolError> {
+        let Some(value) = value else {
+            return Ok(SplitBy::Heading);
+        };
+        match value.as_str() {
+            Some("heading") => Ok(SplitBy::Heading),
+            Some("page_break") => Ok(SplitBy::PageBreak),
+            Some("section") => Ok(SplitBy::Section),
+            _ => Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "by must be heading, page_break, or section".to_string(),
+            }),
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            SplitBy::Heading => "heading",
+            SplitBy::PageBreak => "page_break",
+            SplitBy::Section => "section",
+        }
+    }
+}
+
+enum OutputFormat {
+    Hwp,
+    Hwpx,
+}
+
+impl OutputFormat {
+    /// Parses `to`, defaulting to the input's detected format.
+    fn parse(value: Option<&Value>, detected: InputFormat) -> Result<Self, ToolError> {
+        let Some(value) = value else {
+            return Ok(match detected {
+                InputFormat::Hwpx => OutputFormat::Hwpx,
+                _ => OutputFormat::Hwp,
+            });
+        };
+        match value.as_str() {
+            Some("hwp") => Ok(OutputFormat::Hwp),
+            Some("hwpx") => Ok(OutputFormat::Hwpx),
+            _ => Err(ToolError {
+                kind: errors::INVALID_INPUT,
+                message: "to must be hwp or hwpx".to_string(),
+            }),
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            OutputFormat::Hwp => "hwp",
+            OutputFormat::Hwpx => "hwpx",
+        }
+    }
+}
+
+fn parse_level(value: Option<&Value>) -> Result<usize, ToolError> {
+    let Some(value) = value else {
+        return Ok(1);
+    };
+    match value.as_u64() {
+        Some(level @ 1..=6) => Ok(level as usize),
+        _ => Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "level must be an integer between 1 and 6".to_string(),
+        }),
+    }
+}
+
+fn parse_every(value: Option<&Value>) -> Result<usize, ToolError> {
+    let Some(value) = value else {
+        return Ok(1);
+    };
+    match value.as_u64() {
+        Some(every) if every >= 1 => Ok(every as usize),
+        _ => Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "every must be a positive integer".to_string(),
+        }),
+    }
+}
+
+fn parse_output_dir(value: Option<&Value>) -> Result<Option<String>, ToolError> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    let Some(dir) = value.as_str() else {
+        return Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "output_dir must be a string".to_string(),
+        });
+    };
+    if dir.trim().is_empty() {
+        return Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "output_dir must not be empty".to_string(),
+        });
+    }
+    fs::create_dir_all(dir).map_err(|err| ToolError {
+        kind: errors::INTERNAL_ERROR,
+        message: format!("failed to create output_dir: {err}"),
+    })?;
+    Ok(Some(dir.to_string()))
+}
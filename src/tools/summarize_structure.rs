@@ -1,12 +1,14 @@
-use crate::input::{InputFormat, load_input};
-use crate::mcp::errors;
-use crate::tools::error_result;
-use hwpers::{HwpError, HwpReader, HwpxReader};
+use crate::input::load_input;
+use crate::tools::{check_arguments, error_result, parse_document};
 use serde_json::{Value, json};
 
 const DEFAULT_PREVIEW_CHARS: usize = 120;
 
 pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
     let payload = match load_input(args) {
         Ok(payload) => payload,
         Err(err) => return error_result(err.kind, err.message, None),
@@ -15,6 +17,10 @@ pub fn call(args: &Value) -> Value {
     let max_sections = limit_from_args(args.get("max_sections"));
     let max_paragraphs = limit_from_args(args.get("max_paragraphs_per_section"));
     let preview_chars = preview_chars_from_args(args.get("preview_chars"));
+    let show_controls = args
+        .get("show_controls")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false);
 
     let parsed = match parse_document(&payload.bytes, payload.format) {
         Ok(parsed) => parsed,
@@ -45,6 +51,11 @@ pub fn call(args: &Value) -> Value {
 
             let char_count = text.chars().count() as u64;
             let preview = text.chars().take(preview_chars).collect::<String>();
+            let preview = if show_controls {
+                escape_controls(&preview)
+            } else {
+                preview
+            };
 
             paragraphs_out.push(json!({
                 "index": paragraph_index as u64,
@@ -70,6 +81,8 @@ pub fn call(args: &Value) -> Value {
         "content": [{"type": "text", "text": summary}],
         "structuredContent": {
             "format": parsed.format.as_str(),
+            "detected_format": parsed.format.as_str(),
+            "detection_method": payload.detection_method,
             "sections": sections_out,
             "warnings": parsed.warnings
         },
@@ -77,101 +90,6 @@ pub fn call(args: &Value) -> Value {
     })
 }
 
-struct ToolError {
-    kind: &'static str,
-    message: String,
-}
-
-struct ParsedDocument {
-    document: hwpers::HwpDocument,
-    format: InputFormat,
-    warnings: Vec<String>,
-}
-
-fn parse_document(bytes: &[u8], format: InputFormat) -> Result<ParsedDocument, ToolError> {
-    match format {
-        InputFormat::Hwp => HwpReader::from_bytes(bytes)
-            .map(|document| ParsedDocument {
-                document,
-                format,
-                warnings: Vec::new(),
-            })
-            .map_err(|error| map_hwp_error_with_format(error, format.as_str())),
-        InputFormat::Hwpx => HwpxReader::from_bytes(bytes)
-            .map(|document| ParsedDocument {
-                document,
-                format,
-                warnings: Vec::new(),
-            })
-            .map_err(|error| map_hwp_error_with_format(error, format.as_str())),
-        InputFormat::Auto => {
-            let hwp_result = HwpReader::from_bytes(bytes);
-            match hwp_result {
-                Ok(document) => Ok(ParsedDocument {
-                    document,
-                    format: InputFormat::Hwp,
-                    warnings: Vec::new(),
-                }),
-                Err(hwp_err) => match HwpxReader::from_bytes(bytes) {
-                    Ok(document) => Ok(ParsedDocument {
-                        document,
-                        format: InputFormat::Hwpx,
-                        warnings: vec!["auto format: hwp parse failed; hwpx succeeded".to_string()],
-                    }),
-                    Err(hwpx_err) => Err(ToolError {
-                        kind: errors::PARSE_FAILED,
-                        message: format!(
-                            "auto format parse failed (hwp: {}; hwpx: {})",
-                            hwp_err, hwpx_err
-                        ),
-                    }),
-                },
-            }
-        }
-    }
-}
-
-fn map_hwp_error(error: HwpError) -> ToolError {
-    match error {
-        HwpError::UnsupportedVersion(message) => {
-            if message.contains("Password-encrypted") {
-                ToolError {
-                    kind: errors::ENCRYPTED,
-                    message,
-                }
-            } else {
-                ToolError {
-                    kind: errors::PARSE_FAILED,
-                    message,
-                }
-            }
-        }
-        HwpError::InvalidInput(message) => ToolError {
-            kind: errors::INVALID_INPUT,
-            message,
-        },
-        HwpError::Io(err) => ToolError {
-            kind: errors::INVALID_INPUT,
-            message: err.to_string(),
-        },
-        HwpError::InvalidFormat(message)
-        | HwpError::Cfb(message)
-        | HwpError::CompressionError(message)
-        | HwpError::ParseError(message)
-        | HwpError::EncodingError(message)
-        | HwpError::NotFound(message) => ToolError {
-            kind: errors::PARSE_FAILED,
-            message,
-        },
-    }
-}
-
-fn map_hwp_error_with_format(error: HwpError, format: &str) -> ToolError {
-    let mut mapped = map_hwp_error(error);
-    mapped.message = format!("{format} parse failed: {}", mapped.message);
-    mapped
-}
-
 fn limit_from_args(value: Option<&Value>) -> usize {
     let Some(value) = value else {
         return usize::MAX;
@@ -191,3 +109,19 @@ fn preview_chars_from_args(value: Option<&Value>) -> usize {
     };
     usize::try_from(value).unwrap_or(usize::MAX)
 }
+
+fn escape_controls(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            ch if ch.is_control() => {
+                escaped.push_str(&format!("\\u{{{:04x}}}", ch as u32));
+            }
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,33 @@
+use crate::tools::Compress;
+use crate::tools::check_arguments;
+use crate::tools::extract_rich::{extract_blocks, render_html};
+use serde_json::{Value, json};
+
+pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let extracted = match extract_blocks(args, Compress::None) {
+        Ok(extracted) => extracted,
+        Err(tool_result) => return tool_result,
+    };
+
+    let html = render_html(&extracted.blocks);
+
+    json!({
+        "content": [{
+            "type": "text",
+            "text": html
+        }],
+        "structuredContent": {
+            "format": extracted.format.as_str(),
+            "detected_format": extracted.format.as_str(),
+            "detection_method": extracted.detection_method,
+            "html": html,
+            "timed_out": extracted.timed_out,
+            "warnings": extracted.warnings
+        },
+        "isError": false
+    })
+}
@@ -0,0 +1,332 @@
+use crate::input::load_input;
+use crate::mcp::contracts::limits;
+use crate::mcp::errors;
+use crate::mcp::resources;
+use crate::tools::{
+    ToolError, check_arguments, error_result, parse_document, write_file_atomically,
+};
+use hwpers::model::hyperlink::HyperlinkType;
+use hwpers::model::paragraph::Paragraph;
+use serde_json::{Value, json};
+use std::path::Path;
+
+/// Version of the `document` tree layout. Bump it whenever a field is renamed, removed or
+/// changes meaning; adding fields keeps the version.
+const MODEL_VERSION: u64 = 1;
+
+pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let payload = match load_input(args) {
+        Ok(payload) => payload,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let output_path = match parse_output_path(args.get("output_path")) {
+        Ok(output_path) => output_path,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let mut parsed = match parse_document(&payload.bytes, payload.format) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return error_result(err.kind, err.message, Some(payload.source.as_str()));
+        }
+    };
+
+    let document = document_model(&parsed.document);
+    let section_count = parsed.document.sections().count();
+    let paragraph_count: usize = parsed
+        .document
+        .sections()
+        .map(|section| section.paragraphs.len())
+        .sum();
+    let unshaped = parsed
+        .document
+        .sections()
+        .flat_map(|section| section.paragraphs.iter())
+        .filter(|paragraph| {
+            paragraph.char_shapes.is_none() && !paragraph_text(paragraph).trim().is_empty()
+        })
+        .count();
+    if unshaped > 0 {
+        parsed.warnings.push(format!(
+            "character shapes not available for {unshaped} paragraph(s); their runs are empty"
+        ));
+    }
+    let summary = format!("{section_count} section(s), {paragraph_count} paragraph(s)");
+
+    let serialized = match serde_json::to_string(&document) {
+        Ok(serialized) => serialized,
+        Err(err) => {
+            return error_result(
+                errors::INTERNAL_ERROR,
+                format!("failed to serialize document model: {err}"),
+                None,
+            );
+        }
+    };
+    let bytes_len = serialized.len() as u64;
+
+    match output_path {
+        Some(path) => {
+            if let Err(err) = write_file_atomically(Path::new(&path), serialized.as_bytes()) {
+                return error_result(
+                    errors::INTERNAL_ERROR,
+                    format!("failed to write output: {err}"),
+                    None,
+                );
+            }
+            let uri = resources::register(&path, "application/json");
+            let name = Path::new(&path)
+                .file_name()
+                .and_then(|value| value.to_str())
+                .unwrap_or("document.json");
+            json!({
+                "content": [
+                    {
+                        "type": "text",
+                        "text": format!("{summary}; document model written to {path} ({bytes_len} bytes)")
+                    },
+                    {
+                        "type": "resource_link",
+                        "uri": uri,
+                        "name": name,
+                        "mimeType": "application/json"
+                    }
+                ],
+                "structuredContent": {
+                    "model_version": MODEL_VERSION,
+                    "format": parsed.format.as_str(),
+                    "detected_format": parsed.format.as_str(),
+                    "detection_method": payload.detection_method,
+                    "path": path,
+                    "uri": uri,
+                    "bytes_len": bytes_len,
+                    "warnings": parsed.warnings
+                },
+                "isError": false
+            })
+        }
+        None => {
+            let max_output_bytes = limits().max_output_bytes;
+            if bytes_len > max_output_bytes {
+                return error_result(
+                    errors::TOO_LARGE,
+                    format!(
+                        "document model exceeds limit: {bytes_len} bytes (max {max_output_bytes}); use output_path"
+                    ),
+                    Some(payload.source.as_str()),
+                );
+            }
+            json!({
+                "content": [{"type": "text", "text": summary}],
+                "structuredContent": {
+                    "model_version": MODEL_VERSION,
+                    "format": parsed.format.as_str(),
+                    "detected_format": parsed.format.as_str(),
+                    "detection_method": payload.detection_method,
+                    "document": document,
+                    "bytes_len": bytes_len,
+                    "warnings": parsed.warnings
+                },
+                "isError": false
+            })
+        }
+    }
+}
+
+fn parse_output_path(value: Option<&Value>) -> Result<Option<String>, ToolError> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    let Some(path) = value.as_str() else {
+        return Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "output_path must be a string".to_string(),
+        });
+    };
+    if path.trim().is_empty() {
+        return Err(ToolError {
+            kind: errors::INVALID_INPUT,
+            message: "output_path must not be empty".to_string(),
+        });
+    }
+    Ok(Some(path.to_string()))
+}
+
+/// Mirrors the parsed `HwpDocument`: sections, their paragraphs, the character-shape runs of
+/// each paragraph and the controls (table, picture, text box) hwpers attached to it. Nothing
+/// is inferred; table cell paragraphs stay where the parser lists them, after their table.
+fn document_model(document: &hwpers::HwpDocument) -> Value {
+    let sections: Vec<Value> = document
+        .sections()
+        .enumerate()
+        .map(|(section_index, section)| {
+            let paragraphs: Vec<Value> = section
+                .paragraphs
+                .iter()
+                .enumerate()
+                .map(|(paragraph_index, paragraph)| {
+                    paragraph_model(document, paragraph_index, paragraph)
+                })
+                .collect();
+            json!({
+                "index": section_index,
+                "page_def": section.page_def.as_ref().map(|page_def| json!({
+                    "width": page_def.width,
+                    "height": page_def.height,
+                    "left_margin": page_def.left_margin,
+                    "right_margin": page_def.right_margin,
+                    "top_margin": page_def.top_margin,
+                    "bottom_margin": page_def.bottom_margin,
+                    "header_margin": page_def.header_margin,
+                    "footer_margin": page_def.footer_margin,
+                    "gutter_margin": page_def.gutter_margin,
+                    "landscape": page_def.properties & 0x01 != 0
+                })),
+                "paragraphs": paragraphs
+            })
+        })
+        .collect();
+    json!({ "sections": sections })
+}
+
+fn paragraph_model(
+    document: &hwpers::HwpDocument,
+    paragraph_index: usize,
+    paragraph: &Paragraph,
+) -> Value {
+    let text = paragraph_text(paragraph);
+
+    let mut controls = Vec::new();
+    if let Some(table) = &paragraph.table_data {
+        let cells: Vec<Value> = table
+            .cells
+            .iter()
+            .map(|cell| {
+                json!({
+                    "row": cell.cell_address.0,
+                    "col": cell.cell_address.1,
+                    "row_span": cell.row_span,
+                    "col_span": cell.col_span,
+                    "width": cell.width,
+                    "height": cell.height
+                })
+            })
+            .collect();
+        controls.push(json!({
+            "type": "table",
+            "rows": table.rows,
+            "cols": table.cols,
+            "cells": cells
+        }));
+    }
+    if let Some(picture) = &paragraph.picture_data {
+        controls.push(json!({
+            "type": "picture",
+            "bin_item_id": picture.bin_item_id,
+            "width": picture.right - picture.left,
+            "height": picture.bottom - picture.top
+        }));
+    }
+    if let Some(text_box) = &paragraph.text_box_data {
+        controls.push(json!({
+            "type": "text_box",
+            "text": text_box.text,
+            "x": text_box.x,
+            "y": text_box.y,
+            "width": text_box.width,
+            "height": text_box.height
+        }));
+    }
+
+    let hyperlinks: Vec<Value> = paragraph
+        .hyperlinks
+        .iter()
+        .map(|link| {
+            json!({
+                "kind": hyperlink_kind(link.hyperlink_type),
+                "text": link.display_text,
+                "target": link.target_url,
+                "start": link.start_position,
+                "length": link.length
+            })
+        })
+        .collect();
+
+    json!({
+        "index": paragraph_index,
+        "para_shape_id": paragraph.para_shape_id,
+        "style_id": paragraph.style_id,
+        "text": text,
+        "runs": runs(document, paragraph, text),
+        "controls": controls,
+        "hyperlinks": hyperlinks
+    })
+}
+
+fn paragraph_text(paragraph: &Paragraph) -> &str {
+    paragraph
+        .text
+        .as_ref()
+        .map(|text| text.content.as_str())
+        .unwrap_or("")
+}
+
+/// Splits the paragraph text at its character-shape positions. Positions are treated as
+/// `chars()` indices into `text`; hwpers drops inline control characters from the text, so
+/// runs after a control may start a few characters off. The raw `position` is kept.
+fn runs(document: &hwpers::HwpDocument, paragraph: &Paragraph, text: &str) -> Vec<Value> {
+    let chars: Vec<char> = text.chars().collect();
+    let positions = paragraph
+        .char_shapes
+        .as_ref()
+        .map(|shapes| shapes.char_positions.as_slice())
+        .unwrap_or_default();
+    let mut runs = Vec::new();
+    for (index, position) in positions.iter().enumerate() {
+        let start = (position.position as usize).min(chars.len());
+        let end = positions
+            .get(index + 1)
+            .map(|next| (next.position as usize).min(chars.len()))
+            .unwrap_or(chars.len())
+            .max(start);
+        let shape = document.get_char_shape(usize::from(position.char_shape_id));
+        let font_name = shape
+            .and_then(|shape| {
+                document
+                    .doc_info
+                    .face_names
+                    .get(usize::from(shape.face_name_ids[0]))
+            })
+            .map(|face| face.font_name.clone());
+        runs.push(json!({
+            "position": position.position,
+            "start": start,
+            "end": end,
+            "text": chars[start..end].iter().collect::<String>(),
+            "char_shape_id": position.char_shape_id,
+            "font_name": font_name,
+            "size_pt": shape.map(|shape| f64::from(shape.base_size) / 100.0),
+            "bold": shape.map(|shape| shape.is_bold()),
+            "italic": shape.map(|shape| shape.is_italic()),
+            "underline": shape.map(|shape| shape.is_underline()),
+            "strikethrough": shape.map(|shape| shape.is_strikethrough()),
+            "color": shape.map(|shape| format!("#{:06X}", shape.text_color & 0xFF_FFFF))
+        }));
+    }
+    runs
+}
+
+fn hyperlink_kind(kind: HyperlinkType) -> &'static str {
+    match kind {
+        HyperlinkType::Url => "url",
+        HyperlinkType::Email => "email",
+        HyperlinkType::File => "file",
+        HyperlinkType::Bookmark => "bookmark",
+        HyperlinkType::ExternalBookmark => "external_bookmark",
+    }
+}
@@ -0,0 +1,237 @@
+use crate::tools::Compress;
+use crate::tools::check_arguments;
+use crate::tools::extract_rich::{block_rows, extract_blocks};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+/// Longest paragraph (in chars) still considered a heading candidate.
+const MAX_HEADING_CHARS: usize = 80;
+
+pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let extracted = match extract_blocks(args, Compress::None) {
+        Ok(extracted) => extracted,
+        Err(tool_result) => return tool_result,
+    };
+
+    let markdown = render_markdown(&extracted.document, &extracted.blocks);
+
+    json!({
+        "content": [{
+            "type": "text",
+            "text": markdown
+        }],
+        "structuredContent": {
+            "format": extracted.format.as_str(),
+            "detected_format": extracted.format.as_str(),
+            "detection_method": extracted.detection_method,
+            "markdown": markdown,
+            "timed_out": extracted.timed_out,
+            "warnings": extracted.warnings
+        },
+        "isError": false
+    })
+}
+
+/// Serializes `extract_rich` blocks as GitHub-flavored Markdown. Paragraph blocks are looked up
+/// in the document again to decide whether they are headings.
+pub(crate) fn render_markdown(document: &hwpers::HwpDocument, blocks: &[Value]) -> String {
+    let body_size = body_font_size(document);
+    let mut parts: Vec<String> = Vec::new();
+
+    for block in blocks {
+        match block.get("type").and_then(|v| v.as_str()) {
+            Some("paragraph") => {
+                let text = block.get("text").and_then(|v| v.as_str()).unwrap_or("");
+                let text = text.trim();
+                if text.is_empty() {
+                    continue;
+                }
+                let paragraph = block_paragraph(document, block);
+                match paragraph.and_then(|p| heading_level(document, p, body_size, text)) {
+                    Some(level) => parts.push(format!("{} {text}", "#".repeat(level))),
+                    None => parts.push(text.to_string()),
+                }
+            }
+            Some("table") => {
+                let grid = block_rows(block);
+                if grid.first().is_some_and(|row| !row.is_empty()) {
+                    parts.push(markdown_table(&grid));
+                }
+            }
+            Some("image") => parts.extend(markdown_image(block)),
+            _ => {}
+        }
+    }
+
+    let mut markdown = parts.join("\n\n");
+    if !markdown.is_empty() {
+        markdown.push('\n');
+    }
+    markdown
+}
+
+fn block_paragraph<'a>(
+    document: &'a hwpers::HwpDocument,
+    block: &Value,
+) -> Option<&'a hwpers::model::paragraph::Paragraph> {
+    let section_index = block.get("section_index").and_then(|v| v.as_u64())? as usize;
+    let paragraph_index = block.get("paragraph_index").and_then(|v| v.as_u64())? as usize;
+    document
+        .sections()
+        .nth(section_index)?
+        .paragraphs
+        .get(paragraph_index)
+}
+
+fn paragraph_text(paragraph: &hwpers::model::paragraph::Paragraph) -> String {
+    match &paragraph.text {
+        Some(text) => text.content.clone(),
+        None => String::new(),
+    }
+}
+
+fn paragraph_char_shape<'a>(
+    document: &'a hwpers::HwpDocument,
+    paragraph: &hwpers::model::paragraph::Paragraph,
+) -> Option<&'a hwpers::model::CharShape> {
+    let id = paragraph
+        .char_shapes
+        .as_ref()
+        .and_then(|shapes| shapes.char_positions.first())
+        .map(|position| usize::from(position.char_shape_id))
+        .unwrap_or(0);
+    document.get_char_shape(id)
+}
+
+/// The most common leading font size across text paragraphs, used as the body size.
+fn body_font_size(document: &hwpers::HwpDocument) -> i32 {
+    let mut counts: HashMap<i32, usize> = HashMap::new();
+    for section in document.sections() {
+        for paragraph in &section.paragraphs {
+            if paragraph_text(paragraph).trim().is_empty() {
+                continue;
+            }
+            if let Some(shape) = paragraph_char_shape(document, paragraph) {
+                *counts.entry(shape.base_size).or_default() += 1;
+            }
+        }
+    }
+    counts
+        .into_iter()
+        .max_by_key(|(size, count)| (*count, -*size))
+        .map(|(size, _)| size)
+        .unwrap_or(0)
+}
+
+/// Short bold paragraphs become headings; the level follows the font size relative to the body.
+fn heading_level(
+    document: &hwpers::HwpDocument,
+    paragraph: &hwpers::model::paragraph::Paragraph,
+    body_size: i32,
+    text: &str,
+) -> Option<usize> {
+    if text.chars().count() > MAX_HEADING_CHARS || text.contains(['\r', '\n']) {
+        return None;
+    }
+    let shape = paragraph_char_shape(document, paragraph)?;
+    if !shape.is_bold() {
+        return None;
+    }
+    let ratio = if body_size > 0 {
+        shape.base_size * 100 / body_size
+    } else {
+        100
+    };
+    if ratio >= 200 {
+        Some(1)
+    } else if ratio >= 150 {
+        Some(2)
+    } else if ratio >= 120 {
+        Some(3)
+    } else {
+        Some(4)
+    }
+}
+
+/// Pipe table with the first row as the header. `|` is escaped and line breaks become `<br>`
+/// so every row stays on one line.
+fn markdown_table(grid: &[Vec<String>]) -> String {
+    let escape = |cell: &str| {
+        cell.trim()
+            .replace('|', "\\|")
+            .replace("\r\n", "<br>")
+            .replace(['\r', '\n'], "<br>")
+    };
+    let cols = grid.iter().map(|row| row.len()).max().unwrap_or(0);
+    let mut lines = Vec::with_capacity(grid.len() + 1);
+    for (index, row) in grid.iter().enumerate() {
+        let mut cells: Vec<String> = row.iter().map(|cell| escape(cell)).collect();
+        cells.resize(cols, String::new());
+        lines.push(format!("| {} |", cells.join(" | ")));
+        if index == 0 {
+            lines.push(format!("|{}", " --- |".repeat(cols)));
+        }
+    }
+    lines.join("\n")
+}
+
+/// `![caption](uri)` for images written to disk; otherwise only the caption survives.
+fn markdown_image(block: &Value) -> Option<String> {
+    let caption = block.get("caption").and_then(|v| v.as_str()).unwrap_or("");
+    match block.get("uri").and_then(|v| v.as_str()) {
+        Some(uri) => Some(format!(
+            "![{}]({})",
+            escape_alt(caption),
+            uri.replace(' ', "%20")
+        )),
+        None if !caption.trim().is_empty() => Some(format!("*{}*", caption.trim())),
+        None => None,
+    }
+}
+
+fn escape_alt(caption: &str) -> String {
+    caption
+        .trim()
+        .replace('\\', "\\\\")
+        .replace('[', "\\[")
+        .replace(']', "\\]")
+        .replace(['\r', '\n'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{markdown_image, markdown_table};
+    use serde_json::json;
+
+    #[test]
+    fn markdown_table_escapes_pipes_and_newlines() {
+        let grid = vec![
+            vec!["Key".to_string(), "Value".to_string()],
+            vec!["a|b".to_string(), "line 1\r\nline 2\r".to_string()],
+        ];
+        assert_eq!(
+            markdown_table(&grid),
+            "| Key | Value |\n| --- | --- |\n| a\\|b | line 1<br>line 2 |"
+        );
+    }
+
+    #[test]
+    fn markdown_image_links_resource_files() {
+        let block = json!({
+            "type": "image",
+            "caption": "chart [2024]",
+            "uri": "file:///tmp/hwp images/image_1.png"
+        });
+        assert_eq!(
+            markdown_image(&block).as_deref(),
+            Some("![chart \\[2024\\]](file:///tmp/hwp%20images/image_1.png)")
+        );
+        let block = json!({ "type": "image", "caption": "tiny", "bytes_len": 68 });
+        assert_eq!(markdown_image(&block).as_deref(), Some("*tiny*"));
+        assert_eq!(markdown_image(&json!({ "type": "image" })), None);
+    }
+}
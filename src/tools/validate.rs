@@ -0,0 +1,127 @@
+use crate::input::{InputFormat, load_input};
+use crate::tools::records::{HWPTAG_PAGE_DEF, HwpSections};
+use crate::tools::{check_arguments, error_result, parse_document};
+use serde_json::{Value, json};
+use std::io::{Cursor, Read};
+
+pub fn call(args: &Value) -> Value {
+    if let Some(result) = check_arguments(args) {
+        return result;
+    }
+
+    let payload = match load_input(args) {
+        Ok(payload) => payload,
+        Err(err) => return error_result(err.kind, err.message, None),
+    };
+
+    let parsed = match parse_document(&payload.bytes, payload.format) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return error_result(err.kind, err.message, Some(payload.source.as_str()));
+        }
+    };
+
+    let section_count = parsed.document.sections().count();
+    let mut problems: Vec<Value> = Vec::new();
+    if section_count == 0 {
+        problems.push(json!({
+            "kind": "no_sections",
+            "message": "document has no sections"
+        }));
+    } else {
+        let raw_page_defs = section_page_def_presence(&payload.bytes, parsed.format);
+        for (index, section) in parsed.document.sections().enumerate() {
+            // `None` means the container could not be scanned, which is not a problem in itself.
+            let found = section.page_def.is_some()
+                || raw_page_defs
+                    .as_ref()
+                    .is_none_or(|presence| presence.get(index).copied().unwrap_or(false));
+            if !found {
+                problems.push(json!({
+                    "kind": "missing_page_def",
+                    "section_index": index,
+                    "message": format!("section {index} has no page definition; renderers fall back to A4 portrait")
+                }));
+            }
+        }
+
+        let has_text = parsed.document.sections().any(|section| {
+            section.paragraphs.iter().any(|paragraph| {
+                paragraph.text.as_ref().is_some_and(|text| {
+                    text.content
+                        .chars()
+                        .any(|ch| !ch.is_whitespace() && !ch.is_control())
+                })
+            })
+        });
+        if !has_text {
+            problems.push(json!({
+                "kind": "empty_content",
+                "message": "document contains only empty paragraphs"
+            }));
+        }
+    }
+
+    let summary = match problems.len() {
+        0 => "valid: no problems found".to_string(),
+        count => format!("parseable with {count} problem(s)"),
+    };
+
+    json!({
+        "content": [{"type": "text", "text": summary}],
+        "structuredContent": {
+            "parseable": true,
+            "format": parsed.format.as_str(),
+            "detected_format": parsed.format.as_str(),
+            "detection_method": payload.detection_method,
+            "encrypted": parsed.document.is_encrypted(),
+            "section_count": section_count,
+            "problems": problems,
+            "warnings": parsed.warnings
+        },
+        "isError": false
+    })
+}
+
+/// Reports, per section, whether the container holds a page definition. hwpers 0.5.0 does not
+/// load HWP PAGE_DEF records or HWPX `<hp:pagePr>`, so this looks at the raw section data:
+/// the `BodyText/Section{N}` record streams for HWP and the `Contents/section{N}.xml` entries
+/// for HWPX. Returns `None` when the container cannot be scanned (e.g. distribution documents).
+fn section_page_def_presence(bytes: &[u8], format: InputFormat) -> Option<Vec<bool>> {
+    match format {
+        InputFormat::Hwpx => hwpx_page_def_presence(bytes),
+        _ => hwp_page_def_presence(bytes),
+    }
+}
+
+fn hwp_page_def_presence(bytes: &[u8]) -> Option<Vec<bool>> {
+    let sections = HwpSections::open(bytes).ok()?;
+    Some(
+        sections
+            .map(|records| {
+                records.is_ok_and(|records| {
+                    records
+                        .iter()
+                        .any(|record| record.tag_id() == HWPTAG_PAGE_DEF)
+                })
+            })
+            .collect(),
+    )
+}
+
+fn hwpx_page_def_presence(bytes: &[u8]) -> Option<Vec<bool>> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).ok()?;
+    let mut presence = Vec::new();
+    loop {
+        let name = format!("Contents/section{}.xml", presence.len());
+        let Ok(mut file) = archive.by_name(&name) else {
+            break;
+        };
+        let mut xml = String::new();
+        if file.read_to_string(&mut xml).is_err() {
+            return None;
+        }
+        presence.push(xml.contains("pagePr"));
+    }
+    Some(presence)
+}
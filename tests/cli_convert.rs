@@ -0,0 +1,55 @@
+use hwpers::HwpWriter;
+use std::process::Command;
+use tempfile::tempdir;
+
+#[test]
+fn cli_convert_writes_target_format() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("sample.hwp");
+    let output_path = dir.path().join("sample.hwpx");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("안녕하세요")?;
+    writer.save_to_file(&file_path)?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args([
+            "convert",
+            "--path",
+            file_path.to_string_lossy().as_ref(),
+            "--to",
+            "hwpx",
+            "--output-path",
+            output_path.to_string_lossy().as_ref(),
+            "--json",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(value["to"], "hwpx");
+    assert!(std::fs::read(&output_path)?.starts_with(b"PK"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args([
+            "convert",
+            "--path",
+            file_path.to_string_lossy().as_ref(),
+            "--to",
+            "txt",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)?.contains("안녕하세요"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args([
+            "convert",
+            "--path",
+            file_path.to_string_lossy().as_ref(),
+            "--to",
+            "pdf",
+        ])
+        .output()?;
+    assert!(!output.status.success());
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use hwpers::HwpWriter;
+use std::process::Command;
+use tempfile::tempdir;
+
+#[test]
+fn cli_count_stats_counts_korean_words_and_cjk_chars() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("sample.hwp");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("안녕하세요 세계")?;
+    writer.add_paragraph("Hello world 2024")?;
+    writer.save_to_file(&file_path)?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args([
+            "count-stats",
+            "--path",
+            file_path.to_string_lossy().as_ref(),
+            "--json",
+        ])
+        .output()?;
+
+    assert!(output.status.success());
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let count = |key: &str| value.get(key).and_then(|v| v.as_u64());
+    assert_eq!(count("word_count"), Some(5));
+    assert_eq!(count("char_count"), Some(8 + 16));
+    assert_eq!(count("char_count_no_spaces"), Some(7 + 14));
+    assert_eq!(count("cjk_char_count"), Some(7));
+    assert_eq!(count("paragraph_count"), Some(2));
+    assert_eq!(count("section_count"), Some(1));
+    Ok(())
+}
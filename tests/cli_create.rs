@@ -0,0 +1,88 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+use tempfile::tempdir;
+
+#[test]
+fn cli_create_from_text_and_spec() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let text_path = dir.path().join("text.hwp");
+    let spec_path = dir.path().join("spec.json");
+    let spec_output = dir.path().join("spec.hwpx");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args([
+            "create",
+            "--text",
+            "첫 줄\n둘째 줄",
+            "--output-path",
+            text_path.to_string_lossy().as_ref(),
+            "--json",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(value["path"], text_path.to_string_lossy().as_ref());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args([
+            "extract-text",
+            "--path",
+            text_path.to_string_lossy().as_ref(),
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let text = String::from_utf8(output.stdout)?;
+    assert!(text.contains("첫 줄"));
+    assert!(text.contains("둘째 줄"));
+
+    let spec = serde_json::json!({
+        "blocks": [{ "type": "paragraph", "text": "From spec" }]
+    });
+    std::fs::write(&spec_path, serde_json::to_vec(&spec)?)?;
+    let output = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args([
+            "create",
+            "--spec-file",
+            spec_path.to_string_lossy().as_ref(),
+            "--to",
+            "hwpx",
+            "--output-path",
+            spec_output.to_string_lossy().as_ref(),
+            "--json",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(value["to"], "hwpx");
+    assert!(spec_output.exists());
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["create", "--spec-stdin", "--json"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+    child
+        .stdin
+        .take()
+        .expect("stdin available")
+        .write_all(&serde_json::to_vec(&spec)?)?;
+    let output = child.wait_with_output()?;
+    assert!(output.status.success());
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(value["to"], "hwp");
+    assert!(value["base64"].as_str().is_some());
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["create", "--spec-stdin"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    child
+        .stdin
+        .take()
+        .expect("stdin available")
+        .write_all(br#"{"blocks": [{"type": "unknown"}]}"#)?;
+    assert!(!child.wait_with_output()?.status.success());
+    Ok(())
+}
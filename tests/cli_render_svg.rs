@@ -0,0 +1,64 @@
+use hwpers::HwpWriter;
+use std::process::Command;
+use tempfile::tempdir;
+
+#[test]
+fn cli_render_svg_renders_requested_pages() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("sample.hwp");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("안녕하세요")?;
+    writer.save_to_file(&file_path)?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args([
+            "render-svg",
+            "--path",
+            file_path.to_string_lossy().as_ref(),
+            "--page",
+            "1",
+            "--json",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(value["pages"][0]["page"], 1);
+    assert!(
+        value["pages"][0]["svg"]
+            .as_str()
+            .unwrap_or("")
+            .starts_with("<svg")
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args([
+            "render-svg",
+            "--path",
+            file_path.to_string_lossy().as_ref(),
+            "--all",
+            "--output",
+            "resource",
+            "--json",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let path = value["pages"][0]["path"]
+        .as_str()
+        .expect("page path present");
+    assert!(std::fs::read_to_string(path)?.starts_with("<svg"));
+    let _ = std::fs::remove_file(path);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args([
+            "render-svg",
+            "--path",
+            file_path.to_string_lossy().as_ref(),
+            "--page",
+            "9",
+        ])
+        .output()?;
+    assert!(!output.status.success());
+    Ok(())
+}
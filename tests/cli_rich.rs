@@ -0,0 +1,85 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+use tempfile::tempdir;
+
+#[test]
+fn cli_create_rich_then_extract_rich() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let spec_path = dir.path().join("spec.json");
+    let from_file = dir.path().join("from_file.hwpx");
+    let from_stdin = dir.path().join("from_stdin.hwp");
+
+    let spec = serde_json::json!({
+        "blocks": [
+            { "type": "heading", "level": 1, "text": "Title" },
+            { "type": "paragraph", "text": "Body text" }
+        ]
+    });
+    std::fs::write(&spec_path, serde_json::to_vec(&spec)?)?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args([
+            "create-rich",
+            "--spec",
+            spec_path.to_string_lossy().as_ref(),
+            "--to",
+            "hwpx",
+            "--output-path",
+            from_file.to_string_lossy().as_ref(),
+            "--json",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(value["to"], "hwpx");
+    assert!(from_file.exists());
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args([
+            "create-rich",
+            "--output-path",
+            from_stdin.to_string_lossy().as_ref(),
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+    child
+        .stdin
+        .take()
+        .expect("stdin available")
+        .write_all(&serde_json::to_vec(&spec)?)?;
+    assert!(child.wait_with_output()?.status.success());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args([
+            "extract-rich",
+            "--path",
+            from_stdin.to_string_lossy().as_ref(),
+            "--images",
+            "none",
+            "--json",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(value["format"], "hwp");
+    let texts: Vec<&str> = value["blocks"]
+        .as_array()
+        .expect("blocks present")
+        .iter()
+        .map(|block| block["text"].as_str().unwrap_or("").trim_end())
+        .collect();
+    assert_eq!(texts, vec!["Title", "Body text"]);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args([
+            "create-rich",
+            "--spec",
+            spec_path.to_string_lossy().as_ref(),
+        ])
+        .arg("--to")
+        .arg("docx")
+        .output()?;
+    assert!(!output.status.success());
+    Ok(())
+}
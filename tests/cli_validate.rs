@@ -0,0 +1,36 @@
+use hwpers::HwpWriter;
+use std::process::Command;
+use tempfile::tempdir;
+
+#[test]
+fn cli_validate_reports_parseable_document() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("sample.hwp");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("안녕하세요")?;
+    writer.save_to_file(&file_path)?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args([
+            "validate",
+            "--path",
+            file_path.to_string_lossy().as_ref(),
+            "--json",
+        ])
+        .output()?;
+
+    assert!(output.status.success());
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(value["parseable"], true);
+    assert_eq!(value["section_count"], 1);
+    assert_eq!(value["problems"], serde_json::json!([]));
+
+    let broken_path = dir.path().join("broken.hwp");
+    std::fs::write(&broken_path, b"not a document")?;
+    let output = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["validate", "--path", broken_path.to_string_lossy().as_ref()])
+        .output()?;
+    assert!(!output.status.success());
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use hwpers::HwpWriter;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+use tempfile::tempdir;
+
+fn send_request(
+    stdin: &mut impl Write,
+    stdout: &mut impl BufRead,
+    request: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let serialized = serde_json::to_string(&request)?;
+    writeln!(stdin, "{serialized}")?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    Ok(serde_json::from_str(line.trim())?)
+}
+
+#[test]
+fn allow_input_rejects_disabled_path_source() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("sample.hwp");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Hello")?;
+    writer.save_to_file(&file_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio", "--allow-input", "base64"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let path_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_text",
+            "arguments": { "path": file_path.to_string_lossy() }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, path_request)?;
+    let result = response.get("result").expect("result present");
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(true));
+    let error = result
+        .get("structuredContent")
+        .and_then(|value| value.get("error"))
+        .expect("error present");
+    assert_eq!(
+        error.get("kind").and_then(|v| v.as_str()),
+        Some("invalid_input")
+    );
+    assert_eq!(
+        error.get("message").and_then(|v| v.as_str()),
+        Some("input source 'path' is disabled")
+    );
+
+    let base64_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_text",
+            "arguments": { "base64": STANDARD.encode(std::fs::read(&file_path)?) }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, base64_request)?;
+    let result = response.get("result").expect("result present");
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+
+    let _ = child.kill();
+    Ok(())
+}
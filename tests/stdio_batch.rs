@@ -0,0 +1,126 @@
+use hwpers::HwpWriter;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+use tempfile::tempdir;
+
+fn call_tool(
+    name: &str,
+    arguments: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": { "name": name, "arguments": arguments }
+    });
+    writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+    stdin.flush()?;
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    let _ = child.kill();
+
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+    Ok(response.get("result").cloned().expect("result present"))
+}
+
+#[test]
+fn batch_runs_tool_over_directory_and_continues_past_failures()
+-> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    for name in ["a.hwp", "b.hwp"] {
+        let mut writer = HwpWriter::new();
+        writer.add_paragraph(&format!("문서 {name}"))?;
+        writer.save_to_file(dir.path().join(name))?;
+    }
+    fs::write(dir.path().join("c.hwp"), b"not an hwp file")?;
+    fs::write(dir.path().join("notes.txt"), b"ignored")?;
+
+    let result = call_tool(
+        "hwp.batch",
+        serde_json::json!({
+            "dir": dir.path().to_string_lossy(),
+            "tool": "hwp.extract_text",
+            "arguments": { "max_chars": 100 }
+        }),
+    )?;
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    let structured = &result["structuredContent"];
+    assert_eq!(structured["files"], 3);
+    assert_eq!(structured["succeeded"], 2);
+    assert_eq!(structured["failed"], 1);
+
+    let results = structured["results"].as_object().expect("results object");
+    let key = |name: &str| dir.path().join(name).to_string_lossy().to_string();
+    let a = &results[&key("a.hwp")];
+    assert_eq!(a["isError"], false);
+    assert!(a["kind"].is_null());
+    assert!(
+        a["structuredContent"]["text"]
+            .as_str()
+            .is_some_and(|text| text.contains("문서 a.hwp")),
+        "{a}"
+    );
+    let c = &results[&key("c.hwp")];
+    assert_eq!(c["isError"], true);
+    assert!(c["kind"].is_string(), "{c}");
+    assert!(!results.contains_key(&key("notes.txt")));
+
+    let capped = call_tool(
+        "hwp.batch",
+        serde_json::json!({
+            "paths": [key("a.hwp"), key("b.hwp"), key("c.hwp")],
+            "tool": "hwp.page_count",
+            "max_files": 2
+        }),
+    )?;
+    let structured = &capped["structuredContent"];
+    assert_eq!(structured["files"], 2);
+    assert_eq!(structured["succeeded"], 2);
+    assert!(
+        structured["warnings"][0]
+            .as_str()
+            .is_some_and(|warning| warning.starts_with("max_files reached")),
+        "{structured}"
+    );
+    Ok(())
+}
+
+#[test]
+fn batch_rejects_tools_without_path_input() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    for tool in ["hwp.batch", "hwp.create_document", "hwp.unknown"] {
+        let result = call_tool(
+            "hwp.batch",
+            serde_json::json!({ "dir": dir.path().to_string_lossy(), "tool": tool }),
+        )?;
+        assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(true));
+        assert_eq!(
+            result["structuredContent"]["error"]["kind"], "invalid_input",
+            "{tool}: {result}"
+        );
+    }
+
+    let result = call_tool(
+        "hwp.batch",
+        serde_json::json!({
+            "dir": dir.path().to_string_lossy(),
+            "tool": "hwp.extract_text",
+            "arguments": { "base64": "AAAA" }
+        }),
+    )?;
+    assert_eq!(
+        result["structuredContent"]["error"]["kind"],
+        "invalid_input"
+    );
+    Ok(())
+}
@@ -0,0 +1,170 @@
+use hwpers::HwpWriter;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use tempfile::tempdir;
+
+fn send_request(
+    stdin: &mut impl Write,
+    stdout: &mut impl BufRead,
+    request: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let serialized = serde_json::to_string(&request)?;
+    writeln!(stdin, "{serialized}")?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    Ok(serde_json::from_str(line.trim())?)
+}
+
+fn call_tool(id: u64, name: &str, arguments: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": "tools/call",
+        "params": { "name": name, "arguments": arguments }
+    })
+}
+
+fn rendered_path(response: &serde_json::Value) -> String {
+    assert_eq!(response["result"]["isError"], false, "{response}");
+    response["result"]["structuredContent"]["pages"][0]["path"]
+        .as_str()
+        .expect("path present")
+        .to_string()
+}
+
+#[test]
+fn cleanup_removes_only_server_placed_files() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let input_path = dir.path().join("sample.hwp");
+    let output_dir = dir.path().join("server");
+    let caller_dir = dir.path().join("caller");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Hello")?;
+    writer.save_to_file(&input_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .env("MCP_HWP_OUTPUT_DIR", &output_dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let server_file = rendered_path(&send_request(
+        &mut stdin,
+        &mut stdout,
+        call_tool(
+            1,
+            "hwp.render_svg",
+            serde_json::json!({ "path": input_path.to_string_lossy(), "output": "resource" }),
+        ),
+    )?);
+    let caller_file = rendered_path(&send_request(
+        &mut stdin,
+        &mut stdout,
+        call_tool(
+            2,
+            "hwp.render_svg",
+            serde_json::json!({
+                "path": input_path.to_string_lossy(),
+                "output": "resource",
+                "output_dir": caller_dir.to_string_lossy()
+            }),
+        ),
+    )?);
+
+    let recent = send_request(
+        &mut stdin,
+        &mut stdout,
+        call_tool(
+            3,
+            "hwp.cleanup",
+            serde_json::json!({ "older_than_minutes": 60 }),
+        ),
+    )?;
+    let structured = &recent["result"]["structuredContent"];
+    assert_eq!(structured["removed"], serde_json::json!([]));
+    assert_eq!(structured["remaining"], 1);
+    assert!(Path::new(&server_file).exists());
+
+    let all = send_request(
+        &mut stdin,
+        &mut stdout,
+        call_tool(4, "hwp.cleanup", serde_json::json!({})),
+    )?;
+    let structured = &all["result"]["structuredContent"];
+    assert_eq!(structured["removed"], serde_json::json!([server_file]));
+    assert_eq!(structured["remaining"], 0);
+    assert!(!Path::new(&server_file).exists());
+    assert!(Path::new(&caller_file).exists());
+
+    let listed = send_request(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({ "jsonrpc": "2.0", "id": 5, "method": "resources/list" }),
+    )?;
+    let uris: Vec<&str> = listed["result"]["resources"]
+        .as_array()
+        .expect("resources array")
+        .iter()
+        .filter_map(|resource| resource["uri"].as_str())
+        .collect();
+    assert!(!uris.iter().any(|uri| uri.ends_with(&server_file)));
+
+    let invalid = send_request(
+        &mut stdin,
+        &mut stdout,
+        call_tool(
+            6,
+            "hwp.cleanup",
+            serde_json::json!({ "older_than_minutes": -1 }),
+        ),
+    )?;
+    assert_eq!(invalid["result"]["isError"], true);
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn shutdown_removes_server_placed_files() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let input_path = dir.path().join("sample.hwp");
+    let output_dir = dir.path().join("server");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Hello")?;
+    writer.save_to_file(&input_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .env("MCP_HWP_OUTPUT_DIR", &output_dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let server_file = rendered_path(&send_request(
+        &mut stdin,
+        &mut stdout,
+        call_tool(
+            1,
+            "hwp.render_svg",
+            serde_json::json!({ "path": input_path.to_string_lossy(), "output": "resource" }),
+        ),
+    )?);
+    assert!(Path::new(&server_file).exists());
+
+    drop(stdin);
+    child.wait()?;
+    assert!(!Path::new(&server_file).exists());
+    Ok(())
+}
@@ -0,0 +1,332 @@
+use hwpers::HwpWriter;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use tempfile::tempdir;
+
+fn read_response(
+    stdout: &mut impl BufRead,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    Ok(serde_json::from_str(line.trim())?)
+}
+
+#[test]
+fn slow_call_does_not_block_later_requests() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("fast.hwp");
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Fast path")?;
+    writer.save_to_file(&file_path)?;
+    let body = std::fs::read(&file_path)?;
+
+    // Holds the download open until the test releases it, so the first call stays in flight.
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let url = format!("http://{}/slow.hwp", listener.local_addr()?);
+    let (release, released) = mpsc::channel::<()>();
+    thread::spawn(move || {
+        if let Some(Ok(mut stream)) = listener.incoming().next() {
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request);
+            let _ = released.recv();
+            let mut response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            )
+            .into_bytes();
+            response.extend_from_slice(&body);
+            let _ = stream.write_all(&response);
+        }
+    });
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args([
+            "serve",
+            "--stdio",
+            "--allow-input",
+            "path,url",
+            "--max-concurrency",
+            "2",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    for (id, arguments) in [
+        (1, serde_json::json!({ "url": url })),
+        (
+            2,
+            serde_json::json!({ "path": file_path.to_string_lossy() }),
+        ),
+    ] {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": { "name": "hwp.extract_text", "arguments": arguments }
+        });
+        writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+    }
+    stdin.flush()?;
+
+    let first = read_response(&mut stdout)?;
+    assert_eq!(first["id"], 2);
+    assert_eq!(first["result"]["isError"], false);
+
+    release.send(())?;
+    let second = read_response(&mut stdout)?;
+    assert_eq!(second["id"], 1);
+    assert_eq!(second["result"]["isError"], false, "{second}");
+    assert!(
+        second["result"]["structuredContent"]["text"]
+            .as_str()
+            .is_some_and(|text| text.contains("Fast path"))
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn pending_calls_are_answered_before_exit() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("sample.hwp");
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Hello")?;
+    writer.save_to_file(&file_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio", "--max-concurrency", "3"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    for id in 0..10 {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.page_count",
+                "arguments": { "path": file_path.to_string_lossy() }
+            }
+        });
+        writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+    }
+    drop(stdin);
+
+    let output = child.wait_with_output()?;
+    let mut ids: Vec<u64> = String::from_utf8(output.stdout)?
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).expect("whole json line"))
+        .filter_map(|response| response["id"].as_u64())
+        .collect();
+    ids.sort_unstable();
+    assert_eq!(ids, (0..10).collect::<Vec<_>>());
+    Ok(())
+}
+
+#[test]
+fn cancelled_calls_get_no_response() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("sample.hwp");
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Cancel me")?;
+    writer.save_to_file(&file_path)?;
+    let body = std::fs::read(&file_path)?;
+
+    // Holds the download open so the first render is still running when it is cancelled.
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let url = format!("http://{}/slow.hwp", listener.local_addr()?);
+    let (release, released) = mpsc::channel::<()>();
+    thread::spawn(move || {
+        if let Some(Ok(mut stream)) = listener.incoming().next() {
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request);
+            let _ = released.recv();
+            let mut response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            )
+            .into_bytes();
+            response.extend_from_slice(&body);
+            let _ = stream.write_all(&response);
+        }
+    });
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args([
+            "serve",
+            "--stdio",
+            "--allow-input",
+            "path,url",
+            "--max-concurrency",
+            "1",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    // 1 is in flight, 2 waits in the queue behind it; both are cancelled.
+    for (id, arguments) in [
+        (1, serde_json::json!({ "url": url })),
+        (
+            2,
+            serde_json::json!({ "path": file_path.to_string_lossy() }),
+        ),
+    ] {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": { "name": "hwp.render_svg", "arguments": arguments }
+        });
+        writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+    }
+    for id in [1, 2] {
+        let cancel = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/cancelled",
+            "params": { "requestId": id, "reason": "user navigated away" }
+        });
+        writeln!(stdin, "{}", serde_json::to_string(&cancel)?)?;
+    }
+    writeln!(
+        stdin,
+        "{}",
+        serde_json::json!({ "jsonrpc": "2.0", "id": 3, "method": "ping" })
+    )?;
+    stdin.flush()?;
+
+    // The ping is answered by the reader, so both cancellations have been recorded.
+    let ping = read_response(&mut stdout)?;
+    assert_eq!(ping["id"], 3);
+
+    release.send(())?;
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 4,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.page_count",
+            "arguments": { "path": file_path.to_string_lossy() }
+        }
+    });
+    writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+    drop(stdin);
+
+    let mut rest = String::new();
+    stdout.read_to_string(&mut rest)?;
+    let ids: Vec<serde_json::Value> = rest
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).expect("whole json line"))
+        .map(|response| response["id"].clone())
+        .collect();
+    assert_eq!(ids, vec![serde_json::json!(4)]);
+
+    let _ = child.wait();
+    Ok(())
+}
+
+#[test]
+fn reader_keeps_serving_while_calls_queue_up() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("sample.hwp");
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Queued")?;
+    writer.save_to_file(&file_path)?;
+    let body = std::fs::read(&file_path)?;
+
+    // Holds the download open so the only worker stays busy while the calls behind it queue.
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let url = format!("http://{}/slow.hwp", listener.local_addr()?);
+    let (release, released) = mpsc::channel::<()>();
+    thread::spawn(move || {
+        if let Some(Ok(mut stream)) = listener.incoming().next() {
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request);
+            let _ = released.recv();
+            let mut response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            )
+            .into_bytes();
+            response.extend_from_slice(&body);
+            let _ = stream.write_all(&response);
+        }
+    });
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args([
+            "serve",
+            "--stdio",
+            "--allow-input",
+            "path,url",
+            "--max-concurrency",
+            "1",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let call = |id: u64, arguments: serde_json::Value| {
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": { "name": "hwp.page_count", "arguments": arguments }
+        })
+    };
+    // 1 holds the worker; 2-9 queue behind it, more than one per worker.
+    writeln!(stdin, "{}", call(1, serde_json::json!({ "url": url })))?;
+    for id in 2..10 {
+        let arguments = serde_json::json!({ "path": file_path.to_string_lossy() });
+        writeln!(stdin, "{}", call(id, arguments))?;
+    }
+    for id in 2..9 {
+        let cancel = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/cancelled",
+            "params": { "requestId": id }
+        });
+        writeln!(stdin, "{cancel}")?;
+    }
+    writeln!(
+        stdin,
+        "{}",
+        serde_json::json!({ "jsonrpc": "2.0", "id": 10, "method": "ping" })
+    )?;
+    stdin.flush()?;
+
+    // The reader answers the ping while the worker is still blocked on call 1.
+    let ping = read_response(&mut stdout)?;
+    assert_eq!(ping["id"], 10);
+
+    release.send(())?;
+    drop(stdin);
+    let mut rest = String::new();
+    stdout.read_to_string(&mut rest)?;
+    let mut ids: Vec<u64> = rest
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).expect("whole json line"))
+        .filter_map(|response| response["id"].as_u64())
+        .collect();
+    ids.sort_unstable();
+    assert_eq!(ids, [1, 9]);
+
+    let _ = child.wait();
+    Ok(())
+}
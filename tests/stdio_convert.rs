@@ -152,3 +152,510 @@ fn convert_round_trip_hwp_hwpx() -> Result<(), Box<dyn std::error::Error>> {
     let _ = child.kill();
     Ok(())
 }
+
+#[test]
+fn convert_revision_options() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("sample.hwp");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Final text")?;
+    writer.save_to_file(&file_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let both_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 24,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.convert",
+            "arguments": {
+                "path": file_path.to_string_lossy(),
+                "to": "hwpx",
+                "accept_revisions": true,
+                "reject_revisions": true
+            }
+        }
+    });
+    let both_response = send_request(&mut stdin, &mut stdout, both_request)?;
+    let both_error = both_response
+        .get("result")
+        .and_then(|value| value.get("structuredContent"))
+        .and_then(|value| value.get("error"))
+        .expect("error present");
+    assert_eq!(
+        both_error.get("kind").and_then(|v| v.as_str()),
+        Some("invalid_input")
+    );
+
+    // hwpers cannot load tracked changes yet, so only the reporting path is checked here.
+    let accept_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 25,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.convert",
+            "arguments": {
+                "path": file_path.to_string_lossy(),
+                "to": "hwpx",
+                "accept_revisions": true
+            }
+        }
+    });
+    let accept_response = send_request(&mut stdin, &mut stdout, accept_request)?;
+    let accept_result = accept_response.get("result").expect("result present");
+    assert_eq!(
+        accept_result.get("isError").and_then(|v| v.as_bool()),
+        Some(false)
+    );
+    let warnings = accept_result
+        .get("structuredContent")
+        .and_then(|value| value.get("warnings"))
+        .and_then(|value| value.as_array())
+        .expect("warnings present");
+    assert!(warnings.iter().any(|warning| {
+        warning
+            .as_str()
+            .is_some_and(|text| text.starts_with("accept_revisions: applied 0"))
+    }));
+
+    let _ = child.kill();
+    Ok(())
+}
+
+/// Builds a one-section OLE property set of VT_LPSTR strings and FILETIMEs.
+fn property_set(strings: &[(u32, &str)], times: &[(u32, u64)]) -> Vec<u8> {
+    let mut values = Vec::new();
+    let mut entries = Vec::new();
+    let header_len = 8 + 8 * (strings.len() + times.len());
+    for (id, text) in strings {
+        entries.push((*id, (header_len + values.len()) as u32));
+        values.extend(0x1Eu32.to_le_bytes());
+        values.extend((text.len() as u32 + 1).to_le_bytes());
+        values.extend(text.as_bytes());
+        values.push(0);
+        while values.len() % 4 != 0 {
+            values.push(0);
+        }
+    }
+    for (id, filetime) in times {
+        entries.push((*id, (header_len + values.len()) as u32));
+        values.extend(0x40u32.to_le_bytes());
+        values.extend(filetime.to_le_bytes());
+    }
+
+    let mut data = vec![0xFE, 0xFF, 0, 0];
+    data.extend([0u8; 4 + 16]);
+    data.extend(1u32.to_le_bytes());
+    data.extend([0u8; 16]);
+    data.extend(48u32.to_le_bytes());
+    data.extend(((header_len + values.len()) as u32).to_le_bytes());
+    data.extend((entries.len() as u32).to_le_bytes());
+    for (id, offset) in entries {
+        data.extend(id.to_le_bytes());
+        data.extend(offset.to_le_bytes());
+    }
+    data.extend(values);
+    data
+}
+
+#[test]
+fn convert_strips_metadata() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("summary.hwp");
+    let output_path = dir.path().join("stripped.hwp");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Hello")?;
+    writer.save_to_file(&file_path)?;
+
+    let mut compound = cfb::open_rw(&file_path)?;
+    let mut stream = compound.create_stream("/\u{5}HwpSummaryInformation")?;
+    stream.write_all(&property_set(
+        &[(0x02, "Quarterly report"), (0x04, "Hong Gildong")],
+        &[(0x0C, 133_537_700_960_000_000)],
+    ))?;
+    drop(stream);
+    compound.flush()?;
+    drop(compound);
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let strip_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 30,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.convert",
+            "arguments": {
+                "path": file_path.to_string_lossy(),
+                "to": "hwp",
+                "output_path": output_path.to_string_lossy(),
+                "metadata": "strip"
+            }
+        }
+    });
+    let strip_response = send_request(&mut stdin, &mut stdout, strip_request)?;
+    let structured = &strip_response["result"]["structuredContent"];
+    assert_eq!(strip_response["result"]["isError"], false);
+    assert_eq!(
+        structured["stripped"],
+        serde_json::json!(["title", "author", "created"])
+    );
+    let compound = cfb::open(&output_path)?;
+    assert!(!compound.exists("/\u{5}HwpSummaryInformation"));
+
+    let preserve_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 31,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.convert",
+            "arguments": {
+                "path": file_path.to_string_lossy(),
+                "to": "hwpx"
+            }
+        }
+    });
+    let preserve_response = send_request(&mut stdin, &mut stdout, preserve_request)?;
+    assert!(
+        preserve_response["result"]["structuredContent"]
+            .get("stripped")
+            .is_none()
+    );
+
+    let invalid_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 32,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.convert",
+            "arguments": {
+                "path": file_path.to_string_lossy(),
+                "to": "hwpx",
+                "metadata": "remove"
+            }
+        }
+    });
+    let invalid_response = send_request(&mut stdin, &mut stdout, invalid_request)?;
+    assert_eq!(
+        invalid_response["result"]["structuredContent"]["error"]["kind"],
+        "invalid_input"
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn convert_to_text_and_markdown() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("sample.hwp");
+    let output_path = dir.path().join("sample.md");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("First line")?;
+    writer.add_paragraph("Second line")?;
+    writer.save_to_file(&file_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let txt_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 40,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.convert",
+            "arguments": {
+                "path": file_path.to_string_lossy(),
+                "to": "txt"
+            }
+        }
+    });
+    let txt_response = send_request(&mut stdin, &mut stdout, txt_request)?;
+    let structured = &txt_response["result"]["structuredContent"];
+    assert_eq!(txt_response["result"]["isError"], false);
+    assert_eq!(structured["to"], "txt");
+    let text = structured["text"].as_str().expect("text present");
+    assert!(text.contains("First line"));
+    assert!(text.contains("Second line"));
+    assert_eq!(structured["bytes_len"], text.len() as u64);
+    assert!(structured["warnings"].is_array());
+
+    let md_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 41,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.convert",
+            "arguments": {
+                "path": file_path.to_string_lossy(),
+                "to": "md",
+                "output_path": output_path.to_string_lossy()
+            }
+        }
+    });
+    let md_response = send_request(&mut stdin, &mut stdout, md_request)?;
+    let structured = &md_response["result"]["structuredContent"];
+    assert_eq!(md_response["result"]["isError"], false);
+    let written = std::fs::read_to_string(&output_path)?;
+    assert_eq!(written, "First line\n\nSecond line\n");
+    assert_eq!(structured["bytes_len"], written.len() as u64);
+    assert_eq!(
+        md_response["result"]["content"][1]["mimeType"],
+        "text/markdown"
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn convert_reports_fidelity_warnings() -> Result<(), Box<dyn std::error::Error>> {
+    // 1x1 PNG
+    let png_base64 = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAQAAAC1HAwCAAAAC0lEQVR42mP8/x8AAwMCAO6qVt0AAAAASUVORK5CYII=";
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let create_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 50,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": {
+                "to": "hwp",
+                "document": {
+                    "blocks": [
+                        {"type": "paragraph", "text": "Hello"},
+                        {"type": "image", "mimeType": "image/png", "data_base64": png_base64, "width_mm": 10, "height_mm": 10}
+                    ]
+                }
+            }
+        }
+    });
+    let create_response = send_request(&mut stdin, &mut stdout, create_request)?;
+    let base64 = create_response["result"]["structuredContent"]["base64"]
+        .as_str()
+        .expect("base64 present")
+        .to_string();
+
+    // hwpers writers do not carry embedded images over, and the re-parse notices.
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 51,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.convert",
+            "arguments": { "base64": base64, "to": "hwpx" }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, request)?;
+    assert_eq!(response["result"]["isError"], false);
+    let warnings = response["result"]["structuredContent"]["warnings"]
+        .as_array()
+        .expect("warnings present");
+    assert_eq!(
+        warnings,
+        &vec![serde_json::json!(
+            "fidelity: 1 images in source, 0 in output"
+        )]
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn convert_include_base64_returns_written_bytes() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let input_path = dir.path().join("sample.hwp");
+    let output_path = dir.path().join("converted.hwpx");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Both ways")?;
+    writer.save_to_file(&input_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let both = send_request(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.convert",
+                "arguments": {
+                    "path": input_path.to_string_lossy(),
+                    "to": "hwpx",
+                    "output_path": output_path.to_string_lossy(),
+                    "include_base64": true
+                }
+            }
+        }),
+    )?;
+    let structured = &both["result"]["structuredContent"];
+    assert_eq!(both["result"]["isError"], false, "{both}");
+    assert_eq!(structured["path"], output_path.to_string_lossy().as_ref());
+    let inline = base64::Engine::decode(
+        &base64::engine::general_purpose::STANDARD,
+        structured["base64"].as_str().expect("base64 present"),
+    )?;
+    assert_eq!(inline, std::fs::read(&output_path)?);
+    assert_eq!(structured["bytes_len"], inline.len() as u64);
+
+    let file_only = send_request(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.convert",
+                "arguments": {
+                    "path": input_path.to_string_lossy(),
+                    "to": "hwpx",
+                    "output_path": output_path.to_string_lossy()
+                }
+            }
+        }),
+    )?;
+    assert!(
+        file_only["result"]["structuredContent"]
+            .get("base64")
+            .is_none()
+    );
+
+    let invalid = send_request(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 3,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.convert",
+                "arguments": {
+                    "path": input_path.to_string_lossy(),
+                    "to": "hwpx",
+                    "output_path": output_path.to_string_lossy(),
+                    "include_base64": "yes"
+                }
+            }
+        }),
+    )?;
+    assert_eq!(invalid["result"]["isError"], true);
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn convert_gzip_compresses_base64_payload() -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::Read;
+
+    let dir = tempdir()?;
+    let input_path = dir.path().join("sample.hwp");
+    let output_path = dir.path().join("converted.hwpx");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Squeeze me")?;
+    writer.save_to_file(&input_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let mut call = |id: u64, arguments: serde_json::Value| {
+        send_request(
+            &mut stdin,
+            &mut stdout,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "method": "tools/call",
+                "params": { "name": "hwp.convert", "arguments": arguments }
+            }),
+        )
+    };
+
+    let gzipped = call(
+        1,
+        serde_json::json!({
+            "path": input_path.to_string_lossy(),
+            "to": "hwpx",
+            "output_path": output_path.to_string_lossy(),
+            "include_base64": true,
+            "compress": "gzip"
+        }),
+    )?;
+    let structured = &gzipped["result"]["structuredContent"];
+    let expected = std::fs::read(&output_path)?;
+    assert_eq!(gzipped["result"]["isError"], false, "{gzipped}");
+    assert_eq!(structured["encoding"], "gzip");
+    assert_eq!(structured["bytes_len"], expected.len() as u64);
+    let compressed = base64::Engine::decode(
+        &base64::engine::general_purpose::STANDARD,
+        structured["base64"].as_str().expect("base64 present"),
+    )?;
+    let mut decoded = Vec::new();
+    flate2::read::GzDecoder::new(compressed.as_slice()).read_to_end(&mut decoded)?;
+    assert_eq!(decoded, expected);
+
+    let invalid = call(
+        2,
+        serde_json::json!({
+            "path": input_path.to_string_lossy(),
+            "to": "hwpx",
+            "compress": "zstd"
+        }),
+    )?;
+    assert_eq!(invalid["result"]["isError"], true);
+
+    let _ = child.kill();
+    Ok(())
+}
@@ -121,3 +121,92 @@ fn create_document_round_trip() -> Result<(), Box<dyn std::error::Error>> {
     let _ = child.kill();
     Ok(())
 }
+
+#[test]
+fn create_document_rejects_non_object_arguments() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 40,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_document",
+            "arguments": "Hello from a string payload"
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, request)?;
+    let result = response.get("result").expect("result present");
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(true));
+    let error = result
+        .get("structuredContent")
+        .and_then(|v| v.get("error"))
+        .expect("error present");
+    assert_eq!(
+        error.get("kind").and_then(|v| v.as_str()),
+        Some("invalid_input")
+    );
+    assert_eq!(
+        error.get("message").and_then(|v| v.as_str()),
+        Some("arguments must be an object, got a string")
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn create_document_include_base64_with_output_path() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let output_path = dir.path().join("created.hwp");
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let response = send_request(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.create_document",
+                "arguments": {
+                    "text": "Kept on disk and inline",
+                    "output_path": output_path.to_string_lossy(),
+                    "include_base64": true
+                }
+            }
+        }),
+    )?;
+    let structured = &response["result"]["structuredContent"];
+    assert_eq!(response["result"]["isError"], false, "{response}");
+    assert!(
+        structured["uri"]
+            .as_str()
+            .unwrap_or("")
+            .starts_with("file://")
+    );
+    let inline = base64::Engine::decode(
+        &base64::engine::general_purpose::STANDARD,
+        structured["base64"].as_str().expect("base64 present"),
+    )?;
+    assert_eq!(inline, fs::read(&output_path)?);
+
+    let _ = child.kill();
+    Ok(())
+}
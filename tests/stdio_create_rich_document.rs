@@ -1,4 +1,6 @@
-use std::io::{BufRead, BufReader, Write};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use std::io::{BufRead, BufReader, Cursor, Read, Write};
 use std::process::{Command, Stdio};
 
 fn send_request(
@@ -173,3 +175,1553 @@ fn create_rich_document_with_table() -> Result<(), Box<dyn std::error::Error>> {
     let _ = child.kill();
     Ok(())
 }
+
+fn hwpx_table_width(hwpx: &[u8]) -> Result<u64, Box<dyn std::error::Error>> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(hwpx))?;
+    let mut section = String::new();
+    archive
+        .by_name("Contents/section0.xml")?
+        .read_to_string(&mut section)?;
+    let table = &section[section.find("<hp:tbl").expect("table present")..];
+    let size = &table[table.find("<hp:sz width=\"").expect("table size present") + 14..];
+    let width = &size[..size.find('"').expect("closing quote")];
+    Ok(width.parse()?)
+}
+
+#[test]
+fn create_rich_document_fits_wide_table_to_page() -> Result<(), Box<dyn std::error::Error>> {
+    const PRINTABLE_WIDTH: u64 = 59_528 - 2 * 8_504;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let wide_row: Vec<String> = (1..=10).map(|col| format!("Column {col}")).collect();
+    let mut widths = Vec::new();
+    for (id, fit_to_page) in [(50, false), (51, true)] {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.create_rich_document",
+                "arguments": {
+                    "to": "hwpx",
+                    "document": {
+                        "blocks": [
+                            {
+                                "type": "table",
+                                "rows": [wide_row.clone(), wide_row.clone()],
+                                "fit_to_page": fit_to_page
+                            }
+                        ]
+                    }
+                }
+            }
+        });
+        let response = send_request(&mut stdin, &mut stdout, request)?;
+        let result = response.get("result").expect("result present");
+        assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+
+        let encoded = result
+            .get("structuredContent")
+            .and_then(|value| value.get("base64"))
+            .and_then(|value| value.as_str())
+            .expect("base64 present");
+        widths.push(hwpx_table_width(&STANDARD.decode(encoded)?)?);
+    }
+
+    assert!(widths[0] > PRINTABLE_WIDTH);
+    assert!(widths[1] <= PRINTABLE_WIDTH);
+
+    let _ = child.kill();
+    Ok(())
+}
+
+fn hwpx_cell_widths(hwpx: &[u8]) -> Result<Vec<u64>, Box<dyn std::error::Error>> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(hwpx))?;
+    let mut section = String::new();
+    archive
+        .by_name("Contents/section0.xml")?
+        .read_to_string(&mut section)?;
+    section
+        .split("<hp:cellSz width=\"")
+        .skip(1)
+        .map(|rest| Ok(rest[..rest.find('"').expect("closing quote")].parse()?))
+        .collect()
+}
+
+const HWPTAG_LIST_HEADER: u32 = 0x10 + 56;
+
+/// The `(tag, data)` records of an HWP file's first section, inflated when the FileHeader
+/// marks the streams compressed.
+fn hwp_section_records(hwp: &[u8]) -> Result<Vec<(u32, Vec<u8>)>, Box<dyn std::error::Error>> {
+    let mut compound = cfb::CompoundFile::open(Cursor::new(hwp))?;
+    let mut header = Vec::new();
+    compound
+        .open_stream("/FileHeader")?
+        .read_to_end(&mut header)?;
+    let mut section = Vec::new();
+    compound
+        .open_stream("/BodyText/Section0")?
+        .read_to_end(&mut section)?;
+    if u32_at(&header, 36) & 0x01 != 0 {
+        let mut inflated = Vec::new();
+        flate2::read::DeflateDecoder::new(section.as_slice()).read_to_end(&mut inflated)?;
+        section = inflated;
+    }
+
+    let mut records = Vec::new();
+    let mut pos = 0;
+    while pos + 4 <= section.len() {
+        let header = u32_at(&section, pos);
+        pos += 4;
+        let mut size = (header >> 20) as usize;
+        if size == 0xFFF {
+            size = u32_at(&section, pos) as usize;
+            pos += 4;
+        }
+        records.push((header & 0x3FF, section[pos..pos + size].to_vec()));
+        pos += size;
+    }
+    Ok(records)
+}
+
+fn u32_at(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().expect("4 bytes"))
+}
+
+/// The LIST_HEADER bodies of the HWP file's table cells, in cell order.
+fn hwp_cell_list_headers(hwp: &[u8]) -> Result<Vec<Vec<u8>>, Box<dyn std::error::Error>> {
+    Ok(hwp_section_records(hwp)?
+        .into_iter()
+        .filter(|(tag, _)| *tag == HWPTAG_LIST_HEADER)
+        .map(|(_, data)| data)
+        .collect())
+}
+
+/// Each cell's width from its LIST_HEADER, after the paragraph count, list properties,
+/// address and spans.
+fn hwp_cell_widths(hwp: &[u8]) -> Result<Vec<u32>, Box<dyn std::error::Error>> {
+    Ok(hwp_cell_list_headers(hwp)?
+        .iter()
+        .map(|data| u32_at(data, 16))
+        .collect())
+}
+
+/// The width between the side margins of the HWP file's PAGE_DEF record (portrait pages).
+fn hwp_printable_width(hwp: &[u8]) -> Result<u32, Box<dyn std::error::Error>> {
+    const HWPTAG_PAGE_DEF: u32 = 0x10 + 57;
+    let (_, page_def) = hwp_section_records(hwp)?
+        .into_iter()
+        .find(|(tag, _)| *tag == HWPTAG_PAGE_DEF)
+        .expect("page definition present");
+    Ok(u32_at(&page_def, 0) - u32_at(&page_def, 8) - u32_at(&page_def, 12))
+}
+
+fn create_hwp(
+    stdin: &mut std::process::ChildStdin,
+    stdout: &mut BufReader<std::process::ChildStdout>,
+    id: u64,
+    blocks: serde_json::Value,
+) -> Result<(Vec<u8>, Vec<String>), Box<dyn std::error::Error>> {
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": { "to": "hwp", "document": { "blocks": blocks } }
+        }
+    });
+    let response = send_request(stdin, stdout, request)?;
+    let result = &response["result"];
+    assert_eq!(result["isError"], false, "{result}");
+    let warnings = result["structuredContent"]["warnings"]
+        .as_array()
+        .expect("warnings present")
+        .iter()
+        .filter_map(|warning| warning.as_str().map(str::to_string))
+        .collect();
+    let encoded = result["structuredContent"]["base64"]
+        .as_str()
+        .expect("base64 present");
+    Ok((STANDARD.decode(encoded)?, warnings))
+}
+
+#[test]
+fn create_rich_document_writes_hwp_column_widths() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let (hwp, warnings) = create_hwp(
+        &mut stdin,
+        &mut stdout,
+        1,
+        serde_json::json!([{
+            "type": "table",
+            "rows": [["구분", "내용"], ["a", "b"]],
+            "column_widths": [20, 60]
+        }]),
+    )?;
+    assert_eq!(hwp_cell_widths(&hwp)?, [5_669, 17_008, 5_669, 17_008]);
+    assert!(
+        !warnings.iter().any(|w| w.contains("column_widths")),
+        "{warnings:?}"
+    );
+
+    let (hwp, warnings) = create_hwp(
+        &mut stdin,
+        &mut stdout,
+        2,
+        serde_json::json!([{
+            "type": "table",
+            "rows": [["구분", "내용"]],
+            "column_widths": [20, 60],
+            "layout": "fixed"
+        }]),
+    )?;
+    let widths = hwp_cell_widths(&hwp)?;
+    assert_eq!(widths.iter().sum::<u32>(), hwp_printable_width(&hwp)?);
+    let ratio = f64::from(widths[1]) / f64::from(widths[0]);
+    assert!((ratio - 3.0).abs() < 0.01, "{widths:?}");
+    assert!(
+        !warnings.iter().any(|w| w.contains("layout")),
+        "{warnings:?}"
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn create_rich_document_fits_wide_hwp_table_to_page() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let wide_row: Vec<String> = (1..=10).map(|col| format!("Column {col}")).collect();
+    let mut totals = Vec::new();
+    let wide_widths = serde_json::json!(vec![30; 10]);
+    for (id, layout, fit_to_page, column_widths) in [
+        (1, "auto", false, wide_widths.clone()),
+        (2, "auto", true, wide_widths),
+        (3, "fixed", false, serde_json::Value::Null),
+    ] {
+        let mut table = serde_json::json!({
+            "type": "table",
+            "rows": [wide_row.clone()],
+            "layout": layout,
+            "fit_to_page": fit_to_page
+        });
+        if !column_widths.is_null() {
+            table["column_widths"] = column_widths;
+        }
+        let (hwp, warnings) = create_hwp(&mut stdin, &mut stdout, id, serde_json::json!([table]))?;
+        assert!(
+            !warnings.iter().any(|w| w.contains("fit_to_page")),
+            "{warnings:?}"
+        );
+        let widths = hwp_cell_widths(&hwp)?;
+        assert_eq!(widths.len(), 10);
+        totals.push((widths.iter().sum::<u32>(), hwp_printable_width(&hwp)?));
+    }
+
+    let (natural, printable) = totals[0];
+    assert!(natural > printable, "{totals:?}");
+    assert!(totals[1].0 <= totals[1].1, "{totals:?}");
+    assert_eq!(totals[2].0, totals[2].1, "{totals:?}");
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn create_rich_document_applies_column_widths() -> Result<(), Box<dyn std::error::Error>> {
+    const PRINTABLE_WIDTH: u64 = 59_528 - 2 * 8_504;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let mut cell_widths = Vec::new();
+    for (id, layout) in [(1, "auto"), (2, "fixed")] {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.create_rich_document",
+                "arguments": {
+                    "to": "hwpx",
+                    "document": {
+                        "blocks": [{
+                            "type": "table",
+                            "rows": [["구분", "내용"]],
+                            "column_widths": [20, 60],
+                            "layout": layout
+                        }]
+                    }
+                }
+            }
+        });
+        let response = send_request(&mut stdin, &mut stdout, request)?;
+        let result = &response["result"];
+        assert_eq!(result["isError"], false);
+        let encoded = result["structuredContent"]["base64"]
+            .as_str()
+            .expect("base64 present");
+        cell_widths.push(hwpx_cell_widths(&STANDARD.decode(encoded)?)?);
+    }
+
+    assert_eq!(cell_widths[0], [5_669, 17_008]);
+    assert_eq!(cell_widths[1].iter().sum::<u64>(), PRINTABLE_WIDTH);
+    let ratio = cell_widths[1][1] as f64 / cell_widths[1][0] as f64;
+    assert!((ratio - 3.0).abs() < 0.01, "{cell_widths:?}");
+
+    for (id, column_widths) in [
+        (3, serde_json::json!([20])),
+        (4, serde_json::json!([20, 0])),
+        (5, serde_json::json!([20, "wide"])),
+    ] {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.create_rich_document",
+                "arguments": {
+                    "document": {
+                        "blocks": [{
+                            "type": "table",
+                            "rows": [["a", "b"]],
+                            "column_widths": column_widths
+                        }]
+                    }
+                }
+            }
+        });
+        let response = send_request(&mut stdin, &mut stdout, request)?;
+        assert_eq!(response["result"]["isError"], true, "{column_widths}");
+        assert_eq!(
+            response["result"]["structuredContent"]["error"]["kind"],
+            "invalid_input"
+        );
+    }
+
+    let _ = child.kill();
+    Ok(())
+}
+
+fn hwpx_char_pr_for_text(hwpx: &[u8], text: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(hwpx))?;
+    let mut section = String::new();
+    archive
+        .by_name("Contents/section0.xml")?
+        .read_to_string(&mut section)?;
+    let mut header = String::new();
+    archive
+        .by_name("Contents/header.xml")?
+        .read_to_string(&mut header)?;
+
+    let before = &section[..section
+        .find(&format!("<hp:t>{text}</hp:t>"))
+        .expect("text present")];
+    let run = &before[before.rfind("<hp:run charPrIDRef=\"").expect("run present") + 21..];
+    let id = &run[..run.find('"').expect("closing quote")];
+    let char_pr = &header[header
+        .find(&format!("<hh:charPr id=\"{id}\""))
+        .expect("charPr present")..];
+    Ok(char_pr[..char_pr.find('>').expect("charPr end")].to_string())
+}
+
+#[test]
+fn create_rich_document_resolves_named_styles() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let create_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 44,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": {
+                "to": "hwpx",
+                "document": {
+                    "styles": {
+                        "quote": { "italic": true, "color": "#555555" }
+                    },
+                    "blocks": [
+                        { "type": "paragraph", "text": "First quote", "style_ref": "quote" },
+                        { "type": "paragraph", "text": "Second quote", "style_ref": "quote" }
+                    ]
+                }
+            }
+        }
+    });
+
+    let create_response = send_request(&mut stdin, &mut stdout, create_request)?;
+    let create_result = create_response.get("result").expect("result present");
+    assert_eq!(
+        create_result.get("isError").and_then(|v| v.as_bool()),
+        Some(false)
+    );
+    let encoded = create_result
+        .get("structuredContent")
+        .and_then(|value| value.get("base64"))
+        .and_then(|value| value.as_str())
+        .expect("base64 present");
+    let hwpx = STANDARD.decode(encoded)?;
+    for text in ["First quote", "Second quote"] {
+        let char_pr = hwpx_char_pr_for_text(&hwpx, text)?;
+        assert!(char_pr.contains("italic=\"1\""), "{text}: {char_pr}");
+        assert!(
+            char_pr.contains("textColor=\"#555555\""),
+            "{text}: {char_pr}"
+        );
+    }
+
+    let unknown_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 45,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": {
+                "to": "hwpx",
+                "document": {
+                    "blocks": [
+                        { "type": "paragraph", "text": "Orphan", "style_ref": "missing" }
+                    ]
+                }
+            }
+        }
+    });
+    let unknown_response = send_request(&mut stdin, &mut stdout, unknown_request)?;
+    let error = unknown_response
+        .get("result")
+        .and_then(|value| value.get("structuredContent"))
+        .and_then(|value| value.get("error"))
+        .expect("error present");
+    assert_eq!(
+        error.get("kind").and_then(|v| v.as_str()),
+        Some("invalid_input")
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn create_rich_document_rejects_table_beyond_column_limit() -> Result<(), Box<dyn std::error::Error>>
+{
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let wide_row: Vec<String> = (0..1000).map(|i| format!("c{i}")).collect();
+    let create_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 46,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": {
+                "to": "hwp",
+                "document": {
+                    "blocks": [
+                        { "type": "table", "rows": [wide_row] }
+                    ]
+                }
+            }
+        }
+    });
+
+    let create_response = send_request(&mut stdin, &mut stdout, create_request)?;
+    let create_result = create_response.get("result").expect("result present");
+    assert_eq!(
+        create_result.get("isError").and_then(|v| v.as_bool()),
+        Some(true)
+    );
+    let error = create_result
+        .get("structuredContent")
+        .and_then(|value| value.get("error"))
+        .expect("error present");
+    assert_eq!(
+        error.get("kind").and_then(|v| v.as_str()),
+        Some("invalid_input")
+    );
+    let message = error
+        .get("message")
+        .and_then(|v| v.as_str())
+        .expect("message present");
+    assert!(message.contains("limit of 256"), "{message}");
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn create_rich_document_writes_hwp_cell_margins() -> Result<(), Box<dyn std::error::Error>> {
+    const HWPTAG_TABLE: u32 = 0x10 + 61;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let (hwp, warnings) = create_hwp(
+        &mut stdin,
+        &mut stdout,
+        1,
+        serde_json::json!([{
+            "type": "table",
+            "cell_margin_mm": { "top": 3, "right": 5, "bottom": 3, "left": 5 },
+            "rows": [["A1", { "content": "B1", "cell_margin_mm": { "left": 10 } }]]
+        }]),
+    )?;
+    assert!(
+        !warnings.iter().any(|w| w.contains("cell_margin_mm")),
+        "{warnings:?}"
+    );
+
+    // Margins are left, right, top, bottom u16s: after the properties, counts and spacing in
+    // the TABLE record, after the width and height in a cell's LIST_HEADER.
+    let margins = |data: &[u8], offset: usize| -> Vec<u16> {
+        (0..4)
+            .map(|side| {
+                let at = offset + side * 2;
+                u16::from_le_bytes([data[at], data[at + 1]])
+            })
+            .collect()
+    };
+    let (_, table) = hwp_section_records(&hwp)?
+        .into_iter()
+        .find(|(tag, _)| *tag == HWPTAG_TABLE)
+        .expect("table record present");
+    assert_eq!(margins(&table, 10), [1_417, 1_417, 850, 850]);
+    let cells = hwp_cell_list_headers(&hwp)?;
+    assert_eq!(cells.len(), 2);
+    assert_eq!(margins(&cells[0], 24), [1_417, 1_417, 850, 850]);
+    assert_eq!(margins(&cells[1], 24), [2_835, 510, 142, 142]);
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn create_rich_document_warns_on_unsupported_hwpx_cell_margins()
+-> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    // HwpxTable holds only cell text and column widths, so HWPX output validates and reports
+    // the margins rather than writing them.
+    let create_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 48,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": {
+                "to": "hwpx",
+                "document": {
+                    "blocks": [
+                        {
+                            "type": "table",
+                            "cell_margin_mm": { "top": 3, "right": 5, "bottom": 3, "left": 5 },
+                            "rows": [
+                                ["A1", { "content": "B1", "cell_margin_mm": { "left": 10 } }]
+                            ]
+                        }
+                    ]
+                }
+            }
+        }
+    });
+
+    let create_response = send_request(&mut stdin, &mut stdout, create_request)?;
+    let create_result = create_response.get("result").expect("result present");
+    assert_eq!(
+        create_result.get("isError").and_then(|v| v.as_bool()),
+        Some(false)
+    );
+    let warnings: Vec<&str> = create_result
+        .get("structuredContent")
+        .and_then(|value| value.get("warnings"))
+        .and_then(|value| value.as_array())
+        .expect("warnings present")
+        .iter()
+        .filter_map(|value| value.as_str())
+        .collect();
+    assert!(
+        warnings
+            .iter()
+            .any(|w| w.starts_with("hwpx: cell_margin_mm is not supported")),
+        "{warnings:?}"
+    );
+    assert!(
+        warnings
+            .iter()
+            .any(|w| w.contains("cell_margin_mm at (0, 1)")),
+        "{warnings:?}"
+    );
+
+    let invalid_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 49,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": {
+                "to": "hwpx",
+                "document": {
+                    "blocks": [
+                        { "type": "table", "cell_margin_mm": { "top": -1 }, "rows": [["A1"]] }
+                    ]
+                }
+            }
+        }
+    });
+    let invalid_response = send_request(&mut stdin, &mut stdout, invalid_request)?;
+    let error = invalid_response
+        .get("result")
+        .and_then(|value| value.get("structuredContent"))
+        .and_then(|value| value.get("error"))
+        .expect("error present");
+    assert_eq!(
+        error.get("kind").and_then(|v| v.as_str()),
+        Some("invalid_input")
+    );
+    assert_eq!(
+        error.get("message").and_then(|v| v.as_str()),
+        Some("document.blocks[0]: table.cell_margin_mm.top must be a non-negative number")
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn create_rich_document_streams_hwpx_to_output_path() -> Result<(), Box<dyn std::error::Error>> {
+    // 1x1 PNG padded after IEND so every image is a sizeable ZIP entry.
+    let mut png = STANDARD.decode(
+        "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAQAAAC1HAwCAAAAC0lEQVR42mP8/x8AAwMCAO6qVt0AAAAASUVORK5CYII=",
+    )?;
+    png.extend((0..256 * 1024u32).map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8));
+    let png_base64 = STANDARD.encode(&png);
+
+    let dir = tempfile::tempdir()?;
+    let output_path = dir.path().join("images.hwpx");
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let mut blocks = vec![serde_json::json!({"type": "paragraph", "text": "Gallery"})];
+    for index in 0..4 {
+        blocks.push(serde_json::json!({
+            "type": "image",
+            "mimeType": "image/png",
+            "data_base64": png_base64,
+            "width_mm": 20,
+            "height_mm": 20,
+            "caption": format!("image {index}")
+        }));
+    }
+    let create_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 100,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": {
+                "to": "hwpx",
+                "output_path": output_path.to_string_lossy(),
+                "document": { "blocks": blocks }
+            }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, create_request)?;
+    let result = response.get("result").expect("result present");
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    let structured = result.get("structuredContent").expect("structuredContent");
+    assert!(structured.get("base64").is_none());
+    let file_len = std::fs::metadata(&output_path)?.len();
+    assert_eq!(
+        structured.get("bytes_len").and_then(|v| v.as_u64()),
+        Some(file_len)
+    );
+    assert!(file_len > 4 * 256 * 1024, "{file_len}");
+
+    let archive = zip::ZipArchive::new(std::fs::File::open(&output_path)?)?;
+    let images = archive
+        .file_names()
+        .filter(|name| name.starts_with("BinData/") && !name.ends_with('/'))
+        .count();
+    assert_eq!(images, 4);
+
+    let extract_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 101,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_text",
+            "arguments": { "path": output_path.to_string_lossy() }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, extract_request)?;
+    let text = response
+        .get("result")
+        .and_then(|v| v.get("structuredContent"))
+        .and_then(|v| v.get("text"))
+        .and_then(|v| v.as_str())
+        .expect("text present");
+    assert!(text.contains("Gallery"), "{text}");
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn create_rich_document_applies_default_line_spacing() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let blocks = serde_json::json!([
+        {"type": "paragraph", "text": "Alpha"},
+        {"type": "paragraph", "text": "Bravo"},
+        {"type": "paragraph", "text": "Charlie"}
+    ]);
+
+    let mut line_gap =
+        |id: u64, document: serde_json::Value| -> Result<i64, Box<dyn std::error::Error>> {
+            let create_request = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "method": "tools/call",
+                "params": {
+                    "name": "hwp.create_rich_document",
+                    "arguments": { "to": "hwp", "document": document }
+                }
+            });
+            let response = send_request(&mut stdin, &mut stdout, create_request)?;
+            let result = response.get("result").expect("result present");
+            assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+            let base64 = result
+                .get("structuredContent")
+                .and_then(|v| v.get("base64"))
+                .and_then(|v| v.as_str())
+                .expect("base64 present")
+                .to_string();
+
+            let render_request = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": id + 1,
+                "method": "tools/call",
+                "params": {
+                    "name": "hwp.render_svg",
+                    "arguments": { "base64": base64 }
+                }
+            });
+            let response = send_request(&mut stdin, &mut stdout, render_request)?;
+            let svg = response
+                .get("result")
+                .and_then(|v| v.get("structuredContent"))
+                .and_then(|v| v.get("pages"))
+                .and_then(|v| v.as_array())
+                .and_then(|v| v.first())
+                .and_then(|v| v.get("svg"))
+                .and_then(|v| v.as_str())
+                .expect("svg present")
+                .to_string();
+
+            let text_y = |label: &str| -> Option<f64> {
+                let end = svg.find(label)?;
+                let tag = svg[..end].rfind("<text ")?;
+                let start = svg[tag..end].find(" y=\"")? + tag + " y=\"".len();
+                let len = svg[start..].find('"')?;
+                svg[start..start + len].parse().ok()
+            };
+            let alpha = text_y(">Alpha").unwrap_or_else(|| panic!("Alpha missing: {svg}"));
+            let bravo = text_y(">Bravo").unwrap_or_else(|| panic!("Bravo missing: {svg}"));
+            Ok((bravo - alpha).round() as i64)
+        };
+
+    let default_gap = line_gap(110, serde_json::json!({ "blocks": blocks }))?;
+    let double_gap = line_gap(
+        120,
+        serde_json::json!({
+            "default_paragraph_style": { "line_spacing": 200 },
+            "blocks": blocks
+        }),
+    )?;
+    assert!(default_gap > 0, "{default_gap}");
+    assert!(
+        double_gap > default_gap,
+        "200% gap {double_gap} is not larger than default gap {default_gap}"
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
+
+fn svg_dimension(svg: &str, name: &str) -> f64 {
+    let tag_end = svg.find('>').expect("svg tag");
+    let tag = &svg[..tag_end];
+    let start = tag
+        .find(&format!(" {name}=\""))
+        .expect("dimension attribute")
+        + name.len()
+        + 3;
+    let value: String = tag[start..]
+        .chars()
+        .take_while(|ch| ch.is_ascii_digit() || *ch == '.')
+        .collect();
+    value.parse().expect("numeric dimension")
+}
+
+#[test]
+fn create_rich_document_landscape_page_renders_wider() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let mut page_sizes = Vec::new();
+    for (id, orientation) in [(1, "portrait"), (3, "landscape")] {
+        let create_request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.create_rich_document",
+                "arguments": {
+                    "to": "hwp",
+                    "document": {
+                        "page": { "size": "a4", "orientation": orientation },
+                        "blocks": [{ "type": "paragraph", "text": "Page setup" }]
+                    }
+                }
+            }
+        });
+        let create_response = send_request(&mut stdin, &mut stdout, create_request)?;
+        let encoded = create_response["result"]["structuredContent"]["base64"]
+            .as_str()
+            .expect("base64 present")
+            .to_string();
+
+        let render_request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id + 1,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.render_svg",
+                "arguments": { "base64": encoded }
+            }
+        });
+        let render_response = send_request(&mut stdin, &mut stdout, render_request)?;
+        let svg = render_response["result"]["structuredContent"]["pages"][0]["svg"]
+            .as_str()
+            .expect("svg present")
+            .to_string();
+        page_sizes.push((svg_dimension(&svg, "width"), svg_dimension(&svg, "height")));
+    }
+
+    let (portrait_width, portrait_height) = page_sizes[0];
+    let (landscape_width, landscape_height) = page_sizes[1];
+    assert!(portrait_width < portrait_height, "{page_sizes:?}");
+    assert!(landscape_width > landscape_height, "{page_sizes:?}");
+    assert!(landscape_width > portrait_width, "{page_sizes:?}");
+
+    let invalid_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 5,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": {
+                "document": {
+                    "page": { "size": "b5" },
+                    "blocks": [{ "type": "paragraph", "text": "x" }]
+                }
+            }
+        }
+    });
+    let invalid_response = send_request(&mut stdin, &mut stdout, invalid_request)?;
+    assert_eq!(invalid_response["result"]["isError"], true);
+    assert_eq!(
+        invalid_response["result"]["structuredContent"]["error"]["kind"],
+        "invalid_input"
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn create_rich_document_writes_link_blocks() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let blocks = serde_json::json!([
+        { "type": "paragraph", "text": "See the docs:" },
+        { "type": "link", "text": "설명서", "href": "https://example.com/docs?a=1&b=2" }
+    ]);
+
+    let hwpx_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": { "to": "hwpx", "document": { "blocks": blocks } }
+        }
+    });
+    let hwpx_response = send_request(&mut stdin, &mut stdout, hwpx_request)?;
+    assert_eq!(hwpx_response["result"]["isError"], false);
+    let hwpx = STANDARD.decode(
+        hwpx_response["result"]["structuredContent"]["base64"]
+            .as_str()
+            .expect("base64 present"),
+    )?;
+    let mut archive = zip::ZipArchive::new(Cursor::new(hwpx))?;
+    let mut section = String::new();
+    archive
+        .by_name("Contents/section0.xml")?
+        .read_to_string(&mut section)?;
+    assert!(
+        section.contains(r#"<hp:hyperlink url="https://example.com/docs?a=1&amp;b=2""#),
+        "{section}"
+    );
+    assert!(section.contains("<hp:t>설명서</hp:t>"), "{section}");
+
+    let hwp_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": { "to": "hwp", "document": { "blocks": blocks } }
+        }
+    });
+    let hwp_response = send_request(&mut stdin, &mut stdout, hwp_request)?;
+    let hwp_result = &hwp_response["result"]["structuredContent"];
+    assert_eq!(hwp_response["result"]["isError"], false);
+    let warnings = hwp_result["warnings"].as_array().expect("warnings present");
+    assert!(
+        warnings
+            .iter()
+            .any(|w| w.as_str().is_some_and(|w| w.contains("hyperlinks"))),
+        "{warnings:?}"
+    );
+
+    let extract_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 3,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_text",
+            "arguments": { "base64": hwp_result["base64"] }
+        }
+    });
+    let extract_response = send_request(&mut stdin, &mut stdout, extract_request)?;
+    let text = extract_response["result"]["structuredContent"]["text"]
+        .as_str()
+        .expect("text present");
+    assert!(
+        text.contains("설명서 (https://example.com/docs?a=1&b=2)"),
+        "{text}"
+    );
+
+    let invalid_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 4,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": {
+                "document": { "blocks": [{ "type": "link", "text": "nowhere" }] }
+            }
+        }
+    });
+    let invalid_response = send_request(&mut stdin, &mut stdout, invalid_request)?;
+    assert_eq!(invalid_response["result"]["isError"], true);
+    assert_eq!(
+        invalid_response["result"]["structuredContent"]["error"]["kind"],
+        "invalid_input"
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn create_rich_document_nests_list_items() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let list = serde_json::json!({
+        "type": "list",
+        "list_type": "numbered",
+        "items": [
+            "Alpha",
+            { "text": "Alpha one", "level": 1 },
+            { "text": "Alpha two", "level": 1 },
+            { "text": "Beta" }
+        ]
+    });
+
+    let hwp_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": { "to": "hwp", "document": { "blocks": [list] } }
+        }
+    });
+    let hwp_response = send_request(&mut stdin, &mut stdout, hwp_request)?;
+    assert_eq!(hwp_response["result"]["isError"], false);
+    let hwp_warnings = hwp_response["result"]["structuredContent"]["warnings"]
+        .as_array()
+        .expect("warnings present");
+    assert!(
+        hwp_warnings
+            .iter()
+            .any(|w| w.as_str().is_some_and(|w| w.contains("list indentation"))),
+        "{hwp_warnings:?}"
+    );
+    let extract_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_text",
+            "arguments": { "base64": hwp_response["result"]["structuredContent"]["base64"] }
+        }
+    });
+    let extract_response = send_request(&mut stdin, &mut stdout, extract_request)?;
+    let text = extract_response["result"]["structuredContent"]["text"]
+        .as_str()
+        .expect("text present");
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect();
+    assert_eq!(
+        lines,
+        ["1. Alpha", "1. Alpha one", "2. Alpha two", "2. Beta"],
+        "{text}"
+    );
+
+    let hwpx_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 3,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": { "to": "hwpx", "document": { "blocks": [list] } }
+        }
+    });
+    let hwpx_response = send_request(&mut stdin, &mut stdout, hwpx_request)?;
+    let hwpx_result = &hwpx_response["result"]["structuredContent"];
+    let warnings = hwpx_result["warnings"]
+        .as_array()
+        .expect("warnings present");
+    assert!(
+        warnings
+            .iter()
+            .any(|w| w.as_str().is_some_and(|w| w.contains("list indentation"))),
+        "{warnings:?}"
+    );
+    let hwpx = STANDARD.decode(hwpx_result["base64"].as_str().expect("base64 present"))?;
+    let mut archive = zip::ZipArchive::new(Cursor::new(hwpx))?;
+    let mut section = String::new();
+    archive
+        .by_name("Contents/section0.xml")?
+        .read_to_string(&mut section)?;
+    assert!(
+        section.contains("<hp:t>    2. Alpha two</hp:t>"),
+        "{section}"
+    );
+    assert!(section.contains("<hp:t>2. Beta</hp:t>"), "{section}");
+
+    let invalid_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 4,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": {
+                "document": {
+                    "blocks": [{ "type": "list", "items": [{ "text": "Deep", "level": 10 }] }]
+                }
+            }
+        }
+    });
+    let invalid_response = send_request(&mut stdin, &mut stdout, invalid_request)?;
+    assert_eq!(invalid_response["result"]["isError"], true);
+    assert_eq!(
+        invalid_response["result"]["structuredContent"]["error"]["kind"],
+        "invalid_input"
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn create_rich_document_reports_unsupported_hwpx_cell_styling_once()
+-> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let header_cell = |text: &str| {
+        serde_json::json!({
+            "content": text,
+            "background_color": "0x1F4E79",
+            "text_align": "center",
+            "style": { "bold": true, "color": "0xFFFFFF" }
+        })
+    };
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": {
+                "to": "hwpx",
+                "document": {
+                    "blocks": [{
+                        "type": "table",
+                        "header_row": true,
+                        "rows": [
+                            [header_cell("항목"), header_cell("금액")],
+                            ["인건비", "1,000"]
+                        ]
+                    }]
+                }
+            }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, request)?;
+    let result = &response["result"];
+    assert_eq!(result["isError"], false);
+
+    let warnings: Vec<&str> = result["structuredContent"]["warnings"]
+        .as_array()
+        .expect("warnings present")
+        .iter()
+        .filter_map(|w| w.as_str())
+        .collect();
+    for attribute in ["background_color", "text_align", "style"] {
+        let matching: Vec<&&str> = warnings
+            .iter()
+            .filter(|w| w.starts_with(&format!("hwpx: cell {attribute} ")))
+            .collect();
+        assert_eq!(matching.len(), 1, "{warnings:?}");
+        assert!(
+            matching[0].starts_with(&format!("hwpx: cell {attribute} at (0, 0), (0, 1) ")),
+            "{warnings:?}"
+        );
+    }
+
+    let hwpx = STANDARD.decode(
+        result["structuredContent"]["base64"]
+            .as_str()
+            .expect("base64 present"),
+    )?;
+    assert_eq!(hwpx_cell_widths(&hwpx)?.len(), 4);
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn create_rich_document_writes_quote_blocks() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": {
+                "to": "hwpx",
+                "document": {
+                    "blocks": [
+                        { "type": "quote", "text": "Italic callout", "style": { "color": "0x555555" } },
+                        { "type": "quote", "text": "Upright callout", "italic": false }
+                    ]
+                }
+            }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, request)?;
+    let result = &response["result"];
+    assert_eq!(result["isError"], false);
+    let warnings = result["structuredContent"]["warnings"]
+        .as_array()
+        .expect("warnings present");
+    assert!(
+        warnings
+            .iter()
+            .any(|w| w.as_str().is_some_and(|w| w.contains("quote borders"))),
+        "{warnings:?}"
+    );
+
+    let hwpx = STANDARD.decode(
+        result["structuredContent"]["base64"]
+            .as_str()
+            .expect("base64 present"),
+    )?;
+    let italic = hwpx_char_pr_for_text(&hwpx, "    Italic callout")?;
+    assert!(italic.contains("italic=\"1\""), "{italic}");
+    assert!(italic.contains("textColor=\"#555555\""), "{italic}");
+    let upright = hwpx_char_pr_for_text(&hwpx, "    Upright callout")?;
+    assert!(!upright.contains("italic=\"1\""), "{upright}");
+
+    let invalid_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": {
+                "to": "hwp",
+                "document": { "blocks": [{ "type": "quote", "text": "x", "italic": "yes" }] }
+            }
+        }
+    });
+    let invalid_response = send_request(&mut stdin, &mut stdout, invalid_request)?;
+    assert_eq!(invalid_response["result"]["isError"], true);
+    assert_eq!(
+        invalid_response["result"]["structuredContent"]["error"]["kind"],
+        "invalid_input"
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn create_rich_document_writes_styled_runs() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let paragraph = serde_json::json!({
+        "type": "paragraph",
+        "style": { "color": "0x333333" },
+        "runs": [
+            { "text": "계약의 " },
+            { "text": "핵심", "style": { "bold": true } },
+            { "text": " 조항" }
+        ]
+    });
+
+    let hwpx_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": { "to": "hwpx", "document": { "blocks": [paragraph] } }
+        }
+    });
+    let hwpx_response = send_request(&mut stdin, &mut stdout, hwpx_request)?;
+    assert_eq!(hwpx_response["result"]["isError"], false);
+    let hwpx = STANDARD.decode(
+        hwpx_response["result"]["structuredContent"]["base64"]
+            .as_str()
+            .expect("base64 present"),
+    )?;
+    let bold = hwpx_char_pr_for_text(&hwpx, "핵심")?;
+    assert!(bold.contains("bold=\"1\""), "{bold}");
+    assert!(bold.contains("textColor=\"#333333\""), "{bold}");
+    let plain = hwpx_char_pr_for_text(&hwpx, " 조항")?;
+    assert!(!plain.contains("bold=\"1\""), "{plain}");
+    assert!(plain.contains("textColor=\"#333333\""), "{plain}");
+
+    let hwp_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": { "to": "hwp", "document": { "blocks": [paragraph] } }
+        }
+    });
+    let hwp_response = send_request(&mut stdin, &mut stdout, hwp_request)?;
+    assert_eq!(hwp_response["result"]["isError"], false);
+    let extract_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 3,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_text",
+            "arguments": { "base64": hwp_response["result"]["structuredContent"]["base64"] }
+        }
+    });
+    let extract_response = send_request(&mut stdin, &mut stdout, extract_request)?;
+    let text = extract_response["result"]["structuredContent"]["text"]
+        .as_str()
+        .expect("text present");
+    assert!(text.contains("계약의 핵심 조항"), "{text}");
+
+    let invalid_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 4,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": {
+                "document": {
+                    "blocks": [{ "type": "paragraph", "text": "x", "runs": [{ "text": "x" }] }]
+                }
+            }
+        }
+    });
+    let invalid_response = send_request(&mut stdin, &mut stdout, invalid_request)?;
+    assert_eq!(invalid_response["result"]["isError"], true);
+    assert_eq!(
+        invalid_response["result"]["structuredContent"]["error"]["kind"],
+        "invalid_input"
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn create_rich_document_scales_image_from_one_dimension() -> Result<(), Box<dyn std::error::Error>>
+{
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    // PNG header only: signature and a 400x100 IHDR chunk.
+    let mut png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 13];
+    png.extend_from_slice(b"IHDR");
+    png.extend_from_slice(&400u32.to_be_bytes());
+    png.extend_from_slice(&100u32.to_be_bytes());
+    png.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": {
+                "to": "hwpx",
+                "document": {
+                    "blocks": [{
+                        "type": "image",
+                        "mimeType": "image/png",
+                        "data_base64": STANDARD.encode(&png),
+                        "width_mm": 80
+                    }]
+                }
+            }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, request)?;
+    assert_eq!(response["result"]["isError"], false);
+    let hwpx = STANDARD.decode(
+        response["result"]["structuredContent"]["base64"]
+            .as_str()
+            .expect("base64 present"),
+    )?;
+    let mut archive = zip::ZipArchive::new(Cursor::new(hwpx))?;
+    let mut section = String::new();
+    archive
+        .by_name("Contents/section0.xml")?
+        .read_to_string(&mut section)?;
+    let picture = &section[section.find("<hp:pic").expect("picture present")..];
+    assert!(
+        picture.contains(r#"<hp:sz width="22677" widthRelTo="ABSOLUTE" height="5669""#),
+        "{picture}"
+    );
+
+    let undecodable = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": {
+                "to": "hwp",
+                "document": {
+                    "blocks": [{
+                        "type": "image",
+                        "mimeType": "image/png",
+                        "data_base64": STANDARD.encode([0x89, b'P', b'N', b'G']),
+                        "height_mm": 30
+                    }]
+                }
+            }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, undecodable)?;
+    assert_eq!(response["result"]["isError"], true);
+    assert_eq!(
+        response["result"]["structuredContent"]["error"]["kind"],
+        "invalid_input"
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn create_rich_document_explains_webp_and_svg_images() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let webp = b"RIFF\x1a\x00\x00\x00WEBPVP8L\x0d\x00\x00\x00".to_vec();
+    let svg = br#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10"/>"#.to_vec();
+    let mut id = 0;
+    for (to, mime_type, data, format) in [
+        ("hwp", "image/webp", &webp, "WebP"),
+        ("hwpx", "image/webp", &webp, "WebP"),
+        ("hwp", "image/svg+xml", &svg, "SVG"),
+        ("hwpx", "image/svg+xml", &svg, "SVG"),
+    ] {
+        id += 1;
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.create_rich_document",
+                "arguments": {
+                    "to": to,
+                    "document": {
+                        "blocks": [{
+                            "type": "image",
+                            "mimeType": mime_type,
+                            "data_base64": STANDARD.encode(data),
+                            "width_mm": 20
+                        }]
+                    }
+                }
+            }
+        });
+        let response = send_request(&mut stdin, &mut stdout, request)?;
+        let error = &response["result"]["structuredContent"]["error"];
+        assert_eq!(response["result"]["isError"], true, "{to} {format}");
+        assert_eq!(error["kind"], "invalid_input");
+        let message = error["message"].as_str().expect("message present");
+        assert!(
+            message.starts_with(&format!("{format} images are not supported")),
+            "{to}: {message}"
+        );
+    }
+
+    let _ = child.kill();
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+
+fn send_request(
+    stdin: &mut impl Write,
+    stdout: &mut impl BufRead,
+    request: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let serialized = serde_json::to_string(&request)?;
+    writeln!(stdin, "{serialized}")?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    Ok(serde_json::from_str(line.trim())?)
+}
+
+fn hwp_with_paragraphs(paragraphs: &[&str]) -> Result<String, Box<dyn std::error::Error>> {
+    let mut writer = hwpers::HwpWriter::new();
+    for paragraph in paragraphs {
+        writer.add_paragraph(paragraph)?;
+    }
+    Ok(STANDARD.encode(writer.to_bytes()?))
+}
+
+#[test]
+fn diff_reports_paragraph_and_char_changes() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let left = hwp_with_paragraphs(&["제목", "첫 번째 문단입니다.", "삭제될 문단", "끝"])?;
+    let right = hwp_with_paragraphs(&["제목", "첫 번째 단락입니다.", "끝", "새 문단"])?;
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.diff",
+            "arguments": {
+                "left": { "base64": left },
+                "right": { "base64": right }
+            }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, request)?;
+    let result = response.get("result").expect("result present");
+    assert_eq!(result["isError"], false);
+    let structured = &result["structuredContent"];
+    assert_eq!(
+        structured["counts"],
+        serde_json::json!({ "added": 2, "removed": 2, "unchanged": 2 })
+    );
+    assert_eq!(
+        result["content"][0]["text"],
+        "2 added, 2 removed, 2 unchanged paragraphs"
+    );
+
+    let diff = structured["diff"].as_array().expect("diff present");
+    let ops: Vec<(&str, &str)> = diff
+        .iter()
+        .map(|entry| {
+            (
+                entry["op"].as_str().unwrap_or(""),
+                entry["text"].as_str().unwrap_or(""),
+            )
+        })
+        .collect();
+    assert_eq!(
+        ops,
+        vec![
+            ("unchanged", "제목"),
+            ("removed", "첫 번째 문단입니다."),
+            ("removed", "삭제될 문단"),
+            ("added", "첫 번째 단락입니다."),
+            ("unchanged", "끝"),
+            ("added", "새 문단"),
+        ],
+        "{structured}"
+    );
+    assert!(diff[0]["left_index"].is_u64());
+    assert!(diff[1]["right_index"].is_null());
+
+    let replaced = &diff[3];
+    assert_eq!(replaced["replaces_left_index"], diff[1]["left_index"]);
+    assert_eq!(
+        replaced["changes"],
+        serde_json::json!([
+            { "op": "unchanged", "text": "첫 번째 " },
+            { "op": "removed", "text": "문" },
+            { "op": "unchanged", "text": "단" },
+            { "op": "added", "text": "락" },
+            { "op": "unchanged", "text": "입니다." }
+        ])
+    );
+    assert!(diff[5].get("changes").is_none());
+
+    let _ = child.kill();
+    Ok(())
+}
@@ -0,0 +1,202 @@
+use aes::Aes128;
+use aes::cipher::{BlockEncrypt, KeyInit};
+use hwpers::HwpWriter;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use tempfile::tempdir;
+
+const DISTRIBUTION_KEY: [u8; 16] = *b"mcp-hwp-test-key";
+
+/// Writes an HWP with the FileHeader distribution flag set. When `readable`, the
+/// DocInfo and section streams are encrypted the way hwpers 0.5 decrypts them
+/// (a 260-byte distribution record followed by AES-128-ECB data, sections under
+/// `ViewText/`); otherwise only the flag is set and the content cannot be decrypted.
+fn write_distribution_document(
+    path: &Path,
+    readable: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("배포용 본문 text")?;
+    writer.save_to_file(path)?;
+
+    let mut compound = cfb::open_rw(path)?;
+    let mut header = Vec::new();
+    compound
+        .open_stream("/FileHeader")?
+        .read_to_end(&mut header)?;
+    let flags = u32::from_le_bytes(header[36..40].try_into()?) | 0x04;
+    header[36..40].copy_from_slice(&flags.to_le_bytes());
+    compound.create_stream("/FileHeader")?.write_all(&header)?;
+
+    if readable {
+        let record = distribution_record(&DISTRIBUTION_KEY);
+        for (from, to) in [
+            ("/DocInfo", "/DocInfo"),
+            ("/BodyText/Section0", "/ViewText/Section0"),
+        ] {
+            let mut plain = Vec::new();
+            compound.open_stream(from)?.read_to_end(&mut plain)?;
+            let mut encrypted = record.clone();
+            encrypted.extend_from_slice(&encrypt(&plain, &DISTRIBUTION_KEY));
+            if from != to {
+                compound.remove_stream(from)?;
+                compound.create_storage_all("/ViewText")?;
+            }
+            compound.create_stream(to)?.write_all(&encrypted)?;
+        }
+    }
+    compound.flush()?;
+    Ok(())
+}
+
+/// Builds a DISTRIBUTE_DOC_DATA record whose de-obfuscated payload carries `key`.
+fn distribution_record(key: &[u8; 16]) -> Vec<u8> {
+    // A zero seed puts the key right after the seed bytes.
+    let mut payload = [0u8; 256];
+    payload[4..20].copy_from_slice(key);
+    // The obfuscation is an XOR stream, so applying it again encodes the payload.
+    let mut rng_state = 0u32;
+    let mut rand = move || {
+        rng_state = rng_state.wrapping_mul(214013).wrapping_add(2531011);
+        (rng_state >> 16) & 0x7FFF
+    };
+    let mut value = 0u8;
+    let mut count = 0u32;
+    for (i, byte) in payload.iter_mut().enumerate() {
+        if count == 0 {
+            value = (rand() & 0xFF) as u8;
+            count = (rand() & 0x0F) + 1;
+        }
+        if i >= 4 {
+            *byte ^= value;
+        }
+        count -= 1;
+    }
+
+    let mut record = (0x1Cu32 | (256 << 20)).to_le_bytes().to_vec();
+    record.extend_from_slice(&payload);
+    record
+}
+
+/// AES-128-ECB, zero-padding the plaintext to a whole number of blocks.
+fn encrypt(plain: &[u8], key: &[u8; 16]) -> Vec<u8> {
+    let cipher = Aes128::new_from_slice(key).expect("16-byte key");
+    let mut data = plain.to_vec();
+    data.resize(plain.len().div_ceil(16) * 16, 0);
+    for chunk in data.chunks_exact_mut(16) {
+        cipher.encrypt_block(aes::Block::from_mut_slice(chunk));
+    }
+    data
+}
+
+fn call_tool(
+    name: &str,
+    arguments: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": { "name": name, "arguments": arguments }
+    });
+    writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+    stdin.flush()?;
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    let _ = child.kill();
+
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+    Ok(response.get("result").cloned().expect("result present"))
+}
+
+fn error_kind(result: &serde_json::Value) -> Option<&str> {
+    result
+        .get("structuredContent")
+        .and_then(|value| value.get("error"))
+        .and_then(|value| value.get("kind"))
+        .and_then(|value| value.as_str())
+}
+
+#[test]
+fn distribution_document_is_readable_but_not_editable() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("distribution.hwp");
+    write_distribution_document(&file_path, true)?;
+    let path = file_path.to_string_lossy();
+
+    let extracted = call_tool("hwp.extract_text", serde_json::json!({ "path": path }))?;
+    assert_eq!(
+        extracted.get("isError").and_then(|v| v.as_bool()),
+        Some(false)
+    );
+    let structured = extracted.get("structuredContent").expect("structured");
+    assert!(
+        structured
+            .get("text")
+            .and_then(|v| v.as_str())
+            .is_some_and(|text| text.contains("배포용 본문 text"))
+    );
+    assert!(
+        structured
+            .get("warnings")
+            .and_then(|v| v.as_array())
+            .is_some_and(|warnings| warnings.iter().any(|w| w
+                .as_str()
+                .is_some_and(|w| w.starts_with("distribution document"))))
+    );
+
+    let metadata = call_tool("hwp.inspect_metadata", serde_json::json!({ "path": path }))?;
+    assert_eq!(
+        metadata
+            .get("structuredContent")
+            .and_then(|v| v.get("distribution"))
+            .and_then(|v| v.as_bool()),
+        Some(true)
+    );
+
+    let converted = call_tool(
+        "hwp.convert",
+        serde_json::json!({ "path": path, "to": "hwpx" }),
+    )?;
+    assert_eq!(error_kind(&converted), Some("encrypted"));
+    let message = converted
+        .get("structuredContent")
+        .and_then(|v| v.get("error"))
+        .and_then(|v| v.get("message"))
+        .and_then(|v| v.as_str())
+        .unwrap_or_default();
+    assert!(message.contains("read-only"));
+    Ok(())
+}
+
+#[test]
+fn undecryptable_distribution_document_is_reported_as_encrypted()
+-> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("locked.hwp");
+    write_distribution_document(&file_path, false)?;
+
+    let extracted = call_tool(
+        "hwp.extract_text",
+        serde_json::json!({ "path": file_path.to_string_lossy() }),
+    )?;
+    assert_eq!(error_kind(&extracted), Some("encrypted"));
+    let message = extracted
+        .get("structuredContent")
+        .and_then(|v| v.get("error"))
+        .and_then(|v| v.get("message"))
+        .and_then(|v| v.as_str())
+        .unwrap_or_default();
+    assert!(message.starts_with("distribution document"));
+    Ok(())
+}
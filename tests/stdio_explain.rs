@@ -0,0 +1,116 @@
+use hwpers::HwpWriter;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+use tempfile::tempdir;
+
+fn call_tool(
+    arguments: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": { "name": "hwp.explain", "arguments": arguments }
+    });
+    writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+    stdin.flush()?;
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    let _ = child.kill();
+
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+    let result = response.get("result").cloned().expect("result present");
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    Ok(result
+        .get("structuredContent")
+        .cloned()
+        .expect("structuredContent"))
+}
+
+#[test]
+fn explain_names_missing_doc_info_stage() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("broken.hwp");
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("본문")?;
+    writer.save_to_file(&file_path)?;
+
+    let mut compound = cfb::open_rw(&file_path)?;
+    compound.remove_stream("/DocInfo")?;
+    compound.flush()?;
+    drop(compound);
+
+    let explanation = call_tool(serde_json::json!({ "path": file_path.to_string_lossy() }))?;
+    assert_eq!(explanation["ok"], false);
+    assert_eq!(explanation["detected_format"], "hwp");
+    assert_eq!(explanation["stage"], "doc info");
+    assert_eq!(explanation["kind"], "parse_failed");
+    assert_eq!(explanation["error_variant"], "NotFound");
+    assert!(
+        explanation["backend_error"]
+            .as_str()
+            .is_some_and(|e| e.contains("DocInfo")),
+        "{explanation}"
+    );
+    assert!(
+        explanation["remedy"]
+            .as_str()
+            .is_some_and(|r| r.contains("DocInfo")),
+        "{explanation}"
+    );
+    assert_eq!(
+        explanation["stages_passed"],
+        serde_json::json!(["format detection", "container open", "file header"])
+    );
+    Ok(())
+}
+
+#[test]
+fn explain_recognizes_hwp_v3_files() -> Result<(), Box<dyn std::error::Error>> {
+    let mut bytes = b"HWP Document File V3.00 \x1a\x01\x02\x03\x04\x05".to_vec();
+    bytes.resize(128, 0);
+    let dir = tempdir()?;
+    let file_path = dir.path().join("old.hwp");
+    std::fs::write(&file_path, bytes)?;
+
+    let explanation = call_tool(serde_json::json!({ "path": file_path.to_string_lossy() }))?;
+    assert_eq!(explanation["ok"], false);
+    assert_eq!(explanation["stage"], "format detection");
+    assert_eq!(explanation["kind"], "unsupported_format");
+    assert!(
+        explanation["remedy"]
+            .as_str()
+            .is_some_and(|r| r.contains("HWP v3")),
+        "{explanation}"
+    );
+    Ok(())
+}
+
+#[test]
+fn explain_reports_no_failure_for_valid_documents() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("ok.hwp");
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("정상 문서")?;
+    writer.save_to_file(&file_path)?;
+
+    let explanation = call_tool(serde_json::json!({
+        "path": file_path.to_string_lossy(),
+        "to": "hwpx"
+    }))?;
+    assert_eq!(explanation["ok"], true, "{explanation}");
+    assert_eq!(explanation["stage"], serde_json::Value::Null);
+    let passed = explanation["stages_passed"].as_array().expect("stages");
+    assert!(passed.iter().any(|s| s == "section 0"), "{passed:?}");
+    assert!(passed.iter().any(|s| s == "convert to hwpx"), "{passed:?}");
+    Ok(())
+}
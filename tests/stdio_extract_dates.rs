@@ -0,0 +1,68 @@
+use hwpers::HwpWriter;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+use tempfile::tempdir;
+
+#[test]
+fn extract_dates_normalizes_korean_and_western_dates() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("contract.hwp");
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("계약서")?;
+    writer.add_paragraph("계약일: 2024년 3월 5일")?;
+    writer.add_paragraph("Effective March 5, 2024 until 04/03/2025.")?;
+    writer.save_to_file(&file_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    for (id, date_order, last) in [(1, "mdy", "2025-04-03"), (2, "dmy", "2025-03-04")] {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.extract_dates",
+                "arguments": { "path": file_path.to_string_lossy(), "date_order": date_order }
+            }
+        });
+        writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+        stdin.flush()?;
+
+        let mut line = String::new();
+        stdout.read_line(&mut line)?;
+        let response: serde_json::Value = serde_json::from_str(line.trim())?;
+        let result = response.get("result").expect("result present");
+        assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+        assert_eq!(
+            result
+                .get("content")
+                .and_then(|v| v.get(0))
+                .and_then(|v| v.get("text"))
+                .and_then(|v| v.as_str()),
+            Some("found 3 dates")
+        );
+        let dates = result
+            .get("structuredContent")
+            .and_then(|v| v.get("dates"))
+            .and_then(|v| v.as_array())
+            .expect("dates array");
+        assert_eq!(dates[0]["raw"], "2024년 3월 5일");
+        assert_eq!(dates[0]["iso"], "2024-03-05");
+        assert_eq!(dates[0]["section_index"], 0);
+        assert_eq!(dates[0]["paragraph_index"], 1);
+        assert_eq!(dates[1]["raw"], "March 5, 2024");
+        assert_eq!(dates[1]["iso"], "2024-03-05");
+        assert_eq!(dates[2]["raw"], "04/03/2025");
+        assert_eq!(dates[2]["iso"], last);
+    }
+
+    let _ = child.kill();
+    Ok(())
+}
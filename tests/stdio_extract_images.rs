@@ -0,0 +1,160 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+use tempfile::tempdir;
+
+// 1x1 PNG
+const PNG_BASE64: &str =
+    "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAQAAAC1HAwCAAAAC0lEQVR42mP8/x8AAwMCAO6qVt0AAAAASUVORK5CYII=";
+
+fn send_request(
+    stdin: &mut impl Write,
+    stdout: &mut impl BufRead,
+    request: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let serialized = serde_json::to_string(&request)?;
+    writeln!(stdin, "{serialized}")?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    Ok(serde_json::from_str(line.trim())?)
+}
+
+fn call_tool(
+    stdin: &mut impl Write,
+    stdout: &mut impl BufRead,
+    name: &str,
+    arguments: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let response = send_request(
+        stdin,
+        stdout,
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": name, "arguments": arguments }
+        }),
+    )?;
+    let result = response.get("result").expect("result present");
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    Ok(result
+        .get("structuredContent")
+        .expect("structuredContent present")
+        .clone())
+}
+
+#[test]
+fn extract_images_returns_embedded_images() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let created = call_tool(
+        &mut stdin,
+        &mut stdout,
+        "hwp.create_rich_document",
+        serde_json::json!({
+            "to": "hwp",
+            "document": {
+                "blocks": [
+                    { "type": "paragraph", "text": "Before the image" },
+                    { "type": "image", "mimeType": "image/png", "data_base64": PNG_BASE64, "width_mm": 10, "height_mm": 10 }
+                ]
+            }
+        }),
+    )?;
+    let document = created
+        .get("base64")
+        .and_then(|v| v.as_str())
+        .expect("base64 present")
+        .to_string();
+
+    let inline = call_tool(
+        &mut stdin,
+        &mut stdout,
+        "hwp.extract_images",
+        serde_json::json!({ "base64": document }),
+    )?;
+    assert_eq!(inline.get("count").and_then(|v| v.as_u64()), Some(1));
+    assert_eq!(inline.get("skipped").and_then(|v| v.as_u64()), Some(0));
+    let image = &inline["images"][0];
+    assert_eq!(
+        image.get("mimeType").and_then(|v| v.as_str()),
+        Some("image/png")
+    );
+    let bytes = STANDARD.decode(
+        image
+            .get("base64")
+            .and_then(|v| v.as_str())
+            .expect("base64"),
+    )?;
+    assert_eq!(bytes, STANDARD.decode(PNG_BASE64)?);
+    assert_eq!(
+        image.get("bytes_len").and_then(|v| v.as_u64()),
+        Some(bytes.len() as u64)
+    );
+
+    let filtered = call_tool(
+        &mut stdin,
+        &mut stdout,
+        "hwp.extract_images",
+        serde_json::json!({ "base64": document, "min_bytes": 1024 }),
+    )?;
+    assert_eq!(filtered.get("count").and_then(|v| v.as_u64()), Some(0));
+    assert_eq!(filtered.get("skipped").and_then(|v| v.as_u64()), Some(1));
+
+    let dir = tempdir()?;
+    let resource = call_tool(
+        &mut stdin,
+        &mut stdout,
+        "hwp.extract_images",
+        serde_json::json!({
+            "base64": document,
+            "output": "resource",
+            "output_dir": dir.path().to_string_lossy()
+        }),
+    )?;
+    let image = &resource["images"][0];
+    assert!(image.get("base64").is_none());
+    let path = image.get("path").and_then(|v| v.as_str()).expect("path");
+    assert!(path.starts_with(dir.path().to_string_lossy().as_ref()));
+    assert_eq!(std::fs::read(path)?, STANDARD.decode(PNG_BASE64)?);
+
+    let hwpx = call_tool(
+        &mut stdin,
+        &mut stdout,
+        "hwp.create_rich_document",
+        serde_json::json!({
+            "to": "hwpx",
+            "document": {
+                "blocks": [
+                    { "type": "image", "mimeType": "image/png", "data_base64": PNG_BASE64, "width_mm": 10, "height_mm": 10 }
+                ]
+            }
+        }),
+    )?;
+    let hwpx_images = call_tool(
+        &mut stdin,
+        &mut stdout,
+        "hwp.extract_images",
+        serde_json::json!({ "base64": hwpx.get("base64").and_then(|v| v.as_str()).expect("base64") }),
+    )?;
+    assert_eq!(hwpx_images.get("count").and_then(|v| v.as_u64()), Some(1));
+    assert_eq!(
+        hwpx_images["images"][0]
+            .get("base64")
+            .and_then(|v| v.as_str()),
+        Some(PNG_BASE64)
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
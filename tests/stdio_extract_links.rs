@@ -0,0 +1,247 @@
+use flate2::Compression;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use hwpers::{HwpWriter, HwpxWriter};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use tempfile::tempdir;
+
+const PARA_HEADER: u32 = 0x10 + 50;
+const PARA_TEXT: u32 = 0x10 + 51;
+const CTRL_HEADER: u32 = 0x10 + 55;
+
+fn call_tool(
+    name: &str,
+    arguments: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": { "name": name, "arguments": arguments }
+    });
+    writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+    stdin.flush()?;
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    let _ = child.kill();
+
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+    Ok(response.get("result").cloned().expect("result present"))
+}
+
+fn links(result: &serde_json::Value) -> Vec<serde_json::Value> {
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    result
+        .get("structuredContent")
+        .and_then(|v| v.get("links"))
+        .and_then(|v| v.as_array())
+        .cloned()
+        .expect("links array")
+}
+
+struct RawRecord {
+    tag: u32,
+    level: u32,
+    data: Vec<u8>,
+}
+
+fn read_records(data: &[u8]) -> Vec<RawRecord> {
+    let mut records = Vec::new();
+    let mut pos = 0;
+    while pos + 4 <= data.len() {
+        let header = u32::from_le_bytes(data[pos..pos + 4].try_into().expect("4 bytes"));
+        pos += 4;
+        let mut size = (header >> 20) as usize;
+        if size == 0xFFF {
+            size = u32::from_le_bytes(data[pos..pos + 4].try_into().expect("4 bytes")) as usize;
+            pos += 4;
+        }
+        records.push(RawRecord {
+            tag: header & 0x3FF,
+            level: (header >> 10) & 0x3FF,
+            data: data[pos..pos + size].to_vec(),
+        });
+        pos += size;
+    }
+    records
+}
+
+fn write_records(records: &[RawRecord]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for record in records {
+        let size = record.data.len() as u32;
+        if size >= 0xFFF {
+            out.extend_from_slice(
+                &(record.tag | (record.level << 10) | (0xFFF << 20)).to_le_bytes(),
+            );
+            out.extend_from_slice(&size.to_le_bytes());
+        } else {
+            out.extend_from_slice(
+                &(record.tag | (record.level << 10) | (size << 20)).to_le_bytes(),
+            );
+        }
+        out.extend_from_slice(&record.data);
+    }
+    out
+}
+
+fn utf16(text: &str) -> Vec<u8> {
+    text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+}
+
+/// An eight-unit field control (`code`, the `%hlk` id, padding, `code`).
+fn field_control(code: u16) -> Vec<u8> {
+    let mut units = vec![code];
+    units.extend(
+        b"klh%"
+            .chunks(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]])),
+    );
+    units.extend([0, 0, 0, 0, code]);
+    units.into_iter().flat_map(u16::to_le_bytes).collect()
+}
+
+/// Writes an HWP whose paragraph "Visit Example now" carries a `%hlk` field around "Example",
+/// the way Hangul stores hyperlinks (hwpers cannot write them).
+fn write_hwp_with_hyperlink(path: &Path, command: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Intro")?;
+    writer.add_paragraph("Visit Example now")?;
+    writer.save_to_file(path)?;
+
+    let mut compound = cfb::open_rw(path)?;
+    let mut header = Vec::new();
+    compound
+        .open_stream("/FileHeader")?
+        .read_to_end(&mut header)?;
+    let compressed = u32::from_le_bytes(header[36..40].try_into()?) & 0x01 != 0;
+    let mut section = Vec::new();
+    compound
+        .open_stream("/BodyText/Section0")?
+        .read_to_end(&mut section)?;
+    if compressed {
+        let mut inflated = Vec::new();
+        DeflateDecoder::new(section.as_slice()).read_to_end(&mut inflated)?;
+        section = inflated;
+    }
+
+    let mut records = read_records(&section);
+    let text_idx = records
+        .iter()
+        .position(|r| r.tag == PARA_TEXT && r.data.starts_with(&utf16("Visit Example")))
+        .expect("paragraph text record");
+    let mut text = utf16("Visit ");
+    text.extend(field_control(0x03));
+    text.extend(utf16("Example"));
+    text.extend(field_control(0x04));
+    text.extend(utf16(" now\r"));
+    records[text_idx].data = text;
+
+    let header_idx = records[..text_idx]
+        .iter()
+        .rposition(|r| r.tag == PARA_HEADER)
+        .expect("paragraph header");
+    let nchars = u32::from_le_bytes(records[header_idx].data[0..4].try_into()?) + 16;
+    records[header_idx].data[0..4].copy_from_slice(&nchars.to_le_bytes());
+    let para_level = records[header_idx].level;
+
+    let mut ctrl = b"klh%".to_vec();
+    ctrl.extend_from_slice(&0u32.to_le_bytes());
+    ctrl.push(0);
+    ctrl.extend_from_slice(&(command.encode_utf16().count() as u16).to_le_bytes());
+    ctrl.extend(utf16(command));
+    ctrl.extend_from_slice(&1u32.to_le_bytes());
+    let insert_at = records[text_idx + 1..]
+        .iter()
+        .position(|r| r.level <= para_level)
+        .map(|offset| text_idx + 1 + offset)
+        .unwrap_or(records.len());
+    records.insert(
+        insert_at,
+        RawRecord {
+            tag: CTRL_HEADER,
+            level: para_level + 1,
+            data: ctrl,
+        },
+    );
+
+    let mut section = write_records(&records);
+    if compressed {
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&section)?;
+        section = encoder.finish()?;
+    }
+    compound
+        .create_stream("/BodyText/Section0")?
+        .write_all(&section)?;
+    compound.flush()?;
+    Ok(())
+}
+
+#[test]
+fn extract_links_reads_hwp_hyperlink_fields() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("links.hwp");
+    write_hwp_with_hyperlink(&file_path, "https\\://example.com/a;1;0;0;")?;
+
+    let result = call_tool(
+        "hwp.extract_links",
+        serde_json::json!({ "path": file_path.to_string_lossy() }),
+    )?;
+    let links = links(&result);
+    assert_eq!(links.len(), 1, "{links:?}");
+    assert_eq!(links[0]["section_index"], 0);
+    assert_eq!(links[0]["paragraph_index"], 1);
+    assert_eq!(links[0]["text"], "Example");
+    assert_eq!(links[0]["target"], "https://example.com/a");
+    assert_eq!(links[0]["kind"], "url");
+    Ok(())
+}
+
+#[test]
+fn extract_links_reads_hwpx_hyperlinks() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("links.hwpx");
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("Intro")?;
+    writer.add_hyperlink("Docs", "https://example.com/docs?a=1&b=2")?;
+    writer.save_to_file(&file_path)?;
+
+    let result = call_tool(
+        "hwp.extract_links",
+        serde_json::json!({ "path": file_path.to_string_lossy() }),
+    )?;
+    let links = links(&result);
+    assert_eq!(links.len(), 1, "{links:?}");
+    assert_eq!(links[0]["paragraph_index"], 1);
+    assert_eq!(links[0]["text"], "Docs");
+    assert_eq!(links[0]["target"], "https://example.com/docs?a=1&b=2");
+    Ok(())
+}
+
+#[test]
+fn extract_links_returns_empty_list_without_links() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("plain.hwp");
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("No links here")?;
+    writer.save_to_file(&file_path)?;
+
+    let result = call_tool(
+        "hwp.extract_links",
+        serde_json::json!({ "path": file_path.to_string_lossy() }),
+    )?;
+    assert!(links(&result).is_empty());
+    Ok(())
+}
@@ -0,0 +1,212 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use flate2::Compression;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use std::io::{BufRead, BufReader, Cursor, Read, Write};
+use std::process::{Command, Stdio};
+use zip::write::SimpleFileOptions;
+
+fn send_request(
+    stdin: &mut impl Write,
+    stdout: &mut impl BufRead,
+    request: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let serialized = serde_json::to_string(&request)?;
+    writeln!(stdin, "{serialized}")?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    Ok(serde_json::from_str(line.trim())?)
+}
+
+fn call_extract_notes(bytes: &[u8]) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_notes",
+            "arguments": { "base64": STANDARD.encode(bytes) }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, request)?;
+
+    drop(stdin);
+    let _ = child.wait();
+    Ok(response)
+}
+
+fn note_xml(tag: &str, body: &str) -> String {
+    format!(
+        "<hp:{tag} number=\"1\"><hp:subList><hp:p><hp:run><hp:t>{body}</hp:t></hp:run></hp:p></hp:subList></hp:{tag}>"
+    )
+}
+
+/// The HWPX writer has no notes API, so the note elements are spliced into the run that
+/// holds the anchor text.
+fn hwpx_with_notes() -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut writer = hwpers::HwpxWriter::new();
+    writer.add_paragraph("Intro")?;
+    writer.add_paragraph("Anchored here")?;
+    writer.add_paragraph("Closing words")?;
+    let bytes = writer.to_bytes()?;
+
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))?;
+    let mut output = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index)?;
+        let name = file.name().to_string();
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)?;
+        if name == "Contents/section0.xml" {
+            let mut xml = String::from_utf8(data)?;
+            let anchor = "Anchored here</hp:t>";
+            let at = xml.find(anchor).expect("anchor text") + anchor.len();
+            xml.insert_str(at, &note_xml("footNote", "First &amp; only footnote"));
+            let closing = "Closing words</hp:t>";
+            let at = xml.find(closing).expect("closing text") + closing.len();
+            xml.insert_str(at, &note_xml("endNote", "The endnote"));
+            data = xml.into_bytes();
+        }
+        output.start_file(name, SimpleFileOptions::default())?;
+        output.write_all(&data)?;
+    }
+    Ok(output.finish()?.into_inner())
+}
+
+fn record(tag: u16, level: u32, data: &[u8]) -> Vec<u8> {
+    let header = u32::from(tag) | (level << 10) | ((data.len() as u32) << 20);
+    let mut bytes = header.to_le_bytes().to_vec();
+    bytes.extend_from_slice(data);
+    bytes
+}
+
+fn utf16(text: &str) -> Vec<u8> {
+    text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+}
+
+/// Appends a paragraph carrying a footnote control to BodyText/Section0 of a writer document.
+fn hwp_with_footnote() -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut writer = hwpers::HwpWriter::new();
+    writer.add_paragraph("Intro")?;
+    let bytes = writer.to_bytes()?;
+
+    let mut compound = cfb::CompoundFile::open(Cursor::new(bytes))?;
+    let mut file_header = Vec::new();
+    compound
+        .open_stream("/FileHeader")?
+        .read_to_end(&mut file_header)?;
+    let compressed = file_header[36] & 0x01 != 0;
+    let mut stored = Vec::new();
+    compound
+        .open_stream("/BodyText/Section0")?
+        .read_to_end(&mut stored)?;
+    let mut section = Vec::new();
+    if compressed {
+        DeflateDecoder::new(stored.as_slice()).read_to_end(&mut section)?;
+    } else {
+        section = stored;
+    }
+
+    let mut para_header = vec![0u8; 24];
+    para_header[0..4].copy_from_slice(&(0x8000_0000u32 | 5).to_le_bytes());
+    let mut anchor_text = utf16("Note");
+    // Extended control char 17 (footnote/endnote) occupies eight code units.
+    anchor_text.extend(std::iter::repeat_n([17u8, 0], 8).flatten());
+    anchor_text.extend(utf16("\r"));
+    let mut ctrl_header = b"  nf".to_vec();
+    ctrl_header.extend_from_slice(&[0u8; 8]);
+    let mut body_header = vec![0u8; 24];
+    body_header[0..4].copy_from_slice(&(0x8000_0000u32 | 10).to_le_bytes());
+
+    section.extend(record(0x10 + 50, 0, &para_header));
+    section.extend(record(0x10 + 51, 1, &anchor_text));
+    section.extend(record(0x10 + 55, 1, &ctrl_header));
+    section.extend(record(0x10 + 56, 2, &[0u8; 8]));
+    section.extend(record(0x10 + 50, 2, &body_header));
+    section.extend(record(0x10 + 51, 3, &utf16("Body text\r")));
+
+    if compressed {
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&section)?;
+        section = encoder.finish()?;
+    }
+    let mut stream = compound.create_stream("/BodyText/Section0")?;
+    stream.write_all(&section)?;
+    drop(stream);
+    compound.flush()?;
+    Ok(compound.into_inner().into_inner())
+}
+
+#[test]
+fn extract_notes_returns_hwpx_footnotes_and_endnotes() -> Result<(), Box<dyn std::error::Error>> {
+    let response = call_extract_notes(&hwpx_with_notes()?)?;
+    let structured = response
+        .get("result")
+        .and_then(|value| value.get("structuredContent"))
+        .expect("structured content");
+
+    let footnotes = structured["footnotes"].as_array().expect("footnotes");
+    assert_eq!(footnotes.len(), 1, "{structured}");
+    assert_eq!(footnotes[0]["number"], 1);
+    assert_eq!(footnotes[0]["text"], "First & only footnote");
+    assert_eq!(footnotes[0]["anchor"]["section_index"], 0);
+    assert_eq!(footnotes[0]["anchor"]["paragraph_index"], 1);
+    assert_eq!(footnotes[0]["anchor"]["text"], "Anchored here");
+
+    let endnotes = structured["endnotes"].as_array().expect("endnotes");
+    assert_eq!(endnotes.len(), 1, "{structured}");
+    assert_eq!(endnotes[0]["text"], "The endnote");
+    assert_eq!(endnotes[0]["anchor"]["paragraph_index"], 2);
+    assert_eq!(endnotes[0]["anchor"]["text"], "Closing words");
+    Ok(())
+}
+
+#[test]
+fn extract_notes_reads_hwp_footnote_controls() -> Result<(), Box<dyn std::error::Error>> {
+    let response = call_extract_notes(&hwp_with_footnote()?)?;
+    let structured = response
+        .get("result")
+        .and_then(|value| value.get("structuredContent"))
+        .expect("structured content");
+
+    let footnotes = structured["footnotes"].as_array().expect("footnotes");
+    assert_eq!(footnotes.len(), 1, "{structured}");
+    assert_eq!(footnotes[0]["number"], 1);
+    assert_eq!(footnotes[0]["text"], "Body text");
+    assert_eq!(footnotes[0]["anchor"]["text"], "Note");
+    assert_eq!(footnotes[0]["anchor"]["section_index"], 0);
+    assert!(
+        structured["endnotes"]
+            .as_array()
+            .is_some_and(|notes| notes.is_empty())
+    );
+    Ok(())
+}
+
+#[test]
+fn extract_notes_returns_empty_lists_without_notes() -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = hwpers::HwpWriter::new();
+    writer.add_paragraph("No notes here")?;
+    let response = call_extract_notes(&writer.to_bytes()?)?;
+    let structured = response
+        .get("result")
+        .and_then(|value| value.get("structuredContent"))
+        .expect("structured content");
+
+    assert_eq!(response["result"]["isError"], false);
+    assert_eq!(structured["footnotes"], serde_json::json!([]));
+    assert_eq!(structured["endnotes"], serde_json::json!([]));
+    Ok(())
+}
@@ -0,0 +1,157 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use hwpers::HwpWriter;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+use tempfile::tempdir;
+
+const PNG_1X1_BASE64: &str = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNkYPhfDwAChwGA60e6kgAAAABJRU5ErkJggg==";
+
+fn send_request(
+    stdin: &mut impl Write,
+    stdout: &mut impl BufRead,
+    request: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let serialized = serde_json::to_string(&request)?;
+    writeln!(stdin, "{serialized}")?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    Ok(serde_json::from_str(line.trim())?)
+}
+
+#[test]
+fn extract_preview_returns_embedded_image() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("preview.hwp");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Preview sample")?;
+    writer.save_to_file(&file_path)?;
+
+    let png = STANDARD.decode(PNG_1X1_BASE64)?;
+    let mut compound = cfb::open_rw(&file_path)?;
+    let mut stream = compound.create_stream("/PrvImage")?;
+    stream.write_all(&png)?;
+    drop(stream);
+    compound.flush()?;
+    drop(compound);
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_preview",
+            "arguments": {
+                "path": file_path.to_string_lossy(),
+                "fallback_render": true
+            }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, request)?;
+    let result = response.get("result").expect("result present");
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+
+    let structured = result
+        .get("structuredContent")
+        .expect("structuredContent present");
+    assert_eq!(
+        structured.get("found").and_then(|v| v.as_bool()),
+        Some(true)
+    );
+    assert_eq!(
+        structured.get("source").and_then(|v| v.as_str()),
+        Some("embedded")
+    );
+    assert_eq!(
+        structured.get("mimeType").and_then(|v| v.as_str()),
+        Some("image/png")
+    );
+    assert_eq!(
+        structured.get("base64").and_then(|v| v.as_str()),
+        Some(PNG_1X1_BASE64)
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn extract_preview_without_embedded_image() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("plain.hwp");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("No preview here")?;
+    writer.save_to_file(&file_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_preview",
+            "arguments": { "path": file_path.to_string_lossy() }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, request)?;
+    let result = response.get("result").expect("result present");
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    assert_eq!(
+        result
+            .get("structuredContent")
+            .and_then(|v| v.get("found"))
+            .and_then(|v| v.as_bool()),
+        Some(false)
+    );
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 3,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_preview",
+            "arguments": {
+                "path": file_path.to_string_lossy(),
+                "fallback_render": true
+            }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, request)?;
+    let result = response.get("result").expect("result present");
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    let structured = result
+        .get("structuredContent")
+        .expect("structuredContent present");
+    assert_eq!(
+        structured.get("source").and_then(|v| v.as_str()),
+        Some("rendered")
+    );
+    assert_eq!(
+        structured.get("mimeType").and_then(|v| v.as_str()),
+        Some("image/svg+xml")
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
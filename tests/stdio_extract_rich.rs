@@ -0,0 +1,613 @@
+use flate2::Compression;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use hwpers::HwpWriter;
+use std::io::{BufRead, BufReader, Cursor, Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use tempfile::tempdir;
+use zip::write::SimpleFileOptions;
+
+const PARA_HEADER: u32 = 0x10 + 50;
+const PARA_TEXT: u32 = 0x10 + 51;
+const CTRL_HEADER: u32 = 0x10 + 55;
+const LIST_HEADER: u32 = 0x10 + 56;
+const TABLE: u32 = 0x10 + 61;
+const SHAPE_COMPONENT_PICTURE: u32 = 0x10 + 69;
+
+// 1x1 PNG
+const PNG_BASE64: &str =
+    "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAQAAAC1HAwCAAAAC0lEQVR42mP8/x8AAwMCAO6qVt0AAAAASUVORK5CYII=";
+
+fn call_tool(
+    name: &str,
+    arguments: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": { "name": name, "arguments": arguments }
+    });
+    writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+    stdin.flush()?;
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    let _ = child.kill();
+
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+    Ok(response.get("result").cloned().expect("result present"))
+}
+
+fn blocks(result: &serde_json::Value) -> Vec<serde_json::Value> {
+    assert_eq!(
+        result.get("isError").and_then(|v| v.as_bool()),
+        Some(false),
+        "{result}"
+    );
+    result["structuredContent"]["blocks"]
+        .as_array()
+        .cloned()
+        .expect("blocks array")
+}
+
+struct RawRecord {
+    tag: u32,
+    level: u32,
+    data: Vec<u8>,
+}
+
+fn read_records(data: &[u8]) -> Vec<RawRecord> {
+    let mut records = Vec::new();
+    let mut pos = 0;
+    while pos + 4 <= data.len() {
+        let header = u32::from_le_bytes(data[pos..pos + 4].try_into().expect("4 bytes"));
+        pos += 4;
+        let mut size = (header >> 20) as usize;
+        if size == 0xFFF {
+            size = u32::from_le_bytes(data[pos..pos + 4].try_into().expect("4 bytes")) as usize;
+            pos += 4;
+        }
+        records.push(RawRecord {
+            tag: header & 0x3FF,
+            level: (header >> 10) & 0x3FF,
+            data: data[pos..pos + size].to_vec(),
+        });
+        pos += size;
+    }
+    records
+}
+
+fn write_records(records: &[RawRecord]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for record in records {
+        let size = record.data.len() as u32;
+        if size >= 0xFFF {
+            out.extend_from_slice(
+                &(record.tag | (record.level << 10) | (0xFFF << 20)).to_le_bytes(),
+            );
+            out.extend_from_slice(&size.to_le_bytes());
+        } else {
+            out.extend_from_slice(
+                &(record.tag | (record.level << 10) | (size << 20)).to_le_bytes(),
+            );
+        }
+        out.extend_from_slice(&record.data);
+    }
+    out
+}
+
+/// A TABLE record body: properties, row and column counts, spacing, margins, row sizes and
+/// the border fill id.
+fn table_record(rows: u16, cols: u16) -> Vec<u8> {
+    let mut data = 0u32.to_le_bytes().to_vec();
+    data.extend_from_slice(&rows.to_le_bytes());
+    data.extend_from_slice(&cols.to_le_bytes());
+    data.extend_from_slice(&[0; 10]);
+    for _ in 0..rows {
+        data.extend_from_slice(&cols.to_le_bytes());
+    }
+    data.extend_from_slice(&1u16.to_le_bytes());
+    data
+}
+
+/// A CTRL_HEADER body for control id `id`, given in its on-disk (reversed) byte order.
+fn ctrl_header(id: &[u8; 4]) -> Vec<u8> {
+    let mut data = id.to_vec();
+    data.extend_from_slice(&[0; 8]);
+    data
+}
+
+/// A SHAPE_COMPONENT_PICTURE body pointing at BinItem `bin_id`.
+fn picture_record(bin_id: u16) -> Vec<u8> {
+    let mut data = vec![0; 71];
+    data.extend_from_slice(&bin_id.to_le_bytes());
+    data
+}
+
+/// A table cell's LIST_HEADER body: paragraph count, list properties, then the cell's address,
+/// spans, size, margins and border fill id.
+fn cell_record(row: u16, col: u16, row_span: u16, col_span: u16, paragraphs: u16) -> Vec<u8> {
+    let mut data = paragraphs.to_le_bytes().to_vec();
+    data.extend_from_slice(&[0; 6]);
+    for value in [col, row, col_span, row_span] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(&[0; 18]);
+    data
+}
+
+/// A cell's LIST_HEADER body and the number of paragraphs it holds.
+type Cell = (Vec<u8>, usize);
+
+/// Records to attach to an anchor paragraph: a CTRL_HEADER and the record describing the
+/// control, as `(tag, data)` pairs, and for a table each cell's LIST_HEADER body with the
+/// number of the following paragraphs that belong to it.
+struct Control {
+    records: [(u32, Vec<u8>); 2],
+    cells: Vec<Cell>,
+}
+
+fn control(records: [(u32, Vec<u8>); 2]) -> Control {
+    Control {
+        records,
+        cells: Vec::new(),
+    }
+}
+
+/// A `rows` x `cols` table control whose cells are `(row, col, row_span, col_span, paragraphs)`.
+fn table_control(rows: u16, cols: u16, cells: &[(u16, u16, u16, u16, u16)]) -> Control {
+    Control {
+        records: [
+            (CTRL_HEADER, ctrl_header(b" lbt")),
+            (TABLE, table_record(rows, cols)),
+        ],
+        cells: cells
+            .iter()
+            .map(|&(row, col, row_span, col_span, paragraphs)| {
+                (
+                    cell_record(row, col, row_span, col_span, paragraphs),
+                    usize::from(paragraphs),
+                )
+            })
+            .collect(),
+    }
+}
+
+/// Attaches control records to the anchor paragraphs hwpers writes for tables and images (the
+/// empty paragraphs), in order, the way Hangul stores them: a CTRL_HEADER one level below the
+/// paragraph and its body record one level below that. A table's cell paragraphs are moved
+/// under the control, each cell's behind its LIST_HEADER.
+fn add_controls(path: &Path, controls: Vec<Control>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut compound = cfb::open_rw(path)?;
+    let mut header = Vec::new();
+    compound
+        .open_stream("/FileHeader")?
+        .read_to_end(&mut header)?;
+    let compressed = u32::from_le_bytes(header[36..40].try_into()?) & 0x01 != 0;
+    let mut section = Vec::new();
+    compound
+        .open_stream("/BodyText/Section0")?
+        .read_to_end(&mut section)?;
+    if compressed {
+        let mut inflated = Vec::new();
+        DeflateDecoder::new(section.as_slice()).read_to_end(&mut inflated)?;
+        section = inflated;
+    }
+
+    let records = read_records(&section);
+    let mut patched = Vec::with_capacity(records.len());
+    let mut controls = controls.into_iter();
+    let mut pending: Option<(u32, Control)> = None;
+    // Base level, cells still to open and paragraphs left in the current cell of the table
+    // whose paragraphs are being moved.
+    let mut table: Option<(u32, std::vec::IntoIter<Cell>, usize)> = None;
+    for mut record in records {
+        if record.tag == PARA_HEADER
+            && let Some((level, control)) = pending.take()
+        {
+            let [(ctrl_tag, ctrl), (body_tag, body)] = control.records;
+            patched.push(RawRecord {
+                tag: ctrl_tag,
+                level: level + 1,
+                data: ctrl,
+            });
+            patched.push(RawRecord {
+                tag: body_tag,
+                level: level + 2,
+                data: body,
+            });
+            if !control.cells.is_empty() {
+                table = Some((level, control.cells.into_iter(), 0));
+            }
+        }
+        if record.tag == PARA_HEADER
+            && let Some((level, cells, left)) = table.as_mut()
+            && record.level == *level
+        {
+            if *left == 0 {
+                match cells.next() {
+                    Some((list_header, paragraphs)) => {
+                        patched.push(RawRecord {
+                            tag: LIST_HEADER,
+                            level: *level + 2,
+                            data: list_header,
+                        });
+                        *left = paragraphs;
+                    }
+                    None => table = None,
+                }
+            }
+            if let Some((_, _, left)) = table.as_mut() {
+                *left -= 1;
+            }
+        }
+        if table.is_some() {
+            record.level += 2;
+        }
+        if record.tag == PARA_TEXT && record.data == [0x0d, 0x00] {
+            let level = patched
+                .iter()
+                .rev()
+                .find(|r: &&RawRecord| r.tag == PARA_HEADER)
+                .map(|r| r.level)
+                .unwrap_or(0);
+            pending = controls.next().map(|control| (level, control));
+        }
+        patched.push(record);
+    }
+    assert!(
+        pending.is_none(),
+        "anchors must be followed by another paragraph"
+    );
+    assert!(
+        table.is_none_or(|(_, mut cells, left)| left == 0 && cells.next().is_none()),
+        "tables must be followed by another paragraph"
+    );
+
+    let mut section = write_records(&patched);
+    if compressed {
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&section)?;
+        section = encoder.finish()?;
+    }
+    compound
+        .create_stream("/BodyText/Section0")?
+        .write_all(&section)?;
+    compound.flush()?;
+    Ok(())
+}
+
+#[test]
+fn extract_rich_uses_declared_table_columns() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("tables.hwp");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Before")?;
+    writer.add_simple_table(&[vec!["a", "b", "c", "d"]])?;
+    writer.add_paragraph("Between")?;
+    writer.add_simple_table(&[vec!["1"], vec!["2"], vec!["3"], vec!["4"]])?;
+    writer.add_paragraph("After")?;
+    writer.save_to_file(&file_path)?;
+    add_controls(
+        &file_path,
+        vec![
+            table_control(
+                1,
+                4,
+                &[
+                    (0, 0, 1, 1, 1),
+                    (0, 1, 1, 1, 1),
+                    (0, 2, 1, 1, 1),
+                    (0, 3, 1, 1, 1),
+                ],
+            ),
+            table_control(
+                4,
+                1,
+                &[
+                    (0, 0, 1, 1, 1),
+                    (1, 0, 1, 1, 1),
+                    (2, 0, 1, 1, 1),
+                    (3, 0, 1, 1, 1),
+                ],
+            ),
+        ],
+    )?;
+
+    let result = call_tool(
+        "hwp.extract_rich",
+        serde_json::json!({ "path": file_path.to_string_lossy() }),
+    )?;
+    let tables: Vec<serde_json::Value> = blocks(&result)
+        .into_iter()
+        .filter(|block| block["type"] == "table")
+        .collect();
+    assert_eq!(tables.len(), 2, "{result}");
+    assert_eq!(tables[0]["rows"], serde_json::json!([["a", "b", "c", "d"]]));
+    assert_eq!(
+        tables[1]["rows"],
+        serde_json::json!([["1"], ["2"], ["3"], ["4"]])
+    );
+    for table in &tables {
+        assert_eq!(table["inferred"], true);
+        assert_eq!(table["cells_count"], 4);
+    }
+
+    let texts: Vec<String> = blocks(&result)
+        .iter()
+        .filter(|block| block["type"] == "paragraph")
+        .filter_map(|block| block["text"].as_str())
+        .map(|text| text.trim().to_string())
+        .collect();
+    assert!(texts.contains(&"Between".to_string()), "{texts:?}");
+    Ok(())
+}
+
+#[test]
+fn extract_rich_takes_cell_paragraphs_from_declared_cells() -> Result<(), Box<dyn std::error::Error>>
+{
+    let dir = tempdir()?;
+    let file_path = dir.path().join("cells.hwp");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Before")?;
+    writer.add_simple_table(&[vec!["a", "b", "c"]])?;
+    writer.add_paragraph("Between")?;
+    writer.add_simple_table(&[vec!["x", "y", "z"]])?;
+    writer.add_paragraph("After")?;
+    writer.save_to_file(&file_path)?;
+    add_controls(
+        &file_path,
+        vec![
+            // 1x2 whose first cell holds two paragraphs.
+            table_control(1, 2, &[(0, 0, 1, 1, 2), (0, 1, 1, 1, 1)]),
+            // 2x2 whose first row is one merged cell.
+            table_control(2, 2, &[(0, 0, 1, 2, 1), (1, 0, 1, 1, 1), (1, 1, 1, 1, 1)]),
+        ],
+    )?;
+
+    let result = call_tool(
+        "hwp.extract_rich",
+        serde_json::json!({ "path": file_path.to_string_lossy() }),
+    )?;
+    let blocks = blocks(&result);
+    let tables: Vec<&serde_json::Value> = blocks
+        .iter()
+        .filter(|block| block["type"] == "table")
+        .collect();
+    assert_eq!(tables.len(), 2, "{result}");
+    assert_eq!(tables[0]["rows"], serde_json::json!([["a\nb", "c"]]));
+    assert_eq!(tables[0]["cells_count"], 2);
+    assert_eq!(
+        tables[1]["rows"],
+        serde_json::json!([["x", ""], ["y", "z"]])
+    );
+    assert_eq!(
+        tables[1]["spans"],
+        serde_json::json!([{ "row": 0, "col": 0, "row_span": 1, "col_span": 2 }])
+    );
+
+    let texts: Vec<&str> = blocks
+        .iter()
+        .filter(|block| block["type"] == "paragraph")
+        .filter_map(|block| block["text"].as_str())
+        .collect();
+    assert_eq!(texts, ["Before", "Between", "After"], "{result}");
+    Ok(())
+}
+
+fn captioned_images(result: &serde_json::Value) -> Vec<(String, u64)> {
+    blocks(result)
+        .iter()
+        .filter(|block| block["type"] == "image")
+        .map(|block| {
+            (
+                block["caption"].as_str().unwrap_or_default().to_string(),
+                block["bin_id"].as_u64().expect("bin_id present"),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn extract_rich_pairs_captions_by_referenced_hwp_bin_id() -> Result<(), Box<dyn std::error::Error>>
+{
+    use base64::Engine;
+    use hwpers::writer::style::{ImageAlign, ImageFormat, ImageOptions};
+
+    let dir = tempdir()?;
+    let file_path = dir.path().join("images.hwp");
+    let png = base64::engine::general_purpose::STANDARD.decode(PNG_BASE64)?;
+    let options = |caption: &str| ImageOptions {
+        width: Some(10),
+        height: Some(10),
+        alignment: ImageAlign::Left,
+        wrap_text: false,
+        caption: Some(caption.to_string()),
+    };
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Intro")?;
+    writer.add_image_with_options(&png, ImageFormat::Png, &options("first"))?;
+    writer.add_image_with_options(&png, ImageFormat::Png, &options("second"))?;
+    writer.save_to_file(&file_path)?;
+    // The first picture shows BIN0002 and the second BIN0001.
+    add_controls(
+        &file_path,
+        vec![
+            control([
+                (CTRL_HEADER, ctrl_header(b" osg")),
+                (SHAPE_COMPONENT_PICTURE, picture_record(2)),
+            ]),
+            control([
+                (CTRL_HEADER, ctrl_header(b" osg")),
+                (SHAPE_COMPONENT_PICTURE, picture_record(1)),
+            ]),
+        ],
+    )?;
+
+    let result = call_tool(
+        "hwp.extract_rich",
+        serde_json::json!({ "path": file_path.to_string_lossy() }),
+    )?;
+    assert_eq!(
+        captioned_images(&result),
+        vec![("first".to_string(), 2), ("second".to_string(), 1)],
+        "{result}"
+    );
+    Ok(())
+}
+
+#[test]
+fn extract_rich_pairs_captions_by_referenced_hwpx_bin_id() -> Result<(), Box<dyn std::error::Error>>
+{
+    use base64::Engine;
+
+    let dir = tempdir()?;
+    let file_path = dir.path().join("images.hwpx");
+    let png = base64::engine::general_purpose::STANDARD.decode(PNG_BASE64)?;
+
+    let mut writer = hwpers::HwpxWriter::new();
+    writer.add_image(hwpers::hwpx::HwpxImage::from_bytes(png.clone()).expect("png"))?;
+    writer.add_paragraph("그림: first")?;
+    writer.add_image(hwpers::hwpx::HwpxImage::from_bytes(png).expect("png"))?;
+    writer.add_paragraph("그림: second")?;
+    let bytes = writer.to_bytes()?;
+
+    // Swap the picture references so the first picture shows image2 and the second image1.
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))?;
+    let mut output = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index)?;
+        let name = file.name().to_string();
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)?;
+        if name == "Contents/section0.xml" {
+            let xml = String::from_utf8(data)?
+                .replace("binaryItemIDRef=\"IMG1\"", "binaryItemIDRef=\"SWAP\"")
+                .replace("binaryItemIDRef=\"IMG2\"", "binaryItemIDRef=\"IMG1\"")
+                .replace("binaryItemIDRef=\"SWAP\"", "binaryItemIDRef=\"IMG2\"");
+            data = xml.into_bytes();
+        }
+        output.start_file(name, SimpleFileOptions::default())?;
+        output.write_all(&data)?;
+    }
+    std::fs::write(&file_path, output.finish()?.into_inner())?;
+
+    let result = call_tool(
+        "hwp.extract_rich",
+        serde_json::json!({ "path": file_path.to_string_lossy() }),
+    )?;
+    assert_eq!(
+        captioned_images(&result),
+        vec![("first".to_string(), 2), ("second".to_string(), 1)],
+        "{result}"
+    );
+    Ok(())
+}
+
+#[test]
+fn extract_rich_keeps_prose_after_blank_line_as_paragraphs()
+-> Result<(), Box<dyn std::error::Error>> {
+    let prose = [
+        "See you on Monday.",
+        "Bring the slides.",
+        "Call Jane first.",
+        "Thanks everyone.",
+        "Lunch is at noon.",
+        "Room 4B is booked.",
+    ];
+    // Without a table control or delimiters, even counts that would fill a 2x2 or 2x3 grid
+    // stay paragraphs.
+    for count in [2, 4, 6] {
+        let dir = tempdir()?;
+        let file_path = dir.path().join("prose.hwp");
+        let mut writer = HwpWriter::new();
+        writer.add_paragraph("Meeting notes")?;
+        writer.add_paragraph("")?;
+        for line in &prose[..count] {
+            writer.add_paragraph(line)?;
+        }
+        writer.save_to_file(&file_path)?;
+
+        let result = call_tool(
+            "hwp.extract_rich",
+            serde_json::json!({ "path": file_path.to_string_lossy() }),
+        )?;
+        let blocks = blocks(&result);
+        assert!(
+            blocks.iter().all(|block| block["type"] == "paragraph"),
+            "{result}"
+        );
+        let texts: Vec<&str> = blocks
+            .iter()
+            .filter_map(|block| block["text"].as_str().map(str::trim))
+            .collect();
+        for line in &prose[..count] {
+            assert!(texts.contains(line), "{result}");
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn extract_rich_strips_paragraph_line_endings() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("endings.hwp");
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Intro")?;
+    writer.add_paragraph("Ends with CRLF\r\n")?;
+    writer.add_simple_table(&[vec!["a", "b"], vec!["c", "d"]])?;
+    writer.add_paragraph("Outro")?;
+    writer.save_to_file(&file_path)?;
+    add_controls(
+        &file_path,
+        vec![table_control(
+            2,
+            2,
+            &[
+                (0, 0, 1, 1, 1),
+                (0, 1, 1, 1, 1),
+                (1, 0, 1, 1, 1),
+                (1, 1, 1, 1, 1),
+            ],
+        )],
+    )?;
+
+    let result = call_tool(
+        "hwp.extract_rich",
+        serde_json::json!({ "path": file_path.to_string_lossy() }),
+    )?;
+    let blocks = blocks(&result);
+    let texts: Vec<&str> = blocks
+        .iter()
+        .filter_map(|block| block["text"].as_str())
+        .collect();
+    assert!(texts.contains(&"Ends with CRLF"), "{result}");
+    assert!(
+        texts.iter().all(|text| !text.ends_with(['\r', '\n'])),
+        "{result}"
+    );
+
+    let table = blocks
+        .iter()
+        .find(|block| block["type"] == "table")
+        .expect("table block");
+    assert_eq!(
+        table["rows"],
+        serde_json::json!([["a", "b"], ["c", "d"]]),
+        "{result}"
+    );
+    Ok(())
+}
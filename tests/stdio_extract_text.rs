@@ -1,7 +1,11 @@
+use flate2::Compression;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
 use hwpers::HwpWriter;
-use std::io::{BufRead, BufReader, Write};
+use std::io::{BufRead, BufReader, Cursor, Read, Write};
 use std::process::{Command, Stdio};
 use tempfile::tempdir;
+use zip::write::SimpleFileOptions;
 
 #[test]
 fn extract_text_round_trip() -> Result<(), Box<dyn std::error::Error>> {
@@ -55,3 +59,923 @@ fn extract_text_round_trip() -> Result<(), Box<dyn std::error::Error>> {
     let _ = child.kill();
     Ok(())
 }
+
+#[test]
+fn extract_text_footnotes_option() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("sample.hwp");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Body text")?;
+    writer.save_to_file(&file_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    for (id, mode) in [(4, "inline"), (5, "endnotes"), (6, "omit")] {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.extract_text",
+                "arguments": {
+                    "path": file_path.to_string_lossy(),
+                    "footnotes": mode
+                }
+            }
+        });
+        writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+        stdin.flush()?;
+
+        let mut line = String::new();
+        stdout.read_line(&mut line)?;
+        let response: serde_json::Value = serde_json::from_str(line.trim())?;
+        let structured = response
+            .get("result")
+            .and_then(|value| value.get("structuredContent"))
+            .expect("structuredContent present");
+        let text = structured
+            .get("text")
+            .and_then(|value| value.as_str())
+            .expect("text present");
+        assert!(text.contains("Body text"));
+    }
+
+    let invalid_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 7,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_text",
+            "arguments": {
+                "path": file_path.to_string_lossy(),
+                "footnotes": "sidebar"
+            }
+        }
+    });
+    writeln!(stdin, "{}", serde_json::to_string(&invalid_request)?)?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+    let kind = response
+        .get("result")
+        .and_then(|value| value.get("structuredContent"))
+        .and_then(|value| value.get("error"))
+        .and_then(|value| value.get("kind"))
+        .and_then(|value| value.as_str());
+    assert_eq!(kind, Some("invalid_input"));
+
+    let _ = child.kill();
+    Ok(())
+}
+
+fn note_xml(tag: &str, body: &str) -> String {
+    format!(
+        "<hp:{tag} number=\"1\"><hp:subList><hp:p><hp:run><hp:t>{body}</hp:t></hp:run></hp:p></hp:subList></hp:{tag}>"
+    )
+}
+
+/// The HWPX writer has no notes API, so the note elements are spliced into the run that
+/// holds the anchor text.
+fn hwpx_with_notes() -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut writer = hwpers::HwpxWriter::new();
+    writer.add_paragraph("Intro")?;
+    writer.add_paragraph("Anchored here")?;
+    writer.add_paragraph("Closing words")?;
+    let bytes = writer.to_bytes()?;
+
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))?;
+    let mut output = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index)?;
+        let name = file.name().to_string();
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)?;
+        if name == "Contents/section0.xml" {
+            let mut xml = String::from_utf8(data)?;
+            let anchor = "Anchored here</hp:t>";
+            let at = xml.find(anchor).expect("anchor text") + anchor.len();
+            xml.insert_str(at, &note_xml("footNote", "A footnote"));
+            let closing = "Closing words</hp:t>";
+            let at = xml.find(closing).expect("closing text") + closing.len();
+            xml.insert_str(at, &note_xml("endNote", "An endnote"));
+            data = xml.into_bytes();
+        }
+        output.start_file(name, SimpleFileOptions::default())?;
+        output.write_all(&data)?;
+    }
+    Ok(output.finish()?.into_inner())
+}
+
+fn record(tag: u16, level: u32, data: &[u8]) -> Vec<u8> {
+    let header = u32::from(tag) | (level << 10) | ((data.len() as u32) << 20);
+    let mut bytes = header.to_le_bytes().to_vec();
+    bytes.extend_from_slice(data);
+    bytes
+}
+
+fn utf16(text: &str) -> Vec<u8> {
+    text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+}
+
+/// Appends a paragraph carrying a footnote control, and then a closing paragraph, to
+/// BodyText/Section0 of a writer document.
+fn hwp_with_footnote() -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Intro")?;
+    let bytes = writer.to_bytes()?;
+
+    let mut compound = cfb::CompoundFile::open(Cursor::new(bytes))?;
+    let mut file_header = Vec::new();
+    compound
+        .open_stream("/FileHeader")?
+        .read_to_end(&mut file_header)?;
+    let compressed = file_header[36] & 0x01 != 0;
+    let mut stored = Vec::new();
+    compound
+        .open_stream("/BodyText/Section0")?
+        .read_to_end(&mut stored)?;
+    let mut section = Vec::new();
+    if compressed {
+        DeflateDecoder::new(stored.as_slice()).read_to_end(&mut section)?;
+    } else {
+        section = stored;
+    }
+
+    let mut para_header = vec![0u8; 24];
+    para_header[0..4].copy_from_slice(&(0x8000_0000u32 | 5).to_le_bytes());
+    let mut anchor_text = utf16("Note");
+    // Extended control char 17 (footnote/endnote) occupies eight code units.
+    anchor_text.extend(std::iter::repeat_n([17u8, 0], 8).flatten());
+    anchor_text.extend(utf16("\r"));
+    let mut ctrl_header = b"  nf".to_vec();
+    ctrl_header.extend_from_slice(&[0u8; 8]);
+    let mut body_header = vec![0u8; 24];
+    body_header[0..4].copy_from_slice(&(0x8000_0000u32 | 11).to_le_bytes());
+    let mut closing_header = vec![0u8; 24];
+    closing_header[0..4].copy_from_slice(&(0x8000_0000u32 | 7).to_le_bytes());
+
+    section.extend(record(0x10 + 50, 0, &para_header));
+    section.extend(record(0x10 + 51, 1, &anchor_text));
+    section.extend(record(0x10 + 55, 1, &ctrl_header));
+    section.extend(record(0x10 + 56, 2, &[0u8; 8]));
+    section.extend(record(0x10 + 50, 2, &body_header));
+    section.extend(record(0x10 + 51, 3, &utf16("Note body\r")));
+    section.extend(record(0x10 + 50, 0, &closing_header));
+    section.extend(record(0x10 + 51, 1, &utf16("Closing\r")));
+
+    if compressed {
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&section)?;
+        section = encoder.finish()?;
+    }
+    let mut stream = compound.create_stream("/BodyText/Section0")?;
+    stream.write_all(&section)?;
+    drop(stream);
+    compound.flush()?;
+    Ok(compound.into_inner().into_inner())
+}
+
+fn text_with_footnotes(
+    path: &std::path::Path,
+    mode: &str,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let structured = call_extract_text(serde_json::json!({
+        "path": path.to_string_lossy(),
+        "footnotes": mode
+    }))?;
+    Ok(structured
+        .get("text")
+        .and_then(|value| value.as_str())
+        .expect("text present")
+        .to_string())
+}
+
+#[test]
+fn extract_text_places_hwpx_notes_per_footnotes_mode() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("notes.hwpx");
+    std::fs::write(&file_path, hwpx_with_notes()?)?;
+
+    let inline = text_with_footnotes(&file_path, "inline")?;
+    assert!(
+        inline.contains("Anchored here[1]\n[1] A footnote\nClosing words[1]\n[1] An endnote\n"),
+        "{inline}"
+    );
+
+    let endnotes = text_with_footnotes(&file_path, "endnotes")?;
+    assert!(
+        endnotes.contains("Anchored here[1]\nClosing words[1]\n\n[1] A footnote\n[1] An endnote\n"),
+        "{endnotes}"
+    );
+
+    let omitted = text_with_footnotes(&file_path, "omit")?;
+    assert!(
+        omitted.contains("Anchored here\nClosing words\n"),
+        "{omitted}"
+    );
+    assert!(!omitted.contains("A footnote"), "{omitted}");
+    assert!(!omitted.contains("An endnote"), "{omitted}");
+    assert!(!omitted.contains("[1]"), "{omitted}");
+    Ok(())
+}
+
+#[test]
+fn extract_text_places_hwp_footnotes_per_footnotes_mode() -> Result<(), Box<dyn std::error::Error>>
+{
+    let dir = tempdir()?;
+    let file_path = dir.path().join("notes.hwp");
+    std::fs::write(&file_path, hwp_with_footnote()?)?;
+
+    // The note body is placed once, after its anchor, and not where hwpers lists it.
+    let inline = text_with_footnotes(&file_path, "inline")?;
+    assert_eq!(inline.matches("Note body").count(), 1, "{inline}");
+    let marker = inline.find("[1]\n[1] Note body\n").expect("inline note");
+    assert!(inline[..marker].contains("Note"), "{inline}");
+    assert!(inline[marker..].contains("Closing"), "{inline}");
+
+    let endnotes = text_with_footnotes(&file_path, "endnotes")?;
+    assert_eq!(endnotes.matches("Note body").count(), 1, "{endnotes}");
+    let closing = endnotes.find("Closing").expect("closing paragraph");
+    let note = endnotes.find("[1] Note body").expect("collected note");
+    assert!(closing < note, "{endnotes}");
+
+    let omitted = text_with_footnotes(&file_path, "omit")?;
+    assert!(!omitted.contains("Note body"), "{omitted}");
+    assert!(!omitted.contains("[1]"), "{omitted}");
+    assert!(omitted.contains("Closing"), "{omitted}");
+    Ok(())
+}
+
+#[test]
+fn extract_text_returns_text_and_markdown() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    // The leading styled paragraph pins HWPX char shape 0 to the body size, which
+    // unstyled paragraphs fall back to.
+    let create_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 8,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": {
+                "to": "hwpx",
+                "document": {
+                    "blocks": [
+                        { "type": "paragraph", "text": "Intro.", "style": { "font_size": 10 } },
+                        { "type": "heading", "level": 1, "text": "Overview" },
+                        { "type": "paragraph", "text": "Body text." }
+                    ]
+                }
+            }
+        }
+    });
+    writeln!(stdin, "{}", serde_json::to_string(&create_request)?)?;
+    stdin.flush()?;
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+    let encoded = response
+        .get("result")
+        .and_then(|value| value.get("structuredContent"))
+        .and_then(|value| value.get("base64"))
+        .and_then(|value| value.as_str())
+        .expect("base64 present")
+        .to_string();
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 9,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_text",
+            "arguments": {
+                "base64": encoded,
+                "representations": ["text", "markdown"]
+            }
+        }
+    });
+    writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+    stdin.flush()?;
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+    let structured = response
+        .get("result")
+        .and_then(|value| value.get("structuredContent"))
+        .expect("structuredContent present");
+
+    let text = structured
+        .get("text")
+        .and_then(|value| value.as_str())
+        .expect("text present");
+    let markdown = structured
+        .get("markdown")
+        .and_then(|value| value.as_str())
+        .expect("markdown present");
+    assert!(text.contains("Overview"));
+    assert!(!text.contains('#'));
+    assert!(markdown.contains("# Overview"), "{markdown}");
+    assert!(markdown.contains("Body text."));
+
+    let _ = child.kill();
+    Ok(())
+}
+
+/// Embeds an image the way HWP files store it: a DocInfo BIN_DATA record naming
+/// `BIN0001.{extension}` and the image bytes in that `BinData` stream, deflated when
+/// the document is compressed.
+fn store_bin_data(
+    path: &std::path::Path,
+    extension: &str,
+    bytes: &[u8],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut compound = cfb::open_rw(path)?;
+    let mut header = Vec::new();
+    compound
+        .open_stream("/FileHeader")?
+        .read_to_end(&mut header)?;
+    let compressed = header.get(36).is_some_and(|flags| flags & 1 != 0);
+
+    let mut doc_info = Vec::new();
+    compound
+        .open_stream("/DocInfo")?
+        .read_to_end(&mut doc_info)?;
+    if compressed {
+        let mut decoder = DeflateDecoder::new(doc_info.as_slice());
+        let mut inflated = Vec::new();
+        decoder.read_to_end(&mut inflated)?;
+        doc_info = inflated;
+    }
+
+    let mut data = Vec::new();
+    data.extend_from_slice(&1u16.to_le_bytes()); // embedding
+    data.extend_from_slice(&0u16.to_le_bytes()); // absolute name length
+    data.extend_from_slice(&0u16.to_le_bytes()); // relative name length
+    data.extend_from_slice(&1u16.to_le_bytes()); // bin id
+    data.extend_from_slice(&(extension.len() as u16).to_le_bytes());
+    for unit in extension.encode_utf16() {
+        data.extend_from_slice(&unit.to_le_bytes());
+    }
+    let record_header = 0x12u32 | ((data.len() as u32) << 20);
+    doc_info.extend_from_slice(&record_header.to_le_bytes());
+    doc_info.extend_from_slice(&data);
+
+    let mut image = bytes.to_vec();
+    if compressed {
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&doc_info)?;
+        doc_info = encoder.finish()?;
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&image)?;
+        image = encoder.finish()?;
+    }
+
+    compound.create_stream("/DocInfo")?.write_all(&doc_info)?;
+    compound.create_storage_all("/BinData")?;
+    compound
+        .create_stream(format!("/BinData/BIN0001.{extension}"))?
+        .write_all(&image)?;
+    compound.flush()?;
+    Ok(())
+}
+
+/// PNG signature; enough for an image stream that is not empty.
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+#[test]
+fn extract_text_flags_image_only_document() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("scanned.hwp");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph(" ")?;
+    writer.save_to_file(&file_path)?;
+    store_bin_data(&file_path, "png", &PNG_SIGNATURE)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 10,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_text",
+            "arguments": {
+                "path": file_path.to_string_lossy()
+            }
+        }
+    });
+    writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+    stdin.flush()?;
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+    let structured = response
+        .get("result")
+        .and_then(|value| value.get("structuredContent"))
+        .expect("structuredContent present");
+
+    let text = structured
+        .get("text")
+        .and_then(|value| value.as_str())
+        .expect("text present");
+    assert!(text.trim().is_empty());
+    assert_eq!(
+        structured.get("likely_scanned").and_then(|v| v.as_bool()),
+        Some(true)
+    );
+    let warnings = structured
+        .get("warnings")
+        .and_then(|value| value.as_array())
+        .expect("warnings present");
+    assert!(warnings.iter().any(|warning| {
+        warning
+            .as_str()
+            .is_some_and(|text| text.contains("image-based (1 images)"))
+    }));
+
+    let _ = child.kill();
+    Ok(())
+}
+
+/// Writes an image-only HWP whose single embedded image is `bytes`.
+fn write_scanned_document(
+    path: &std::path::Path,
+    extension: &str,
+    bytes: &[u8],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph(" ")?;
+    writer.save_to_file(path)?;
+    store_bin_data(path, extension, bytes)
+}
+
+fn call_extract_text(
+    arguments: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 11,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_text",
+            "arguments": arguments
+        }
+    });
+    writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+    stdin.flush()?;
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    let _ = child.kill();
+
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+    Ok(response
+        .get("result")
+        .and_then(|value| value.get("structuredContent"))
+        .cloned()
+        .expect("structuredContent present"))
+}
+
+fn has_warning(structured: &serde_json::Value, prefix: &str) -> bool {
+    structured
+        .get("warnings")
+        .and_then(|value| value.as_array())
+        .is_some_and(|warnings| {
+            warnings.iter().any(|warning| {
+                warning
+                    .as_str()
+                    .is_some_and(|text| text.starts_with(prefix))
+            })
+        })
+}
+
+#[cfg(not(feature = "ocr"))]
+#[test]
+fn extract_text_ocr_requires_feature() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("scanned.hwp");
+    write_scanned_document(&file_path, "png", &PNG_SIGNATURE)?;
+
+    let structured = call_extract_text(serde_json::json!({
+        "path": file_path.to_string_lossy(),
+        "ocr": true
+    }))?;
+    assert_eq!(
+        structured.get("source").and_then(|v| v.as_str()),
+        Some("document")
+    );
+    assert!(has_warning(&structured, "ocr: not available"));
+    Ok(())
+}
+
+/// Renders `text` (letters E, H, L, O only) as a 24-bit BMP using a 5x7 block font.
+#[cfg(feature = "ocr")]
+fn block_letter_bmp(text: &str) -> Vec<u8> {
+    const SCALE: usize = 12;
+    const MARGIN: usize = 48;
+    let glyph = |ch: char| -> [&str; 7] {
+        match ch {
+            'E' => [
+                "#####", "#....", "#....", "####.", "#....", "#....", "#####",
+            ],
+            'H' => [
+                "#...#", "#...#", "#...#", "#####", "#...#", "#...#", "#...#",
+            ],
+            'L' => [
+                "#....", "#....", "#....", "#....", "#....", "#....", "#####",
+            ],
+            _ => [
+                ".###.", "#...#", "#...#", "#...#", "#...#", "#...#", ".###.",
+            ],
+        }
+    };
+
+    let letters: Vec<char> = text.chars().collect();
+    let width = MARGIN * 2 + letters.len() * 6 * SCALE;
+    let height = MARGIN * 2 + 7 * SCALE;
+    let row_bytes = (width * 3).div_ceil(4) * 4;
+    let is_ink = |x: usize, y: usize| {
+        if x < MARGIN || !(MARGIN..MARGIN + 7 * SCALE).contains(&y) {
+            return false;
+        }
+        let cell = (x - MARGIN) / SCALE;
+        let (letter, column) = (cell / 6, cell % 6);
+        column < 5
+            && letters
+                .get(letter)
+                .is_some_and(|ch| glyph(*ch)[(y - MARGIN) / SCALE].as_bytes()[column] == b'#')
+    };
+
+    let pixel_bytes = row_bytes * height;
+    let mut bmp = Vec::with_capacity(54 + pixel_bytes);
+    bmp.extend_from_slice(b"BM");
+    bmp.extend_from_slice(&((54 + pixel_bytes) as u32).to_le_bytes());
+    bmp.extend_from_slice(&0u32.to_le_bytes());
+    bmp.extend_from_slice(&54u32.to_le_bytes());
+    bmp.extend_from_slice(&40u32.to_le_bytes());
+    bmp.extend_from_slice(&(width as i32).to_le_bytes());
+    bmp.extend_from_slice(&(height as i32).to_le_bytes());
+    bmp.extend_from_slice(&1u16.to_le_bytes());
+    bmp.extend_from_slice(&24u16.to_le_bytes());
+    bmp.extend_from_slice(&[0u8; 24]);
+    for y in (0..height).rev() {
+        let start = bmp.len();
+        for x in 0..width {
+            let value = if is_ink(x, y) { 0 } else { 255 };
+            bmp.extend_from_slice(&[value, value, value]);
+        }
+        bmp.resize(start + row_bytes, 0);
+    }
+    bmp
+}
+
+#[cfg(feature = "ocr")]
+#[test]
+fn extract_text_ocr_recognizes_image_text() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("scanned.hwp");
+    write_scanned_document(&file_path, "bmp", &block_letter_bmp("HELLO"))?;
+
+    let structured = call_extract_text(serde_json::json!({
+        "path": file_path.to_string_lossy(),
+        "ocr": true
+    }))?;
+
+    let tesseract_available = Command::new("tesseract")
+        .arg("--version")
+        .output()
+        .is_ok_and(|output| output.status.success());
+    if !tesseract_available {
+        assert!(has_warning(&structured, "ocr: no OCR backend found"));
+        return Ok(());
+    }
+
+    assert_eq!(
+        structured.get("source").and_then(|v| v.as_str()),
+        Some("ocr")
+    );
+    let text = structured
+        .get("text")
+        .and_then(|value| value.as_str())
+        .expect("text present");
+    assert!(text.contains("HELLO"), "{text}");
+    let images = structured
+        .get("ocr")
+        .and_then(|value| value.get("images"))
+        .and_then(|value| value.as_array())
+        .expect("ocr images present");
+    assert_eq!(images.len(), 1);
+    assert!(images[0].get("confidence").is_some());
+    Ok(())
+}
+
+#[test]
+fn extract_text_returns_partial_text_on_timeout() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("large.hwp");
+
+    let mut writer = HwpWriter::new();
+    for index in 0..2000 {
+        writer.add_paragraph(&format!("Paragraph number {index}"))?;
+    }
+    writer.save_to_file(&file_path)?;
+
+    // A zero deadline has always passed once the first paragraph is gathered.
+    let structured = call_extract_text(serde_json::json!({
+        "path": file_path.to_string_lossy(),
+        "timeout_ms": 0,
+        "partial_on_timeout": true
+    }))?;
+    assert_eq!(
+        structured.get("timed_out").and_then(|v| v.as_bool()),
+        Some(true)
+    );
+    assert!(has_warning(&structured, "partial:"));
+    let text = structured
+        .get("text")
+        .and_then(|v| v.as_str())
+        .expect("text present");
+    assert!(text.starts_with("Paragraph number 0"));
+    assert!(!text.contains("Paragraph number 1999"));
+
+    let structured = call_extract_text(serde_json::json!({
+        "path": file_path.to_string_lossy(),
+        "timeout_ms": 0
+    }))?;
+    assert_eq!(
+        structured
+            .get("error")
+            .and_then(|v| v.get("kind"))
+            .and_then(|v| v.as_str()),
+        Some("timeout")
+    );
+
+    let structured = call_extract_text(serde_json::json!({
+        "path": file_path.to_string_lossy()
+    }))?;
+    assert_eq!(
+        structured.get("timed_out").and_then(|v| v.as_bool()),
+        Some(false)
+    );
+    assert!(
+        structured
+            .get("text")
+            .and_then(|v| v.as_str())
+            .is_some_and(|text| text.contains("Paragraph number 1999"))
+    );
+    Ok(())
+}
+
+#[test]
+fn extract_text_writes_output_path() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("sample.hwp");
+    let output_path = dir.path().join("sample.txt");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Hello 안녕")?;
+    writer.save_to_file(&file_path)?;
+
+    let structured = call_extract_text(serde_json::json!({
+        "path": file_path.to_string_lossy(),
+        "output_path": output_path.to_string_lossy()
+    }))?;
+    assert!(structured.get("text").is_none());
+    assert_eq!(
+        structured.get("path").and_then(|value| value.as_str()),
+        Some(output_path.to_string_lossy().as_ref())
+    );
+
+    let written = std::fs::read_to_string(&output_path)?;
+    assert!(written.contains("Hello 안녕"));
+    assert_eq!(
+        structured.get("bytes_len").and_then(|value| value.as_u64()),
+        Some(written.len() as u64)
+    );
+    Ok(())
+}
+
+#[test]
+fn extract_text_splits_by_section() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let first_path = dir.path().join("first.hwp");
+    let second_path = dir.path().join("second.hwp");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("First   section")?;
+    writer.save_to_file(&first_path)?;
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Second section text")?;
+    writer.save_to_file(&second_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.merge",
+            "arguments": {
+                "inputs": [
+                    { "path": first_path.to_string_lossy() },
+                    { "path": second_path.to_string_lossy() }
+                ],
+                "to": "hwp",
+                "separator": "section_break"
+            }
+        }
+    });
+    writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+    stdin.flush()?;
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    let _ = child.kill();
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+    let merged = response["result"]["structuredContent"]["base64"]
+        .as_str()
+        .expect("base64 present")
+        .to_string();
+
+    let structured = call_extract_text(serde_json::json!({
+        "base64": merged,
+        "by_section": true,
+        "normalize_whitespace": true,
+        "include_newlines": false,
+        "max_chars": 14
+    }))?;
+    assert_eq!(
+        structured["sections"],
+        serde_json::json!([
+            { "section_index": 0, "text": "First section" },
+            { "section_index": 1, "text": "Second section" }
+        ])
+    );
+    assert!(structured["text"].is_string());
+
+    let structured = call_extract_text(serde_json::json!({ "base64": merged }))?;
+    assert!(structured.get("sections").is_none());
+    Ok(())
+}
+
+#[test]
+fn extract_text_returns_paragraph_offsets() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("paragraphs.hwp");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("안녕하세요 세계")?;
+    writer.add_paragraph("두 번째 문단")?;
+    writer.save_to_file(&file_path)?;
+
+    let structured = call_extract_text(serde_json::json!({
+        "path": file_path.to_string_lossy(),
+        "granularity": "paragraph"
+    }))?;
+    let paragraphs = structured["paragraphs"]
+        .as_array()
+        .expect("paragraphs present");
+    assert_eq!(paragraphs.len(), 2);
+    let first = paragraphs[0]["text"].as_str().expect("paragraph text");
+    assert_eq!(first, "안녕하세요 세계");
+    assert_eq!(paragraphs[0]["char_start"], 0);
+    assert_eq!(paragraphs[0]["char_end"], 8);
+    assert_eq!(paragraphs[1]["paragraph_index"], 1);
+    assert_eq!(paragraphs[1]["char_start"], 9);
+
+    // The spans index the document text in chars.
+    let text: Vec<char> = structured["text"]
+        .as_str()
+        .expect("text present")
+        .chars()
+        .collect();
+    for paragraph in paragraphs {
+        let start = paragraph["char_start"].as_u64().expect("char_start") as usize;
+        let end = paragraph["char_end"].as_u64().expect("char_end") as usize;
+        assert_eq!(
+            text[start..end].iter().collect::<String>(),
+            paragraph["text"].as_str().expect("paragraph text")
+        );
+    }
+
+    let structured = call_extract_text(serde_json::json!({
+        "path": file_path.to_string_lossy(),
+        "granularity": "sentence"
+    }));
+    assert!(structured?["error"]["kind"] == "invalid_input");
+    Ok(())
+}
+
+/// Builds an HWP document with `hwp.create_rich_document` and returns it as base64.
+fn create_rich_hwp(blocks: serde_json::Value) -> Result<String, Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": { "to": "hwp", "document": { "blocks": blocks } }
+        }
+    });
+    writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+    stdin.flush()?;
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    let _ = child.kill();
+
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+    Ok(response["result"]["structuredContent"]["base64"]
+        .as_str()
+        .expect("base64 present")
+        .to_string())
+}
+
+#[test]
+fn extract_text_renders_tables_of_parsed_documents() -> Result<(), Box<dyn std::error::Error>> {
+    let hwp = create_rich_hwp(serde_json::json!([
+        { "type": "paragraph", "text": "Before" },
+        { "type": "table", "rows": [["Name", "Qty"], ["Apple", "3"]] },
+        { "type": "paragraph", "text": "After" }
+    ]))?;
+
+    let structured = call_extract_text(serde_json::json!({
+        "base64": hwp,
+        "table_format": "tsv",
+        "representations": ["text", "markdown"]
+    }))?;
+    let text = structured["text"].as_str().expect("text present");
+    assert!(
+        text.contains("Before\nName\tQty\nApple\t3\nAfter"),
+        "{text}"
+    );
+    let markdown = structured["markdown"].as_str().expect("markdown present");
+    assert!(
+        markdown.contains("Before\n\n| Name | Qty |\n| --- | --- |\n| Apple | 3 |\n\nAfter"),
+        "{markdown}"
+    );
+
+    let structured = call_extract_text(serde_json::json!({
+        "base64": hwp,
+        "table_format": "markdown"
+    }))?;
+    let text = structured["text"].as_str().expect("text present");
+    assert!(
+        text.contains("Before\n| Name | Qty |\n| --- | --- |\n| Apple | 3 |\nAfter"),
+        "{text}"
+    );
+
+    let structured = call_extract_text(serde_json::json!({ "base64": hwp }))?;
+    let text = structured["text"].as_str().expect("text present");
+    assert!(text.contains("Name\nQty\nApple\n3\n"), "{text}");
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+
+fn send_request(
+    stdin: &mut impl Write,
+    stdout: &mut impl BufRead,
+    request: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let serialized = serde_json::to_string(&request)?;
+    writeln!(stdin, "{serialized}")?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    Ok(serde_json::from_str(line.trim())?)
+}
+
+#[test]
+fn extract_toc_lists_headings_with_levels() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    // The leading empty styled paragraph pins HWPX char shape 0 to the body size,
+    // which unstyled paragraphs fall back to.
+    let create_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": {
+                "to": "hwpx",
+                "document": {
+                    "blocks": [
+                        { "type": "paragraph", "text": "", "style": { "font_size": 10 } },
+                        { "type": "heading", "level": 1, "text": "Overview" },
+                        { "type": "paragraph", "text": "Overview body." },
+                        { "type": "heading", "level": 2, "text": "Details" },
+                        { "type": "paragraph", "text": "Details body." },
+                        { "type": "heading", "level": 1, "text": "Appendix" },
+                        { "type": "paragraph", "text": "Appendix body." }
+                    ]
+                }
+            }
+        }
+    });
+    let create_response = send_request(&mut stdin, &mut stdout, create_request)?;
+    let encoded = create_response
+        .get("result")
+        .and_then(|value| value.get("structuredContent"))
+        .and_then(|value| value.get("base64"))
+        .and_then(|value| value.as_str())
+        .expect("base64 present")
+        .to_string();
+
+    let toc_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_toc",
+            "arguments": { "base64": encoded }
+        }
+    });
+    let toc_response = send_request(&mut stdin, &mut stdout, toc_request)?;
+    let structured = toc_response
+        .get("result")
+        .and_then(|value| value.get("structuredContent"))
+        .expect("structured content");
+    let toc = structured["toc"].as_array().expect("toc present");
+    let entries: Vec<(u64, &str)> = toc
+        .iter()
+        .map(|entry| {
+            (
+                entry["level"].as_u64().unwrap_or(0),
+                entry["text"].as_str().unwrap_or(""),
+            )
+        })
+        .collect();
+    assert_eq!(
+        entries,
+        vec![(1, "Overview"), (2, "Details"), (1, "Appendix")],
+        "{structured}"
+    );
+    assert_eq!(toc[0]["section_index"], 0);
+    assert!(
+        toc[0]["paragraph_index"].as_u64() < toc[1]["paragraph_index"].as_u64(),
+        "{structured}"
+    );
+
+    let capped_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 3,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_toc",
+            "arguments": { "base64": encoded, "max_level": 1 }
+        }
+    });
+    let capped_response = send_request(&mut stdin, &mut stdout, capped_request)?;
+    let capped: Vec<&str> = capped_response["result"]["structuredContent"]["toc"]
+        .as_array()
+        .expect("toc present")
+        .iter()
+        .filter_map(|entry| entry["text"].as_str())
+        .collect();
+    assert_eq!(capped, vec!["Overview", "Appendix"]);
+
+    let invalid_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 4,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_toc",
+            "arguments": { "base64": encoded, "max_level": 0 }
+        }
+    });
+    let invalid_response = send_request(&mut stdin, &mut stdout, invalid_request)?;
+    assert_eq!(invalid_response["result"]["isError"], true);
+
+    let _ = child.kill();
+    Ok(())
+}
@@ -69,3 +69,322 @@ fn inspect_metadata_round_trip() -> Result<(), Box<dyn std::error::Error>> {
     let _ = child.kill();
     Ok(())
 }
+
+#[test]
+fn inspect_metadata_reports_detection_method() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("sample.hwp");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Hello")?;
+    writer.save_to_file(&file_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    for (id, format, expected_method) in [(5, "auto", "magic"), (6, "hwp", "explicit")] {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.inspect_metadata",
+                "arguments": {
+                    "path": file_path.to_string_lossy(),
+                    "format": format
+                }
+            }
+        });
+        let serialized = serde_json::to_string(&request)?;
+        writeln!(stdin, "{serialized}")?;
+        stdin.flush()?;
+
+        let mut line = String::new();
+        stdout.read_line(&mut line)?;
+
+        let response: serde_json::Value = serde_json::from_str(line.trim())?;
+        let structured = response
+            .get("result")
+            .and_then(|value| value.get("structuredContent"))
+            .expect("structured content present");
+        assert_eq!(
+            structured.get("detected_format").and_then(|v| v.as_str()),
+            Some("hwp")
+        );
+        assert_eq!(
+            structured.get("detection_method").and_then(|v| v.as_str()),
+            Some(expected_method)
+        );
+    }
+
+    let _ = child.kill();
+    Ok(())
+}
+
+/// Builds a one-section OLE property set holding UTF-16 strings and FILETIMEs, as Hangul writes.
+fn property_set(strings: &[(u32, &str)], times: &[(u32, u64)]) -> Vec<u8> {
+    let mut values = Vec::new();
+    let mut entries = Vec::new();
+    let header_len = 8 + 8 * (strings.len() + times.len());
+    for (id, text) in strings {
+        entries.push((*id, (header_len + values.len()) as u32));
+        let units: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+        values.extend(0x1Fu32.to_le_bytes());
+        values.extend((units.len() as u32).to_le_bytes());
+        values.extend(units.iter().flat_map(|unit| unit.to_le_bytes()));
+        while values.len() % 4 != 0 {
+            values.push(0);
+        }
+    }
+    for (id, filetime) in times {
+        entries.push((*id, (header_len + values.len()) as u32));
+        values.extend(0x40u32.to_le_bytes());
+        values.extend(filetime.to_le_bytes());
+    }
+
+    let mut data = vec![0xFE, 0xFF, 0, 0];
+    data.extend([0u8; 4 + 16]);
+    data.extend(1u32.to_le_bytes());
+    data.extend([0u8; 16]);
+    data.extend(48u32.to_le_bytes());
+    data.extend(((header_len + values.len()) as u32).to_le_bytes());
+    data.extend((entries.len() as u32).to_le_bytes());
+    for (id, offset) in entries {
+        data.extend(id.to_le_bytes());
+        data.extend(offset.to_le_bytes());
+    }
+    data.extend(values);
+    data
+}
+
+#[test]
+fn inspect_metadata_reports_summary_information() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("summary.hwp");
+    let plain_path = dir.path().join("plain.hwpx");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Hello")?;
+    writer.save_to_file(&file_path)?;
+
+    let mut compound = cfb::open_rw(&file_path)?;
+    let mut stream = compound.create_stream("/\u{5}HwpSummaryInformation")?;
+    stream.write_all(&property_set(
+        &[
+            (0x02, "분기 보고서"),
+            (0x04, "홍길동"),
+            (0x05, "보고, 분기"),
+        ],
+        &[
+            (0x0C, 133_537_700_960_000_000),
+            (0x0D, 133_537_700_960_000_000),
+        ],
+    ))?;
+    drop(stream);
+    let mut stream = compound.create_stream("/\u{5}DocumentSummaryInformation")?;
+    stream.write_all(&property_set(&[(0x0F, "한글과컴퓨터")], &[]))?;
+    drop(stream);
+    compound.flush()?;
+    drop(compound);
+
+    let mut hwpx = hwpers::HwpxWriter::new();
+    hwpx.add_paragraph("Hello")?;
+    hwpx.save_to_file(&plain_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let mut summaries = Vec::new();
+    for (id, path) in [(20, &file_path), (21, &plain_path)] {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.inspect_metadata",
+                "arguments": { "path": path.to_string_lossy() }
+            }
+        });
+        writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+        stdin.flush()?;
+        let mut line = String::new();
+        stdout.read_line(&mut line)?;
+        let response: serde_json::Value = serde_json::from_str(line.trim())?;
+        summaries.push(response["result"]["structuredContent"]["summary"].clone());
+    }
+
+    let summary = &summaries[0];
+    assert_eq!(summary["title"], "분기 보고서");
+    assert_eq!(summary["author"], "홍길동");
+    assert_eq!(summary["keywords"], "보고, 분기");
+    assert_eq!(summary["company"], "한글과컴퓨터");
+    assert_eq!(summary["created"], "2024-03-01T12:34:56Z");
+    assert_eq!(summary["modified"], "2024-03-01T12:34:56Z");
+    assert!(summary["subject"].is_null());
+
+    // Absent fields stay present as null.
+    let empty = summaries[1].as_object().expect("summary object");
+    for key in [
+        "title",
+        "subject",
+        "author",
+        "keywords",
+        "comments",
+        "last_saved_by",
+        "company",
+        "created",
+        "modified",
+    ] {
+        assert!(empty.get(key).is_some_and(|value| value.is_null()), "{key}");
+    }
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn inspect_metadata_lists_font_faces() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let hwp_path = dir.path().join("fonts.hwp");
+    let hwpx_path = dir.path().join("fonts.hwpx");
+
+    // Both writers declare every script's face as 맑은 고딕.
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Hello")?;
+    writer.save_to_file(&hwp_path)?;
+    let mut hwpx = hwpers::HwpxWriter::new();
+    hwpx.add_paragraph("Hello")?;
+    hwpx.save_to_file(&hwpx_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    for (id, path) in [(30, &hwp_path), (31, &hwpx_path)] {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.inspect_metadata",
+                "arguments": { "path": path.to_string_lossy() }
+            }
+        });
+        writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+        stdin.flush()?;
+        let mut line = String::new();
+        stdout.read_line(&mut line)?;
+        let response: serde_json::Value = serde_json::from_str(line.trim())?;
+        let structured = &response["result"]["structuredContent"];
+        let fonts = structured["fonts"].as_array().expect("fonts array");
+        let names: Vec<&str> = fonts
+            .iter()
+            .filter_map(|font| font["name"].as_str())
+            .collect();
+        assert!(names.contains(&"맑은 고딕"), "{structured}");
+        let mut unique = names.clone();
+        unique.dedup();
+        assert_eq!(unique.len(), names.len(), "{names:?}");
+        assert!(fonts.iter().all(|font| font.get("substitute").is_some()));
+    }
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn inspect_metadata_without_counts_reads_header_only() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("summary.hwp");
+    let hwpx_path = dir.path().join("plain.hwpx");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Hello")?;
+    writer.save_to_file(&file_path)?;
+
+    let mut compound = cfb::open_rw(&file_path)?;
+    let mut stream = compound.create_stream("/\u{5}HwpSummaryInformation")?;
+    stream.write_all(&property_set(&[(0x02, "분기 보고서")], &[]))?;
+    drop(stream);
+    compound.flush()?;
+    drop(compound);
+
+    let mut hwpx = hwpers::HwpxWriter::new();
+    hwpx.add_paragraph("Hello")?;
+    hwpx.save_to_file(&hwpx_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let mut results = Vec::new();
+    for (id, path, counts) in [
+        (1, &file_path, true),
+        (2, &file_path, false),
+        (3, &hwpx_path, true),
+        (4, &hwpx_path, false),
+    ] {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.inspect_metadata",
+                "arguments": { "path": path.to_string_lossy(), "counts": counts }
+            }
+        });
+        writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+        stdin.flush()?;
+        let mut line = String::new();
+        stdout.read_line(&mut line)?;
+        let response: serde_json::Value = serde_json::from_str(line.trim())?;
+        assert_eq!(response["result"]["isError"], false);
+        results.push(response["result"]["structuredContent"].clone());
+    }
+
+    for pair in results.chunks(2) {
+        let (full, header) = (&pair[0], &pair[1]);
+        assert!(full.get("sections").is_some());
+        for key in ["sections", "paragraphs", "fonts"] {
+            assert!(header.get(key).is_none(), "{key}");
+        }
+        for key in [
+            "format",
+            "detection_method",
+            "version",
+            "compressed",
+            "encrypted",
+            "distribution",
+            "summary",
+        ] {
+            assert_eq!(header[key], full[key], "{key}");
+        }
+    }
+    assert_eq!(results[1]["summary"]["title"], "분기 보고서");
+    assert_eq!(results[3]["format"], "hwpx");
+
+    let _ = child.kill();
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use hwpers::HwpWriter;
+use hwpers::writer::style::TextStyle;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+use tempfile::tempdir;
+
+fn call_tool(
+    name: &str,
+    arguments: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": { "name": name, "arguments": arguments }
+    });
+    writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+    stdin.flush()?;
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    let _ = child.kill();
+
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+    Ok(response.get("result").cloned().expect("result present"))
+}
+
+#[test]
+fn list_styles_reports_styles_and_char_shapes() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("styled.hwp");
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("본문")?;
+    writer.add_paragraph_with_style("강조", &TextStyle::new().size(20).bold())?;
+    writer.save_to_file(&file_path)?;
+
+    let result = call_tool(
+        "hwp.list_styles",
+        serde_json::json!({ "path": file_path.to_string_lossy() }),
+    )?;
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    let structured = &result["structuredContent"];
+
+    let styles = structured["para_styles"].as_array().expect("para_styles");
+    let normal = &styles[0];
+    assert_eq!(normal["id"], 0);
+    assert_eq!(normal["name"], "바탕글");
+    assert_eq!(normal["kind"], "paragraph");
+    assert!(normal["alignment"].is_string(), "{normal}");
+    assert_eq!(normal["line_spacing"]["type"], "percent");
+    assert_eq!(normal["line_spacing"]["value"], 160);
+
+    let shapes = structured["char_shapes"].as_array().expect("char_shapes");
+    let emphasis = shapes
+        .iter()
+        .find(|shape| shape["bold"] == true)
+        .unwrap_or_else(|| panic!("bold shape missing: {structured}"));
+    assert_eq!(emphasis["size_pt"], 20.0);
+    assert_eq!(emphasis["italic"], false);
+    assert_eq!(emphasis["font_name"], "맑은 고딕");
+    assert!(emphasis["id"].as_u64().is_some());
+    Ok(())
+}
+
+#[test]
+fn list_styles_warns_when_hwpx_has_no_styles() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("plain.hwpx");
+    let mut writer = hwpers::HwpxWriter::new();
+    writer.add_paragraph("본문")?;
+    writer.save_to_file(&file_path)?;
+
+    let result = call_tool(
+        "hwp.list_styles",
+        serde_json::json!({ "path": file_path.to_string_lossy() }),
+    )?;
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    let structured = &result["structuredContent"];
+    assert!(
+        structured["char_shapes"]
+            .as_array()
+            .is_some_and(|shapes| !shapes.is_empty()),
+        "{structured}"
+    );
+    assert_eq!(structured["para_styles"], serde_json::json!([]));
+    let warnings = structured["warnings"].as_array().expect("warnings");
+    assert!(
+        warnings
+            .iter()
+            .any(|w| w.as_str().is_some_and(|w| w.starts_with("para_styles:"))),
+        "{warnings:?}"
+    );
+    Ok(())
+}
@@ -0,0 +1,196 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use hwpers::{HwpReader, HwpWriter, HwpxWriter};
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+use tempfile::tempdir;
+
+fn send_request(
+    stdin: &mut impl Write,
+    stdout: &mut impl BufRead,
+    request: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let serialized = serde_json::to_string(&request)?;
+    writeln!(stdin, "{serialized}")?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    Ok(serde_json::from_str(line.trim())?)
+}
+
+#[test]
+fn merge_section_break_keeps_each_source_section() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let first_path = dir.path().join("first.hwp");
+    let second_path = dir.path().join("second.hwp");
+
+    let mut writer = HwpWriter::new();
+    writer.add_header("First header");
+    writer.add_paragraph("First document body")?;
+    writer.save_to_file(&first_path)?;
+
+    let mut writer = HwpWriter::new();
+    writer.add_header("Second header");
+    writer.add_paragraph("Second document body")?;
+    writer.save_to_file(&second_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.merge",
+            "arguments": {
+                "inputs": [
+                    { "path": first_path.to_string_lossy() },
+                    { "path": second_path.to_string_lossy() }
+                ],
+                "to": "hwp",
+                "separator": "section_break"
+            }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, request)?;
+    let result = response.get("result").expect("result present");
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+
+    let structured = result
+        .get("structuredContent")
+        .expect("structuredContent present");
+    assert_eq!(
+        structured.get("separator").and_then(|v| v.as_str()),
+        Some("section_break")
+    );
+    assert_eq!(structured.get("sections").and_then(|v| v.as_u64()), Some(2));
+
+    let encoded = structured
+        .get("base64")
+        .and_then(|v| v.as_str())
+        .expect("base64 present");
+    let merged = HwpReader::from_bytes(&STANDARD.decode(encoded)?)?;
+    let section_texts: Vec<String> = merged
+        .sections()
+        .map(|section| {
+            section
+                .paragraphs
+                .iter()
+                .filter_map(|paragraph| paragraph.text.as_ref())
+                .map(|text| text.content.clone())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect();
+    assert_eq!(section_texts.len(), 2);
+    assert!(section_texts[0].contains("First document body"));
+    assert!(section_texts[1].contains("Second document body"));
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn merge_rejects_single_input() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.merge",
+            "arguments": {
+                "inputs": [{ "base64": "AAAA" }],
+                "to": "hwp"
+            }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, request)?;
+    let result = response.get("result").expect("result present");
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(true));
+    assert_eq!(
+        result
+            .get("structuredContent")
+            .and_then(|v| v.get("error"))
+            .and_then(|v| v.get("kind"))
+            .and_then(|v| v.as_str()),
+        Some("invalid_input")
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn merge_warns_when_inputs_mix_formats() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let hwp_path = dir.path().join("first.hwp");
+    let hwpx_path = dir.path().join("second.hwpx");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("HWP body")?;
+    writer.save_to_file(&hwp_path)?;
+
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("HWPX body")?;
+    writer.save_to_file(&hwpx_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 3,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.merge",
+            "arguments": {
+                "inputs": [
+                    { "path": hwp_path.to_string_lossy() },
+                    { "path": hwpx_path.to_string_lossy() }
+                ],
+                "to": "hwp"
+            }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, request)?;
+    let result = response.get("result").expect("result present");
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+
+    let warnings = result
+        .get("structuredContent")
+        .and_then(|v| v.get("warnings"))
+        .and_then(|v| v.as_array())
+        .expect("warnings present");
+    assert!(warnings.iter().any(|warning| {
+        warning.as_str()
+            == Some(
+                "inputs mix hwp and hwpx formats; inputs[0] (hwp) was used as the base document",
+            )
+    }));
+
+    let _ = child.kill();
+    Ok(())
+}
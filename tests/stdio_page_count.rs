@@ -0,0 +1,86 @@
+use hwpers::HwpWriter;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+use tempfile::tempdir;
+
+fn call_tool(
+    name: &str,
+    arguments: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": { "name": name, "arguments": arguments }
+    });
+    writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+    stdin.flush()?;
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    let _ = child.kill();
+
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+    Ok(response.get("result").cloned().expect("result present"))
+}
+
+#[test]
+fn page_count_matches_render_svg_pages() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("long.hwp");
+    let mut writer = HwpWriter::new();
+    for index in 0..200 {
+        writer.add_paragraph(&format!("문단 {index}: 페이지 수를 세기 위한 긴 문서"))?;
+    }
+    writer.save_to_file(&file_path)?;
+
+    let result = call_tool(
+        "hwp.page_count",
+        serde_json::json!({ "path": file_path.to_string_lossy() }),
+    )?;
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    let page_count = result["structuredContent"]["page_count"]
+        .as_u64()
+        .expect("page_count");
+    assert!(page_count > 1, "{result}");
+
+    let last = call_tool(
+        "hwp.render_svg",
+        serde_json::json!({ "path": file_path.to_string_lossy(), "page": page_count }),
+    )?;
+    assert_eq!(last.get("isError").and_then(|v| v.as_bool()), Some(false));
+    let past_end = call_tool(
+        "hwp.render_svg",
+        serde_json::json!({ "path": file_path.to_string_lossy(), "page": page_count + 1 }),
+    )?;
+    assert_eq!(
+        past_end.get("isError").and_then(|v| v.as_bool()),
+        Some(true)
+    );
+    Ok(())
+}
+
+#[test]
+fn page_count_single_page_document() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("short.hwp");
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("한 쪽짜리 문서")?;
+    writer.save_to_file(&file_path)?;
+
+    let result = call_tool(
+        "hwp.page_count",
+        serde_json::json!({ "path": file_path.to_string_lossy() }),
+    )?;
+    assert_eq!(result["structuredContent"]["page_count"], 1, "{result}");
+    assert_eq!(result["content"][0]["text"], "1 page(s)");
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+
+#[test]
+fn malformed_json_returns_parse_error() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    writeln!(stdin, "{{not json")?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+    assert_eq!(
+        response,
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": null,
+            "error": { "code": -32700, "message": "Parse error" }
+        })
+    );
+
+    // The server keeps serving after a bad line.
+    let request = serde_json::json!({ "jsonrpc": "2.0", "id": 2, "method": "ping" });
+    writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+    stdin.flush()?;
+    line.clear();
+    stdout.read_line(&mut line)?;
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+    assert_eq!(response["id"], 2);
+    assert!(response.get("result").is_some());
+
+    let _ = child.kill();
+    Ok(())
+}
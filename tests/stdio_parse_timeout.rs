@@ -0,0 +1,102 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use hwpers::HwpWriter;
+use std::io::{BufRead, BufReader, Cursor, Write};
+use std::process::{Command, Stdio};
+use tempfile::tempdir;
+use zip::write::SimpleFileOptions;
+
+fn send_request(
+    stdin: &mut impl Write,
+    stdout: &mut impl BufRead,
+    request: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let serialized = serde_json::to_string(&request)?;
+    writeln!(stdin, "{serialized}")?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    Ok(serde_json::from_str(line.trim())?)
+}
+
+/// A small HWPX-looking archive whose version.xml inflates to ~40 MiB of padding followed by
+/// an unterminated element, so the parser spends a long time before failing.
+fn malformed_hwpx() -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    zip.start_file("mimetype", SimpleFileOptions::default())?;
+    zip.write_all(b"application/hwp+zip")?;
+    zip.start_file("version.xml", SimpleFileOptions::default())?;
+    zip.write_all(b"<?xml version=\"1.0\"?><!--")?;
+    let padding = vec![b' '; 1024 * 1024];
+    for _ in 0..40 {
+        zip.write_all(&padding)?;
+    }
+    zip.write_all(b"--><hv:HCFVersion")?;
+    Ok(zip.finish()?.into_inner())
+}
+
+#[test]
+fn parse_timeout_returns_error_and_server_keeps_serving() -> Result<(), Box<dyn std::error::Error>>
+{
+    let dir = tempdir()?;
+    let file_path = dir.path().join("ok.hwp");
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Still serving")?;
+    writer.save_to_file(&file_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let malformed_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_text",
+            "arguments": {
+                "base64": STANDARD.encode(malformed_hwpx()?),
+                "format": "hwpx",
+                "timeout_ms": 1
+            }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, malformed_request)?;
+    let error = response
+        .get("result")
+        .and_then(|value| value.get("structuredContent"))
+        .and_then(|value| value.get("error"))
+        .expect("error present");
+    assert_eq!(error.get("kind").and_then(|v| v.as_str()), Some("timeout"));
+    assert_eq!(
+        error.get("message").and_then(|v| v.as_str()),
+        Some("parse timed out after 1 ms")
+    );
+
+    let ok_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_text",
+            "arguments": { "path": file_path.to_string_lossy() }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, ok_request)?;
+    let text = response
+        .get("result")
+        .and_then(|value| value.get("structuredContent"))
+        .and_then(|value| value.get("text"))
+        .and_then(|value| value.as_str())
+        .unwrap_or_default();
+    assert!(text.contains("Still serving"), "{text}");
+
+    let _ = child.kill();
+    Ok(())
+}
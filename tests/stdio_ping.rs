@@ -0,0 +1,35 @@
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+
+#[test]
+fn ping_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": "ping-1",
+        "method": "ping"
+    });
+    let serialized = serde_json::to_string(&request)?;
+    writeln!(stdin, "{serialized}")?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+    assert_eq!(
+        response,
+        serde_json::json!({ "jsonrpc": "2.0", "id": "ping-1", "result": {} })
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
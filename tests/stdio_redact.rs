@@ -0,0 +1,148 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+
+fn send_request(
+    stdin: &mut impl Write,
+    stdout: &mut impl BufRead,
+    request: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let serialized = serde_json::to_string(&request)?;
+    writeln!(stdin, "{serialized}")?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    Ok(serde_json::from_str(line.trim())?)
+}
+
+#[test]
+fn redact_masks_paragraph_and_table_cell_text() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let create_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": {
+                "to": "hwp",
+                "document": {
+                    "blocks": [
+                        { "type": "paragraph", "text": "담당자 홍길동, 전화 010-1234-5678" },
+                        {
+                            "type": "table",
+                            "rows": [["이름", "연락처"], ["홍길동", "010-9876-5432"]]
+                        },
+                        { "type": "paragraph", "text": "Nothing secret here." }
+                    ]
+                }
+            }
+        }
+    });
+    let create_response = send_request(&mut stdin, &mut stdout, create_request)?;
+    let encoded = create_response["result"]["structuredContent"]["base64"]
+        .as_str()
+        .expect("base64 present")
+        .to_string();
+
+    let redact_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.redact",
+            "arguments": {
+                "base64": encoded,
+                "patterns": ["홍길동", { "regex": "\\d{3}-\\d{4}-\\d{4}" }, "absent"]
+            }
+        }
+    });
+    let redact_response = send_request(&mut stdin, &mut stdout, redact_request)?;
+    let result = redact_response.get("result").expect("result present");
+    assert_eq!(result["isError"], false, "{result}");
+    let structured = &result["structuredContent"];
+    assert_eq!(structured["redactions"], 4, "{structured}");
+    assert_eq!(
+        structured["matched_patterns"],
+        serde_json::json!([
+            { "pattern": "홍길동", "regex": false, "count": 2 },
+            { "pattern": "\\d{3}-\\d{4}-\\d{4}", "regex": true, "count": 2 }
+        ])
+    );
+
+    let bytes = STANDARD.decode(structured["base64"].as_str().expect("base64 present"))?;
+    let text = hwpers::HwpReader::from_bytes(&bytes)?.extract_text();
+    assert!(!text.contains("홍길동"), "{text}");
+    assert!(!text.contains("010-"), "{text}");
+    assert!(text.contains("담당자 ███, 전화 █████████████"), "{text}");
+    assert!(text.contains("연락처"), "{text}");
+    assert!(text.contains("Nothing secret here."), "{text}");
+
+    let invalid_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 3,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.redact",
+            "arguments": { "base64": encoded, "patterns": ["x"], "mask": "##" }
+        }
+    });
+    let invalid_response = send_request(&mut stdin, &mut stdout, invalid_request)?;
+    assert_eq!(invalid_response["result"]["isError"], true);
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn redact_regex_handles_long_paragraphs() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let mut writer = hwpers::HwpWriter::new();
+    writer.add_paragraph("Intro")?;
+    writer.add_paragraph(&format!("secret {} end", "a".repeat(12_000)))?;
+    let encoded = STANDARD.encode(writer.to_bytes()?);
+
+    let redact_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.redact",
+            "arguments": { "base64": encoded, "patterns": [{ "regex": "a+" }] }
+        }
+    });
+    let redact_response = send_request(&mut stdin, &mut stdout, redact_request)?;
+    let result = redact_response.get("result").expect("result present");
+    assert_eq!(result["isError"], false, "{result}");
+    assert_eq!(result["structuredContent"]["redactions"], 1);
+
+    let bytes = STANDARD.decode(
+        result["structuredContent"]["base64"]
+            .as_str()
+            .expect("base64 present"),
+    )?;
+    let text = hwpers::HwpReader::from_bytes(&bytes)?.extract_text();
+    assert!(!text.contains('a'), "masked text still has matches");
+    assert!(text.contains(&format!("secret {} end", "█".repeat(12_000))));
+
+    let _ = child.kill();
+    Ok(())
+}
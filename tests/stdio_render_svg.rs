@@ -105,3 +105,672 @@ fn render_svg_inline_and_resource() -> Result<(), Box<dyn std::error::Error>> {
     let _ = child.kill();
     Ok(())
 }
+
+#[test]
+fn render_svg_after_preload() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("sample.hwp");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Hello")?;
+    writer.save_to_file(&file_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio", "--preload"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 12,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.render_svg",
+            "arguments": {
+                "path": file_path.to_string_lossy(),
+                "page": 1
+            }
+        }
+    });
+    let serialized = serde_json::to_string(&request)?;
+    writeln!(stdin, "{serialized}")?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+    let result = response.get("result").expect("result present");
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn render_svg_draws_line_numbers_in_left_margin() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("numbered.hwp");
+
+    let mut writer = HwpWriter::new();
+    writer.set_a4_portrait()?;
+    writer.add_paragraph("First line")?;
+    writer.add_paragraph("Second line")?;
+    writer.add_paragraph("Third line")?;
+    writer.save_to_file(&file_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 30,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.render_svg",
+            "arguments": {
+                "path": file_path.to_string_lossy(),
+                "line_numbers": true
+            }
+        }
+    });
+    writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+    let result = response.get("result").expect("result present");
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    let svg = result
+        .get("structuredContent")
+        .and_then(|value| value.get("pages"))
+        .and_then(|value| value.as_array())
+        .and_then(|value| value.first())
+        .and_then(|value| value.get("svg"))
+        .and_then(|value| value.as_str())
+        .expect("svg present");
+
+    // Body runs keep their trailing paragraph terminator, so match on the prefix.
+    let text_x = |label: &str| -> Option<i64> {
+        let end = svg.find(label)?;
+        let start = svg[..end].rfind("<text x=\"")? + "<text x=\"".len();
+        let len = svg[start..].find('"')?;
+        svg[start..start + len].parse().ok()
+    };
+    let body_x = text_x(">First line").expect("body text rendered");
+    for label in ["1", "2", "3"] {
+        let x = text_x(&format!(">{label}</text>"))
+            .unwrap_or_else(|| panic!("line number {label} missing: {svg}"));
+        assert!(
+            x < body_x,
+            "line number {label} at x={x} is not left of body x={body_x}"
+        );
+    }
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn render_svg_substitutes_fonts_from_font_map() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("fonts.hwp");
+    let map_path = dir.path().join("fonts.json");
+
+    // HwpWriter sets every run in 맑은 고딕.
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Hello")?;
+    writer.save_to_file(&file_path)?;
+    fs::write(&map_path, r#"{ "맑은 고딕": "Server Sans" }"#)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio", "--font-map"])
+        .arg(&map_path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    for (id, font_map, expected) in [
+        (
+            40,
+            Some(serde_json::json!({ "맑은 고딕": "Nanum Gothic" })),
+            "Nanum Gothic",
+        ),
+        (41, None, "Server Sans"),
+    ] {
+        let mut arguments = serde_json::json!({ "path": file_path.to_string_lossy(), "page": 1 });
+        if let Some(font_map) = font_map {
+            arguments["font_map"] = font_map;
+        }
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": { "name": "hwp.render_svg", "arguments": arguments }
+        });
+        writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+        stdin.flush()?;
+
+        let mut line = String::new();
+        stdout.read_line(&mut line)?;
+        let response: serde_json::Value = serde_json::from_str(line.trim())?;
+        let structured = response
+            .get("result")
+            .and_then(|value| value.get("structuredContent"))
+            .expect("structuredContent present");
+        let svg = structured
+            .get("pages")
+            .and_then(|value| value.get(0))
+            .and_then(|value| value.get("svg"))
+            .and_then(|value| value.as_str())
+            .expect("svg present");
+        assert!(
+            svg.contains(&format!("font-family=\"{expected}\"")),
+            "{svg}"
+        );
+        assert!(!svg.contains("맑은 고딕"), "{svg}");
+        let warnings = structured
+            .get("warnings")
+            .and_then(|value| value.as_array())
+            .expect("warnings present");
+        assert!(
+            warnings.iter().any(|warning| warning.as_str()
+                == Some(&format!("font substituted: '맑은 고딕' -> {expected}"))),
+            "{warnings:?}"
+        );
+    }
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn render_svg_appends_default_korean_font_fallbacks() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("fonts.hwp");
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Hello")?;
+    writer.save_to_file(&file_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 42,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.render_svg",
+            "arguments": { "path": file_path.to_string_lossy(), "page": 1 }
+        }
+    });
+    writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+    let svg = response
+        .get("result")
+        .and_then(|value| value.get("structuredContent"))
+        .and_then(|value| value.get("pages"))
+        .and_then(|value| value.get(0))
+        .and_then(|value| value.get("svg"))
+        .and_then(|value| value.as_str())
+        .expect("svg present");
+    assert!(
+        svg.contains("font-family=\"'맑은 고딕', 'Noto Sans CJK KR',"),
+        "{svg}"
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn render_svg_all_pages_ignores_page_selection() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("long.hwp");
+
+    let mut writer = HwpWriter::new();
+    for index in 0..200 {
+        writer.add_paragraph(&format!(
+            "문단 {index}: 모든 페이지를 렌더링하기 위한 긴 문서"
+        ))?;
+    }
+    writer.save_to_file(&file_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let mut call = |id: u64,
+                    arguments: serde_json::Value|
+     -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": { "name": "hwp.render_svg", "arguments": arguments }
+        });
+        writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+        stdin.flush()?;
+        let mut line = String::new();
+        stdout.read_line(&mut line)?;
+        let response: serde_json::Value = serde_json::from_str(line.trim())?;
+        Ok(response.get("result").cloned().expect("result present"))
+    };
+
+    let result = call(
+        41,
+        serde_json::json!({
+            "path": file_path.to_string_lossy(),
+            "all": true,
+            "page": 999
+        }),
+    )?;
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    let pages = result["structuredContent"]["pages"]
+        .as_array()
+        .expect("pages array");
+    assert!(pages.len() > 1, "{result}");
+    for (index, page) in pages.iter().enumerate() {
+        assert_eq!(page["page"].as_u64(), Some(index as u64 + 1));
+        assert!(
+            page["svg"]
+                .as_str()
+                .is_some_and(|svg| svg.starts_with("<svg"))
+        );
+    }
+
+    let resource = call(
+        42,
+        serde_json::json!({
+            "path": file_path.to_string_lossy(),
+            "all": true,
+            "output": "resource"
+        }),
+    )?;
+    assert_eq!(
+        resource.get("isError").and_then(|v| v.as_bool()),
+        Some(false)
+    );
+    let files = resource["structuredContent"]["pages"]
+        .as_array()
+        .expect("pages array");
+    assert_eq!(files.len(), pages.len());
+    for file in files {
+        let path = file["path"].as_str().expect("path present");
+        assert!(fs::read_to_string(path)?.starts_with("<svg"));
+    }
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn render_svg_accepts_page_ranges() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("long.hwp");
+
+    let mut writer = HwpWriter::new();
+    for index in 0..200 {
+        writer.add_paragraph(&format!("문단 {index}: 쪽 범위를 확인하기 위한 긴 문서"))?;
+    }
+    writer.save_to_file(&file_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let mut call =
+        |id: u64, range: &str| -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+            let request = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "method": "tools/call",
+                "params": {
+                    "name": "hwp.render_svg",
+                    "arguments": { "path": file_path.to_string_lossy(), "range": range }
+                }
+            });
+            writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+            stdin.flush()?;
+            let mut line = String::new();
+            stdout.read_line(&mut line)?;
+            let response: serde_json::Value = serde_json::from_str(line.trim())?;
+            Ok(response.get("result").cloned().expect("result present"))
+        };
+
+    let result = call(50, "2-3, 1,2")?;
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    let pages: Vec<u64> = result["structuredContent"]["pages"]
+        .as_array()
+        .expect("pages array")
+        .iter()
+        .filter_map(|page| page["page"].as_u64())
+        .collect();
+    assert_eq!(pages, vec![2, 3, 1]);
+
+    for (id, range) in [(51, "5-2"), (52, "0-1"), (53, "1,,2")] {
+        let result = call(id, range)?;
+        assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(true));
+        assert_eq!(
+            result["structuredContent"]["error"]["kind"].as_str(),
+            Some("invalid_input"),
+            "{range}: {result}"
+        );
+    }
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn render_svg_rasterizes_png_through_resvg() -> Result<(), Box<dyn std::error::Error>> {
+    use base64::Engine;
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempdir()?;
+    let file_path = dir.path().join("sample.hwp");
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("안녕하세요")?;
+    writer.save_to_file(&file_path)?;
+
+    // Stand-in rasterizer: records its arguments and stdin, then emits a PNG signature.
+    let bin_dir = dir.path().join("bin");
+    fs::create_dir(&bin_dir)?;
+    let args_log = dir.path().join("args.txt");
+    let svg_log = dir.path().join("input.svg");
+    let script = bin_dir.join("resvg");
+    fs::write(
+        &script,
+        format!(
+            "#!/bin/sh\n[ \"$1\" = \"--version\" ] && exit 0\necho \"$@\" > '{}'\ncat > '{}'\nprintf '\\211PNG\\r\\n\\032\\nfake'\n",
+            args_log.display(),
+            svg_log.display()
+        ),
+    )?;
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o755))?;
+    let path = format!(
+        "{}:{}",
+        bin_dir.display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .env("PATH", path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let mut call = |id: u64,
+                    arguments: serde_json::Value|
+     -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": { "name": "hwp.render_svg", "arguments": arguments }
+        });
+        writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+        stdin.flush()?;
+        let mut line = String::new();
+        stdout.read_line(&mut line)?;
+        let response: serde_json::Value = serde_json::from_str(line.trim())?;
+        Ok(response.get("result").cloned().expect("result present"))
+    };
+
+    let inline = call(
+        60,
+        serde_json::json!({ "path": file_path.to_string_lossy(), "as": "png", "dpi": 192 }),
+    )?;
+    assert_eq!(inline.get("isError").and_then(|v| v.as_bool()), Some(false));
+    assert_eq!(
+        inline["structuredContent"]["rasterizer"].as_str(),
+        Some("resvg")
+    );
+    let page = &inline["structuredContent"]["pages"][0];
+    assert_eq!(page["mimeType"].as_str(), Some("image/png"));
+    let png =
+        base64::engine::general_purpose::STANDARD.decode(page["base64"].as_str().unwrap_or(""))?;
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+    let args = fs::read_to_string(&args_log)?;
+    assert!(args.contains("--zoom 2"), "{args}");
+    assert!(args.contains("Noto Sans CJK KR"), "{args}");
+    assert!(fs::read_to_string(&svg_log)?.starts_with("<svg"));
+
+    let resource = call(
+        61,
+        serde_json::json!({ "path": file_path.to_string_lossy(), "as": "png", "output": "resource" }),
+    )?;
+    assert_eq!(
+        resource.get("isError").and_then(|v| v.as_bool()),
+        Some(false)
+    );
+    let written = resource["structuredContent"]["pages"][0]["path"]
+        .as_str()
+        .expect("path present");
+    assert!(written.ends_with(".png"), "{written}");
+    assert!(fs::read(written)?.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+    for (id, arguments) in [
+        (
+            62,
+            serde_json::json!({ "path": file_path.to_string_lossy(), "as": "jpeg" }),
+        ),
+        (
+            63,
+            serde_json::json!({ "path": file_path.to_string_lossy(), "as": "png", "scale": 0 }),
+        ),
+        (
+            64,
+            serde_json::json!({ "path": file_path.to_string_lossy(), "as": "png", "scale": 2, "dpi": 192 }),
+        ),
+    ] {
+        let result = call(id, arguments)?;
+        assert_eq!(
+            result["structuredContent"]["error"]["kind"].as_str(),
+            Some("invalid_input"),
+            "{result}"
+        );
+    }
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn render_svg_resources_land_in_configured_output_dir() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("sample.hwp");
+    let env_dir = dir.path().join("from-env");
+    let arg_dir = dir.path().join("from-arg").join("nested");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Hello")?;
+    writer.save_to_file(&file_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .env("MCP_HWP_OUTPUT_DIR", &env_dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let mut render = |id: u64,
+                      output_dir: Option<&std::path::Path>|
+     -> Result<String, Box<dyn std::error::Error>> {
+        let mut arguments = serde_json::json!({
+            "path": file_path.to_string_lossy(),
+            "output": "resource"
+        });
+        if let Some(output_dir) = output_dir {
+            arguments["output_dir"] = serde_json::json!(output_dir.to_string_lossy());
+        }
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": { "name": "hwp.render_svg", "arguments": arguments }
+        });
+        writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+        stdin.flush()?;
+        let mut line = String::new();
+        stdout.read_line(&mut line)?;
+        let response: serde_json::Value = serde_json::from_str(line.trim())?;
+        assert_eq!(response["result"]["isError"], false, "{response}");
+        Ok(response["result"]["structuredContent"]["pages"][0]["path"]
+            .as_str()
+            .expect("path present")
+            .to_string())
+    };
+
+    let first = render(1, None)?;
+    let second = render(2, None)?;
+    assert!(
+        std::path::Path::new(&first).starts_with(&env_dir),
+        "{first}"
+    );
+    assert_ne!(first, second);
+    let file_name = std::path::Path::new(&first)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("");
+    assert!(
+        file_name.starts_with(&format!("hwp-render-{}-", child.id())),
+        "{file_name}"
+    );
+
+    let overridden = render(3, Some(&arg_dir))?;
+    assert!(
+        std::path::Path::new(&overridden).starts_with(&arg_dir),
+        "{overridden}"
+    );
+    assert!(fs::metadata(&overridden).is_ok());
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn render_svg_reports_progress_when_token_given() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("long.hwp");
+
+    let mut writer = HwpWriter::new();
+    for index in 0..200 {
+        writer.add_paragraph(&format!("문단 {index}: 진행 상황을 알리기 위한 긴 문서"))?;
+    }
+    writer.save_to_file(&file_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    // Returns the notifications sent before the response, then the response itself.
+    let mut call = |id: u64,
+                    params: serde_json::Value|
+     -> Result<
+        (Vec<serde_json::Value>, serde_json::Value),
+        Box<dyn std::error::Error>,
+    > {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": params
+        });
+        writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+        stdin.flush()?;
+        let mut notifications = Vec::new();
+        loop {
+            let mut line = String::new();
+            stdout.read_line(&mut line)?;
+            let message: serde_json::Value = serde_json::from_str(line.trim())?;
+            if message.get("id").is_some() {
+                return Ok((notifications, message));
+            }
+            notifications.push(message);
+        }
+    };
+
+    let (notifications, response) = call(
+        1,
+        serde_json::json!({
+            "name": "hwp.render_svg",
+            "arguments": { "path": file_path.to_string_lossy(), "all": true },
+            "_meta": { "progressToken": "render-1" }
+        }),
+    )?;
+    assert_eq!(response["id"], 1);
+    assert_eq!(response["result"]["isError"], false, "{response}");
+    let pages = response["result"]["structuredContent"]["pages"]
+        .as_array()
+        .expect("pages array")
+        .len() as u64;
+    assert!(pages > 1);
+    assert_eq!(notifications.len() as u64, pages);
+    for (done, notification) in (1..).zip(&notifications) {
+        assert_eq!(notification["method"], "notifications/progress");
+        assert!(notification.get("id").is_none());
+        assert_eq!(notification["params"]["progressToken"], "render-1");
+        assert_eq!(notification["params"]["progress"], done);
+        assert_eq!(notification["params"]["total"], pages);
+    }
+
+    let (notifications, response) = call(
+        2,
+        serde_json::json!({
+            "name": "hwp.render_svg",
+            "arguments": { "path": file_path.to_string_lossy(), "all": true }
+        }),
+    )?;
+    assert_eq!(response["id"], 2);
+    assert!(notifications.is_empty());
+
+    let _ = child.kill();
+    Ok(())
+}
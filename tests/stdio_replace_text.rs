@@ -0,0 +1,184 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use hwpers::hwpx::{HwpxTextStyle, StyledText};
+use hwpers::{HwpReader, HwpWriter, HwpxReader, HwpxWriter};
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+use tempfile::tempdir;
+
+fn call_tool(
+    arguments: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": { "name": "hwp.replace_text", "arguments": arguments }
+    });
+    writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+    stdin.flush()?;
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    let _ = child.kill();
+
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+    Ok(response.get("result").cloned().expect("result present"))
+}
+
+fn paragraph_texts(document: &hwpers::HwpDocument) -> Vec<String> {
+    document
+        .sections()
+        .flat_map(|section| section.paragraphs.iter())
+        .filter_map(|paragraph| paragraph.text.as_ref())
+        .map(|text| text.content.trim_end_matches(['\r', '\n']).to_string())
+        .collect()
+}
+
+#[test]
+fn replace_text_rewrites_paragraphs_and_keeps_format() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("contract.hwp");
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("갑은 을에게 대금을 지급한다.")?;
+    writer.add_paragraph("을은 갑에게 물품을 인도한다.")?;
+    writer.save_to_file(&file_path)?;
+
+    let result = call_tool(serde_json::json!({
+        "path": file_path.to_string_lossy(),
+        "find": "갑",
+        "replace": "주식회사 가나"
+    }))?;
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    let structured = result.get("structuredContent").expect("structuredContent");
+    assert_eq!(structured["replacements"], 2);
+    assert_eq!(structured["to"], "hwp");
+
+    let bytes = STANDARD.decode(structured["base64"].as_str().expect("base64"))?;
+    let document = HwpReader::from_bytes(&bytes)?;
+    let texts = paragraph_texts(&document);
+    assert!(
+        texts.contains(&"주식회사 가나은 을에게 대금을 지급한다.".to_string()),
+        "{texts:?}"
+    );
+    assert!(
+        texts.contains(&"을은 주식회사 가나에게 물품을 인도한다.".to_string()),
+        "{texts:?}"
+    );
+    Ok(())
+}
+
+#[test]
+fn replace_text_honors_max_replacements() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("draft.hwpx");
+    let output_path = dir.path().join("final.hwpx");
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("DRAFT one")?;
+    writer.add_paragraph("DRAFT two DRAFT three")?;
+    writer.save_to_file(&file_path)?;
+
+    let result = call_tool(serde_json::json!({
+        "path": file_path.to_string_lossy(),
+        "find": "DRAFT",
+        "replace": "FINAL",
+        "max_replacements": 2,
+        "output_path": output_path.to_string_lossy()
+    }))?;
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    let structured = result.get("structuredContent").expect("structuredContent");
+    assert_eq!(structured["replacements"], 2);
+    assert_eq!(structured["to"], "hwpx");
+    let warnings = structured["warnings"].as_array().expect("warnings");
+    assert!(
+        warnings.iter().any(|w| w
+            .as_str()
+            .is_some_and(|w| w.contains("1 further occurrences"))),
+        "{warnings:?}"
+    );
+
+    let document = HwpxReader::from_file(&output_path)?;
+    let texts = paragraph_texts(&document);
+    assert!(texts.contains(&"FINAL one".to_string()), "{texts:?}");
+    assert!(
+        texts.contains(&"FINAL two DRAFT three".to_string()),
+        "{texts:?}"
+    );
+    Ok(())
+}
+
+#[test]
+fn replace_text_skips_matches_spanning_styled_runs() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("styled.hwpx");
+    // hwpers trims whitespace at HWPX run edges, so the runs carry none.
+    let mut writer = HwpxWriter::new();
+    writer.add_mixed_styled_paragraph(vec![
+        StyledText::new("Total:"),
+        StyledText::with_style("100", HwpxTextStyle::new().bold()),
+        StyledText::new("won:1"),
+    ])?;
+    writer.save_to_file(&file_path)?;
+
+    let result = call_tool(serde_json::json!({
+        "path": file_path.to_string_lossy(),
+        "find": ":1",
+        "replace": "=2"
+    }))?;
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    let structured = result.get("structuredContent").expect("structuredContent");
+    assert_eq!(structured["replacements"], 1);
+    let warnings = structured["warnings"].as_array().expect("warnings");
+    assert!(
+        warnings.iter().any(|w| w.as_str().is_some_and(|w| {
+            w.contains("offset 5") && w.contains("spans multiple styled runs")
+        })),
+        "{warnings:?}"
+    );
+
+    let bytes = STANDARD.decode(structured["base64"].as_str().expect("base64"))?;
+    let document = HwpxReader::from_bytes(&bytes)?;
+    assert!(
+        paragraph_texts(&document).contains(&"Total:100won=2".to_string()),
+        "{:?}",
+        paragraph_texts(&document)
+    );
+    let paragraph = document
+        .sections()
+        .flat_map(|section| section.paragraphs.iter())
+        .find(|paragraph| paragraph.text.is_some())
+        .expect("paragraph");
+    let runs: Vec<u32> = paragraph
+        .char_shapes
+        .as_ref()
+        .expect("char shapes")
+        .char_positions
+        .iter()
+        .map(|position| position.position)
+        .collect();
+    assert_eq!(runs, vec![6, 9]);
+    Ok(())
+}
+
+#[test]
+fn replace_text_requires_find() -> Result<(), Box<dyn std::error::Error>> {
+    let result = call_tool(serde_json::json!({
+        "base64": "AAAA",
+        "find": "",
+        "replace": "x"
+    }))?;
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(true));
+    assert_eq!(
+        result["structuredContent"]["error"]["kind"],
+        "invalid_input"
+    );
+    Ok(())
+}
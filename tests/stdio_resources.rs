@@ -0,0 +1,131 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+use tempfile::tempdir;
+
+fn send_request(
+    stdin: &mut std::process::ChildStdin,
+    stdout: &mut BufReader<std::process::ChildStdout>,
+    request: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let serialized = serde_json::to_string(&request)?;
+    writeln!(stdin, "{serialized}")?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+    Ok(response)
+}
+
+#[test]
+fn written_files_are_listed_and_readable_as_resources() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let output_path = dir.path().join("created.hwp");
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let initialize = send_request(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }),
+    )?;
+    assert!(initialize["result"]["capabilities"]["resources"].is_object());
+
+    let create = send_request(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.create_document",
+                "arguments": {
+                    "text": "Resource body",
+                    "output_path": output_path.to_string_lossy()
+                }
+            }
+        }),
+    )?;
+    let document_uri = create["result"]["structuredContent"]["uri"]
+        .as_str()
+        .expect("uri present")
+        .to_string();
+
+    let render = send_request(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 3,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.render_svg",
+                "arguments": { "path": output_path.to_string_lossy(), "output": "resource" }
+            }
+        }),
+    )?;
+    let svg_uri = render["result"]["structuredContent"]["pages"][0]["uri"]
+        .as_str()
+        .expect("svg uri present")
+        .to_string();
+
+    let list = send_request(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({ "jsonrpc": "2.0", "id": 4, "method": "resources/list" }),
+    )?;
+    let resources = list["result"]["resources"].as_array().expect("resources");
+    let mime_of = |uri: &str| {
+        resources
+            .iter()
+            .find(|resource| resource["uri"] == uri)
+            .and_then(|resource| resource["mimeType"].as_str())
+            .map(str::to_string)
+    };
+    assert_eq!(mime_of(&document_uri).as_deref(), Some("application/x-hwp"));
+    assert_eq!(mime_of(&svg_uri).as_deref(), Some("image/svg+xml"));
+
+    let read = send_request(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 5,
+            "method": "resources/read",
+            "params": { "uri": document_uri }
+        }),
+    )?;
+    let contents = &read["result"]["contents"][0];
+    assert_eq!(contents["uri"], document_uri.as_str());
+    assert_eq!(contents["mimeType"], "application/x-hwp");
+    let blob = STANDARD.decode(contents["blob"].as_str().expect("blob present"))?;
+    assert_eq!(blob, std::fs::read(&output_path)?);
+
+    // Only files written by tools are exposed.
+    for (id, uri) in [(6, "file:///etc/passwd"), (7, "file:///nonexistent.hwp")] {
+        let missing = send_request(
+            &mut stdin,
+            &mut stdout,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "method": "resources/read",
+                "params": { "uri": uri }
+            }),
+        )?;
+        assert_eq!(missing["error"]["code"], -32002, "{missing}");
+    }
+
+    let _ = child.kill();
+    Ok(())
+}
@@ -108,6 +108,371 @@ fn create_and_extract_rich_hwp() -> Result<(), Box<dyn std::error::Error>> {
     assert!(saw_table);
     assert!(saw_image);
 
+    let summary = extract_result
+        .get("content")
+        .and_then(|v| v.get(0))
+        .and_then(|v| v.get("text"))
+        .and_then(|v| v.as_str())
+        .expect("summary text");
+    let paragraphs = blocks
+        .iter()
+        .filter(|b| b.get("type").and_then(|v| v.as_str()) == Some("paragraph"))
+        .count();
+    assert_eq!(
+        summary,
+        format!(
+            "extracted {} blocks: {paragraphs} paragraphs, 1 table, 1 image",
+            blocks.len()
+        )
+    );
+
+    let html_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 82,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_rich",
+            "arguments": {
+                "base64": base64,
+                "format": "hwp",
+                "images": "inline",
+                "as_html": true
+            }
+        }
+    });
+    let html_response = send_request(&mut stdin, &mut stdout, html_request)?;
+    let html_result = html_response.get("result").expect("result present");
+    let html = html_result
+        .get("structuredContent")
+        .and_then(|v| v.get("html"))
+        .and_then(|v| v.as_str())
+        .expect("html present");
+    assert!(html.contains("<meta charset=\"utf-8\">"), "{html}");
+    assert!(html.contains("<table"), "{html}");
+    assert!(html.contains("<td>A</td><td>B</td>"), "{html}");
+    assert!(html.contains("<img "), "{html}");
+    assert_eq!(
+        html_result
+            .get("content")
+            .and_then(|v| v.get(1))
+            .and_then(|v| v.get("text"))
+            .and_then(|v| v.as_str()),
+        Some(html)
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn create_equation_block_round_trips_markup() -> Result<(), Box<dyn std::error::Error>> {
+    let latex = r"E = mc^{2} + \frac{a}{b}";
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    for (id, to) in [(82, "hwp"), (84, "hwpx")] {
+        let create_request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.create_rich_document",
+                "arguments": {
+                    "to": to,
+                    "document": {
+                        "blocks": [
+                            {"type": "paragraph", "text": "Mass-energy equivalence:"},
+                            {"type": "equation", "latex": latex}
+                        ]
+                    }
+                }
+            }
+        });
+        let create_response = send_request(&mut stdin, &mut stdout, create_request)?;
+        let create_result = create_response.get("result").expect("result present");
+        assert_eq!(
+            create_result.get("isError").and_then(|v| v.as_bool()),
+            Some(false)
+        );
+        let base64 = create_result
+            .get("structuredContent")
+            .and_then(|v| v.get("base64"))
+            .and_then(|v| v.as_str())
+            .expect("base64 present");
+
+        let extract_request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id + 1,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.extract_rich",
+                "arguments": { "base64": base64 }
+            }
+        });
+        let extract_response = send_request(&mut stdin, &mut stdout, extract_request)?;
+        let blocks = extract_response
+            .get("result")
+            .and_then(|v| v.get("structuredContent"))
+            .and_then(|v| v.get("blocks"))
+            .and_then(|v| v.as_array())
+            .expect("blocks array");
+
+        assert!(
+            blocks.iter().any(|b| {
+                b.get("type").and_then(|v| v.as_str()) == Some("paragraph")
+                    && b.get("text").and_then(|v| v.as_str()).map(str::trim) == Some(latex)
+            }),
+            "{to}: equation markup missing from {blocks:?}"
+        );
+    }
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn extract_rich_returns_partial_blocks_on_timeout() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let file_path = dir.path().join("large.hwp");
+
+    let mut writer = hwpers::HwpWriter::new();
+    for index in 0..2000 {
+        writer.add_paragraph(&format!("Paragraph number {index}"))?;
+    }
+    writer.save_to_file(&file_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 86,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_rich",
+            "arguments": {
+                "path": file_path.to_string_lossy(),
+                "timeout_ms": 0,
+                "partial_on_timeout": true
+            }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, request)?;
+    let result = response.get("result").expect("result present");
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    let structured = result.get("structuredContent").expect("structuredContent");
+    assert_eq!(
+        structured.get("timed_out").and_then(|v| v.as_bool()),
+        Some(true)
+    );
+    let blocks = structured
+        .get("blocks")
+        .and_then(|v| v.as_array())
+        .expect("blocks array");
+    assert!(!blocks.is_empty());
+    assert!(blocks.len() < 2000);
+    assert_eq!(
+        blocks[0]
+            .get("text")
+            .and_then(|v| v.as_str())
+            .map(str::trim),
+        Some("Paragraph number 0")
+    );
+    assert!(
+        structured
+            .get("warnings")
+            .and_then(|v| v.as_array())
+            .is_some_and(|warnings| warnings
+                .iter()
+                .any(|w| w.as_str().is_some_and(|w| w.starts_with("partial:"))))
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn create_bar_chart_embeds_image() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let create_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 87,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": {
+                "to": "hwp",
+                "document": {
+                    "blocks": [{
+                        "type": "chart",
+                        "chart_type": "bar",
+                        "header_row": true,
+                        "caption": "Quarterly sales",
+                        "rows": [["Quarter", "Sales"], ["Q1", 12], ["Q2", "18.5"], ["Q3", 9]]
+                    }]
+                }
+            }
+        }
+    });
+    let create_response = send_request(&mut stdin, &mut stdout, create_request)?;
+    let create_result = create_response.get("result").expect("result present");
+    assert_eq!(
+        create_result.get("isError").and_then(|v| v.as_bool()),
+        Some(false)
+    );
+    let base64 = create_result
+        .get("structuredContent")
+        .and_then(|v| v.get("base64"))
+        .and_then(|v| v.as_str())
+        .expect("base64 present");
+
+    let extract_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 88,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_rich",
+            "arguments": { "base64": base64 }
+        }
+    });
+    let extract_response = send_request(&mut stdin, &mut stdout, extract_request)?;
+    let blocks = extract_response
+        .get("result")
+        .and_then(|v| v.get("structuredContent"))
+        .and_then(|v| v.get("blocks"))
+        .and_then(|v| v.as_array())
+        .expect("blocks array");
+    assert!(blocks.iter().any(|b| {
+        b.get("type").and_then(|v| v.as_str()) == Some("image")
+            && b.get("caption").and_then(|v| v.as_str()) == Some("Quarterly sales")
+    }));
+
+    let invalid_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 89,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": {
+                "document": {
+                    "blocks": [{
+                        "type": "chart",
+                        "rows": [["Q1", 12], ["Q2", "lots"]]
+                    }]
+                }
+            }
+        }
+    });
+    let invalid_response = send_request(&mut stdin, &mut stdout, invalid_request)?;
+    let error = invalid_response
+        .get("result")
+        .and_then(|v| v.get("structuredContent"))
+        .and_then(|v| v.get("error"))
+        .expect("error present");
+    assert_eq!(
+        error.get("kind").and_then(|v| v.as_str()),
+        Some("invalid_input")
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn create_hwp_table_keeps_merged_cells() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let create_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": {
+                "to": "hwp",
+                "document": {
+                    "blocks": [
+                        {"type": "paragraph", "text": "Before"},
+                        {"type": "table", "rows": [
+                            [{"content": "Total", "col_span": 2}, "", "9"],
+                            ["a", "b", "c"]
+                        ]},
+                        {"type": "paragraph", "text": "After"}
+                    ]
+                }
+            }
+        }
+    });
+    let create_response = send_request(&mut stdin, &mut stdout, create_request)?;
+    let base64 = create_response["result"]["structuredContent"]["base64"]
+        .as_str()
+        .expect("base64 present")
+        .to_string();
+
+    let extract_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_rich",
+            "arguments": { "base64": base64, "format": "hwp" }
+        }
+    });
+    let extract_response = send_request(&mut stdin, &mut stdout, extract_request)?;
+    let result = &extract_response["result"];
+    assert_eq!(result["isError"], false, "{result}");
+    let blocks = result["structuredContent"]["blocks"]
+        .as_array()
+        .expect("blocks array");
+
+    let table = blocks
+        .iter()
+        .find(|block| block["type"] == "table")
+        .expect("table block");
+    assert_eq!(
+        table["rows"],
+        serde_json::json!([["Total", "", "9"], ["a", "b", "c"]]),
+        "{result}"
+    );
+    assert_eq!(
+        table["spans"],
+        serde_json::json!([{"row": 0, "col": 0, "row_span": 1, "col_span": 2}]),
+        "{result}"
+    );
+    let texts: Vec<&str> = blocks
+        .iter()
+        .filter_map(|block| block["text"].as_str())
+        .collect();
+    assert_eq!(texts, ["Before", "After"], "{result}");
+
     let _ = child.kill();
     Ok(())
 }
@@ -0,0 +1,155 @@
+use hwpers::HwpWriter;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+use tempfile::tempdir;
+
+fn send_request(
+    stdin: &mut impl Write,
+    stdout: &mut impl BufRead,
+    request: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let serialized = serde_json::to_string(&request)?;
+    writeln!(stdin, "{serialized}")?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    Ok(serde_json::from_str(line.trim())?)
+}
+
+fn search(
+    stdin: &mut impl Write,
+    stdout: &mut impl BufRead,
+    arguments: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": { "name": "hwp.search_text", "arguments": arguments }
+    });
+    let response = send_request(stdin, stdout, request)?;
+    Ok(response.get("result").cloned().expect("result present"))
+}
+
+#[test]
+fn search_text_returns_offsets_and_context() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("search.hwp");
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("제1조 (목적) 이 계약은 Apple 제품 공급에 관한 사항을 정한다.")?;
+    writer.add_paragraph("제2조 (정의) apple은 공급 품목을 말한다.")?;
+    writer.save_to_file(&file_path)?;
+    let path = file_path.to_string_lossy();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let result = search(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({ "path": path, "query": "apple" }),
+    )?;
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    assert_eq!(
+        result["content"][0]["text"].as_str(),
+        Some("found 2 matches for \"apple\"")
+    );
+    let matches = result["structuredContent"]["matches"]
+        .as_array()
+        .expect("matches array");
+    assert_eq!(matches.len(), 2);
+    assert_eq!(matches[0]["paragraph_index"], 0);
+    assert_eq!(matches[0]["char_offset"], 15);
+    assert_eq!(matches[0]["text"], "Apple");
+    assert_eq!(
+        matches[0]["context"],
+        "제1조 (목적) 이 계약은 Apple 제품 공급에 관한 사항을 정한다."
+    );
+    assert_eq!(matches[1]["paragraph_index"], 1);
+    assert_eq!(matches[1]["char_offset"], 9);
+
+    let result = search(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({ "path": path, "query": "apple", "case_sensitive": true }),
+    )?;
+    assert_eq!(result["structuredContent"]["total_matches"], 1);
+
+    let result = search(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({ "path": path, "query": r"제\d+조", "regex": true }),
+    )?;
+    let matches = result["structuredContent"]["matches"]
+        .as_array()
+        .expect("matches array");
+    let texts: Vec<_> = matches.iter().map(|m| m["text"].clone()).collect();
+    assert_eq!(texts, ["제1조", "제2조"]);
+
+    let result = search(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({ "path": path, "query": "(unclosed", "regex": true }),
+    )?;
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(true));
+    assert_eq!(
+        result["structuredContent"]["error"]["kind"].as_str(),
+        Some("invalid_input")
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn search_text_regex_handles_long_paragraphs() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("long.hwp");
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Intro")?;
+    writer.add_paragraph(&format!("{}b", "a".repeat(12_000)))?;
+    writer.save_to_file(&file_path)?;
+    let path = file_path.to_string_lossy();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let result = search(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({ "path": path, "query": "a+b", "regex": true }),
+    )?;
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    let matches = result["structuredContent"]["matches"]
+        .as_array()
+        .expect("matches array");
+    assert_eq!(matches.len(), 1, "{result}");
+    assert_eq!(matches[0]["char_offset"], 0);
+    assert_eq!(
+        matches[0]["text"].as_str().map(|text| text.chars().count()),
+        Some(12_001)
+    );
+
+    let result = search(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({ "path": path, "query": "(a*)*c", "regex": true }),
+    )?;
+    assert_eq!(result["structuredContent"]["total_matches"], 0, "{result}");
+
+    let _ = child.kill();
+    Ok(())
+}
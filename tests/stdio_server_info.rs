@@ -0,0 +1,171 @@
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+
+fn send_request(
+    stdin: &mut impl Write,
+    stdout: &mut impl BufRead,
+    request: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let serialized = serde_json::to_string(&request)?;
+    writeln!(stdin, "{serialized}")?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    Ok(serde_json::from_str(line.trim())?)
+}
+
+/// The `hwpers` version recorded in `Cargo.lock`, which the reported version must track.
+fn locked_hwpers_version() -> String {
+    let lock = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.lock"));
+    let mut lines = lock.lines();
+    while let Some(line) = lines.next() {
+        if line == "name = \"hwpers\"" {
+            let version = lines.next().expect("version line");
+            return version
+                .trim_start_matches("version = \"")
+                .trim_end_matches('"')
+                .to_string();
+        }
+    }
+    panic!("hwpers missing from Cargo.lock");
+}
+
+#[test]
+fn server_info_reports_versions_limits_and_formats() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let response = send_request(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "hwp.server_info", "arguments": {} }
+        }),
+    )?;
+    let result = &response["result"];
+    assert_eq!(result["isError"], false);
+    let server = &result["structuredContent"]["server"];
+    assert_eq!(server["name"], "mcp-hwp");
+    assert_eq!(server["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(server["hwpers_version"], locked_hwpers_version());
+    assert_eq!(server["limits"]["max_input_bytes"], 50 * 1024 * 1024);
+    assert_eq!(server["limits"]["max_output_bytes"], 20 * 1024 * 1024);
+    assert_eq!(server["limits"]["max_svg_output_bytes"], 50 * 1024 * 1024);
+    assert_eq!(server["limits"]["max_parse_ms"], 10_000);
+    assert_eq!(server["limits"]["max_table_rows"], 10_000);
+    assert_eq!(server["limits"]["max_table_cols"], 256);
+    assert_eq!(
+        server["formats"]["input"],
+        serde_json::json!(["hwp", "hwpx"])
+    );
+    assert!(
+        server["formats"]["output"]
+            .as_array()
+            .expect("output formats")
+            .contains(&serde_json::json!("md"))
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn limits_come_from_environment() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let input_path = dir.path().join("large.hwp");
+    std::fs::write(&input_path, vec![0u8; 2048])?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .env("MCP_HWP_MAX_INPUT_BYTES", "1024")
+        .env("MCP_HWP_MAX_PARSE_MS", "soon")
+        .env("MCP_HWP_MAX_TABLE_COLS", "8")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let info = send_request(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "hwp.server_info", "arguments": {} }
+        }),
+    )?;
+    let limits = &info["result"]["structuredContent"]["server"]["limits"];
+    assert_eq!(limits["max_input_bytes"], 1024);
+    assert_eq!(limits["max_parse_ms"], 10_000);
+    assert_eq!(limits["max_output_bytes"], 20 * 1024 * 1024);
+    assert_eq!(limits["max_table_cols"], 8);
+
+    let rejected = send_request(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.extract_text",
+                "arguments": { "path": input_path.to_string_lossy() }
+            }
+        }),
+    )?;
+    let error = &rejected["result"]["structuredContent"]["error"];
+    assert_eq!(error["kind"], "too_large");
+    assert!(
+        error["message"]
+            .as_str()
+            .unwrap_or("")
+            .contains("(max 1024)")
+    );
+
+    let wide = send_request(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 3,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.create_rich_document",
+                "arguments": {
+                    "to": "hwp",
+                    "document": {
+                        "blocks": [
+                            { "type": "table", "rows": [["1", "2", "3", "4", "5", "6", "7", "8", "9"]] }
+                        ]
+                    }
+                }
+            }
+        }),
+    )?;
+    let error = &wide["result"]["structuredContent"]["error"];
+    assert_eq!(error["kind"], "invalid_input");
+    assert_eq!(
+        error["message"],
+        "document.blocks[0]: table has 9 columns, exceeding the limit of 8"
+    );
+
+    drop(stdin);
+    let output = child.wait_with_output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("MCP_HWP_MAX_PARSE_MS"), "{stderr}");
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use hwpers::HwpWriter;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+use tempfile::tempdir;
+
+fn send_request(
+    stdin: &mut impl Write,
+    stdout: &mut impl BufRead,
+    request: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let serialized = serde_json::to_string(&request)?;
+    writeln!(stdin, "{serialized}")?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    Ok(serde_json::from_str(line.trim())?)
+}
+
+fn similarity_score(result: &serde_json::Value) -> f64 {
+    result
+        .get("structuredContent")
+        .and_then(|value| value.get("similarity"))
+        .and_then(|value| value.as_f64())
+        .expect("similarity present")
+}
+
+#[test]
+fn similarity_scores_identical_and_different_documents() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let first_path = dir.path().join("first.hwp");
+    let copy_path = dir.path().join("copy.hwp");
+    let other_path = dir.path().join("other.hwp");
+
+    for path in [&first_path, &copy_path] {
+        let mut writer = HwpWriter::new();
+        writer.add_paragraph(
+            "The quarterly budget review covers staffing, travel and equipment costs.",
+        )?;
+        writer.add_paragraph(
+            "All departments must submit their estimates before the end of the month.",
+        )?;
+        writer.save_to_file(path)?;
+    }
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph(
+        "Spring planting guide for tomatoes, peppers and cucumbers in raised beds.",
+    )?;
+    writer
+        .add_paragraph("Water seedlings early in the morning and mulch to keep the soil moist.")?;
+    writer.save_to_file(&other_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let fingerprint_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.fingerprint",
+            "arguments": { "path": first_path.to_string_lossy() }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, fingerprint_request)?;
+    let result = response.get("result").expect("result present");
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    let signature = result
+        .get("structuredContent")
+        .and_then(|value| value.get("signature"))
+        .and_then(|value| value.as_array())
+        .expect("signature present");
+    assert_eq!(signature.len(), 64);
+
+    let identical_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.similarity",
+            "arguments": {
+                "left": { "path": first_path.to_string_lossy() },
+                "right": { "path": copy_path.to_string_lossy() }
+            }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, identical_request)?;
+    let result = response.get("result").expect("result present");
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    assert_eq!(similarity_score(result), 1.0);
+
+    let different_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 3,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.similarity",
+            "arguments": {
+                "left": { "path": first_path.to_string_lossy() },
+                "right": { "path": other_path.to_string_lossy() }
+            }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, different_request)?;
+    let result = response.get("result").expect("result present");
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    assert!(similarity_score(result) < 0.2);
+
+    let _ = child.kill();
+    Ok(())
+}
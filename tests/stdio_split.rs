@@ -0,0 +1,339 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use std::io::{BufRead, BufReader, Cursor, Read, Write};
+use std::process::{Command, Stdio};
+use tempfile::tempdir;
+use zip::write::SimpleFileOptions;
+
+fn send_request(
+    stdin: &mut impl Write,
+    stdout: &mut impl BufRead,
+    request: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let serialized = serde_json::to_string(&request)?;
+    writeln!(stdin, "{serialized}")?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    Ok(serde_json::from_str(line.trim())?)
+}
+
+#[test]
+fn split_by_heading_returns_one_part_per_h1() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    // The leading empty styled paragraph pins HWPX char shape 0 to the body size,
+    // which unstyled paragraphs fall back to.
+    let create_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": {
+                "to": "hwpx",
+                "document": {
+                    "blocks": [
+                        { "type": "paragraph", "text": "", "style": { "font_size": 10 } },
+                        { "type": "heading", "level": 1, "text": "Alpha" },
+                        { "type": "paragraph", "text": "Alpha body." },
+                        { "type": "heading", "level": 1, "text": "Beta" },
+                        { "type": "paragraph", "text": "Beta body." },
+                        { "type": "heading", "level": 1, "text": "Gamma" },
+                        { "type": "paragraph", "text": "Gamma body." }
+                    ]
+                }
+            }
+        }
+    });
+    let create_response = send_request(&mut stdin, &mut stdout, create_request)?;
+    let encoded = create_response
+        .get("result")
+        .and_then(|value| value.get("structuredContent"))
+        .and_then(|value| value.get("base64"))
+        .and_then(|value| value.as_str())
+        .expect("base64 present")
+        .to_string();
+
+    let split_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.split",
+            "arguments": {
+                "base64": encoded,
+                "by": "heading"
+            }
+        }
+    });
+    let split_response = send_request(&mut stdin, &mut stdout, split_request)?;
+    let split_result = split_response.get("result").expect("result present");
+    assert_eq!(
+        split_result.get("isError").and_then(|v| v.as_bool()),
+        Some(false)
+    );
+    let parts = split_result
+        .get("structuredContent")
+        .and_then(|value| value.get("parts"))
+        .and_then(|value| value.as_array())
+        .expect("parts present");
+    assert_eq!(parts.len(), 3);
+
+    for (part, heading) in parts.iter().zip(["Alpha", "Beta", "Gamma"]) {
+        assert_eq!(part.get("heading").and_then(|v| v.as_str()), Some(heading));
+        let bytes = STANDARD.decode(
+            part.get("base64")
+                .and_then(|value| value.as_str())
+                .expect("part base64 present"),
+        )?;
+        let document = hwpers::HwpxReader::from_bytes(&bytes)?;
+        let first = document
+            .sections()
+            .flat_map(|section| section.paragraphs.iter())
+            .filter_map(|paragraph| paragraph.text.as_ref())
+            .map(|text| text.content.trim().to_string())
+            .find(|text| !text.is_empty())
+            .expect("part has text");
+        assert_eq!(first, heading);
+        assert!(
+            document
+                .extract_text()
+                .contains(&format!("{heading} body."))
+        );
+    }
+
+    let _ = child.kill();
+    Ok(())
+}
+
+fn call_split(
+    stdin: &mut impl Write,
+    stdout: &mut impl BufRead,
+    arguments: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let response = send_request(
+        stdin,
+        stdout,
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "hwp.split", "arguments": arguments }
+        }),
+    )?;
+    let result = response.get("result").expect("result present");
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    Ok(result
+        .get("structuredContent")
+        .expect("structuredContent present")
+        .clone())
+}
+
+fn part_texts(
+    structured: &serde_json::Value,
+    format: &str,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let parts = structured
+        .get("parts")
+        .and_then(|value| value.as_array())
+        .expect("parts present");
+    let mut texts = Vec::new();
+    for part in parts {
+        let bytes = STANDARD.decode(
+            part.get("base64")
+                .and_then(|value| value.as_str())
+                .expect("part base64 present"),
+        )?;
+        let document = match format {
+            "hwpx" => hwpers::HwpxReader::from_bytes(&bytes)?,
+            _ => hwpers::HwpReader::from_bytes(&bytes)?,
+        };
+        texts.push(document.extract_text());
+    }
+    Ok(texts)
+}
+
+/// Writes an HWPX document with three paragraphs and sets `pageBreak="1"` on the
+/// paragraph holding `Second page`. hwpers always writes `pageBreak="0"`.
+fn hwpx_with_page_break() -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut writer = hwpers::HwpxWriter::new();
+    writer.add_paragraph("First page")?;
+    writer.add_paragraph("Second page")?;
+    writer.add_paragraph("Still second page")?;
+    let bytes = writer.to_bytes()?;
+
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))?;
+    let mut output = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index)?;
+        let name = file.name().to_string();
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)?;
+        if name == "Contents/section0.xml" {
+            let mut xml = String::from_utf8(data)?;
+            let text_at = xml.find(">Second page<").expect("paragraph present");
+            let paragraph_at = xml[..text_at].rfind("<hp:p ").expect("paragraph tag");
+            let flag_at = paragraph_at
+                + xml[paragraph_at..]
+                    .find("pageBreak=\"0\"")
+                    .expect("pageBreak attribute");
+            xml.replace_range(flag_at..flag_at + 13, "pageBreak=\"1\"");
+            data = xml.into_bytes();
+        }
+        output.start_file(name, SimpleFileOptions::default())?;
+        output.write_all(&data)?;
+    }
+    Ok(output.finish()?.into_inner())
+}
+
+#[test]
+fn split_by_page_break_cuts_before_flagged_paragraphs() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let structured = call_split(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({
+            "base64": STANDARD.encode(hwpx_with_page_break()?),
+            "by": "page_break"
+        }),
+    )?;
+    assert_eq!(
+        structured.get("by").and_then(|v| v.as_str()),
+        Some("page_break")
+    );
+    let texts = part_texts(&structured, "hwpx")?;
+    assert_eq!(texts.len(), 2);
+    assert!(texts[0].contains("First page") && !texts[0].contains("Second page"));
+    assert!(texts[1].contains("Second page") && texts[1].contains("Still second page"));
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn split_by_section_groups_every_n_sections() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let first_path = dir.path().join("first.hwp");
+    let second_path = dir.path().join("second.hwp");
+
+    let mut writer = hwpers::HwpWriter::new();
+    writer.add_paragraph("First section")?;
+    writer.save_to_file(&first_path)?;
+    let mut writer = hwpers::HwpWriter::new();
+    writer.add_paragraph("Second section")?;
+    writer.save_to_file(&second_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let response = send_request(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.merge",
+                "arguments": {
+                    "inputs": [
+                        { "path": first_path.to_string_lossy() },
+                        { "path": second_path.to_string_lossy() }
+                    ],
+                    "to": "hwp",
+                    "separator": "section_break"
+                }
+            }
+        }),
+    )?;
+    let merged = response
+        .get("result")
+        .and_then(|value| value.get("structuredContent"))
+        .and_then(|value| value.get("base64"))
+        .and_then(|value| value.as_str())
+        .expect("base64 present")
+        .to_string();
+
+    let structured = call_split(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({ "base64": merged, "by": "section" }),
+    )?;
+    assert_eq!(structured.get("every").and_then(|v| v.as_u64()), Some(1));
+    let texts = part_texts(&structured, "hwp")?;
+    assert_eq!(texts.len(), 2);
+    assert!(texts[0].contains("First section") && !texts[0].contains("Second section"));
+    assert!(texts[1].contains("Second section"));
+
+    let structured = call_split(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({ "base64": merged, "by": "section", "every": 2 }),
+    )?;
+    assert_eq!(part_texts(&structured, "hwp")?.len(), 1);
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn split_by_page_break_without_breaks_returns_one_part() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let path = dir.path().join("single.hwp");
+    let mut writer = hwpers::HwpWriter::new();
+    writer.add_paragraph("Only page")?;
+    writer.add_paragraph("Still the only page")?;
+    writer.save_to_file(&path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let structured = call_split(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({ "path": path.to_string_lossy(), "by": "page_break" }),
+    )?;
+    let texts = part_texts(&structured, "hwp")?;
+    assert_eq!(texts.len(), 1);
+    assert!(texts[0].contains("Still the only page"));
+    let warnings = structured
+        .get("warnings")
+        .and_then(|value| value.as_array())
+        .expect("warnings present");
+    assert!(warnings.iter().any(|warning| {
+        warning.as_str()
+            == Some("no page breaks were found; returning the whole document as one part")
+    }));
+
+    let _ = child.kill();
+    Ok(())
+}
@@ -110,3 +110,54 @@ fn summarize_structure_round_trip() -> Result<(), Box<dyn std::error::Error>> {
     let _ = child.kill();
     Ok(())
 }
+
+#[test]
+fn summarize_structure_show_controls_escapes_tabs() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("tabs.hwp");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Name\tValue")?;
+    writer.save_to_file(&file_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 7,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.summarize_structure",
+            "arguments": {
+                "path": file_path.to_string_lossy(),
+                "show_controls": true
+            }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, request)?;
+    let result = response.get("result").expect("result present");
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+
+    let preview = result
+        .get("structuredContent")
+        .and_then(|value| value.get("sections"))
+        .and_then(|value| value.as_array())
+        .and_then(|sections| sections.first())
+        .and_then(|section| section.get("paragraphs"))
+        .and_then(|value| value.as_array())
+        .and_then(|paragraphs| paragraphs.first())
+        .and_then(|paragraph| paragraph.get("preview"))
+        .and_then(|value| value.as_str())
+        .expect("preview present");
+    assert!(preview.starts_with("Name\\tValue"));
+
+    let _ = child.kill();
+    Ok(())
+}
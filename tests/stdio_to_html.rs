@@ -0,0 +1,84 @@
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+
+#[test]
+fn to_html_renders_paragraphs_and_tables() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": {
+                "to": "hwp",
+                "document": { "blocks": [
+                    { "type": "paragraph", "text": "안녕하세요 <world> & co" },
+                    { "type": "table", "rows": [["이름", "값"], ["a", "1"]] }
+                ] }
+            }
+        }
+    });
+    writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+    stdin.flush()?;
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+    let hwp = response["result"]["structuredContent"]["base64"]
+        .as_str()
+        .expect("base64 present")
+        .to_string();
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.to_html",
+            "arguments": { "base64": hwp, "images": "inline" }
+        }
+    });
+    writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+    let result = response.get("result").expect("result present");
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    let html = result
+        .get("structuredContent")
+        .and_then(|v| v.get("html"))
+        .and_then(|v| v.as_str())
+        .expect("html present");
+    assert_eq!(
+        result
+            .get("content")
+            .and_then(|v| v.get(0))
+            .and_then(|v| v.get("text"))
+            .and_then(|v| v.as_str()),
+        Some(html)
+    );
+
+    assert!(html.starts_with("<!DOCTYPE html>"), "{html}");
+    assert!(html.contains("<meta charset=\"utf-8\">"), "{html}");
+    assert!(
+        html.contains("<p>안녕하세요 &lt;world&gt; &amp; co</p>"),
+        "{html}"
+    );
+    assert!(
+        html.contains("<tr><td>이름</td><td>값</td></tr>\n<tr><td>a</td><td>1</td></tr>"),
+        "{html}"
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use hwpers::hwpx::{HwpxTextStyle, StyledText};
+use hwpers::{HwpWriter, HwpxWriter};
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+use tempfile::tempdir;
+
+fn send_request(
+    stdin: &mut impl Write,
+    stdout: &mut impl BufRead,
+    request: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let serialized = serde_json::to_string(&request)?;
+    writeln!(stdin, "{serialized}")?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    Ok(serde_json::from_str(line.trim())?)
+}
+
+#[test]
+fn to_json_returns_document_model() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let input_path = dir.path().join("input.hwp");
+    let output_path = dir.path().join("model.json");
+    let hwpx_path = dir.path().join("input.hwpx");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("First paragraph")?;
+    writer.add_paragraph("두 번째 문단")?;
+    writer.save_to_file(&input_path)?;
+
+    let mut writer = HwpxWriter::new();
+    writer.add_mixed_styled_paragraph(vec![
+        StyledText::new("Plain"),
+        StyledText::with_style("Bold", HwpxTextStyle::new().bold().size(14)),
+    ])?;
+    writer.save_to_file(&hwpx_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let inline = send_request(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.to_json",
+                "arguments": { "path": input_path.to_string_lossy() }
+            }
+        }),
+    )?;
+    let result = &inline["result"];
+    assert_eq!(result["isError"], false);
+    let structured = &result["structuredContent"];
+    assert_eq!(structured["model_version"], 1);
+    assert_eq!(structured["format"], "hwp");
+
+    let sections = structured["document"]["sections"]
+        .as_array()
+        .expect("sections present");
+    assert_eq!(sections.len(), 1);
+    assert_eq!(sections[0]["index"], 0);
+    let paragraphs = sections[0]["paragraphs"]
+        .as_array()
+        .expect("paragraphs present");
+    let texts: Vec<&str> = paragraphs
+        .iter()
+        .map(|paragraph| paragraph["text"].as_str().unwrap_or("").trim_end())
+        .collect();
+    assert!(texts.contains(&"First paragraph"));
+    assert!(texts.contains(&"두 번째 문단"));
+
+    assert!(
+        structured["warnings"]
+            .as_array()
+            .expect("warnings present")
+            .iter()
+            .any(|warning| warning
+                .as_str()
+                .unwrap_or("")
+                .starts_with("character shapes not available"))
+    );
+    assert_eq!(paragraphs[0]["controls"], serde_json::json!([]));
+
+    let written = send_request(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.to_json",
+                "arguments": {
+                    "path": input_path.to_string_lossy(),
+                    "output_path": output_path.to_string_lossy()
+                }
+            }
+        }),
+    )?;
+    let result = &written["result"];
+    assert_eq!(result["isError"], false);
+    assert!(result["structuredContent"].get("document").is_none());
+    assert_eq!(result["content"][1]["type"], "resource_link");
+    assert_eq!(result["content"][1]["mimeType"], "application/json");
+
+    let file: serde_json::Value = serde_json::from_slice(&std::fs::read(&output_path)?)?;
+    assert_eq!(file, structured["document"]);
+    assert_eq!(
+        result["structuredContent"]["bytes_len"],
+        std::fs::metadata(&output_path)?.len()
+    );
+
+    let hwpx = send_request(
+        &mut stdin,
+        &mut stdout,
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 3,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.to_json",
+                "arguments": { "path": hwpx_path.to_string_lossy() }
+            }
+        }),
+    )?;
+    let structured = &hwpx["result"]["structuredContent"];
+    assert_eq!(structured["format"], "hwpx");
+    let paragraph = &structured["document"]["sections"][0]["paragraphs"][0];
+    assert_eq!(paragraph["text"], "PlainBold");
+    let runs = paragraph["runs"].as_array().expect("runs present");
+    let bold = runs
+        .iter()
+        .find(|run| run["text"] == "Bold")
+        .expect("bold run present");
+    assert_eq!(bold["start"], 5);
+    assert_eq!(bold["end"], 9);
+    assert_eq!(bold["bold"], true);
+    assert!(bold["size_pt"].as_f64().unwrap_or(0.0) > 0.0);
+    assert!(bold["color"].as_str().unwrap_or("").starts_with('#'));
+
+    let _ = child.kill();
+    Ok(())
+}
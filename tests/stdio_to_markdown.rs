@@ -0,0 +1,121 @@
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+
+fn send_request(
+    stdin: &mut impl Write,
+    stdout: &mut impl BufRead,
+    request: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let serialized = serde_json::to_string(&request)?;
+    writeln!(stdin, "{serialized}")?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    Ok(serde_json::from_str(line.trim())?)
+}
+
+fn create_document(
+    stdin: &mut impl Write,
+    stdout: &mut impl BufRead,
+    to: &str,
+    blocks: serde_json::Value,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.create_rich_document",
+            "arguments": { "to": to, "document": { "blocks": blocks } }
+        }
+    });
+    let response = send_request(stdin, stdout, request)?;
+    Ok(response
+        .get("result")
+        .and_then(|value| value.get("structuredContent"))
+        .and_then(|value| value.get("base64"))
+        .and_then(|value| value.as_str())
+        .expect("base64 present")
+        .to_string())
+}
+
+fn to_markdown(
+    stdin: &mut impl Write,
+    stdout: &mut impl BufRead,
+    base64: &str,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.to_markdown",
+            "arguments": { "base64": base64 }
+        }
+    });
+    let response = send_request(stdin, stdout, request)?;
+    let result = response.get("result").expect("result present");
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    let markdown = result
+        .get("structuredContent")
+        .and_then(|v| v.get("markdown"))
+        .and_then(|v| v.as_str())
+        .expect("markdown present");
+    assert_eq!(
+        result
+            .get("content")
+            .and_then(|v| v.get(0))
+            .and_then(|v| v.get("text"))
+            .and_then(|v| v.as_str()),
+        Some(markdown)
+    );
+    Ok(markdown.to_string())
+}
+
+#[test]
+fn to_markdown_renders_headings_and_pipe_tables() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    // The leading styled paragraph pins HWPX char shape 0 to the body size, which
+    // unstyled paragraphs fall back to.
+    let hwpx = create_document(
+        &mut stdin,
+        &mut stdout,
+        "hwpx",
+        serde_json::json!([
+            { "type": "paragraph", "text": "Intro.", "style": { "font_size": 10 } },
+            { "type": "heading", "level": 1, "text": "Overview" },
+            { "type": "paragraph", "text": "Body text." }
+        ]),
+    )?;
+    let markdown = to_markdown(&mut stdin, &mut stdout, &hwpx)?;
+    assert!(markdown.contains("# Overview\n"), "{markdown}");
+    assert!(markdown.contains("\n\nBody text.\n"), "{markdown}");
+
+    // hwpers reads tables back from HWP only.
+    let hwp = create_document(
+        &mut stdin,
+        &mut stdout,
+        "hwp",
+        serde_json::json!([
+            { "type": "paragraph", "text": "Before" },
+            { "type": "table", "header_row": true, "rows": [["Key", "Value"], ["a|b", "c"]] }
+        ]),
+    )?;
+    let markdown = to_markdown(&mut stdin, &mut stdout, &hwp)?;
+    assert!(
+        markdown.contains("| Key | Value |\n| --- | --- |\n| a\\|b | c |"),
+        "{markdown}"
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
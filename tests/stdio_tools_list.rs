@@ -47,11 +47,127 @@ fn tools_list_includes_expected_tools() -> Result<(), Box<dyn std::error::Error>
         "hwp.create_document",
         "hwp.create_rich_document",
         "hwp.extract_rich",
+        "hwp.fingerprint",
+        "hwp.similarity",
+        "hwp.merge",
+        "hwp.extract_preview",
+        "hwp.split",
+        "hwp.count_stats",
+        "hwp.extract_links",
+        "hwp.to_markdown",
+        "hwp.to_html",
+        "hwp.extract_dates",
+        "hwp.search_text",
+        "hwp.replace_text",
+        "hwp.explain",
+        "hwp.page_count",
+        "hwp.list_styles",
+        "hwp.batch",
+        "hwp.extract_images",
+        "hwp.extract_notes",
+        "hwp.extract_toc",
+        "hwp.diff",
+        "hwp.redact",
+        "hwp.validate",
+        "hwp.to_json",
+        "hwp.server_info",
+        "hwp.cleanup",
     ]
     .into_iter()
     .collect();
 
     assert_eq!(names, expected);
+    assert!(response["result"].get("nextCursor").is_none());
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn tools_list_honors_cursor() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let mut responses = Vec::new();
+    for (id, cursor) in [
+        (1, serde_json::Value::Null),
+        (2, serde_json::json!("1")),
+        (3, serde_json::json!("not-a-cursor")),
+    ] {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/list",
+            "params": { "cursor": cursor }
+        });
+        writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+        stdin.flush()?;
+        let mut line = String::new();
+        stdout.read_line(&mut line)?;
+        responses.push(serde_json::from_str::<serde_json::Value>(line.trim())?);
+    }
+
+    let all = responses[0]["result"]["tools"]
+        .as_array()
+        .expect("tools array present");
+    let rest = responses[1]["result"]["tools"]
+        .as_array()
+        .expect("tools array present");
+    assert_eq!(rest.len(), all.len() - 1);
+    assert_eq!(rest[0]["name"], all[1]["name"]);
+    assert!(responses[1]["result"].get("nextCursor").is_none());
+
+    assert_eq!(responses[2]["error"]["code"], -32602);
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn tools_list_declares_input_and_output_schemas() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/list"
+    });
+    writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+    stdin.flush()?;
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+
+    let tools = response["result"]["tools"]
+        .as_array()
+        .expect("tools array present");
+    assert!(!tools.is_empty());
+    for tool in tools {
+        let name = tool["name"].as_str().unwrap_or("");
+        for key in ["inputSchema", "outputSchema"] {
+            assert_eq!(tool[key]["type"], "object", "{name} {key}");
+            assert!(tool[key]["properties"].is_object(), "{name} {key}");
+        }
+        // Failures share structuredContent, so every output schema must allow `error`.
+        assert_eq!(
+            tool["outputSchema"]["properties"]["error"]["type"], "object",
+            "{name}"
+        );
+        assert!(tool["outputSchema"].get("required").is_none(), "{name}");
+    }
 
     let _ = child.kill();
     Ok(())
@@ -0,0 +1,40 @@
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+
+#[test]
+fn unknown_method_returns_method_not_found() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    // Notifications (no id) stay unanswered, whether known or not; the bogus request that
+    // follows must be the first line written back.
+    for request in [
+        serde_json::json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+        serde_json::json!({ "jsonrpc": "2.0", "method": "bogus/notification" }),
+        serde_json::json!({ "jsonrpc": "2.0", "id": 7, "method": "bogus", "params": {} }),
+    ] {
+        writeln!(stdin, "{}", serde_json::to_string(&request)?)?;
+    }
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    let response: serde_json::Value = serde_json::from_str(line.trim())?;
+    assert_eq!(
+        response,
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "error": { "code": -32601, "message": "Method not found" }
+        })
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
@@ -0,0 +1,169 @@
+use hwpers::HwpWriter;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::process::{Command, Stdio};
+use std::thread;
+use tempfile::tempdir;
+
+fn send_request(
+    stdin: &mut impl Write,
+    stdout: &mut impl BufRead,
+    request: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let serialized = serde_json::to_string(&request)?;
+    writeln!(stdin, "{serialized}")?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    Ok(serde_json::from_str(line.trim())?)
+}
+
+/// Serves `/doc.hwp` with `body`, `/huge.hwp` with an oversized Content-Length and 404
+/// otherwise, one request per connection.
+fn serve(body: Vec<u8>, connections: usize) -> Result<String, Box<dyn std::error::Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let base = format!("http://{}", listener.local_addr()?);
+    thread::spawn(move || {
+        for stream in listener.incoming().take(connections) {
+            let Ok(mut stream) = stream else { continue };
+            let mut request = [0u8; 1024];
+            let read = stream.read(&mut request).unwrap_or(0);
+            let request = String::from_utf8_lossy(&request[..read]);
+            let response = if request.starts_with("GET /doc.hwp ") {
+                let mut response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                )
+                .into_bytes();
+                response.extend_from_slice(&body);
+                response
+            } else if request.starts_with("GET /huge.hwp ") {
+                b"HTTP/1.1 200 OK\r\nContent-Length: 104857600\r\nConnection: close\r\n\r\n"
+                    .to_vec()
+            } else {
+                b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_vec()
+            };
+            let _ = stream.write_all(&response);
+        }
+    });
+    Ok(base)
+}
+
+#[test]
+fn url_input_downloads_document() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let file_path = dir.path().join("remote.hwp");
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Fetched over HTTP")?;
+    writer.save_to_file(&file_path)?;
+    let base = serve(std::fs::read(&file_path)?, 3)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio", "--allow-input", "path,base64,url"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_text",
+            "arguments": { "url": format!("{base}/doc.hwp") }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, request)?;
+    let result = response.get("result").expect("result present");
+    assert_eq!(result.get("isError").and_then(|v| v.as_bool()), Some(false));
+    let text = result
+        .get("structuredContent")
+        .and_then(|value| value.get("text"))
+        .and_then(|value| value.as_str())
+        .unwrap_or_default();
+    assert!(text.contains("Fetched over HTTP"), "{text}");
+
+    for (id, path, kind) in [
+        (2, "huge.hwp", "too_large"),
+        (3, "missing.hwp", "invalid_input"),
+    ] {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.extract_text",
+                "arguments": { "url": format!("{base}/{path}") }
+            }
+        });
+        let response = send_request(&mut stdin, &mut stdout, request)?;
+        let error = response
+            .get("result")
+            .and_then(|value| value.get("structuredContent"))
+            .and_then(|value| value.get("error"))
+            .expect("error present");
+        assert_eq!(error.get("kind").and_then(|v| v.as_str()), Some(kind));
+    }
+
+    let both_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 4,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_text",
+            "arguments": { "url": format!("{base}/doc.hwp"), "path": file_path.to_string_lossy() }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, both_request)?;
+    let error = response
+        .get("result")
+        .and_then(|value| value.get("structuredContent"))
+        .and_then(|value| value.get("error"))
+        .expect("error present");
+    assert_eq!(
+        error.get("message").and_then(|v| v.as_str()),
+        Some("only one of path, base64, or url can be set")
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
+
+#[test]
+fn url_input_is_disabled_by_default() -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "hwp.extract_text",
+            "arguments": { "url": "http://127.0.0.1:9/doc.hwp" }
+        }
+    });
+    let response = send_request(&mut stdin, &mut stdout, request)?;
+    let error = response
+        .get("result")
+        .and_then(|value| value.get("structuredContent"))
+        .and_then(|value| value.get("error"))
+        .expect("error present");
+    assert_eq!(
+        error.get("message").and_then(|v| v.as_str()),
+        Some("input source 'url' is disabled")
+    );
+
+    let _ = child.kill();
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use hwpers::{HwpWriter, HwpxWriter};
+use std::io::{BufRead, BufReader, Cursor, Read, Write};
+use std::process::{Command, Stdio};
+use tempfile::tempdir;
+
+fn send_request(
+    stdin: &mut impl Write,
+    stdout: &mut impl BufRead,
+    request: serde_json::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let serialized = serde_json::to_string(&request)?;
+    writeln!(stdin, "{serialized}")?;
+    stdin.flush()?;
+
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    Ok(serde_json::from_str(line.trim())?)
+}
+
+/// Rewrites every entry of an HWPX package, removing the `<hp:pagePr>` element from sections.
+fn without_page_pr(hwpx: &[u8]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(hwpx))?;
+    let mut output = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index)?;
+        let name = file.name().to_string();
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)?;
+        if name.starts_with("Contents/section") {
+            let xml = String::from_utf8(data)?;
+            let start = xml.find("<hp:pagePr").expect("pagePr present");
+            let end = xml.find("</hp:pagePr>").expect("pagePr closed") + "</hp:pagePr>".len();
+            data = format!("{}{}", &xml[..start], &xml[end..]).into_bytes();
+        }
+        output.start_file(name, zip::write::SimpleFileOptions::default())?;
+        output.write_all(&data)?;
+    }
+    Ok(output.finish()?.into_inner())
+}
+
+#[test]
+fn validate_reports_problems_without_failing() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let valid_path = dir.path().join("valid.hwp");
+    let empty_path = dir.path().join("empty.hwpx");
+    let no_page_path = dir.path().join("no_page.hwpx");
+    let broken_path = dir.path().join("broken.hwp");
+
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("Hello")?;
+    writer.save_to_file(&valid_path)?;
+
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("   ")?;
+    writer.save_to_file(&empty_path)?;
+
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("Hello")?;
+    std::fs::write(&no_page_path, without_page_pr(&writer.to_bytes()?)?)?;
+
+    std::fs::write(&broken_path, b"not a document")?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mcp-hwp"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin available");
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout available"));
+
+    let mut results = Vec::new();
+    for (id, path) in [
+        (1, &valid_path),
+        (2, &empty_path),
+        (3, &no_page_path),
+        (4, &broken_path),
+    ] {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": {
+                "name": "hwp.validate",
+                "arguments": { "path": path.to_string_lossy() }
+            }
+        });
+        let response = send_request(&mut stdin, &mut stdout, request)?;
+        results.push(response["result"].clone());
+    }
+
+    let valid = &results[0];
+    assert_eq!(valid["isError"], false);
+    let structured = &valid["structuredContent"];
+    assert_eq!(structured["parseable"], true);
+    assert_eq!(structured["format"], "hwp");
+    assert_eq!(structured["encrypted"], false);
+    assert_eq!(structured["section_count"], 1);
+    assert_eq!(structured["problems"], serde_json::json!([]));
+
+    let kinds = |result: &serde_json::Value| -> Vec<String> {
+        result["structuredContent"]["problems"]
+            .as_array()
+            .expect("problems present")
+            .iter()
+            .map(|problem| problem["kind"].as_str().unwrap_or("").to_string())
+            .collect()
+    };
+
+    assert_eq!(results[1]["isError"], false);
+    assert_eq!(results[1]["structuredContent"]["format"], "hwpx");
+    assert_eq!(kinds(&results[1]), vec!["empty_content"]);
+
+    assert_eq!(results[2]["isError"], false);
+    assert_eq!(kinds(&results[2]), vec!["missing_page_def"]);
+    assert_eq!(
+        results[2]["structuredContent"]["problems"][0]["section_index"],
+        0
+    );
+
+    assert_eq!(results[3]["isError"], true);
+    assert_eq!(
+        results[3]["structuredContent"]["error"]["kind"],
+        "parse_failed"
+    );
+    assert_eq!(results[3]["structuredContent"]["error"]["code"], 5);
+
+    let _ = child.kill();
+    Ok(())
+}